@@ -0,0 +1,30 @@
+-- AlterTable
+ALTER TABLE "Task" ADD COLUMN     "recurrenceId" TEXT,
+ADD COLUMN     "recurrenceIndex" INTEGER;
+
+-- CreateTable
+CREATE TABLE "TaskRecurrence" (
+    "id" TEXT NOT NULL,
+    "tenantId" TEXT NOT NULL DEFAULT 'default-tenant',
+    "rule" TEXT NOT NULL,
+    "anchorDate" TIMESTAMP(3) NOT NULL,
+    "occurrenceCount" INTEGER NOT NULL DEFAULT 1,
+    "endedAt" TIMESTAMP(3),
+    "createdById" TEXT,
+    "createdAt" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP(3) NOT NULL,
+
+    CONSTRAINT "TaskRecurrence_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE INDEX "TaskRecurrence_tenantId_idx" ON "TaskRecurrence"("tenantId");
+
+-- CreateIndex
+CREATE UNIQUE INDEX "Task_recurrenceId_recurrenceIndex_key" ON "Task"("recurrenceId", "recurrenceIndex");
+
+-- AddForeignKey
+ALTER TABLE "Task" ADD CONSTRAINT "Task_recurrenceId_fkey" FOREIGN KEY ("recurrenceId") REFERENCES "TaskRecurrence"("id") ON DELETE SET NULL ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "TaskRecurrence" ADD CONSTRAINT "TaskRecurrence_tenantId_fkey" FOREIGN KEY ("tenantId") REFERENCES "Tenant"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
@@ -0,0 +1,34 @@
+-- AlterEnum
+ALTER TYPE "NotificationType" ADD VALUE 'TASK_DUE_SOON';
+ALTER TYPE "NotificationType" ADD VALUE 'TASK_OVERDUE';
+
+-- CreateTable
+CREATE TABLE "TaskReminder" (
+    "id" TEXT NOT NULL,
+    "tenantId" TEXT NOT NULL DEFAULT 'default-tenant',
+    "taskId" TEXT NOT NULL,
+    "recipientId" TEXT NOT NULL,
+    "kind" TEXT NOT NULL,
+    "dueDate" TIMESTAMP(3) NOT NULL,
+    "sentAt" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "TaskReminder_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE INDEX "TaskReminder_recipientId_idx" ON "TaskReminder"("recipientId");
+
+-- CreateIndex
+CREATE INDEX "TaskReminder_tenantId_idx" ON "TaskReminder"("tenantId");
+
+-- CreateIndex
+CREATE UNIQUE INDEX "TaskReminder_taskId_recipientId_kind_dueDate_key" ON "TaskReminder"("taskId", "recipientId", "kind", "dueDate");
+
+-- AddForeignKey
+ALTER TABLE "TaskReminder" ADD CONSTRAINT "TaskReminder_tenantId_fkey" FOREIGN KEY ("tenantId") REFERENCES "Tenant"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "TaskReminder" ADD CONSTRAINT "TaskReminder_taskId_fkey" FOREIGN KEY ("taskId") REFERENCES "Task"("id") ON DELETE CASCADE ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "TaskReminder" ADD CONSTRAINT "TaskReminder_recipientId_fkey" FOREIGN KEY ("recipientId") REFERENCES "User"("id") ON DELETE CASCADE ON UPDATE CASCADE;
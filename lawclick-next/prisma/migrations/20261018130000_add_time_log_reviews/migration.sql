@@ -0,0 +1,38 @@
+-- CreateEnum
+CREATE TYPE "TimeLogReviewAction" AS ENUM ('APPROVE', 'REJECT', 'ADJUST');
+
+-- AlterEnum
+ALTER TYPE "NotificationType" ADD VALUE 'TIME_LOG_REVIEWED';
+
+-- CreateTable
+CREATE TABLE "TimeLogReview" (
+    "id" TEXT NOT NULL,
+    "tenantId" TEXT NOT NULL DEFAULT 'default-tenant',
+    "timeLogId" TEXT NOT NULL,
+    "reviewerId" TEXT NOT NULL,
+    "action" "TimeLogReviewAction" NOT NULL,
+    "reason" TEXT,
+    "before" JSONB NOT NULL,
+    "after" JSONB NOT NULL,
+    "createdAt" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "TimeLogReview_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE INDEX "TimeLogReview_timeLogId_createdAt_idx" ON "TimeLogReview"("timeLogId", "createdAt");
+
+-- CreateIndex
+CREATE INDEX "TimeLogReview_reviewerId_idx" ON "TimeLogReview"("reviewerId");
+
+-- CreateIndex
+CREATE INDEX "TimeLogReview_tenantId_idx" ON "TimeLogReview"("tenantId");
+
+-- AddForeignKey
+ALTER TABLE "TimeLogReview" ADD CONSTRAINT "TimeLogReview_tenantId_fkey" FOREIGN KEY ("tenantId") REFERENCES "Tenant"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "TimeLogReview" ADD CONSTRAINT "TimeLogReview_timeLogId_fkey" FOREIGN KEY ("timeLogId") REFERENCES "TimeLog"("id") ON DELETE CASCADE ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "TimeLogReview" ADD CONSTRAINT "TimeLogReview_reviewerId_fkey" FOREIGN KEY ("reviewerId") REFERENCES "User"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
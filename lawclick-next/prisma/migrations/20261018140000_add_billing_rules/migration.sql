@@ -0,0 +1,34 @@
+-- CreateEnum
+CREATE TYPE "BillingRoundingMode" AS ENUM ('UP', 'NEAREST');
+
+-- AlterTable
+ALTER TABLE "TimeLog" ADD COLUMN "billedDuration" INTEGER;
+
+-- CreateTable
+CREATE TABLE "BillingRule" (
+    "id" TEXT NOT NULL,
+    "tenantId" TEXT NOT NULL DEFAULT 'default-tenant',
+    "caseId" TEXT,
+    "incrementMinutes" INTEGER NOT NULL DEFAULT 6,
+    "roundingMode" "BillingRoundingMode" NOT NULL DEFAULT 'UP',
+    "minimumMinutes" INTEGER NOT NULL DEFAULT 0,
+    "createdAt" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP(3) NOT NULL,
+
+    CONSTRAINT "BillingRule_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX "BillingRule_caseId_key" ON "BillingRule"("caseId");
+
+-- CreateIndex
+CREATE INDEX "BillingRule_tenantId_idx" ON "BillingRule"("tenantId");
+
+-- CreateIndex（租户默认规则唯一；Prisma schema 无法表达部分索引）
+CREATE UNIQUE INDEX "BillingRule_tenantId_default_key" ON "BillingRule"("tenantId") WHERE "caseId" IS NULL;
+
+-- AddForeignKey
+ALTER TABLE "BillingRule" ADD CONSTRAINT "BillingRule_tenantId_fkey" FOREIGN KEY ("tenantId") REFERENCES "Tenant"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "BillingRule" ADD CONSTRAINT "BillingRule_caseId_fkey" FOREIGN KEY ("caseId") REFERENCES "Case"("id") ON DELETE CASCADE ON UPDATE CASCADE;
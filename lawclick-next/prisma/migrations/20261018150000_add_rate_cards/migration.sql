@@ -0,0 +1,54 @@
+-- CreateEnum
+CREATE TYPE "RateCardScope" AS ENUM ('TASK', 'CASE_USER', 'CASE_ROLE', 'CLIENT');
+
+-- CreateTable
+CREATE TABLE "RateCard" (
+    "id" TEXT NOT NULL,
+    "tenantId" TEXT NOT NULL DEFAULT 'default-tenant',
+    "scope" "RateCardScope" NOT NULL,
+    "taskId" TEXT,
+    "caseId" TEXT,
+    "userId" TEXT,
+    "role" "Role",
+    "clientId" TEXT,
+    "hourlyRate" DECIMAL(65,30) NOT NULL,
+    "note" TEXT,
+    "createdById" TEXT NOT NULL,
+    "createdAt" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP(3) NOT NULL,
+
+    CONSTRAINT "RateCard_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX "RateCard_taskId_key" ON "RateCard"("taskId");
+
+-- CreateIndex
+CREATE UNIQUE INDEX "RateCard_clientId_key" ON "RateCard"("clientId");
+
+-- CreateIndex
+CREATE INDEX "RateCard_tenantId_scope_idx" ON "RateCard"("tenantId", "scope");
+
+-- CreateIndex
+CREATE UNIQUE INDEX "RateCard_caseId_userId_key" ON "RateCard"("caseId", "userId");
+
+-- CreateIndex
+CREATE UNIQUE INDEX "RateCard_caseId_role_key" ON "RateCard"("caseId", "role");
+
+-- AddForeignKey
+ALTER TABLE "RateCard" ADD CONSTRAINT "RateCard_tenantId_fkey" FOREIGN KEY ("tenantId") REFERENCES "Tenant"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "RateCard" ADD CONSTRAINT "RateCard_taskId_fkey" FOREIGN KEY ("taskId") REFERENCES "Task"("id") ON DELETE CASCADE ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "RateCard" ADD CONSTRAINT "RateCard_caseId_fkey" FOREIGN KEY ("caseId") REFERENCES "Case"("id") ON DELETE CASCADE ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "RateCard" ADD CONSTRAINT "RateCard_userId_fkey" FOREIGN KEY ("userId") REFERENCES "User"("id") ON DELETE CASCADE ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "RateCard" ADD CONSTRAINT "RateCard_clientId_fkey" FOREIGN KEY ("clientId") REFERENCES "Contact"("id") ON DELETE CASCADE ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "RateCard" ADD CONSTRAINT "RateCard_createdById_fkey" FOREIGN KEY ("createdById") REFERENCES "User"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
@@ -0,0 +1,5 @@
+-- AlterEnum
+ALTER TYPE "NotificationType" ADD VALUE 'CASE_BUDGET_THRESHOLD';
+
+-- AlterTable
+ALTER TABLE "TimeLog" ADD COLUMN "writeOffAmount" DECIMAL(65,30);
@@ -0,0 +1,6 @@
+-- AlterEnum
+ALTER TYPE "NotificationType" ADD VALUE 'TIMER_IDLE_PAUSED';
+
+-- AlterTable
+ALTER TABLE "TimeLog" ADD COLUMN "idlePausedAt" TIMESTAMP(3),
+ADD COLUMN "idleSeconds" INTEGER;
@@ -0,0 +1,47 @@
+-- CreateEnum
+CREATE TYPE "InvoiceItemType" AS ENUM ('TIME', 'EXPENSE');
+
+-- AlterTable
+ALTER TABLE "Invoice" ADD COLUMN     "issuedById" TEXT,
+ADD COLUMN     "periodEnd" TIMESTAMP(3),
+ADD COLUMN     "periodStart" TIMESTAMP(3);
+
+-- CreateTable
+CREATE TABLE "InvoiceItem" (
+    "id" TEXT NOT NULL,
+    "tenantId" TEXT NOT NULL DEFAULT 'default-tenant',
+    "invoiceId" TEXT NOT NULL,
+    "type" "InvoiceItemType" NOT NULL,
+    "timeLogId" TEXT,
+    "expenseId" TEXT,
+    "date" TIMESTAMP(3) NOT NULL,
+    "description" TEXT NOT NULL,
+    "quantity" DECIMAL(65,30),
+    "rate" DECIMAL(65,30),
+    "originalAmount" DECIMAL(65,30) NOT NULL,
+    "amount" DECIMAL(65,30) NOT NULL,
+    "createdAt" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "InvoiceItem_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX "InvoiceItem_timeLogId_key" ON "InvoiceItem"("timeLogId");
+
+-- CreateIndex
+CREATE UNIQUE INDEX "InvoiceItem_expenseId_key" ON "InvoiceItem"("expenseId");
+
+-- CreateIndex
+CREATE INDEX "InvoiceItem_tenantId_invoiceId_idx" ON "InvoiceItem"("tenantId", "invoiceId");
+
+-- AddForeignKey
+ALTER TABLE "InvoiceItem" ADD CONSTRAINT "InvoiceItem_tenantId_fkey" FOREIGN KEY ("tenantId") REFERENCES "Tenant"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "InvoiceItem" ADD CONSTRAINT "InvoiceItem_invoiceId_fkey" FOREIGN KEY ("invoiceId") REFERENCES "Invoice"("id") ON DELETE CASCADE ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "InvoiceItem" ADD CONSTRAINT "InvoiceItem_timeLogId_fkey" FOREIGN KEY ("timeLogId") REFERENCES "TimeLog"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "InvoiceItem" ADD CONSTRAINT "InvoiceItem_expenseId_fkey" FOREIGN KEY ("expenseId") REFERENCES "Expense"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
@@ -0,0 +1,26 @@
+-- AlterEnum
+ALTER TYPE "NotificationType" ADD VALUE 'EXPENSE_REVIEWED';
+
+-- AlterTable
+ALTER TABLE "Expense" ADD COLUMN     "isBillable" BOOLEAN NOT NULL DEFAULT false,
+ADD COLUMN     "reviewNote" TEXT,
+ADD COLUMN     "reviewedAt" TIMESTAMP(3),
+ADD COLUMN     "reviewerId" TEXT;
+
+-- CreateTable
+CREATE TABLE "ExpenseMarkupRule" (
+    "id" TEXT NOT NULL,
+    "tenantId" TEXT NOT NULL DEFAULT 'default-tenant',
+    "category" TEXT NOT NULL,
+    "markupPercent" DECIMAL(65,30) NOT NULL,
+    "createdAt" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP(3) NOT NULL,
+
+    CONSTRAINT "ExpenseMarkupRule_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX "ExpenseMarkupRule_tenantId_category_key" ON "ExpenseMarkupRule"("tenantId", "category");
+
+-- AddForeignKey
+ALTER TABLE "ExpenseMarkupRule" ADD CONSTRAINT "ExpenseMarkupRule_tenantId_fkey" FOREIGN KEY ("tenantId") REFERENCES "Tenant"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
@@ -0,0 +1,21 @@
+-- CreateEnum
+CREATE TYPE "UtbmsCodeKind" AS ENUM ('TASK', 'ACTIVITY', 'EXPENSE');
+
+-- CreateTable
+CREATE TABLE "UtbmsCodeMapping" (
+    "id" TEXT NOT NULL,
+    "tenantId" TEXT NOT NULL DEFAULT 'default-tenant',
+    "kind" "UtbmsCodeKind" NOT NULL,
+    "matchValue" TEXT NOT NULL,
+    "code" TEXT NOT NULL,
+    "createdAt" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP(3) NOT NULL,
+
+    CONSTRAINT "UtbmsCodeMapping_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX "UtbmsCodeMapping_tenantId_kind_matchValue_key" ON "UtbmsCodeMapping"("tenantId", "kind", "matchValue");
+
+-- AddForeignKey
+ALTER TABLE "UtbmsCodeMapping" ADD CONSTRAINT "UtbmsCodeMapping_tenantId_fkey" FOREIGN KEY ("tenantId") REFERENCES "Tenant"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
@@ -0,0 +1,5 @@
+-- AlterTable
+ALTER TABLE "Invoice" ADD COLUMN     "documentId" TEXT;
+
+-- CreateIndex
+CREATE UNIQUE INDEX "Invoice_documentId_key" ON "Invoice"("documentId");
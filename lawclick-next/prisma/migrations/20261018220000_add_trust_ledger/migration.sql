@@ -0,0 +1,102 @@
+-- AlterEnum
+ALTER TYPE "PaymentMethod" ADD VALUE 'TRUST';
+
+-- AlterTable
+ALTER TABLE "Payment" ADD COLUMN     "voidedAt" TIMESTAMP(3);
+
+-- CreateEnum
+CREATE TYPE "TrustAccount" AS ENUM ('TRUST_BANK', 'CLIENT_LEDGER');
+
+-- CreateEnum
+CREATE TYPE "TrustTransactionType" AS ENUM ('DEPOSIT', 'TRANSFER', 'REFUND', 'REVERSAL');
+
+-- CreateTable
+CREATE TABLE "TrustTransaction" (
+    "id" TEXT NOT NULL,
+    "tenantId" TEXT NOT NULL DEFAULT 'default-tenant',
+    "type" "TrustTransactionType" NOT NULL,
+    "clientId" TEXT NOT NULL,
+    "caseId" TEXT,
+    "amount" DECIMAL(65,30) NOT NULL,
+    "occurredAt" TIMESTAMP(3) NOT NULL,
+    "invoiceId" TEXT,
+    "paymentId" TEXT,
+    "reversesId" TEXT,
+    "reference" TEXT,
+    "note" TEXT,
+    "createdById" TEXT,
+    "createdAt" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "TrustTransaction_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateTable
+CREATE TABLE "TrustEntry" (
+    "id" TEXT NOT NULL,
+    "tenantId" TEXT NOT NULL DEFAULT 'default-tenant',
+    "transactionId" TEXT NOT NULL,
+    "account" "TrustAccount" NOT NULL,
+    "clientId" TEXT NOT NULL,
+    "caseId" TEXT,
+    "debit" DECIMAL(65,30) NOT NULL DEFAULT 0,
+    "credit" DECIMAL(65,30) NOT NULL DEFAULT 0,
+    "createdAt" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "TrustEntry_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX "TrustTransaction_paymentId_key" ON "TrustTransaction"("paymentId");
+
+-- CreateIndex
+CREATE UNIQUE INDEX "TrustTransaction_reversesId_key" ON "TrustTransaction"("reversesId");
+
+-- CreateIndex
+CREATE INDEX "TrustTransaction_tenantId_clientId_caseId_idx" ON "TrustTransaction"("tenantId", "clientId", "caseId");
+
+-- CreateIndex
+CREATE INDEX "TrustTransaction_tenantId_occurredAt_idx" ON "TrustTransaction"("tenantId", "occurredAt");
+
+-- CreateIndex
+CREATE INDEX "TrustEntry_tenantId_account_clientId_caseId_idx" ON "TrustEntry"("tenantId", "account", "clientId", "caseId");
+
+-- CreateIndex
+CREATE INDEX "TrustEntry_transactionId_idx" ON "TrustEntry"("transactionId");
+
+-- AddForeignKey
+ALTER TABLE "TrustTransaction" ADD CONSTRAINT "TrustTransaction_tenantId_fkey" FOREIGN KEY ("tenantId") REFERENCES "Tenant"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "TrustTransaction" ADD CONSTRAINT "TrustTransaction_clientId_fkey" FOREIGN KEY ("clientId") REFERENCES "Contact"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "TrustTransaction" ADD CONSTRAINT "TrustTransaction_caseId_fkey" FOREIGN KEY ("caseId") REFERENCES "Case"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "TrustTransaction" ADD CONSTRAINT "TrustTransaction_invoiceId_fkey" FOREIGN KEY ("invoiceId") REFERENCES "Invoice"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "TrustTransaction" ADD CONSTRAINT "TrustTransaction_paymentId_fkey" FOREIGN KEY ("paymentId") REFERENCES "Payment"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "TrustEntry" ADD CONSTRAINT "TrustEntry_tenantId_fkey" FOREIGN KEY ("tenantId") REFERENCES "Tenant"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "TrustEntry" ADD CONSTRAINT "TrustEntry_transactionId_fkey" FOREIGN KEY ("transactionId") REFERENCES "TrustTransaction"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- 信托流水与分录只增不改：禁止 UPDATE / DELETE（更正须通过冲正流水）
+CREATE FUNCTION "trust_ledger_immutable"() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION '信托账记录不可修改或删除（%）', TG_TABLE_NAME;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER "TrustTransaction_immutable" BEFORE UPDATE OR DELETE ON "TrustTransaction"
+    FOR EACH ROW EXECUTE FUNCTION "trust_ledger_immutable"();
+
+CREATE TRIGGER "TrustEntry_immutable" BEFORE UPDATE OR DELETE ON "TrustEntry"
+    FOR EACH ROW EXECUTE FUNCTION "trust_ledger_immutable"();
+
+-- 分录金额非负且单边记账
+ALTER TABLE "TrustEntry" ADD CONSTRAINT "TrustEntry_amount_check"
+    CHECK ("debit" >= 0 AND "credit" >= 0 AND ("debit" = 0) <> ("credit" = 0));
@@ -0,0 +1,33 @@
+-- AlterEnum
+ALTER TYPE "NotificationType" ADD VALUE 'CONTRACT_EXPIRING';
+
+-- CreateTable
+CREATE TABLE "ContractReminder" (
+    "id" TEXT NOT NULL,
+    "tenantId" TEXT NOT NULL DEFAULT 'default-tenant',
+    "contractId" TEXT NOT NULL,
+    "recipientId" TEXT NOT NULL,
+    "kind" TEXT NOT NULL,
+    "endDate" TIMESTAMP(3) NOT NULL,
+    "sentAt" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "ContractReminder_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE INDEX "ContractReminder_recipientId_idx" ON "ContractReminder"("recipientId");
+
+-- CreateIndex
+CREATE INDEX "ContractReminder_tenantId_idx" ON "ContractReminder"("tenantId");
+
+-- CreateIndex
+CREATE UNIQUE INDEX "ContractReminder_contractId_recipientId_kind_endDate_key" ON "ContractReminder"("contractId", "recipientId", "kind", "endDate");
+
+-- AddForeignKey
+ALTER TABLE "ContractReminder" ADD CONSTRAINT "ContractReminder_tenantId_fkey" FOREIGN KEY ("tenantId") REFERENCES "Tenant"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "ContractReminder" ADD CONSTRAINT "ContractReminder_contractId_fkey" FOREIGN KEY ("contractId") REFERENCES "Contract"("id") ON DELETE CASCADE ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "ContractReminder" ADD CONSTRAINT "ContractReminder_recipientId_fkey" FOREIGN KEY ("recipientId") REFERENCES "User"("id") ON DELETE CASCADE ON UPDATE CASCADE;
@@ -0,0 +1,93 @@
+-- CreateEnum
+CREATE TYPE "ApprovalApproverKind" AS ENUM ('CASE_HANDLER', 'SUPERVISOR', 'ROLE', 'USER');
+
+-- CreateEnum
+CREATE TYPE "ApprovalStepStatus" AS ENUM ('WAITING', 'PENDING', 'APPROVED', 'REJECTED', 'SKIPPED', 'CANCELLED');
+
+-- AlterEnum
+ALTER TYPE "NotificationType" ADD VALUE 'APPROVAL_REQUESTED';
+ALTER TYPE "NotificationType" ADD VALUE 'APPROVAL_UPDATED';
+
+-- AlterTable
+ALTER TABLE "ApprovalRequest" ADD COLUMN     "currentStep" INTEGER;
+
+-- CreateTable
+CREATE TABLE "ApprovalRule" (
+    "id" TEXT NOT NULL,
+    "tenantId" TEXT NOT NULL DEFAULT 'default-tenant',
+    "type" "ApprovalType" NOT NULL,
+    "minAmount" DECIMAL(65,30) NOT NULL DEFAULT 0,
+    "name" TEXT,
+    "createdAt" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP(3) NOT NULL,
+
+    CONSTRAINT "ApprovalRule_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateTable
+CREATE TABLE "ApprovalRuleStep" (
+    "id" TEXT NOT NULL,
+    "ruleId" TEXT NOT NULL,
+    "stepOrder" INTEGER NOT NULL,
+    "approverKind" "ApprovalApproverKind" NOT NULL,
+    "approverRole" "Role",
+    "approverUserId" TEXT,
+
+    CONSTRAINT "ApprovalRuleStep_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateTable
+CREATE TABLE "ApprovalStep" (
+    "id" TEXT NOT NULL,
+    "tenantId" TEXT NOT NULL DEFAULT 'default-tenant',
+    "requestId" TEXT NOT NULL,
+    "stepOrder" INTEGER NOT NULL,
+    "approverId" TEXT,
+    "approverRole" "Role",
+    "status" "ApprovalStepStatus" NOT NULL DEFAULT 'WAITING',
+    "note" TEXT,
+    "actedById" TEXT,
+    "actedAt" TIMESTAMP(3),
+    "createdAt" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "ApprovalStep_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX "ApprovalRule_tenantId_type_minAmount_key" ON "ApprovalRule"("tenantId", "type", "minAmount");
+
+-- CreateIndex
+CREATE INDEX "ApprovalRuleStep_approverUserId_idx" ON "ApprovalRuleStep"("approverUserId");
+
+-- CreateIndex
+CREATE UNIQUE INDEX "ApprovalRuleStep_ruleId_stepOrder_key" ON "ApprovalRuleStep"("ruleId", "stepOrder");
+
+-- CreateIndex
+CREATE INDEX "ApprovalStep_approverId_status_idx" ON "ApprovalStep"("approverId", "status");
+
+-- CreateIndex
+CREATE INDEX "ApprovalStep_tenantId_approverRole_status_idx" ON "ApprovalStep"("tenantId", "approverRole", "status");
+
+-- CreateIndex
+CREATE UNIQUE INDEX "ApprovalStep_requestId_stepOrder_key" ON "ApprovalStep"("requestId", "stepOrder");
+
+-- AddForeignKey
+ALTER TABLE "ApprovalRule" ADD CONSTRAINT "ApprovalRule_tenantId_fkey" FOREIGN KEY ("tenantId") REFERENCES "Tenant"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "ApprovalRuleStep" ADD CONSTRAINT "ApprovalRuleStep_ruleId_fkey" FOREIGN KEY ("ruleId") REFERENCES "ApprovalRule"("id") ON DELETE CASCADE ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "ApprovalRuleStep" ADD CONSTRAINT "ApprovalRuleStep_approverUserId_fkey" FOREIGN KEY ("approverUserId") REFERENCES "User"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "ApprovalStep" ADD CONSTRAINT "ApprovalStep_tenantId_fkey" FOREIGN KEY ("tenantId") REFERENCES "Tenant"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "ApprovalStep" ADD CONSTRAINT "ApprovalStep_requestId_fkey" FOREIGN KEY ("requestId") REFERENCES "ApprovalRequest"("id") ON DELETE CASCADE ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "ApprovalStep" ADD CONSTRAINT "ApprovalStep_approverId_fkey" FOREIGN KEY ("approverId") REFERENCES "User"("id") ON DELETE SET NULL ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "ApprovalStep" ADD CONSTRAINT "ApprovalStep_actedById_fkey" FOREIGN KEY ("actedById") REFERENCES "User"("id") ON DELETE SET NULL ON UPDATE CASCADE;
@@ -0,0 +1,23 @@
+-- AlterEnum
+ALTER TYPE "NotificationType" ADD VALUE 'APPROVAL_DELEGATED';
+
+-- AlterTable
+ALTER TABLE "OutOfOffice" ADD COLUMN     "delegateId" TEXT;
+
+-- AlterTable
+ALTER TABLE "ApprovalStep" ADD COLUMN     "onBehalfOfId" TEXT;
+
+-- AlterTable
+ALTER TABLE "TimeLogReview" ADD COLUMN     "onBehalfOfId" TEXT;
+
+-- CreateIndex
+CREATE INDEX "OutOfOffice_tenantId_delegateId_idx" ON "OutOfOffice"("tenantId", "delegateId");
+
+-- AddForeignKey
+ALTER TABLE "OutOfOffice" ADD CONSTRAINT "OutOfOffice_delegateId_fkey" FOREIGN KEY ("delegateId") REFERENCES "User"("id") ON DELETE SET NULL ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "ApprovalStep" ADD CONSTRAINT "ApprovalStep_onBehalfOfId_fkey" FOREIGN KEY ("onBehalfOfId") REFERENCES "User"("id") ON DELETE SET NULL ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "TimeLogReview" ADD CONSTRAINT "TimeLogReview_onBehalfOfId_fkey" FOREIGN KEY ("onBehalfOfId") REFERENCES "User"("id") ON DELETE SET NULL ON UPDATE CASCADE;
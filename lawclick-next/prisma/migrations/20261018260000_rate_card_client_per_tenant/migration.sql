@@ -0,0 +1,5 @@
+-- DropIndex
+DROP INDEX "RateCard_clientId_key";
+
+-- CreateIndex
+CREATE UNIQUE INDEX "RateCard_tenantId_clientId_key" ON "RateCard"("tenantId", "clientId");
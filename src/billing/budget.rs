@@ -0,0 +1,90 @@
+//! 案件收费模式下的工时金额拆分
+//!
+//! - HOURLY：按工时金额全额计费
+//! - FIXED：工时只作为成本投入记录，金额全部计入核销（用于利润分析）
+//! - CAPPED：累计计费金额不超过合同金额，超出部分计入核销；未填写合同金额时按 HOURLY 处理
+
+use sea_orm::prelude::Decimal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetMode {
+    Hourly,
+    Fixed,
+    Capped { cap: Decimal },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetSplit {
+    /// 向客户计费的金额
+    pub billable: Decimal,
+    /// 核销（不计费）的金额
+    pub write_off: Decimal,
+}
+
+impl BudgetMode {
+    /// 在案件已计费 `billed_so_far` 的基础上拆分本条工时金额
+    pub fn split(&self, billed_so_far: Decimal, amount: Decimal) -> BudgetSplit {
+        match *self {
+            BudgetMode::Hourly => BudgetSplit { billable: amount, write_off: Decimal::ZERO },
+            BudgetMode::Fixed => BudgetSplit { billable: Decimal::ZERO, write_off: amount },
+            BudgetMode::Capped { cap } => {
+                let remaining = (cap - billed_so_far).max(Decimal::ZERO);
+                let billable = amount.min(remaining);
+                BudgetSplit { billable, write_off: amount - billable }
+            }
+        }
+    }
+}
+
+/// 已用比例（百分比）；合同金额非正数时视为 0
+pub fn used_percent(cap: Decimal, billed: Decimal) -> Decimal {
+    if cap <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+    billed * Decimal::from(100) / cap
+}
+
+/// 累计金额由 `before` 变为 `after` 时新跨过的预警阈值（百分比，升序）
+pub fn crossed_thresholds(cap: Decimal, before: Decimal, after: Decimal, thresholds: &[u32]) -> Vec<u32> {
+    let (before, after) = (used_percent(cap, before), used_percent(cap, after));
+    thresholds
+        .iter()
+        .copied()
+        .filter(|t| {
+            let t = Decimal::from(*t);
+            before < t && after >= t
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(v: i64) -> Decimal {
+        Decimal::from(v)
+    }
+
+    #[test]
+    fn split_by_mode() {
+        assert_eq!(BudgetMode::Hourly.split(d(900), d(300)), BudgetSplit { billable: d(300), write_off: d(0) });
+        assert_eq!(BudgetMode::Fixed.split(d(0), d(300)), BudgetSplit { billable: d(0), write_off: d(300) });
+
+        let capped = BudgetMode::Capped { cap: d(1000) };
+        assert_eq!(capped.split(d(500), d(300)), BudgetSplit { billable: d(300), write_off: d(0) });
+        assert_eq!(capped.split(d(900), d(300)), BudgetSplit { billable: d(100), write_off: d(200) });
+        assert_eq!(capped.split(d(1000), d(300)), BudgetSplit { billable: d(0), write_off: d(300) });
+        // 历史数据已超额时不会出现负数
+        assert_eq!(capped.split(d(1200), d(300)), BudgetSplit { billable: d(0), write_off: d(300) });
+    }
+
+    #[test]
+    fn thresholds_fire_once_when_crossed() {
+        let cap = d(1000);
+        assert_eq!(crossed_thresholds(cap, d(700), d(790), &[80, 100]), Vec::<u32>::new());
+        assert_eq!(crossed_thresholds(cap, d(700), d(800), &[80, 100]), vec![80]);
+        assert_eq!(crossed_thresholds(cap, d(700), d(1000), &[80, 100]), vec![80, 100]);
+        assert_eq!(crossed_thresholds(cap, d(800), d(900), &[80, 100]), Vec::<u32>::new());
+        assert_eq!(crossed_thresholds(d(0), d(0), d(100), &[80]), Vec::<u32>::new());
+    }
+}
@@ -0,0 +1,153 @@
+//! 人民币大写金额
+//!
+//! - 按《支付结算办法》书写：到“元”为止的加“整”；有角无分的不加“整”；有分的“分”后不写“整”
+//! - 金额先四舍五入到分；四位一节，节内中间连续的零只写一个“零”，节末尾的零不写；整数为零时省略“元”（如 `伍角`）
+//! - 支持到万亿级（绝对值小于 1 亿亿元），超出范围返回校验错误；负数前加“负”
+
+use sea_orm::prelude::Decimal;
+
+use super::invoice::round_money;
+use crate::error::{AppError, AppResult};
+
+const DIGITS: [char; 10] = ['零', '壹', '贰', '叁', '肆', '伍', '陆', '柒', '捌', '玖'];
+const UNITS: [&str; 4] = ["", "拾", "佰", "仟"];
+const SECTIONS: [&str; 4] = ["", "万", "亿", "万亿"];
+/// 可转换的整数部分上限（不含）：四节 16 位
+const MAX_YUAN: u64 = 10_000_000_000_000_000;
+
+/// 四位一节（1-9999）转大写，不含节单位；节内中间的零只写一个“零”，末尾的零不写
+fn section_words(value: u64, out: &mut String) {
+    let mut pending_zero = false;
+    let mut started = false;
+    for pos in (0..4).rev() {
+        let digit = (value / 10u64.pow(pos as u32) % 10) as usize;
+        if digit == 0 {
+            pending_zero = started;
+            continue;
+        }
+        if pending_zero {
+            out.push('零');
+            pending_zero = false;
+        }
+        out.push(DIGITS[digit]);
+        out.push_str(UNITS[pos]);
+        started = true;
+    }
+}
+
+/// 整数部分转大写（不含“元”）；节开头有零（或整节为零）时补一个“零”
+fn integer_words(mut value: u64) -> String {
+    let mut sections = Vec::new();
+    while value > 0 {
+        sections.push(value % 10_000);
+        value /= 10_000;
+    }
+
+    let mut out = String::new();
+    let mut pending_zero = false;
+    for (index, section) in sections.iter().enumerate().rev() {
+        if *section == 0 {
+            pending_zero = !out.is_empty();
+            continue;
+        }
+        if !out.is_empty() && (pending_zero || *section < 1000) {
+            out.push('零');
+        }
+        pending_zero = false;
+        section_words(*section, &mut out);
+        out.push_str(SECTIONS[index]);
+    }
+    out
+}
+
+/// 金额转人民币大写，如 `1234.05` → `壹仟贰佰叁拾肆元零伍分`
+pub fn rmb_uppercase(amount: Decimal) -> AppResult<String> {
+    let rounded = round_money(amount);
+    let negative = rounded < Decimal::ZERO;
+    let out_of_range = || AppError::Validation(format!("金额 {rounded} 超出大写金额支持范围"));
+    if rounded.abs() >= Decimal::from(MAX_YUAN) {
+        return Err(out_of_range());
+    }
+    let cents_total: u64 =
+        (rounded.abs() * Decimal::from(100)).trunc().to_string().parse().map_err(|_| out_of_range())?;
+    let yuan = cents_total / 100;
+    let jiao = (cents_total / 10 % 10) as usize;
+    let fen = (cents_total % 10) as usize;
+
+    let mut out = String::new();
+    if negative {
+        out.push('负');
+    }
+    if yuan == 0 && jiao == 0 && fen == 0 {
+        out.push_str("零元整");
+        return Ok(out);
+    }
+    if yuan > 0 {
+        out.push_str(&integer_words(yuan));
+        out.push('元');
+    }
+    match (jiao, fen) {
+        (0, 0) => out.push('整'),
+        (0, f) => {
+            if yuan > 0 {
+                out.push('零');
+            }
+            out.push(DIGITS[f]);
+            out.push('分');
+        }
+        (j, 0) => {
+            out.push(DIGITS[j]);
+            out.push('角');
+        }
+        (j, f) => {
+            out.push(DIGITS[j]);
+            out.push('角');
+            out.push(DIGITS[f]);
+            out.push('分');
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn words(s: &str) -> String {
+        rmb_uppercase(Decimal::from_str(s).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn converts_integers_with_zero_runs() {
+        assert_eq!(words("0"), "零元整");
+        assert_eq!(words("10"), "壹拾元整");
+        assert_eq!(words("1001"), "壹仟零壹元整");
+        assert_eq!(words("105000"), "壹拾万伍仟元整");
+        assert_eq!(words("1000500"), "壹佰万零伍佰元整");
+        assert_eq!(words("10010000"), "壹仟零壹万元整");
+        assert_eq!(words("100000001"), "壹亿零壹元整");
+        assert_eq!(words("120003400"), "壹亿贰仟万叁仟肆佰元整");
+    }
+
+    #[test]
+    fn converts_jiao_and_fen() {
+        assert_eq!(words("1234.05"), "壹仟贰佰叁拾肆元零伍分");
+        assert_eq!(words("1234.5"), "壹仟贰佰叁拾肆元伍角");
+        assert_eq!(words("0.56"), "伍角陆分");
+        assert_eq!(words("0.05"), "伍分");
+        assert_eq!(words("-8.005"), "负捌元零壹分");
+    }
+
+    #[test]
+    fn rejects_amounts_beyond_wan_yi() {
+        assert_eq!(
+            words("9999999999999999.99"),
+            "玖仟玖佰玖拾玖万亿玖仟玖佰玖拾玖亿玖仟玖佰玖拾玖万玖仟玖佰玖拾玖元玖角玖分"
+        );
+        assert_eq!(words("-1000000000000000"), "负壹仟万亿元整");
+        assert!(rmb_uppercase(Decimal::from_str("10000000000000000").unwrap()).is_err());
+        assert!(rmb_uppercase(Decimal::from_str("9999999999999999.995").unwrap()).is_err());
+        assert!(rmb_uppercase(Decimal::from_str("-100000000000000000000").unwrap()).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+//! 计费时长取整
+//!
+//! 按“计费单位 + 取整方式 + 最低计费时长”把实际工时（秒）换算为计费时长（秒）：
+//! - UP：不足一个单位按一个单位计（如 6 分钟单位下 1 秒计 6 分钟）
+//! - NEAREST：四舍五入到最近的单位，恰好一半时向上
+//! - 最低计费时长仅作用于非零工时；0 秒始终计 0
+
+/// 取整方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Up,
+    Nearest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncrementRule {
+    /// 计费单位（秒），至少为 1
+    pub increment_secs: i64,
+    pub rounding: Rounding,
+    /// 最低计费时长（秒）
+    pub minimum_secs: i64,
+}
+
+impl IncrementRule {
+    /// 未配置规则时按实际秒数计费
+    pub const EXACT: IncrementRule = IncrementRule { increment_secs: 1, rounding: Rounding::Up, minimum_secs: 0 };
+
+    pub fn from_minutes(increment_minutes: i32, rounding: Rounding, minimum_minutes: i32) -> Self {
+        Self {
+            increment_secs: i64::from(increment_minutes.max(1)) * 60,
+            rounding,
+            minimum_secs: i64::from(minimum_minutes.max(0)) * 60,
+        }
+    }
+
+    /// 实际工时（秒）→ 计费时长（秒）
+    pub fn billed_seconds(&self, raw_secs: i32) -> i32 {
+        let raw = i64::from(raw_secs);
+        if raw <= 0 {
+            return 0;
+        }
+        let inc = self.increment_secs.max(1);
+        let units = match self.rounding {
+            Rounding::Up => (raw + inc - 1) / inc,
+            Rounding::Nearest => (2 * raw + inc) / (2 * inc),
+        };
+        (units * inc).max(self.minimum_secs).min(i64::from(i32::MAX)) as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_and_exact_rule() {
+        let six_up = IncrementRule::from_minutes(6, Rounding::Up, 15);
+        assert_eq!(six_up.billed_seconds(0), 0);
+        assert_eq!(six_up.billed_seconds(-5), 0);
+
+        assert_eq!(IncrementRule::EXACT.billed_seconds(0), 0);
+        assert_eq!(IncrementRule::EXACT.billed_seconds(1), 1);
+        assert_eq!(IncrementRule::EXACT.billed_seconds(3601), 3601);
+    }
+
+    #[test]
+    fn round_up_to_increment() {
+        let rule = IncrementRule::from_minutes(6, Rounding::Up, 0);
+        assert_eq!(rule.billed_seconds(1), 360);
+        assert_eq!(rule.billed_seconds(360), 360);
+        assert_eq!(rule.billed_seconds(361), 720);
+        assert_eq!(rule.billed_seconds(3600), 3600);
+
+        let quarter = IncrementRule::from_minutes(15, Rounding::Up, 0);
+        assert_eq!(quarter.billed_seconds(899), 900);
+        assert_eq!(quarter.billed_seconds(900), 900);
+        assert_eq!(quarter.billed_seconds(901), 1800);
+    }
+
+    #[test]
+    fn round_to_nearest_increment() {
+        let rule = IncrementRule::from_minutes(15, Rounding::Nearest, 0);
+        assert_eq!(rule.billed_seconds(449), 0);
+        assert_eq!(rule.billed_seconds(450), 900);
+        assert_eq!(rule.billed_seconds(900), 900);
+        assert_eq!(rule.billed_seconds(1349), 900);
+        assert_eq!(rule.billed_seconds(1350), 1800);
+    }
+
+    #[test]
+    fn minimum_applies_to_non_zero_only() {
+        let rule = IncrementRule::from_minutes(6, Rounding::Nearest, 15);
+        assert_eq!(rule.billed_seconds(0), 0);
+        assert_eq!(rule.billed_seconds(60), 900);
+        assert_eq!(rule.billed_seconds(700), 900);
+        assert_eq!(rule.billed_seconds(1000), 1080);
+
+        // 最低计费时长不要求是计费单位的整数倍
+        let odd = IncrementRule::from_minutes(6, Rounding::Up, 10);
+        assert_eq!(odd.billed_seconds(1), 600);
+        assert_eq!(odd.billed_seconds(601), 720);
+    }
+
+    #[test]
+    fn saturates_at_i32_max() {
+        let rule = IncrementRule::from_minutes(60, Rounding::Up, 0);
+        assert_eq!(rule.billed_seconds(i32::MAX), i32::MAX);
+    }
+}
@@ -0,0 +1,77 @@
+//! 开票金额与发票号
+//!
+//! - 明细金额、税额均按分（2 位小数）四舍五入
+//! - 费用明细按类别加收百分比（ExpenseMarkupRule）
+//! - 发票号：`INV-YYYYMM-NNNN`，租户内按月递增
+
+use sea_orm::prelude::Decimal;
+use rust_decimal::RoundingStrategy;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvoiceTotals {
+    /// 不含税金额
+    pub amount: Decimal,
+    pub tax: Decimal,
+    /// 含税总额
+    pub total: Decimal,
+}
+
+/// 金额四舍五入到分
+pub fn round_money(value: Decimal) -> Decimal {
+    value.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero)
+}
+
+/// 按加收百分比计算费用开票金额
+pub fn marked_up(amount: Decimal, markup_percent: Decimal) -> Decimal {
+    round_money(amount + amount * markup_percent / Decimal::from(100))
+}
+
+/// 汇总明细金额并按税率计算税额
+pub fn invoice_totals(line_amounts: impl IntoIterator<Item = Decimal>, tax_rate: Decimal) -> InvoiceTotals {
+    let amount: Decimal = line_amounts.into_iter().map(round_money).sum();
+    let tax = round_money(amount * tax_rate);
+    InvoiceTotals { amount, tax, total: amount + tax }
+}
+
+/// 在同一前缀已有发票号的基础上生成下一个发票号（按序号取最大值，序号超过 4 位时字典序不再可靠）
+pub fn next_invoice_no<'a>(prefix: &str, existing: impl IntoIterator<Item = &'a str>) -> String {
+    let last_seq = existing
+        .into_iter()
+        .filter_map(|no| no.strip_prefix(prefix)?.strip_prefix('-')?.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0);
+    format!("{prefix}-{:04}", last_seq.saturating_add(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn d(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn totals_round_lines_and_tax() {
+        let totals = invoice_totals([d("1333.335"), d("200"), d("0.004")], d("0.06"));
+        assert_eq!(totals.amount, d("1533.34"));
+        assert_eq!(totals.tax, d("92.00"));
+        assert_eq!(totals.total, d("1625.34"));
+    }
+
+    #[test]
+    fn markup_applies_percent_and_rounds() {
+        assert_eq!(marked_up(d("200"), d("10")), d("220.00"));
+        assert_eq!(marked_up(d("33.33"), d("12.5")), d("37.50"));
+        assert_eq!(marked_up(d("99.99"), Decimal::ZERO), d("99.99"));
+    }
+
+    #[test]
+    fn invoice_no_increments_within_prefix() {
+        assert_eq!(next_invoice_no("INV-202610", []), "INV-202610-0001");
+        assert_eq!(next_invoice_no("INV-202610", ["INV-202610-0041", "INV-202610-0007"]), "INV-202610-0042");
+        assert_eq!(next_invoice_no("INV-202610", ["INV-202610-9999", "INV-202610-10000"]), "INV-202610-10001");
+        assert_eq!(next_invoice_no("INV-202610", ["INV-202610-MANUAL"]), "INV-202610-0001");
+    }
+}
@@ -0,0 +1,459 @@
+//! 发票 / 费用账单 PDF 渲染
+//!
+//! - A4 纵向；律所抬头、客户、案件编号、计费期间、明细表（工时/费用）、小计/税额/合计与人民币大写金额
+//! - 中文使用 PDF 标准 CJK 字体 `STSong-Light`（Adobe-GB1，`UniGB-UCS2-H` 编码），不嵌入字体文件，
+//!   由阅读器提供宋体字形；ASCII 按半角、其余按全角估算宽度用于换行与右对齐
+//! - 明细过多时自动分页，每页重复表头并标注页码
+
+use chrono::NaiveDate;
+use pdf_writer::types::{CidFontType, FontFlags, SystemInfo};
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+use sea_orm::prelude::Decimal;
+
+use super::chinese_amount::rmb_uppercase;
+use super::invoice::round_money;
+use crate::error::AppResult;
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+/// 正文最低位置（其下为页脚）
+const BODY_BOTTOM: f32 = 70.0;
+const FONT: Name<'static> = Name(b"F1");
+
+const COL_DATE: f32 = MARGIN;
+const COL_KIND: f32 = 112.0;
+const COL_DESC: f32 = 145.0;
+const COL_DESC_WIDTH: f32 = 225.0;
+const COL_QTY_RIGHT: f32 = 415.0;
+const COL_RATE_RIGHT: f32 = 475.0;
+const COL_AMOUNT_RIGHT: f32 = PAGE_WIDTH - MARGIN;
+
+const BODY_SIZE: f32 = 9.5;
+const ROW_LEADING: f32 = 13.0;
+
+/// PDF 用的发票头
+#[derive(Debug, Clone)]
+pub struct PdfInvoice {
+    pub firm_name: String,
+    pub invoice_no: String,
+    pub issued_on: Option<NaiveDate>,
+    pub due_on: Option<NaiveDate>,
+    pub client_name: Option<String>,
+    pub case_code: Option<String>,
+    pub case_title: Option<String>,
+    pub period: Option<(NaiveDate, NaiveDate)>,
+    /// 不含税金额
+    pub amount: Decimal,
+    pub tax: Decimal,
+    pub total: Decimal,
+    pub notes: Option<String>,
+}
+
+/// PDF 用的明细行
+#[derive(Debug, Clone)]
+pub struct PdfLine {
+    pub date: NaiveDate,
+    /// 工时 / 费用
+    pub kind: String,
+    pub description: String,
+    /// 小时数（工时）
+    pub quantity: Option<Decimal>,
+    /// 小时费率（工时）
+    pub rate: Option<Decimal>,
+    pub amount: Decimal,
+}
+
+fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().map(|c| if c.is_ascii() { 0.5 } else { 1.0 }).sum::<f32>() * size
+}
+
+/// UCS-2 大端编码；超出 BMP 的字符以 `?` 代替，控制字符以空格代替
+fn encode(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len() * 2);
+    for c in text.chars() {
+        let code = match c as u32 {
+            0..=0x1f | 0x7f => 0x20,
+            v if v > 0xffff => u32::from(b'?'),
+            v => v,
+        };
+        out.extend_from_slice(&(code as u16).to_be_bytes());
+    }
+    out
+}
+
+/// 按宽度折行（中文逐字、英文不拆词；超长单词强制截断）
+fn wrap(text: &str, max_width: f32, size: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut word = String::new();
+        let flush_word = |line: &mut String, word: &mut String, lines: &mut Vec<String>| {
+            if word.is_empty() {
+                return;
+            }
+            if !line.is_empty() && text_width(&format!("{line}{word}"), size) > max_width {
+                lines.push(std::mem::take(line).trim_end().to_string());
+            }
+            for c in word.drain(..) {
+                if !line.is_empty() && text_width(&format!("{line}{c}"), size) > max_width {
+                    lines.push(std::mem::take(line));
+                }
+                line.push(c);
+            }
+        };
+        for c in paragraph.chars() {
+            if c.is_ascii_alphanumeric() {
+                word.push(c);
+                continue;
+            }
+            flush_word(&mut line, &mut word, &mut lines);
+            if !line.is_empty() && text_width(&format!("{line}{c}"), size) > max_width {
+                lines.push(std::mem::take(&mut line).trim_end().to_string());
+                if c == ' ' {
+                    continue;
+                }
+            }
+            line.push(c);
+        }
+        flush_word(&mut line, &mut word, &mut lines);
+        lines.push(line.trim_end().to_string());
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+fn money(value: Decimal) -> String {
+    let rounded = format!("{:.2}", round_money(value).abs());
+    let (int_part, frac) = rounded.split_once('.').unwrap_or((&rounded, "00"));
+    let mut grouped = String::new();
+    for (i, c) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    let sign = if value < Decimal::ZERO { "-" } else { "" };
+    format!("{sign}{grouped}.{frac}")
+}
+
+fn date_text(date: Option<NaiveDate>) -> String {
+    date.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "-".to_string())
+}
+
+struct Layout {
+    pages: Vec<Content>,
+    y: f32,
+}
+
+impl Layout {
+    fn new() -> Self {
+        Self { pages: vec![Content::new()], y: PAGE_HEIGHT - MARGIN }
+    }
+
+    fn page(&mut self) -> &mut Content {
+        self.pages.last_mut().expect("layout 至少有一页")
+    }
+
+    fn text(&mut self, x: f32, y: f32, size: f32, text: &str) {
+        let bytes = encode(text);
+        self.page().begin_text().set_font(FONT, size).next_line(x, y).show(Str(&bytes)).end_text();
+    }
+
+    fn text_right(&mut self, right: f32, y: f32, size: f32, text: &str) {
+        self.text(right - text_width(text, size), y, size, text);
+    }
+
+    fn text_center(&mut self, y: f32, size: f32, text: &str) {
+        self.text((PAGE_WIDTH - text_width(text, size)) / 2.0, y, size, text);
+    }
+
+    fn rule(&mut self, y: f32, width: f32) {
+        self.page().set_line_width(width).move_to(MARGIN, y).line_to(PAGE_WIDTH - MARGIN, y).stroke();
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(Content::new());
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    fn ensure_space(&mut self, height: f32) -> bool {
+        if self.y - height < BODY_BOTTOM {
+            self.new_page();
+            return true;
+        }
+        false
+    }
+
+    fn table_header(&mut self) {
+        let y = self.y - 12.0;
+        self.rule(self.y, 0.8);
+        self.text(COL_DATE, y, BODY_SIZE, "日期");
+        self.text(COL_KIND, y, BODY_SIZE, "类型");
+        self.text(COL_DESC, y, BODY_SIZE, "描述");
+        self.text_right(COL_QTY_RIGHT, y, BODY_SIZE, "小时");
+        self.text_right(COL_RATE_RIGHT, y, BODY_SIZE, "费率");
+        self.text_right(COL_AMOUNT_RIGHT, y, BODY_SIZE, "金额（元）");
+        self.rule(self.y - 17.0, 0.5);
+        self.y -= 19.0;
+    }
+}
+
+fn write_header(layout: &mut Layout, invoice: &PdfInvoice) {
+    layout.text_center(layout.y - 18.0, 18.0, &invoice.firm_name);
+    layout.text_center(layout.y - 44.0, 14.0, "法律服务费账单");
+    layout.y -= 56.0;
+    layout.rule(layout.y, 1.0);
+    layout.y -= 18.0;
+
+    let case_text = match (&invoice.case_code, &invoice.case_title) {
+        (Some(code), Some(title)) => format!("{code}  {title}"),
+        (Some(code), None) => code.clone(),
+        (None, Some(title)) => title.clone(),
+        (None, None) => "-".to_string(),
+    };
+    let period = invoice
+        .period
+        .map(|(from, to)| format!("{} 至 {}", from.format("%Y-%m-%d"), to.format("%Y-%m-%d")))
+        .unwrap_or_else(|| "-".to_string());
+    let left = [
+        format!("客户：{}", invoice.client_name.as_deref().unwrap_or("-")),
+        format!("案件：{case_text}"),
+        format!("计费期间：{period}"),
+    ];
+    let right = [
+        format!("账单编号：{}", invoice.invoice_no),
+        format!("开票日期：{}", date_text(invoice.issued_on)),
+        format!("付款期限：{}", date_text(invoice.due_on)),
+    ];
+    let right_x = 370.0;
+    let mut y = layout.y;
+    for (l, r) in left.iter().zip(right.iter()) {
+        let wrapped = wrap(l, right_x - MARGIN - 10.0, 10.0);
+        layout.text(right_x, y, 10.0, r);
+        for line in &wrapped {
+            layout.text(MARGIN, y, 10.0, line);
+            y -= 15.0;
+        }
+    }
+    layout.y = y - 8.0;
+}
+
+/// 明细行；描述超过一页时在行内分页续排（续页只重复表头，不重复日期与金额）
+fn write_lines(layout: &mut Layout, lines: &[PdfLine]) {
+    layout.table_header();
+    let page_capacity = PAGE_HEIGHT - MARGIN - 19.0 - BODY_BOTTOM;
+    for line in lines {
+        let description = wrap(&line.description, COL_DESC_WIDTH, BODY_SIZE);
+        let height = description.len() as f32 * ROW_LEADING + 4.0;
+        // 一页放得下的行整体换页；放不下的至少保证首行与日期、金额同页
+        let keep_together = if height <= page_capacity { height } else { ROW_LEADING + 4.0 };
+        if layout.ensure_space(keep_together) {
+            layout.table_header();
+        }
+        let y = layout.y - 10.0;
+        layout.text(COL_DATE, y, BODY_SIZE, &line.date.format("%Y-%m-%d").to_string());
+        layout.text(COL_KIND, y, BODY_SIZE, &line.kind);
+        if let Some(quantity) = line.quantity {
+            layout.text_right(COL_QTY_RIGHT, y, BODY_SIZE, &quantity.normalize().to_string());
+        }
+        if let Some(rate) = line.rate {
+            layout.text_right(COL_RATE_RIGHT, y, BODY_SIZE, &money(rate));
+        }
+        layout.text_right(COL_AMOUNT_RIGHT, y, BODY_SIZE, &money(line.amount));
+
+        let mut rest = description.as_slice();
+        loop {
+            let fit = (((layout.y - 4.0 - BODY_BOTTOM) / ROW_LEADING).floor() as usize).max(1).min(rest.len());
+            let top = layout.y - 10.0;
+            for (i, text) in rest[..fit].iter().enumerate() {
+                layout.text(COL_DESC, top - i as f32 * ROW_LEADING, BODY_SIZE, text);
+            }
+            layout.y -= fit as f32 * ROW_LEADING;
+            rest = &rest[fit..];
+            if rest.is_empty() {
+                break;
+            }
+            layout.new_page();
+            layout.table_header();
+        }
+        layout.y -= 4.0;
+    }
+    layout.rule(layout.y, 0.8);
+}
+
+fn write_totals(layout: &mut Layout, invoice: &PdfInvoice) -> AppResult<()> {
+    let uppercase = format!("人民币（大写）：{}", rmb_uppercase(invoice.total)?);
+    let uppercase_lines = wrap(&uppercase, PAGE_WIDTH - 2.0 * MARGIN, 11.0);
+    layout.ensure_space(70.0 + uppercase_lines.len() as f32 * 16.0);
+
+    let label_right = COL_RATE_RIGHT;
+    let rows = [("小计", invoice.amount), ("税额", invoice.tax), ("合计", invoice.total)];
+    for (label, value) in rows {
+        layout.y -= 16.0;
+        let size = if label == "合计" { 11.0 } else { BODY_SIZE + 0.5 };
+        layout.text_right(label_right, layout.y, size, label);
+        layout.text_right(COL_AMOUNT_RIGHT, layout.y, size, &format!("¥{}", money(value)));
+    }
+    layout.y -= 10.0;
+    for line in &uppercase_lines {
+        layout.y -= 16.0;
+        layout.text(MARGIN, layout.y, 11.0, line);
+    }
+    Ok(())
+}
+
+fn write_notes(layout: &mut Layout, notes: &str) {
+    let lines = wrap(&format!("备注：{}", notes.trim()), PAGE_WIDTH - 2.0 * MARGIN, BODY_SIZE);
+    layout.y -= 10.0;
+    for line in &lines {
+        layout.ensure_space(ROW_LEADING);
+        layout.y -= ROW_LEADING;
+        layout.text(MARGIN, layout.y, BODY_SIZE, line);
+    }
+}
+
+/// 渲染发票 PDF；合计超出大写金额支持范围时返回校验错误
+pub fn render_invoice_pdf(invoice: &PdfInvoice, lines: &[PdfLine]) -> AppResult<Vec<u8>> {
+    let mut layout = Layout::new();
+    write_header(&mut layout, invoice);
+    write_lines(&mut layout, lines);
+    write_totals(&mut layout, invoice)?;
+    if let Some(notes) = invoice.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+        write_notes(&mut layout, notes);
+    }
+
+    let page_count = layout.pages.len();
+    let footer_left = format!("{}  {}", invoice.firm_name, invoice.invoice_no);
+    for index in 0..page_count {
+        let footer_right = format!("第 {} / {} 页", index + 1, page_count);
+        let bytes_left = encode(&footer_left);
+        let bytes_right = encode(&footer_right);
+        let right_x = PAGE_WIDTH - MARGIN - text_width(&footer_right, 8.0);
+        let page = &mut layout.pages[index];
+        page.set_line_width(0.3).move_to(MARGIN, 45.0).line_to(PAGE_WIDTH - MARGIN, 45.0).stroke();
+        page.begin_text().set_font(FONT, 8.0).next_line(MARGIN, 32.0).show(Str(&bytes_left)).end_text();
+        page.begin_text().set_font(FONT, 8.0).next_line(right_x, 32.0).show(Str(&bytes_right)).end_text();
+    }
+
+    let mut next_id = Ref::new(1);
+    let mut alloc = || next_id.bump();
+    let catalog_id = alloc();
+    let pages_id = alloc();
+    let info_id = alloc();
+    let font_id = alloc();
+    let cid_font_id = alloc();
+    let descriptor_id = alloc();
+    let page_ids: Vec<(Ref, Ref)> = (0..page_count).map(|_| (alloc(), alloc())).collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(pages_id);
+    pdf.document_info(info_id)
+        .title(TextStr(&format!("{} {}", invoice.firm_name, invoice.invoice_no)))
+        .producer(TextStr("LawClick"));
+    pdf.pages(pages_id).kids(page_ids.iter().map(|(page, _)| *page)).count(page_count as i32);
+
+    pdf.type0_font(font_id)
+        .base_font(Name(b"STSong-Light-UniGB-UCS2-H"))
+        .encoding_predefined(Name(b"UniGB-UCS2-H"))
+        .descendant_font(cid_font_id);
+    let mut cid_font = pdf.cid_font(cid_font_id);
+    cid_font
+        .subtype(CidFontType::Type0)
+        .base_font(Name(b"STSong-Light"))
+        .system_info(SystemInfo { registry: Str(b"Adobe"), ordering: Str(b"GB1"), supplement: 2 })
+        .font_descriptor(descriptor_id)
+        .default_width(1000.0);
+    cid_font.widths().same(1, 95, 500.0).same(814, 939, 500.0).same(7712, 7716, 500.0).same(22355, 22357, 500.0);
+    cid_font.finish();
+    pdf.font_descriptor(descriptor_id)
+        .name(Name(b"STSong-Light"))
+        .flags(FontFlags::SYMBOLIC)
+        .bbox(Rect::new(-25.0, -254.0, 1000.0, 880.0))
+        .italic_angle(0.0)
+        .ascent(857.0)
+        .descent(-143.0)
+        .cap_height(857.0)
+        .stem_v(93.0);
+
+    for ((page_id, content_id), content) in page_ids.iter().zip(layout.pages) {
+        let mut page = pdf.page(*page_id);
+        page.parent(pages_id).media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT)).contents(*content_id);
+        page.resources().fonts().pair(FONT, font_id);
+        page.finish();
+        pdf.stream(*content_id, &content.finish());
+    }
+
+    Ok(pdf.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(line_count: usize) -> (PdfInvoice, Vec<PdfLine>) {
+        let date = NaiveDate::from_ymd_opt(2026, 9, 30).unwrap();
+        let invoice = PdfInvoice {
+            firm_name: "某某律师事务所".to_string(),
+            invoice_no: "INV-202609-0001".to_string(),
+            issued_on: Some(date),
+            due_on: Some(date),
+            client_name: Some("某某科技有限公司".to_string()),
+            case_code: Some("CASE-001".to_string()),
+            case_title: Some("买卖合同纠纷".to_string()),
+            period: Some((date, date)),
+            amount: Decimal::new(100000, 2),
+            tax: Decimal::new(6000, 2),
+            total: Decimal::new(106000, 2),
+            notes: None,
+        };
+        let line = PdfLine {
+            date,
+            kind: "工时".to_string(),
+            description: "起草并修改买卖合同，与客户电话沟通 negotiation strategy".to_string(),
+            quantity: Some(Decimal::new(15, 1)),
+            rate: Some(Decimal::new(100000, 2)),
+            amount: Decimal::new(150000, 2),
+        };
+        (invoice, vec![line; line_count])
+    }
+
+    #[test]
+    fn wraps_and_formats_text() {
+        assert_eq!(money(Decimal::new(123456789, 2)), "1,234,567.89");
+        assert_eq!(money(Decimal::new(-5, 1)), "-0.50");
+        let lines = wrap("起草并修改买卖合同 with client", 40.0, 10.0);
+        assert!(lines.iter().all(|l| text_width(l, 10.0) <= 40.0), "{lines:?}");
+        assert_eq!(lines.concat().replace(' ', ""), "起草并修改买卖合同withclient");
+    }
+
+    #[test]
+    fn renders_multi_page_pdf() {
+        let (invoice, lines) = sample(3);
+        let single = render_invoice_pdf(&invoice, &lines).unwrap();
+        assert!(single.starts_with(b"%PDF-"));
+        assert!(single.windows(7).any(|w| w == b"/Count "));
+
+        let (invoice, lines) = sample(120);
+        let multi = String::from_utf8_lossy(&render_invoice_pdf(&invoice, &lines).unwrap()).to_string();
+        let count: usize = multi
+            .split("/Count ")
+            .nth(1)
+            .and_then(|rest| rest.split(|c: char| !c.is_ascii_digit()).next())
+            .and_then(|n| n.parse().ok())
+            .unwrap();
+        assert!(count > 1);
+    }
+
+    #[test]
+    fn paginates_description_taller_than_a_page() {
+        let (_, mut lines) = sample(1);
+        lines[0].description = "起草并修改买卖合同条款".repeat(500);
+        let mut layout = Layout::new();
+        write_lines(&mut layout, &lines);
+        assert!(layout.pages.len() >= 3, "{} pages", layout.pages.len());
+        assert!(layout.y >= BODY_BOTTOM);
+    }
+}
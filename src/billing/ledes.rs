@@ -0,0 +1,434 @@
+//! 电子账单导出（LEDES 1998B / CSV）
+//!
+//! - LEDES 1998B：首行 `LEDES1998B[]`，次行为字段名，每条记录以 `[]` 结尾、字段以 `|` 分隔；
+//!   字段值中的 `|`、`[`、`]` 与换行会被替换为空格
+//! - 1998B 没有税额字段：`INVOICE_TOTAL` 为不含税金额，须等于各行 `LINE_ITEM_TOTAL` 之和
+//! - 明细调整额 = 开票金额 - 数量 × 单价（负数表示折让/核销）
+//! - UTBMS 代码：任务代码按任务类型、费用代码按费用类别完全匹配；活动代码按工时描述关键词匹配（最长关键词优先）；
+//!   匹配值 `*` 为该类型的缺省代码
+
+use chrono::NaiveDate;
+use sea_orm::prelude::Decimal;
+use std::collections::HashMap;
+
+use super::invoice::round_money;
+
+/// 缺省代码的匹配值
+pub const DEFAULT_MATCH: &str = "*";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Ledes1998b,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    Fee,
+    Expense,
+}
+
+impl LineKind {
+    fn ledes_type(self) -> &'static str {
+        match self {
+            Self::Fee => "F",
+            Self::Expense => "E",
+        }
+    }
+}
+
+/// UTBMS 代码表
+#[derive(Debug, Default)]
+pub struct UtbmsCodes {
+    tasks: HashMap<String, String>,
+    activities: Vec<(String, String)>,
+    expenses: HashMap<String, String>,
+}
+
+impl UtbmsCodes {
+    pub fn add_task(&mut self, task_type: &str, code: &str) {
+        self.tasks.insert(task_type.trim().to_string(), code.trim().to_string());
+    }
+
+    pub fn add_activity(&mut self, keyword: &str, code: &str) {
+        self.activities.push((keyword.trim().to_lowercase(), code.trim().to_string()));
+        self.activities.sort_by_key(|(keyword, _)| std::cmp::Reverse(keyword.chars().count()));
+    }
+
+    pub fn add_expense(&mut self, category: &str, code: &str) {
+        self.expenses.insert(category.trim().to_string(), code.trim().to_string());
+    }
+
+    pub fn task_code(&self, task_type: Option<&str>) -> Option<String> {
+        task_type
+            .and_then(|t| self.tasks.get(t.trim()))
+            .or_else(|| self.tasks.get(DEFAULT_MATCH))
+            .cloned()
+    }
+
+    pub fn activity_code(&self, description: &str) -> Option<String> {
+        let description = description.to_lowercase();
+        self.activities
+            .iter()
+            .find(|(keyword, _)| keyword != DEFAULT_MATCH && description.contains(keyword.as_str()))
+            .or_else(|| self.activities.iter().find(|(keyword, _)| keyword == DEFAULT_MATCH))
+            .map(|(_, code)| code.clone())
+    }
+
+    pub fn expense_code(&self, category: &str) -> Option<String> {
+        self.expenses.get(category.trim()).or_else(|| self.expenses.get(DEFAULT_MATCH)).cloned()
+    }
+}
+
+/// 导出用的发票头
+#[derive(Debug, Clone)]
+pub struct ExportInvoice {
+    pub invoice_no: String,
+    pub invoice_date: Option<NaiveDate>,
+    pub client_id: Option<String>,
+    /// 律所案件编号（caseCode）
+    pub matter_id: Option<String>,
+    pub law_firm_id: Option<String>,
+    /// 不含税金额
+    pub total: Decimal,
+    pub billing_start: Option<NaiveDate>,
+    pub billing_end: Option<NaiveDate>,
+    pub description: Option<String>,
+}
+
+/// 导出用的明细行
+#[derive(Debug, Clone)]
+pub struct ExportLine {
+    pub kind: LineKind,
+    pub date: NaiveDate,
+    /// 工时为小时数，费用为 1
+    pub units: Decimal,
+    pub unit_cost: Decimal,
+    pub total: Decimal,
+    pub task_code: Option<String>,
+    pub activity_code: Option<String>,
+    pub expense_code: Option<String>,
+    pub timekeeper_id: Option<String>,
+    pub timekeeper_name: Option<String>,
+    /// PT / AS / LA / OT 等
+    pub timekeeper_classification: Option<String>,
+    pub description: String,
+}
+
+impl ExportLine {
+    pub fn adjustment(&self) -> Decimal {
+        self.total - round_money(self.units * self.unit_cost)
+    }
+}
+
+fn blank(value: &Option<String>) -> bool {
+    value.as_deref().map_or(true, |v| v.trim().is_empty())
+}
+
+/// 校验导出必填字段，返回全部缺失项（为空表示可导出）
+pub fn validate(invoice: &ExportInvoice, lines: &[ExportLine], format: ExportFormat) -> Vec<String> {
+    let ledes = format == ExportFormat::Ledes1998b;
+    let mut errors = Vec::new();
+    let mut require = |ok: bool, message: String| {
+        if !ok {
+            errors.push(message);
+        }
+    };
+
+    require(!invoice.invoice_no.trim().is_empty(), "缺少发票号（INVOICE_NUMBER）".to_string());
+    require(invoice.invoice_date.is_some(), "发票未开具，缺少开票日期（INVOICE_DATE）".to_string());
+    require(!blank(&invoice.client_id), "缺少客户（CLIENT_ID）".to_string());
+    require(!blank(&invoice.matter_id), "缺少案件编号（LAW_FIRM_MATTER_ID）".to_string());
+    require(invoice.billing_start.is_some(), "缺少计费起始日（BILLING_START_DATE）".to_string());
+    require(invoice.billing_end.is_some(), "缺少计费截止日（BILLING_END_DATE）".to_string());
+    if ledes {
+        require(!blank(&invoice.law_firm_id), "未配置律所标识（LAW_FIRM_ID）".to_string());
+    }
+    require(!lines.is_empty(), "发票没有明细".to_string());
+
+    let line_sum: Decimal = lines.iter().map(|l| l.total).sum();
+    require(
+        line_sum == invoice.total,
+        format!("明细合计 {line_sum} 与发票金额 {} 不一致（INVOICE_TOTAL）", invoice.total),
+    );
+
+    for (i, line) in lines.iter().enumerate() {
+        let n = i + 1;
+        require(!line.description.trim().is_empty(), format!("第 {n} 行缺少描述（LINE_ITEM_DESCRIPTION）"));
+        require(line.units > Decimal::ZERO, format!("第 {n} 行数量无效（LINE_ITEM_NUMBER_OF_UNITS）"));
+        match line.kind {
+            LineKind::Fee => {
+                require(!blank(&line.timekeeper_id), format!("第 {n} 行缺少计时人（TIMEKEEPER_ID）"));
+                require(!blank(&line.timekeeper_name), format!("第 {n} 行缺少计时人姓名（TIMEKEEPER_NAME）"));
+                if ledes {
+                    require(
+                        !blank(&line.timekeeper_classification),
+                        format!("第 {n} 行缺少计时人级别（TIMEKEEPER_CLASSIFICATION）"),
+                    );
+                    require(!blank(&line.task_code), format!("第 {n} 行缺少 UTBMS 任务代码（LINE_ITEM_TASK_CODE）"));
+                    require(
+                        !blank(&line.activity_code),
+                        format!("第 {n} 行缺少 UTBMS 活动代码（LINE_ITEM_ACTIVITY_CODE）"),
+                    );
+                }
+            }
+            LineKind::Expense => {
+                if ledes {
+                    require(
+                        !blank(&line.expense_code),
+                        format!("第 {n} 行缺少 UTBMS 费用代码（LINE_ITEM_EXPENSE_CODE）"),
+                    );
+                }
+            }
+        }
+    }
+    errors
+}
+
+const LEDES_FIELDS: [&str; 24] = [
+    "INVOICE_DATE",
+    "INVOICE_NUMBER",
+    "CLIENT_ID",
+    "LAW_FIRM_MATTER_ID",
+    "INVOICE_TOTAL",
+    "BILLING_START_DATE",
+    "BILLING_END_DATE",
+    "INVOICE_DESCRIPTION",
+    "LINE_ITEM_NUMBER",
+    "EXP/FEE/INV_ADJ_TYPE",
+    "LINE_ITEM_NUMBER_OF_UNITS",
+    "LINE_ITEM_ADJUSTMENT_AMOUNT",
+    "LINE_ITEM_TOTAL",
+    "LINE_ITEM_DATE",
+    "LINE_ITEM_TASK_CODE",
+    "LINE_ITEM_EXPENSE_CODE",
+    "LINE_ITEM_ACTIVITY_CODE",
+    "TIMEKEEPER_ID",
+    "LINE_ITEM_DESCRIPTION",
+    "LAW_FIRM_ID",
+    "LINE_ITEM_UNIT_COST",
+    "TIMEKEEPER_NAME",
+    "TIMEKEEPER_CLASSIFICATION",
+    "CLIENT_MATTER_ID",
+];
+
+fn ledes_field(value: &str) -> String {
+    value.replace(['|', '[', ']', '\r', '\n'], " ").trim().to_string()
+}
+
+fn ledes_date(date: Option<NaiveDate>) -> String {
+    date.map(|d| d.format("%Y%m%d").to_string()).unwrap_or_default()
+}
+
+fn money(value: Decimal) -> String {
+    format!("{:.2}", round_money(value))
+}
+
+fn opt(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or_default()
+}
+
+/// 渲染 LEDES 1998B 文本（调用前应先通过 [`validate`]）
+pub fn render_ledes_1998b(invoice: &ExportInvoice, lines: &[ExportLine]) -> String {
+    let mut out = String::from("LEDES1998B[]\r\n");
+    out.push_str(&LEDES_FIELDS.join("|"));
+    out.push_str("[]\r\n");
+
+    for (i, line) in lines.iter().enumerate() {
+        let fields = [
+            ledes_date(invoice.invoice_date),
+            ledes_field(&invoice.invoice_no),
+            ledes_field(opt(&invoice.client_id)),
+            ledes_field(opt(&invoice.matter_id)),
+            money(invoice.total),
+            ledes_date(invoice.billing_start),
+            ledes_date(invoice.billing_end),
+            ledes_field(opt(&invoice.description)),
+            (i + 1).to_string(),
+            line.kind.ledes_type().to_string(),
+            line.units.normalize().to_string(),
+            money(line.adjustment()),
+            money(line.total),
+            ledes_date(Some(line.date)),
+            ledes_field(opt(&line.task_code)),
+            ledes_field(opt(&line.expense_code)),
+            ledes_field(opt(&line.activity_code)),
+            ledes_field(opt(&line.timekeeper_id)),
+            ledes_field(&line.description),
+            ledes_field(opt(&invoice.law_firm_id)),
+            money(line.unit_cost),
+            ledes_field(opt(&line.timekeeper_name)),
+            ledes_field(opt(&line.timekeeper_classification)),
+            // 客户侧案件编号暂无来源，留空
+            String::new(),
+        ];
+        out.push_str(&fields.join("|"));
+        out.push_str("[]\r\n");
+    }
+    out
+}
+
+/// CSV 表头（与 [`csv_records`] 的字段顺序一致）
+pub const CSV_HEADER: [&str; 18] = [
+    "invoiceNumber",
+    "invoiceDate",
+    "clientId",
+    "matterId",
+    "lineNumber",
+    "type",
+    "date",
+    "timekeeperId",
+    "timekeeperName",
+    "taskCode",
+    "activityCode",
+    "expenseCode",
+    "description",
+    "units",
+    "unitCost",
+    "adjustment",
+    "lineTotal",
+    "invoiceTotal",
+];
+
+/// CSV 记录（未转义；由调用方按 CSV 规则转义）
+pub fn csv_records(invoice: &ExportInvoice, lines: &[ExportLine]) -> Vec<[String; 18]> {
+    let invoice_date = invoice.invoice_date.map(|d| d.to_string()).unwrap_or_default();
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            [
+                invoice.invoice_no.clone(),
+                invoice_date.clone(),
+                opt(&invoice.client_id).to_string(),
+                opt(&invoice.matter_id).to_string(),
+                (i + 1).to_string(),
+                match line.kind {
+                    LineKind::Fee => "FEE".to_string(),
+                    LineKind::Expense => "EXPENSE".to_string(),
+                },
+                line.date.to_string(),
+                opt(&line.timekeeper_id).to_string(),
+                opt(&line.timekeeper_name).to_string(),
+                opt(&line.task_code).to_string(),
+                opt(&line.activity_code).to_string(),
+                opt(&line.expense_code).to_string(),
+                line.description.clone(),
+                line.units.normalize().to_string(),
+                money(line.unit_cost),
+                money(line.adjustment()),
+                money(line.total),
+                money(invoice.total),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn d(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 9, day).unwrap()
+    }
+
+    fn sample() -> (ExportInvoice, Vec<ExportLine>) {
+        let invoice = ExportInvoice {
+            invoice_no: "INV-202609-0001".to_string(),
+            invoice_date: Some(date(30)),
+            client_id: Some("client-1".to_string()),
+            matter_id: Some("CASE-001".to_string()),
+            law_firm_id: Some("91110000X".to_string()),
+            total: d("1700.00"),
+            billing_start: Some(date(1)),
+            billing_end: Some(date(30)),
+            description: Some("9月|服务费".to_string()),
+        };
+        let fee = ExportLine {
+            kind: LineKind::Fee,
+            date: date(3),
+            units: d("1.5"),
+            unit_cost: d("1000"),
+            total: d("1400.00"),
+            task_code: Some("L110".to_string()),
+            activity_code: Some("A101".to_string()),
+            expense_code: None,
+            timekeeper_id: Some("u1".to_string()),
+            timekeeper_name: Some("张三".to_string()),
+            timekeeper_classification: Some("PT".to_string()),
+            description: "起草[合同]".to_string(),
+        };
+        let expense = ExportLine {
+            kind: LineKind::Expense,
+            date: date(5),
+            units: Decimal::ONE,
+            unit_cost: d("300"),
+            total: d("300.00"),
+            task_code: None,
+            activity_code: None,
+            expense_code: Some("E110".to_string()),
+            timekeeper_id: None,
+            timekeeper_name: None,
+            timekeeper_classification: None,
+            description: "差旅".to_string(),
+        };
+        (invoice, vec![fee, expense])
+    }
+
+    #[test]
+    fn utbms_codes_match_exact_keyword_and_default() {
+        let mut codes = UtbmsCodes::default();
+        codes.add_task("诉讼", "L100");
+        codes.add_task("*", "L999");
+        codes.add_activity("起草", "A103");
+        codes.add_activity("起草合同", "A104");
+        codes.add_expense("差旅", "E110");
+
+        assert_eq!(codes.task_code(Some("诉讼")).as_deref(), Some("L100"));
+        assert_eq!(codes.task_code(None).as_deref(), Some("L999"));
+        assert_eq!(codes.activity_code("起草合同初稿").as_deref(), Some("A104"));
+        assert_eq!(codes.activity_code("开会"), None);
+        assert_eq!(codes.expense_code("复印"), None);
+    }
+
+    #[test]
+    fn validate_reports_missing_fields() {
+        let (invoice, lines) = sample();
+        assert!(validate(&invoice, &lines, ExportFormat::Ledes1998b).is_empty());
+
+        let mut bad = invoice.clone();
+        bad.law_firm_id = None;
+        bad.total = d("1000");
+        let mut bad_lines = lines.clone();
+        bad_lines[0].task_code = None;
+        let errors = validate(&bad, &bad_lines, ExportFormat::Ledes1998b);
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        // CSV 不要求 UTBMS 代码与律所标识
+        assert_eq!(validate(&bad, &bad_lines, ExportFormat::Csv).len(), 1);
+    }
+
+    #[test]
+    fn renders_ledes_records_with_adjustments() {
+        let (invoice, lines) = sample();
+        let out = render_ledes_1998b(&invoice, &lines);
+        let rows: Vec<&str> = out.split("\r\n").filter(|r| !r.is_empty()).collect();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0], "LEDES1998B[]");
+        assert!(rows.iter().all(|r| r.ends_with("[]")));
+
+        let fee: Vec<&str> = rows[2].trim_end_matches("[]").split('|').collect();
+        assert_eq!(fee.len(), 24);
+        assert_eq!(fee[0], "20260930");
+        assert_eq!(fee[7], "9月 服务费");
+        assert_eq!(fee[9], "F");
+        assert_eq!(fee[10], "1.5");
+        assert_eq!(fee[11], "-100.00");
+        assert_eq!(fee[12], "1400.00");
+        assert_eq!(fee[18], "起草 合同");
+    }
+}
@@ -0,0 +1,13 @@
+//! 计费规则模块
+//!
+//! 与数据库无关的纯计算逻辑（便于单元测试）。
+
+pub mod increments;
+pub mod rates;
+pub mod budget;
+pub mod invoice;
+pub mod receivables;
+pub mod ledes;
+pub mod chinese_amount;
+pub mod invoice_pdf;
+pub mod trust;
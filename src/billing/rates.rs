@@ -0,0 +1,76 @@
+//! 费率优先级
+//!
+//! 任务级覆盖 > 案件内指定律师 > 案件内指定角色 > 客户协议费率 > 用户默认小时费率。
+
+use sea_orm::prelude::Decimal;
+
+/// 最终费率来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateSource {
+    Task,
+    CaseUser,
+    CaseRole,
+    Client,
+    UserDefault,
+}
+
+impl RateSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RateSource::Task => "TASK",
+            RateSource::CaseUser => "CASE_USER",
+            RateSource::CaseRole => "CASE_ROLE",
+            RateSource::Client => "CLIENT",
+            RateSource::UserDefault => "USER_DEFAULT",
+        }
+    }
+}
+
+/// 各层级候选费率（未配置为 None）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RateCandidates {
+    pub task: Option<Decimal>,
+    pub case_user: Option<Decimal>,
+    pub case_role: Option<Decimal>,
+    pub client: Option<Decimal>,
+}
+
+impl RateCandidates {
+    /// 按优先级取第一个已配置的费率；均未配置时回落到用户默认费率
+    pub fn pick(&self, user_default: Decimal) -> (Decimal, RateSource) {
+        [
+            (self.task, RateSource::Task),
+            (self.case_user, RateSource::CaseUser),
+            (self.case_role, RateSource::CaseRole),
+            (self.client, RateSource::Client),
+        ]
+        .into_iter()
+        .find_map(|(rate, source)| rate.map(|r| (r, source)))
+        .unwrap_or((user_default, RateSource::UserDefault))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_highest_precedence_configured_rate() {
+        let d = |v: i64| Decimal::from(v);
+        let mut candidates = RateCandidates::default();
+        assert_eq!(candidates.pick(d(2000)), (d(2000), RateSource::UserDefault));
+
+        candidates.client = Some(d(1500));
+        assert_eq!(candidates.pick(d(2000)), (d(1500), RateSource::Client));
+
+        candidates.case_role = Some(d(1800));
+        assert_eq!(candidates.pick(d(2000)), (d(1800), RateSource::CaseRole));
+
+        candidates.case_user = Some(d(2500));
+        assert_eq!(candidates.pick(d(2000)), (d(2500), RateSource::CaseUser));
+
+        // 任务级覆盖即使为 0（如公益任务）也优先
+        candidates.task = Some(Decimal::ZERO);
+        assert_eq!(candidates.pick(d(2000)), (Decimal::ZERO, RateSource::Task));
+    }
+}
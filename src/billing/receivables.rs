@@ -0,0 +1,110 @@
+//! 应收账款：收款后的发票状态与账龄分段
+//!
+//! - 已收 >= 应收：PAID；未收清且已过付款截止日：OVERDUE；部分收款：PARTIAL；否则 PENDING
+//! - 账龄按开票日起算的自然日：0-30 / 31-60 / 61-90 / 90+
+
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::Decimal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceivableState {
+    Pending,
+    Partial,
+    Paid,
+    Overdue,
+}
+
+/// 按应收/已收金额与付款截止日推导发票状态
+pub fn receivable_state(
+    total: Decimal,
+    paid: Decimal,
+    due_date: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> ReceivableState {
+    if paid >= total {
+        ReceivableState::Paid
+    } else if due_date.is_some_and(|due| due < now) {
+        ReceivableState::Overdue
+    } else if paid > Decimal::ZERO {
+        ReceivableState::Partial
+    } else {
+        ReceivableState::Pending
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgingBucket {
+    Days0To30,
+    Days31To60,
+    Days61To90,
+    Over90,
+}
+
+impl AgingBucket {
+    pub fn for_age_days(days: i64) -> Self {
+        match days {
+            i64::MIN..=30 => Self::Days0To30,
+            31..=60 => Self::Days31To60,
+            61..=90 => Self::Days61To90,
+            _ => Self::Over90,
+        }
+    }
+}
+
+/// 各账龄段未收金额合计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AgingAmounts {
+    pub days_0_30: Decimal,
+    pub days_31_60: Decimal,
+    pub days_61_90: Decimal,
+    pub over_90: Decimal,
+}
+
+impl AgingAmounts {
+    pub fn add(&mut self, bucket: AgingBucket, amount: Decimal) {
+        let slot = match bucket {
+            AgingBucket::Days0To30 => &mut self.days_0_30,
+            AgingBucket::Days31To60 => &mut self.days_31_60,
+            AgingBucket::Days61To90 => &mut self.days_61_90,
+            AgingBucket::Over90 => &mut self.over_90,
+        };
+        *slot += amount;
+    }
+
+    pub fn total(&self) -> Decimal {
+        self.days_0_30 + self.days_31_60 + self.days_61_90 + self.over_90
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn state_follows_payments_and_due_date() {
+        let now = DateTime::parse_from_rfc3339("2026-10-18T00:00:00Z").unwrap().with_timezone(&Utc);
+        let total = Decimal::from(1000);
+        let past = Some(now - Duration::days(1));
+        let future = Some(now + Duration::days(1));
+
+        assert_eq!(receivable_state(total, Decimal::ZERO, future, now), ReceivableState::Pending);
+        assert_eq!(receivable_state(total, Decimal::from(400), future, now), ReceivableState::Partial);
+        assert_eq!(receivable_state(total, Decimal::from(400), past, now), ReceivableState::Overdue);
+        assert_eq!(receivable_state(total, Decimal::from(1000), past, now), ReceivableState::Paid);
+        assert_eq!(receivable_state(total, Decimal::ZERO, None, now), ReceivableState::Pending);
+    }
+
+    #[test]
+    fn aging_buckets_are_inclusive_at_upper_bound() {
+        let mut amounts = AgingAmounts::default();
+        for (days, amount) in [(0, 1), (30, 2), (31, 4), (60, 8), (61, 16), (90, 32), (91, 64)] {
+            amounts.add(AgingBucket::for_age_days(days), Decimal::from(amount));
+        }
+        assert_eq!(amounts.days_0_30, Decimal::from(3));
+        assert_eq!(amounts.days_31_60, Decimal::from(12));
+        assert_eq!(amounts.days_61_90, Decimal::from(48));
+        assert_eq!(amounts.over_90, Decimal::from(64));
+        assert_eq!(amounts.total(), Decimal::from(127));
+    }
+}
@@ -0,0 +1,148 @@
+//! 信托（预收款）账：复式记账规则
+//!
+//! - 两个科目：信托银行存款（资产，借增贷减）与客户信托分户（负债，贷增借减），分户按客户 + 案件划分
+//! - 存入：借 信托银行存款 / 贷 客户分户；划转经营账户（支付发票）与退款：借 客户分户 / 贷 信托银行存款
+//! - 冲正：按原流水的分录借贷互换，资金方向与原流水相反
+//! - 客户分户余额不得为负；对账时信托银行存款账面余额应等于各客户分户余额之和，并可与银行对账单核对
+
+use sea_orm::prelude::Decimal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ledger {
+    TrustBank,
+    ClientLedger,
+}
+
+/// 资金方向（相对客户分户）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// 存入，分户余额增加
+    Inflow,
+    /// 划转或退款，分户余额减少
+    Outflow,
+}
+
+impl Direction {
+    pub fn reversed(self) -> Self {
+        match self {
+            Self::Inflow => Self::Outflow,
+            Self::Outflow => Self::Inflow,
+        }
+    }
+}
+
+/// 单条分录（借贷只记一方）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Posting {
+    pub ledger: Ledger,
+    pub debit: Decimal,
+    pub credit: Decimal,
+}
+
+/// 一笔流水的两条分录
+pub fn postings(direction: Direction, amount: Decimal) -> [Posting; 2] {
+    let debit = |ledger| Posting { ledger, debit: amount, credit: Decimal::ZERO };
+    let credit = |ledger| Posting { ledger, debit: Decimal::ZERO, credit: amount };
+    match direction {
+        Direction::Inflow => [debit(Ledger::TrustBank), credit(Ledger::ClientLedger)],
+        Direction::Outflow => [debit(Ledger::ClientLedger), credit(Ledger::TrustBank)],
+    }
+}
+
+pub fn is_balanced(postings: &[Posting]) -> bool {
+    let debit: Decimal = postings.iter().map(|p| p.debit).sum();
+    let credit: Decimal = postings.iter().map(|p| p.credit).sum();
+    debit == credit
+}
+
+/// 科目余额：资产为借方余额，负债为贷方余额
+pub fn ledger_balance(ledger: Ledger, debit_total: Decimal, credit_total: Decimal) -> Decimal {
+    match ledger {
+        Ledger::TrustBank => debit_total - credit_total,
+        Ledger::ClientLedger => credit_total - debit_total,
+    }
+}
+
+/// 记账后的客户分户余额；余额将为负时返回 None
+pub fn balance_after(balance: Decimal, direction: Direction, amount: Decimal) -> Option<Decimal> {
+    let next = match direction {
+        Direction::Inflow => balance + amount,
+        Direction::Outflow => balance - amount,
+    };
+    (next >= Decimal::ZERO).then_some(next)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reconciliation {
+    /// 信托银行存款账面余额
+    pub book_balance: Decimal,
+    /// 客户分户余额合计
+    pub client_total: Decimal,
+    /// 账面余额 - 分户合计
+    pub book_difference: Decimal,
+    /// 银行对账单余额 - 账面余额（未提供对账单时为 None）
+    pub statement_difference: Option<Decimal>,
+}
+
+impl Reconciliation {
+    pub fn is_balanced(&self) -> bool {
+        self.book_difference.is_zero() && self.statement_difference.map_or(true, |d| d.is_zero())
+    }
+}
+
+/// 三方核对：银行对账单、信托银行存款账面、客户分户合计
+pub fn reconcile(book_balance: Decimal, client_total: Decimal, statement_balance: Option<Decimal>) -> Reconciliation {
+    Reconciliation {
+        book_balance,
+        client_total,
+        book_difference: book_balance - client_total,
+        statement_difference: statement_balance.map(|s| s - book_balance),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(v: i64) -> Decimal {
+        Decimal::from(v)
+    }
+
+    #[test]
+    fn postings_are_balanced_and_reversible() {
+        let inflow = postings(Direction::Inflow, d(500));
+        assert!(is_balanced(&inflow));
+        assert_eq!(inflow[0], Posting { ledger: Ledger::TrustBank, debit: d(500), credit: Decimal::ZERO });
+        assert_eq!(inflow[1], Posting { ledger: Ledger::ClientLedger, debit: Decimal::ZERO, credit: d(500) });
+
+        let outflow = postings(Direction::Inflow.reversed(), d(500));
+        assert!(is_balanced(&outflow));
+        assert_eq!(outflow[0].ledger, Ledger::ClientLedger);
+        assert_eq!(outflow[0].debit, d(500));
+
+        let client: Vec<&Posting> =
+            inflow.iter().chain(&outflow).filter(|p| p.ledger == Ledger::ClientLedger).collect();
+        let client_debit: Decimal = client.iter().map(|p| p.debit).sum();
+        let client_credit: Decimal = client.iter().map(|p| p.credit).sum();
+        assert_eq!(ledger_balance(Ledger::ClientLedger, client_debit, client_credit), Decimal::ZERO);
+    }
+
+    #[test]
+    fn balance_never_goes_negative() {
+        assert_eq!(balance_after(d(100), Direction::Outflow, d(100)), Some(Decimal::ZERO));
+        assert_eq!(balance_after(d(100), Direction::Outflow, d(101)), None);
+        assert_eq!(balance_after(Decimal::ZERO, Direction::Inflow, d(5)), Some(d(5)));
+    }
+
+    #[test]
+    fn reconciles_book_clients_and_statement() {
+        let ok = reconcile(d(1000), d(1000), Some(d(1000)));
+        assert!(ok.is_balanced());
+        assert!(reconcile(d(1000), d(1000), None).is_balanced());
+
+        let off = reconcile(d(1000), d(900), Some(d(1200)));
+        assert!(!off.is_balanced());
+        assert_eq!(off.book_difference, d(100));
+        assert_eq!(off.statement_difference, Some(d(200)));
+    }
+}
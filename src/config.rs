@@ -3,6 +3,9 @@
 //! - 严禁“未配置也能跑”的隐式默认值（尤其是 DB/密钥）。
 //! - 所有关键配置必须显式由环境变量提供，避免生产误配置被掩盖。
 
+use sea_orm::prelude::Decimal;
+use std::str::FromStr;
+
 use crate::error::{AppError, AppResult};
 
 /// 应用配置
@@ -34,6 +37,30 @@ pub struct AppConfig {
     pub openai_base_url: String,
     /// OpenAI 模型（可选；仅在配置 Key 时生效）
     pub openai_model: String,
+    /// 任务到期提醒的提前量（分钟，逗号分隔；默认 1440,60 即提前 24h 与 1h）
+    pub task_reminder_offsets_minutes: Vec<i64>,
+    /// 任务提醒调度间隔（秒；0 表示不启动调度）
+    pub task_reminder_interval_secs: u64,
+    /// 封顶收费案件的预算预警阈值（合同金额百分比，逗号分隔；默认 80,100）
+    pub case_budget_warn_percents: Vec<u32>,
+    /// 单段计时最长运行时长（分钟；超过即视为闲置并自动暂停）
+    pub timer_idle_max_minutes: u64,
+    /// 用户离开/离线超过该时长（分钟）后自动暂停其计时
+    pub timer_idle_away_grace_minutes: u64,
+    /// 闲置计时扫描间隔（秒；0 表示不启动调度）
+    pub timer_idle_sweep_interval_secs: u64,
+    /// 开票默认税率（INVOICE_TAX_RATE，默认 0.06）
+    pub invoice_tax_rate: Decimal,
+    /// 发票默认付款期限（天）（INVOICE_DUE_DAYS，默认 30）
+    pub invoice_due_days: u64,
+    /// 发票逾期标记间隔（秒）（INVOICE_OVERDUE_INTERVAL_SECS，默认 3600；0 关闭）
+    pub invoice_overdue_interval_secs: u64,
+    /// 电子账单（LEDES）中的律所标识（LEDES_LAW_FIRM_ID，通常为律所税号；未配置时拒绝 LEDES 导出）
+    pub ledes_law_firm_id: Option<String>,
+    /// 合同到期提醒的提前量（天，逗号分隔；默认 30,7,1）
+    pub contract_expiry_reminder_days: Vec<i64>,
+    /// 合同到期扫描间隔（秒）（CONTRACT_EXPIRY_INTERVAL_SECS，默认 86400 即每日；0 关闭）
+    pub contract_expiry_interval_secs: u64,
 }
 
 fn env_required(name: &str) -> AppResult<String> {
@@ -68,6 +95,10 @@ fn env_u16(name: &str) -> Option<u16> {
     env_optional(name).and_then(|v| v.parse::<u16>().ok())
 }
 
+fn env_u64(name: &str) -> Option<u64> {
+    env_optional(name).and_then(|v| v.parse::<u64>().ok())
+}
+
 fn parse_csv_list(raw: Option<String>) -> Vec<String> {
     raw.unwrap_or_default()
         .split(',')
@@ -111,6 +142,80 @@ impl AppConfig {
         let openai_base_url = env_optional("OPENAI_BASE_URL").unwrap_or_else(|| "https://api.openai.com/v1".to_string());
         let openai_model = env_optional("OPENAI_MODEL").unwrap_or_else(|| "gpt-4o-mini".to_string());
 
+        let task_reminder_offsets_minutes = match env_optional("TASK_REMINDER_OFFSETS_MINUTES") {
+            None => vec![24 * 60, 60],
+            Some(raw) => {
+                let mut offsets = Vec::new();
+                for item in parse_csv_list(Some(raw)) {
+                    match item.parse::<i64>() {
+                        Ok(v) if v > 0 => offsets.push(v),
+                        _ => {
+                            return Err(AppError::Internal(format!(
+                                "TASK_REMINDER_OFFSETS_MINUTES 含无效值：{item}（需为正整数分钟）"
+                            )))
+                        }
+                    }
+                }
+                offsets
+            }
+        };
+        let task_reminder_interval_secs = env_u64("TASK_REMINDER_INTERVAL_SECS").unwrap_or(60);
+
+        let case_budget_warn_percents = match env_optional("CASE_BUDGET_WARN_PERCENTS") {
+            None => vec![80, 100],
+            Some(raw) => {
+                let mut percents = Vec::new();
+                for item in parse_csv_list(Some(raw)) {
+                    match item.parse::<u32>() {
+                        Ok(v) if (1..=100).contains(&v) => percents.push(v),
+                        _ => {
+                            return Err(AppError::Internal(format!(
+                                "CASE_BUDGET_WARN_PERCENTS 含无效值：{item}（需为 1-100 的整数）"
+                            )))
+                        }
+                    }
+                }
+                percents.sort_unstable();
+                percents.dedup();
+                percents
+            }
+        };
+
+        let timer_idle_max_minutes = env_u64("TIMER_IDLE_MAX_MINUTES").unwrap_or(240).max(1);
+        let timer_idle_away_grace_minutes = env_u64("TIMER_IDLE_AWAY_GRACE_MINUTES").unwrap_or(30);
+        let timer_idle_sweep_interval_secs = env_u64("TIMER_IDLE_SWEEP_INTERVAL_SECS").unwrap_or(300);
+
+        let invoice_tax_rate = match env_optional("INVOICE_TAX_RATE") {
+            None => Decimal::new(6, 2),
+            Some(raw) => match Decimal::from_str(raw.trim()) {
+                Ok(v) if v >= Decimal::ZERO && v < Decimal::ONE => v,
+                _ => return Err(AppError::Internal(format!("INVOICE_TAX_RATE 无效：{raw}（需为 0-1 之间的小数）"))),
+            },
+        };
+        let invoice_due_days = env_u64("INVOICE_DUE_DAYS").unwrap_or(30);
+        let invoice_overdue_interval_secs = env_u64("INVOICE_OVERDUE_INTERVAL_SECS").unwrap_or(3600);
+        let ledes_law_firm_id =
+            env_optional("LEDES_LAW_FIRM_ID").map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+        let contract_expiry_reminder_days = match env_optional("CONTRACT_EXPIRY_REMINDER_DAYS") {
+            None => vec![30, 7, 1],
+            Some(raw) => {
+                let mut days = Vec::new();
+                for item in parse_csv_list(Some(raw)) {
+                    match item.parse::<i64>() {
+                        Ok(v) if v > 0 => days.push(v),
+                        _ => {
+                            return Err(AppError::Internal(format!(
+                                "CONTRACT_EXPIRY_REMINDER_DAYS 含无效值：{item}（需为正整数天数）"
+                            )))
+                        }
+                    }
+                }
+                days
+            }
+        };
+        let contract_expiry_interval_secs = env_u64("CONTRACT_EXPIRY_INTERVAL_SECS").unwrap_or(24 * 3600);
+
         Ok(Self {
             database_url,
             jwt_secret,
@@ -125,6 +230,18 @@ impl AppConfig {
             openai_api_key,
             openai_base_url,
             openai_model,
+            task_reminder_offsets_minutes,
+            task_reminder_interval_secs,
+            case_budget_warn_percents,
+            timer_idle_max_minutes,
+            timer_idle_away_grace_minutes,
+            timer_idle_sweep_interval_secs,
+            invoice_tax_rate,
+            invoice_due_days,
+            invoice_overdue_interval_secs,
+            ledes_law_firm_id,
+            contract_expiry_reminder_days,
+            contract_expiry_interval_secs,
         })
     }
 }
@@ -0,0 +1,92 @@
+//! ApprovalRequest Entity
+//!
+//! 审批申请实体，与 Prisma `model ApprovalRequest` 保持一致；多级审批的步骤快照见 `approval_step`。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 审批类型（与 Prisma ApprovalType 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "ApprovalType")]
+pub enum ApprovalType {
+    #[sea_orm(string_value = "LEAVE")]
+    Leave,
+    #[sea_orm(string_value = "EXPENSE")]
+    Expense,
+    #[sea_orm(string_value = "PURCHASE")]
+    Purchase,
+    #[sea_orm(string_value = "CONTRACT")]
+    Contract,
+    #[sea_orm(string_value = "INVOICE")]
+    Invoice,
+    #[sea_orm(string_value = "OTHER")]
+    Other,
+}
+
+/// 审批状态（与 Prisma ApprovalStatus 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "ApprovalStatus")]
+pub enum ApprovalStatus {
+    #[sea_orm(string_value = "DRAFT")]
+    Draft,
+    #[sea_orm(string_value = "PENDING")]
+    Pending,
+    #[sea_orm(string_value = "APPROVED")]
+    Approved,
+    #[sea_orm(string_value = "REJECTED")]
+    Rejected,
+    #[sea_orm(string_value = "CANCELLED")]
+    Cancelled,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "ApprovalRequest")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "type")]
+    pub approval_type: ApprovalType,
+
+    pub title: String,
+    pub description: Option<String>,
+
+    #[sea_orm(column_name = "caseId")]
+    pub case_id: Option<String>,
+
+    #[sea_orm(column_name = "clientId")]
+    pub client_id: Option<String>,
+
+    #[sea_orm(column_name = "requesterId")]
+    pub requester_id: String,
+
+    #[sea_orm(column_name = "approverId")]
+    pub approver_id: Option<String>,
+
+    pub status: ApprovalStatus,
+    pub amount: Option<Decimal>,
+    pub metadata: Option<Json>,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "submittedAt")]
+    pub submitted_at: Option<DateTimeUtc>,
+
+    #[sea_orm(column_name = "resolvedAt")]
+    pub resolved_at: Option<DateTimeUtc>,
+
+    #[sea_orm(column_name = "approvalNote")]
+    pub approval_note: Option<String>,
+
+    #[sea_orm(column_name = "currentStep")]
+    pub current_step: Option<i32>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,37 @@
+//! ApprovalRule Entity
+//!
+//! 审批路由规则实体，与 Prisma `model ApprovalRule` 保持一致；同一审批类型按金额下限分档。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::approval_request::ApprovalType;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "ApprovalRule")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "type")]
+    pub approval_type: ApprovalType,
+
+    #[sea_orm(column_name = "minAmount")]
+    pub min_amount: Decimal,
+
+    pub name: Option<String>,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,49 @@
+//! ApprovalRuleStep Entity
+//!
+//! 审批路由规则中的顺序步骤，与 Prisma `model ApprovalRuleStep` 保持一致。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::user::Role;
+
+/// 审批人来源（与 Prisma ApprovalApproverKind 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "ApprovalApproverKind")]
+pub enum ApproverKind {
+    #[sea_orm(string_value = "CASE_HANDLER")]
+    CaseHandler,
+    #[sea_orm(string_value = "SUPERVISOR")]
+    Supervisor,
+    #[sea_orm(string_value = "ROLE")]
+    Role,
+    #[sea_orm(string_value = "USER")]
+    User,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "ApprovalRuleStep")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "ruleId")]
+    pub rule_id: String,
+
+    #[sea_orm(column_name = "stepOrder")]
+    pub step_order: i32,
+
+    #[sea_orm(column_name = "approverKind")]
+    pub approver_kind: ApproverKind,
+
+    #[sea_orm(column_name = "approverRole")]
+    pub approver_role: Option<Role>,
+
+    #[sea_orm(column_name = "approverUserId")]
+    pub approver_user_id: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,69 @@
+//! ApprovalStep Entity
+//!
+//! 审批申请的步骤快照，与 Prisma `model ApprovalStep` 保持一致；提交时按路由规则解析出具体审批人。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::user::Role;
+
+/// 审批步骤状态（与 Prisma ApprovalStepStatus 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "ApprovalStepStatus")]
+pub enum ApprovalStepStatus {
+    #[sea_orm(string_value = "WAITING")]
+    Waiting,
+    #[sea_orm(string_value = "PENDING")]
+    Pending,
+    #[sea_orm(string_value = "APPROVED")]
+    Approved,
+    #[sea_orm(string_value = "REJECTED")]
+    Rejected,
+    #[sea_orm(string_value = "SKIPPED")]
+    Skipped,
+    #[sea_orm(string_value = "CANCELLED")]
+    Cancelled,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "ApprovalStep")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "requestId")]
+    pub request_id: String,
+
+    #[sea_orm(column_name = "stepOrder")]
+    pub step_order: i32,
+
+    #[sea_orm(column_name = "approverId")]
+    pub approver_id: Option<String>,
+
+    #[sea_orm(column_name = "approverRole")]
+    pub approver_role: Option<Role>,
+
+    pub status: ApprovalStepStatus,
+    pub note: Option<String>,
+
+    #[sea_orm(column_name = "actedById")]
+    pub acted_by_id: Option<String>,
+
+    #[sea_orm(column_name = "actedAt")]
+    pub acted_at: Option<DateTimeUtc>,
+
+    /// 代理审批时为被代理的审批人
+    #[sea_orm(column_name = "onBehalfOfId")]
+    pub on_behalf_of_id: Option<String>,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
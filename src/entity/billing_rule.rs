@@ -0,0 +1,49 @@
+//! BillingRule Entity
+//!
+//! 计费取整规则实体，与 Prisma `model BillingRule` 保持一致；`caseId` 为空表示租户默认规则。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 取整方式（与 Prisma BillingRoundingMode 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "BillingRoundingMode")]
+pub enum BillingRoundingMode {
+    #[sea_orm(string_value = "UP")]
+    Up,
+    #[sea_orm(string_value = "NEAREST")]
+    Nearest,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "BillingRule")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "caseId")]
+    pub case_id: Option<String>,
+
+    #[sea_orm(column_name = "incrementMinutes")]
+    pub increment_minutes: i32,
+
+    #[sea_orm(column_name = "roundingMode")]
+    pub rounding_mode: BillingRoundingMode,
+
+    #[sea_orm(column_name = "minimumMinutes")]
+    pub minimum_minutes: i32,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,79 @@
+//! Contract Entity
+//!
+//! 合同台账实体，与 Prisma `model Contract` 保持一致；租户内合同编号唯一，删除为软删除。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 合同状态（与 Prisma ContractStatus 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "ContractStatus")]
+pub enum ContractStatus {
+    #[sea_orm(string_value = "DRAFT")]
+    Draft,
+    #[sea_orm(string_value = "SIGNED")]
+    Signed,
+    #[sea_orm(string_value = "ACTIVE")]
+    Active,
+    #[sea_orm(string_value = "EXPIRED")]
+    Expired,
+    #[sea_orm(string_value = "CANCELLED")]
+    Cancelled,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "Contract")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "contractNo")]
+    pub contract_no: String,
+
+    pub title: String,
+    pub status: ContractStatus,
+    pub amount: Option<Decimal>,
+
+    #[sea_orm(column_name = "startDate")]
+    pub start_date: Option<DateTimeUtc>,
+
+    #[sea_orm(column_name = "endDate")]
+    pub end_date: Option<DateTimeUtc>,
+
+    #[sea_orm(column_name = "signedAt")]
+    pub signed_at: Option<DateTimeUtc>,
+
+    pub notes: Option<String>,
+
+    #[sea_orm(column_name = "caseId")]
+    pub case_id: Option<String>,
+
+    #[sea_orm(column_name = "clientId")]
+    pub client_id: Option<String>,
+
+    #[sea_orm(column_name = "documentId")]
+    pub document_id: Option<String>,
+
+    #[sea_orm(column_name = "creatorId")]
+    pub creator_id: String,
+
+    #[sea_orm(column_name = "deletedAt")]
+    pub deleted_at: Option<DateTimeUtc>,
+
+    #[sea_orm(column_name = "deletedById")]
+    pub deleted_by_id: Option<String>,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
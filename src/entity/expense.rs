@@ -0,0 +1,73 @@
+//! Expense Entity
+//!
+//! 费用记录实体，与 Prisma `model Expense` 保持一致。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 费用状态（与 Prisma ExpenseStatus 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "ExpenseStatus")]
+pub enum ExpenseStatus {
+    #[sea_orm(string_value = "PENDING")]
+    Pending,
+    #[sea_orm(string_value = "APPROVED")]
+    Approved,
+    #[sea_orm(string_value = "REJECTED")]
+    Rejected,
+    #[sea_orm(string_value = "REIMBURSED")]
+    Reimbursed,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "Expense")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "caseId")]
+    pub case_id: Option<String>,
+
+    #[sea_orm(column_name = "userId")]
+    pub user_id: String,
+
+    pub category: String,
+    pub amount: Decimal,
+    pub description: Option<String>,
+
+    pub status: ExpenseStatus,
+
+    /// 审批通过时确认是否向客户计费（需关联案件）
+    #[sea_orm(column_name = "isBillable")]
+    pub is_billable: bool,
+
+    #[sea_orm(column_name = "reviewerId")]
+    pub reviewer_id: Option<String>,
+
+    #[sea_orm(column_name = "reviewedAt")]
+    pub reviewed_at: Option<DateTimeUtc>,
+
+    /// 审批意见 / 退回原因
+    #[sea_orm(column_name = "reviewNote")]
+    pub review_note: Option<String>,
+
+    #[sea_orm(column_name = "expenseDate")]
+    pub expense_date: DateTimeUtc,
+
+    /// 收据等附件的对象存储 key
+    pub attachments: Vec<String>,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
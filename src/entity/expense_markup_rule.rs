@@ -0,0 +1,33 @@
+//! ExpenseMarkupRule Entity
+//!
+//! 费用加收规则实体，与 Prisma `model ExpenseMarkupRule` 保持一致；租户内按类别唯一。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "ExpenseMarkupRule")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    pub category: String,
+
+    /// 加收百分比，如 10 表示按费用金额的 110% 开票
+    #[sea_orm(column_name = "markupPercent")]
+    pub markup_percent: Decimal,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
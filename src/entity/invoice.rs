@@ -0,0 +1,84 @@
+//! Invoice Entity
+//!
+//! 发票实体，与 Prisma `model Invoice` 保持一致；`invoiceNo` 租户内唯一。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 发票状态（与 Prisma InvoiceStatus 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "InvoiceStatus")]
+pub enum InvoiceStatus {
+    #[sea_orm(string_value = "DRAFT")]
+    Draft,
+    #[sea_orm(string_value = "PENDING")]
+    Pending,
+    #[sea_orm(string_value = "PAID")]
+    Paid,
+    #[sea_orm(string_value = "PARTIAL")]
+    Partial,
+    #[sea_orm(string_value = "OVERDUE")]
+    Overdue,
+    #[sea_orm(string_value = "CANCELLED")]
+    Cancelled,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "Invoice")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "invoiceNo")]
+    pub invoice_no: String,
+
+    #[sea_orm(column_name = "caseId")]
+    pub case_id: Option<String>,
+
+    #[sea_orm(column_name = "clientId")]
+    pub client_id: Option<String>,
+
+    pub amount: Decimal,
+    pub tax: Decimal,
+
+    #[sea_orm(column_name = "totalAmount")]
+    pub total_amount: Decimal,
+
+    pub status: InvoiceStatus,
+
+    #[sea_orm(column_name = "issuedAt")]
+    pub issued_at: Option<DateTimeUtc>,
+
+    #[sea_orm(column_name = "dueDate")]
+    pub due_date: Option<DateTimeUtc>,
+
+    #[sea_orm(column_name = "periodStart")]
+    pub period_start: Option<DateTimeUtc>,
+
+    #[sea_orm(column_name = "periodEnd")]
+    pub period_end: Option<DateTimeUtc>,
+
+    #[sea_orm(column_name = "issuedById")]
+    pub issued_by_id: Option<String>,
+
+    /// 发票 PDF 对应的案件文档
+    #[sea_orm(column_name = "documentId")]
+    pub document_id: Option<String>,
+
+    pub description: Option<String>,
+    pub notes: Option<String>,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
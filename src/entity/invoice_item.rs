@@ -0,0 +1,61 @@
+//! InvoiceItem Entity
+//!
+//! 发票明细实体，与 Prisma `model InvoiceItem` 保持一致；工时/费用各自只能开票一次。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 明细类型（与 Prisma InvoiceItemType 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "InvoiceItemType")]
+pub enum InvoiceItemType {
+    #[sea_orm(string_value = "TIME")]
+    Time,
+    #[sea_orm(string_value = "EXPENSE")]
+    Expense,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "InvoiceItem")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "invoiceId")]
+    pub invoice_id: String,
+
+    #[sea_orm(column_name = "type")]
+    pub item_type: InvoiceItemType,
+
+    #[sea_orm(column_name = "timeLogId")]
+    pub time_log_id: Option<String>,
+
+    #[sea_orm(column_name = "expenseId")]
+    pub expense_id: Option<String>,
+
+    pub date: DateTimeUtc,
+    pub description: String,
+
+    /// 计费小时数（工时）
+    pub quantity: Option<Decimal>,
+    /// 小时费率（工时）
+    pub rate: Option<Decimal>,
+
+    /// 调整前金额
+    #[sea_orm(column_name = "originalAmount")]
+    pub original_amount: Decimal,
+
+    /// 开票金额
+    pub amount: Decimal,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
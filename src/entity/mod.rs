@@ -15,3 +15,28 @@ pub mod document_version;
 pub mod event;
 pub mod event_participant;
 pub mod notification;
+pub mod tenant_signal;
+pub mod task_dependency;
+pub mod task_recurrence;
+pub mod task_comment;
+pub mod project;
+pub mod project_member;
+pub mod task_reminder;
+pub mod time_log_review;
+pub mod billing_rule;
+pub mod rate_card;
+pub mod invoice;
+pub mod invoice_item;
+pub mod expense;
+pub mod payment;
+pub mod expense_markup_rule;
+pub mod utbms_code_mapping;
+pub mod trust_transaction;
+pub mod trust_entry;
+pub mod contract;
+pub mod contract_reminder;
+pub mod approval_request;
+pub mod approval_rule;
+pub mod approval_rule_step;
+pub mod approval_step;
+pub mod out_of_office;
@@ -25,6 +25,28 @@ pub enum NotificationType {
     InviteAccepted,
     #[sea_orm(string_value = "INVITE_REJECTED")]
     InviteRejected,
+    #[sea_orm(string_value = "TASK_MENTIONED")]
+    TaskMentioned,
+    #[sea_orm(string_value = "TASK_DUE_SOON")]
+    TaskDueSoon,
+    #[sea_orm(string_value = "TASK_OVERDUE")]
+    TaskOverdue,
+    #[sea_orm(string_value = "TIME_LOG_REVIEWED")]
+    TimeLogReviewed,
+    #[sea_orm(string_value = "CASE_BUDGET_THRESHOLD")]
+    CaseBudgetThreshold,
+    #[sea_orm(string_value = "TIMER_IDLE_PAUSED")]
+    TimerIdlePaused,
+    #[sea_orm(string_value = "EXPENSE_REVIEWED")]
+    ExpenseReviewed,
+    #[sea_orm(string_value = "CONTRACT_EXPIRING")]
+    ContractExpiring,
+    #[sea_orm(string_value = "APPROVAL_REQUESTED")]
+    ApprovalRequested,
+    #[sea_orm(string_value = "APPROVAL_UPDATED")]
+    ApprovalUpdated,
+    #[sea_orm(string_value = "APPROVAL_DELEGATED")]
+    ApprovalDelegated,
 }
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
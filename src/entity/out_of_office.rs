@@ -0,0 +1,41 @@
+//! OutOfOffice Entity
+//!
+//! 外出 / 休假时段实体，与 Prisma `model OutOfOffice` 保持一致；`delegateId` 为该时段内的审批代理人。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "OutOfOffice")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "userId")]
+    pub user_id: String,
+
+    #[sea_orm(column_name = "startTime")]
+    pub start_time: DateTimeUtc,
+
+    #[sea_orm(column_name = "endTime")]
+    pub end_time: DateTimeUtc,
+
+    pub reason: Option<String>,
+
+    #[sea_orm(column_name = "delegateId")]
+    pub delegate_id: Option<String>,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,62 @@
+//! Payment Entity
+//!
+//! 收款记录实体，与 Prisma `model Payment` 保持一致。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 付款方式（与 Prisma PaymentMethod 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "PaymentMethod")]
+pub enum PaymentMethod {
+    #[sea_orm(string_value = "BANK")]
+    Bank,
+    #[sea_orm(string_value = "CASH")]
+    Cash,
+    #[sea_orm(string_value = "CHECK")]
+    Check,
+    #[sea_orm(string_value = "ONLINE")]
+    Online,
+    #[sea_orm(string_value = "OTHER")]
+    Other,
+    /// 信托（预收款）划转，由信托账生成，不可直接删除
+    #[sea_orm(string_value = "TRUST")]
+    Trust,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "Payment")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "invoiceId")]
+    pub invoice_id: String,
+
+    pub amount: Decimal,
+    pub method: PaymentMethod,
+
+    #[sea_orm(column_name = "receivedAt")]
+    pub received_at: DateTimeUtc,
+
+    pub reference: Option<String>,
+    pub note: Option<String>,
+
+    #[sea_orm(column_name = "recorderId")]
+    pub recorder_id: Option<String>,
+
+    /// 信托划转冲正后作废的时间；作废的收款不计入已收金额
+    #[sea_orm(column_name = "voidedAt")]
+    pub voided_at: Option<DateTimeUtc>,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
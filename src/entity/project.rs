@@ -0,0 +1,82 @@
+//! Project Entity
+//!
+//! 非案件项目实体（行政/人事/市场/IT 等），与 Prisma `model Project` 保持一致。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 项目状态枚举（与 Prisma ProjectStatus 对应）
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "ProjectStatus")]
+pub enum ProjectStatus {
+    #[sea_orm(string_value = "PLANNED")]
+    Planned,
+    #[sea_orm(string_value = "ACTIVE")]
+    Active,
+    #[sea_orm(string_value = "ON_HOLD")]
+    OnHold,
+    #[sea_orm(string_value = "COMPLETED")]
+    Completed,
+    #[sea_orm(string_value = "ARCHIVED")]
+    Archived,
+}
+
+/// 项目类型枚举（与 Prisma ProjectType 对应）
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "ProjectType")]
+pub enum ProjectType {
+    #[sea_orm(string_value = "ADMIN")]
+    Admin,
+    #[sea_orm(string_value = "HR")]
+    Hr,
+    #[sea_orm(string_value = "MARKETING")]
+    Marketing,
+    #[sea_orm(string_value = "IT")]
+    It,
+    #[sea_orm(string_value = "BUSINESS")]
+    Business,
+    #[sea_orm(string_value = "OTHER")]
+    Other,
+}
+
+/// 项目实体
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "Project")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "projectCode")]
+    pub project_code: String,
+
+    pub title: String,
+    pub description: Option<String>,
+
+    pub status: ProjectStatus,
+
+    #[sea_orm(column_name = "type")]
+    pub project_type: ProjectType,
+
+    #[sea_orm(column_name = "ownerId")]
+    pub owner_id: String,
+
+    #[sea_orm(column_name = "deletedAt")]
+    pub deleted_at: Option<DateTimeUtc>,
+
+    #[sea_orm(column_name = "deletedById")]
+    pub deleted_by_id: Option<String>,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
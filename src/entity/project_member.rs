@@ -0,0 +1,42 @@
+//! ProjectMember Entity
+//!
+//! 项目成员表实体，与 Prisma `model ProjectMember` 保持一致。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 项目成员角色枚举（与 Prisma ProjectRole 对应）
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "ProjectRole")]
+pub enum ProjectRole {
+    #[sea_orm(string_value = "OWNER")]
+    Owner,
+    #[sea_orm(string_value = "MEMBER")]
+    Member,
+    #[sea_orm(string_value = "VIEWER")]
+    Viewer,
+}
+
+/// 项目成员实体
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "ProjectMember")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "projectId")]
+    pub project_id: String,
+
+    #[sea_orm(column_name = "userId")]
+    pub user_id: String,
+
+    pub role: ProjectRole,
+
+    #[sea_orm(column_name = "joinedAt")]
+    pub joined_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,67 @@
+//! RateCard Entity
+//!
+//! 费率卡实体，与 Prisma `model RateCard` 保持一致；按 `scope` 使用对应的关联字段。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::user::Role;
+
+/// 费率卡适用范围（与 Prisma RateCardScope 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "RateCardScope")]
+pub enum RateCardScope {
+    #[sea_orm(string_value = "TASK")]
+    Task,
+    #[sea_orm(string_value = "CASE_USER")]
+    CaseUser,
+    #[sea_orm(string_value = "CASE_ROLE")]
+    CaseRole,
+    #[sea_orm(string_value = "CLIENT")]
+    Client,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "RateCard")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    pub scope: RateCardScope,
+
+    #[sea_orm(column_name = "taskId")]
+    pub task_id: Option<String>,
+
+    #[sea_orm(column_name = "caseId")]
+    pub case_id: Option<String>,
+
+    #[sea_orm(column_name = "userId")]
+    pub user_id: Option<String>,
+
+    pub role: Option<Role>,
+
+    #[sea_orm(column_name = "clientId")]
+    pub client_id: Option<String>,
+
+    #[sea_orm(column_name = "hourlyRate")]
+    pub hourly_rate: Decimal,
+
+    pub note: Option<String>,
+
+    #[sea_orm(column_name = "createdById")]
+    pub created_by_id: String,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
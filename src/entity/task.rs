@@ -33,6 +33,44 @@ pub enum TaskPriority {
     P3Low,
 }
 
+/// 检查清单条目（`Task.checklist` JSON 数组元素）
+///
+/// 历史数据可能仅有 `{text, done}`；缺失字段按默认值解析。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistItem {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+    #[serde(default)]
+    pub done_by: Option<String>,
+    #[serde(default)]
+    pub done_at: Option<DateTimeUtc>,
+    #[serde(default)]
+    pub assignee_id: Option<String>,
+}
+
+/// 解析 checklist JSON（非数组/非法元素视为空；缺失 id 的历史条目按下标补 `legacy-{n}`）
+pub fn parse_checklist(raw: Option<&Json>) -> Vec<ChecklistItem> {
+    let Some(items) = raw.and_then(|v| v.as_array()) else {
+        return vec![];
+    };
+    items
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, v)| {
+            let mut item: ChecklistItem = serde_json::from_value(v.clone()).ok()?;
+            if item.id.trim().is_empty() {
+                item.id = format!("legacy-{idx}");
+            }
+            Some(item)
+        })
+        .collect()
+}
+
 /// 任务实体
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "Task")]
@@ -40,6 +78,10 @@ pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: String,
 
+    /// 与所属案件 / 项目的租户一致
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
     pub title: String,
     pub description: Option<String>,
 
@@ -74,10 +116,22 @@ pub struct Model {
     pub updated_at: DateTimeUtc,
 
     #[sea_orm(column_name = "caseId")]
-    pub case_id: String,
+    pub case_id: Option<String>,
+
+    /// 非案件项目（与 caseId 二选一）
+    #[sea_orm(column_name = "projectId")]
+    pub project_id: Option<String>,
 
     #[sea_orm(column_name = "assigneeId")]
     pub assignee_id: Option<String>,
+
+    /// 周期任务系列（TaskRecurrence）
+    #[sea_orm(column_name = "recurrenceId")]
+    pub recurrence_id: Option<String>,
+
+    /// 系列内序号（从 1 开始）
+    #[sea_orm(column_name = "recurrenceIndex")]
+    pub recurrence_index: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,41 @@
+//! TaskComment Entity
+//!
+//! 任务评论实体，与 Prisma `model TaskComment` 保持一致。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "TaskComment")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "taskId")]
+    pub task_id: String,
+
+    #[sea_orm(column_name = "authorId")]
+    pub author_id: String,
+
+    pub content: String,
+
+    #[sea_orm(column_name = "mentionedUserIds")]
+    pub mentioned_user_ids: Vec<String>,
+
+    #[sea_orm(column_name = "editedAt")]
+    pub edited_at: Option<DateTimeUtc>,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,34 @@
+//! TaskDependency Entity
+//!
+//! 任务依赖实体，与 Prisma `model TaskDependency` 保持一致。
+//! 语义：`task_id` 依赖 `depends_on_id`（前置任务）。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "TaskDependency")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "taskId")]
+    pub task_id: String,
+
+    #[sea_orm(column_name = "dependsOnId")]
+    pub depends_on_id: String,
+
+    #[sea_orm(column_name = "createdById")]
+    pub created_by_id: Option<String>,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
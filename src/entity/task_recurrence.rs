@@ -0,0 +1,42 @@
+//! TaskRecurrence Entity
+//!
+//! 周期任务系列实体，与 Prisma `model TaskRecurrence` 保持一致。
+//! `rule` 为 RRULE 文本（不含 `RRULE:` 前缀），`anchor_date` 即 DTSTART。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "TaskRecurrence")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    pub rule: String,
+
+    #[sea_orm(column_name = "anchorDate")]
+    pub anchor_date: DateTimeUtc,
+
+    #[sea_orm(column_name = "occurrenceCount")]
+    pub occurrence_count: i32,
+
+    #[sea_orm(column_name = "endedAt")]
+    pub ended_at: Option<DateTimeUtc>,
+
+    #[sea_orm(column_name = "createdById")]
+    pub created_by_id: Option<String>,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,35 @@
+//! TaskReminder Entity
+//!
+//! 任务到期提醒/逾期升级发送记录实体，与 Prisma `model TaskReminder` 保持一致。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "TaskReminder")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "taskId")]
+    pub task_id: String,
+
+    #[sea_orm(column_name = "recipientId")]
+    pub recipient_id: String,
+
+    pub kind: String,
+
+    #[sea_orm(column_name = "dueDate")]
+    pub due_date: DateTimeUtc,
+
+    #[sea_orm(column_name = "sentAt")]
+    pub sent_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
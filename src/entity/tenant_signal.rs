@@ -0,0 +1,40 @@
+//! TenantSignal Entity
+//!
+//! 租户级实时信号实体，与 Prisma `model TenantSignal` 保持一致。
+//! 复合主键（tenantId + kind）：每种信号在每个租户仅 1 行，`version` 单调递增。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// TenantSignalKind（与 Prisma TenantSignalKind 对应）
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "TenantSignalKind")]
+pub enum TenantSignalKind {
+    #[sea_orm(string_value = "TASKS_CHANGED")]
+    TasksChanged,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "TenantSignal")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false, column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub kind: TenantSignalKind,
+
+    pub payload: Option<Json>,
+
+    pub version: i32,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -28,6 +28,9 @@ pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: String,
 
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
     pub description: String,
 
     #[sea_orm(column_name = "startTime")]
@@ -38,6 +41,18 @@ pub struct Model {
 
     pub duration: i32,
 
+    /// 按计费规则取整后的计费时长（秒）；不计费时为空
+    #[sea_orm(column_name = "billedDuration")]
+    pub billed_duration: Option<i32>,
+
+    /// 闲置自动暂停的时间点；待用户选择保留/丢弃闲置时段时非空
+    #[sea_orm(column_name = "idlePausedAt")]
+    pub idle_paused_at: Option<DateTimeUtc>,
+
+    /// 自动暂停时已闲置的秒数
+    #[sea_orm(column_name = "idleSeconds")]
+    pub idle_seconds: Option<i32>,
+
     pub status: TimeLogStatus,
 
     #[sea_orm(column_name = "isBillable")]
@@ -49,6 +64,10 @@ pub struct Model {
     #[sea_orm(column_name = "billingAmount")]
     pub billing_amount: Option<Decimal>,
 
+    /// 核销金额：封顶案件超出合同金额部分 / 固定收费案件的工时价值
+    #[sea_orm(column_name = "writeOffAmount")]
+    pub write_off_amount: Option<Decimal>,
+
     #[sea_orm(column_name = "createdAt")]
     pub created_at: DateTimeUtc,
 
@@ -0,0 +1,51 @@
+//! TimeLogReview Entity
+//!
+//! 工时审核留痕实体，与 Prisma `model TimeLogReview` 保持一致。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 审核动作（与 Prisma TimeLogReviewAction 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "TimeLogReviewAction")]
+pub enum TimeLogReviewAction {
+    #[sea_orm(string_value = "APPROVE")]
+    Approve,
+    #[sea_orm(string_value = "REJECT")]
+    Reject,
+    #[sea_orm(string_value = "ADJUST")]
+    Adjust,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "TimeLogReview")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "timeLogId")]
+    pub time_log_id: String,
+
+    #[sea_orm(column_name = "reviewerId")]
+    pub reviewer_id: String,
+
+    /// 代理审核时为被代理的审核人
+    #[sea_orm(column_name = "onBehalfOfId")]
+    pub on_behalf_of_id: Option<String>,
+
+    pub action: TimeLogReviewAction,
+    pub reason: Option<String>,
+    pub before: Json,
+    pub after: Json,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
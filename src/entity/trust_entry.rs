@@ -0,0 +1,50 @@
+//! TrustEntry Entity
+//!
+//! 信托分录实体，与 Prisma `model TrustEntry` 保持一致；每行只记借方或贷方之一。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 信托科目（与 Prisma TrustAccount 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "TrustAccount")]
+pub enum TrustAccount {
+    /// 信托银行存款（资产，借增贷减）
+    #[sea_orm(string_value = "TRUST_BANK")]
+    TrustBank,
+    /// 客户信托分户（负债，贷增借减）
+    #[sea_orm(string_value = "CLIENT_LEDGER")]
+    ClientLedger,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "TrustEntry")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "transactionId")]
+    pub transaction_id: String,
+
+    pub account: TrustAccount,
+
+    #[sea_orm(column_name = "clientId")]
+    pub client_id: String,
+
+    #[sea_orm(column_name = "caseId")]
+    pub case_id: Option<String>,
+
+    pub debit: Decimal,
+    pub credit: Decimal,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
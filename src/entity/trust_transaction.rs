@@ -0,0 +1,71 @@
+//! TrustTransaction Entity
+//!
+//! 信托（预收款）流水实体，与 Prisma `model TrustTransaction` 保持一致；每笔流水对应一组借贷平衡的分录，只增不改。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 信托流水类型（与 Prisma TrustTransactionType 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "TrustTransactionType")]
+pub enum TrustTransactionType {
+    /// 客户存入预收款
+    #[sea_orm(string_value = "DEPOSIT")]
+    Deposit,
+    /// 划转至经营账户，用于支付发票
+    #[sea_orm(string_value = "TRANSFER")]
+    Transfer,
+    /// 退还客户
+    #[sea_orm(string_value = "REFUND")]
+    Refund,
+    /// 冲正某笔流水
+    #[sea_orm(string_value = "REVERSAL")]
+    Reversal,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "TrustTransaction")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    #[sea_orm(column_name = "type")]
+    pub kind: TrustTransactionType,
+
+    #[sea_orm(column_name = "clientId")]
+    pub client_id: String,
+
+    #[sea_orm(column_name = "caseId")]
+    pub case_id: Option<String>,
+
+    pub amount: Decimal,
+
+    #[sea_orm(column_name = "occurredAt")]
+    pub occurred_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "invoiceId")]
+    pub invoice_id: Option<String>,
+
+    #[sea_orm(column_name = "paymentId")]
+    pub payment_id: Option<String>,
+
+    #[sea_orm(column_name = "reversesId")]
+    pub reverses_id: Option<String>,
+
+    pub reference: Option<String>,
+    pub note: Option<String>,
+
+    #[sea_orm(column_name = "createdById")]
+    pub created_by_id: Option<String>,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
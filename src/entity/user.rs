@@ -6,7 +6,7 @@ use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// 用户角色枚举（与 Prisma Role 对应）
-#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "Role")]
 pub enum Role {
     #[sea_orm(string_value = "PARTNER")]
@@ -14,6 +14,7 @@ pub enum Role {
     #[sea_orm(string_value = "SENIOR_LAWYER")]
     SeniorLawyer,
     #[sea_orm(string_value = "LAWYER")]
+    #[default]
     Lawyer,
     #[sea_orm(string_value = "TRAINEE")]
     Trainee,
@@ -31,12 +32,6 @@ pub enum Role {
     FirmEntity,
 }
 
-impl Default for Role {
-    fn default() -> Self {
-        Self::Lawyer
-    }
-}
-
 /// 用户状态枚举
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "UserStatus")]
@@ -62,6 +57,13 @@ pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: String,
     
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    /// 当前会话/工作区使用的租户（可切换）
+    #[sea_orm(column_name = "activeTenantId")]
+    pub active_tenant_id: String,
+
     #[sea_orm(unique)]
     pub email: String,
     
@@ -0,0 +1,50 @@
+//! UtbmsCodeMapping Entity
+//!
+//! UTBMS 代码映射实体，与 Prisma `model UtbmsCodeMapping` 保持一致；租户内按（类型, 匹配值）唯一。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 映射类型（与 Prisma UtbmsCodeKind 对应）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "UtbmsCodeKind")]
+pub enum UtbmsCodeKind {
+    /// 任务代码，按 `Task.taskType` 完全匹配
+    #[sea_orm(string_value = "TASK")]
+    Task,
+    /// 活动代码，按 `TimeLog.description` 关键词匹配
+    #[sea_orm(string_value = "ACTIVITY")]
+    Activity,
+    /// 费用代码，按 `Expense.category` 完全匹配
+    #[sea_orm(string_value = "EXPENSE")]
+    Expense,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "UtbmsCodeMapping")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    #[sea_orm(column_name = "tenantId")]
+    pub tenant_id: String,
+
+    pub kind: UtbmsCodeKind,
+
+    /// 匹配值；`*` 表示该类型的缺省代码
+    #[sea_orm(column_name = "matchValue")]
+    pub match_value: String,
+
+    pub code: String,
+
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
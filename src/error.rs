@@ -27,6 +27,9 @@ pub enum AppError {
     #[error("验证错误: {message}")]
     ValidationWithDetails { message: String, details: JsonValue },
     
+    #[error("冲突: {0}")]
+    Conflict(String),
+    
     #[error("数据库错误: {0}")]
     Database(String),
     
@@ -53,6 +56,7 @@ impl IntoResponse for AppError {
             AppError::ValidationWithDetails { message, details } => {
                 (StatusCode::BAD_REQUEST, "validation", message.clone(), Some(details.clone()))
             }
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, "conflict", msg.clone(), None),
             AppError::Database(msg) => (StatusCode::INTERNAL_SERVER_ERROR, "database", msg.clone(), None),
             AppError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, "internal", msg.clone(), None),
         };
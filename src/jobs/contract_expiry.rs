@@ -0,0 +1,280 @@
+//! 合同到期提醒与到期标记
+//!
+//! - 到期前：按 `CONTRACT_EXPIRY_REMINDER_DAYS`（默认 30 / 7 / 1 天）向合同创建人与案件承办人发送 `CONTRACT_EXPIRING`；
+//!   同一时刻只发送已到达的最小提前量档位（停机补偿时不会连发多档）
+//! - 去重：先插入 `ContractReminder`（contractId, recipientId, kind, endDate 唯一，冲突即跳过）再写通知，
+//!   二者同一事务；修改到期日后按新日期重新提醒
+//! - 到期后：已签署 / 生效中的合同置为 EXPIRED
+//! - 仅处理未删除且状态为 SIGNED / ACTIVE 的合同
+
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::AppState;
+use crate::entity::contract::{self, ContractStatus};
+use crate::entity::{case, contract_reminder, notification};
+use crate::error::{AppError, AppResult};
+
+const BATCH_SIZE: u64 = 500;
+
+fn tracked_statuses() -> Vec<ContractStatus> {
+    vec![ContractStatus::Signed, ContractStatus::Active]
+}
+
+fn reminder_kind(days: i64) -> String {
+    format!("DUE_IN_{days}D")
+}
+
+/// 当前时刻应发送的提前量档位（天）；已到期或尚未进入任何提前量窗口时返回 None
+fn reminder_days_for(end_date: DateTime<Utc>, now: DateTime<Utc>, offsets_days: &[i64]) -> Option<i64> {
+    if now >= end_date {
+        return None;
+    }
+    offsets_days.iter().copied().filter(|days| end_date - Duration::days(*days) <= now).min()
+}
+
+pub fn spawn(state: Arc<AppState>) {
+    let interval_secs = state.config.contract_expiry_interval_secs;
+    if interval_secs == 0 {
+        tracing::info!("⏸️ 合同到期提醒调度已关闭（CONTRACT_EXPIRY_INTERVAL_SECS=0）");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            ticker.tick().await;
+            match run_once(&state, Utc::now()).await {
+                Ok((0, 0)) => {}
+                Ok((expired, sent)) => tracing::info!("📄 合同到期扫描：标记到期 {expired} 份，发送提醒 {sent} 条"),
+                Err(e) => tracing::warn!("合同到期扫描失败: {e}"),
+            }
+        }
+    });
+}
+
+/// 执行一轮扫描，返回（本轮置为到期的合同数, 实际发送的通知数）
+///
+/// 按 (endDate, id) 游标分批扫描整个提醒窗口，合同再多也不会有合同错过较早的提醒档位
+pub async fn run_once(state: &AppState, now: DateTime<Utc>) -> AppResult<(u64, usize)> {
+    let expired = contract::Entity::update_many()
+        .set(contract::ActiveModel {
+            status: sea_orm::ActiveValue::Set(ContractStatus::Expired),
+            updated_at: sea_orm::ActiveValue::Set(now),
+            ..Default::default()
+        })
+        .filter(contract::Column::DeletedAt.is_null())
+        .filter(contract::Column::Status.is_in(tracked_statuses()))
+        .filter(contract::Column::EndDate.lte(now))
+        .exec(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("标记到期合同失败: {e}")))?
+        .rows_affected;
+
+    let offsets = &state.config.contract_expiry_reminder_days;
+    let Some(max_days) = offsets.iter().copied().max() else {
+        return Ok((expired, 0));
+    };
+    let mut sent = 0;
+    let mut cursor: Option<(DateTime<Utc>, String)> = None;
+    loop {
+        let mut select = contract::Entity::find()
+            .filter(contract::Column::DeletedAt.is_null())
+            .filter(contract::Column::Status.is_in(tracked_statuses()))
+            .filter(contract::Column::EndDate.gt(now))
+            .filter(contract::Column::EndDate.lte(now + Duration::days(max_days)));
+        if let Some((end_date, id)) = &cursor {
+            select = select.filter(
+                Condition::any().add(contract::Column::EndDate.gt(*end_date)).add(
+                    Condition::all()
+                        .add(contract::Column::EndDate.eq(*end_date))
+                        .add(contract::Column::Id.gt(id.as_str())),
+                ),
+            );
+        }
+        let candidates = select
+            .order_by_asc(contract::Column::EndDate)
+            .order_by_asc(contract::Column::Id)
+            .limit(BATCH_SIZE)
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询待提醒合同失败: {e}")))?;
+
+        let exhausted = (candidates.len() as u64) < BATCH_SIZE;
+        cursor = candidates.last().and_then(|c| c.end_date.map(|d| (d, c.id.clone())));
+        sent += process_batch(state, now, offsets, candidates).await?;
+        if exhausted || cursor.is_none() {
+            return Ok((expired, sent));
+        }
+    }
+}
+
+async fn process_batch(
+    state: &AppState,
+    now: DateTime<Utc>,
+    offsets: &[i64],
+    candidates: Vec<contract::Model>,
+) -> AppResult<usize> {
+    if candidates.is_empty() {
+        return Ok(0);
+    }
+
+    // 已发送记录：批量查出后跳过，避免对每份已提醒的合同逐条开事务
+    let contract_ids: Vec<String> = candidates.iter().map(|c| c.id.clone()).collect();
+    let already_sent: HashSet<(String, String, String, DateTime<Utc>)> = contract_reminder::Entity::find()
+        .filter(contract_reminder::Column::ContractId.is_in(contract_ids))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询提醒记录失败: {e}")))?
+        .into_iter()
+        .map(|r| (r.contract_id, r.recipient_id, r.kind, r.end_date))
+        .collect();
+
+    let case_ids: Vec<String> = candidates.iter().filter_map(|c| c.case_id.clone()).collect();
+    let handlers: HashMap<String, Option<String>> = if case_ids.is_empty() {
+        HashMap::new()
+    } else {
+        case::Entity::find()
+            .filter(case::Column::Id.is_in(case_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询案件失败: {e}")))?
+            .into_iter()
+            .map(|c| (c.id, c.handler_id))
+            .collect()
+    };
+
+    let mut sent = 0;
+    for model in candidates {
+        let Some(end_date) = model.end_date else {
+            continue;
+        };
+        let Some(days) = reminder_days_for(end_date, now, offsets) else {
+            continue;
+        };
+
+        let mut recipients = vec![model.creator_id.clone()];
+        let handler = model.case_id.as_ref().and_then(|id| handlers.get(id)).cloned().flatten();
+        if let Some(handler_id) = handler {
+            if !recipients.contains(&handler_id) {
+                recipients.push(handler_id);
+            }
+        }
+        for recipient_id in recipients {
+            if already_sent.contains(&(model.id.clone(), recipient_id.clone(), reminder_kind(days), end_date)) {
+                continue;
+            }
+            if send_reminder(state, &model, end_date, days, &recipient_id).await? {
+                sent += 1;
+            }
+        }
+    }
+    Ok(sent)
+}
+
+/// 写入去重记录并发送通知；已发送过（唯一约束冲突）时返回 false
+async fn send_reminder(
+    state: &AppState,
+    model: &contract::Model,
+    end_date: DateTime<Utc>,
+    days: i64,
+    recipient_id: &str,
+) -> AppResult<bool> {
+    let model = model.clone();
+    let recipient_id = recipient_id.to_string();
+
+    state
+        .db
+        .transaction::<_, bool, AppError>(|txn| {
+            Box::pin(async move {
+                let now = Utc::now();
+                let inserted = contract_reminder::Entity::insert(contract_reminder::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    tenant_id: sea_orm::ActiveValue::Set(model.tenant_id.clone()),
+                    contract_id: sea_orm::ActiveValue::Set(model.id.clone()),
+                    recipient_id: sea_orm::ActiveValue::Set(recipient_id.clone()),
+                    kind: sea_orm::ActiveValue::Set(reminder_kind(days)),
+                    end_date: sea_orm::ActiveValue::Set(end_date),
+                    sent_at: sea_orm::ActiveValue::Set(now),
+                })
+                .on_conflict(
+                    OnConflict::columns([
+                        contract_reminder::Column::ContractId,
+                        contract_reminder::Column::RecipientId,
+                        contract_reminder::Column::Kind,
+                        contract_reminder::Column::EndDate,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .do_nothing()
+                .exec_without_returning(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("写入提醒记录失败: {e}")))?;
+
+                if !matches!(inserted, sea_orm::TryInsertResult::Inserted(n) if n > 0) {
+                    return Ok(false);
+                }
+
+                let end_text = end_date.format("%Y-%m-%d");
+                notification::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    user_id: sea_orm::ActiveValue::Set(recipient_id),
+                    actor_id: sea_orm::ActiveValue::Set(None),
+                    notification_type: sea_orm::ActiveValue::Set(notification::NotificationType::ContractExpiring),
+                    title: sea_orm::ActiveValue::Set(format!("合同即将到期：{}", model.title)),
+                    content: sea_orm::ActiveValue::Set(Some(format!(
+                        "合同 {} 将于 {end_text} 到期，剩余不足 {days} 天，请及时续签或办理终止",
+                        model.contract_no
+                    ))),
+                    action_url: sea_orm::ActiveValue::Set(Some(format!("/contracts/{}", model.id))),
+                    metadata: sea_orm::ActiveValue::Set(Some(json!({
+                        "contractId": model.id,
+                        "contractNo": model.contract_no,
+                        "caseId": model.case_id,
+                        "clientId": model.client_id,
+                        "endDate": end_date,
+                        "reminderKind": reminder_kind(days),
+                    }))),
+                    read_at: sea_orm::ActiveValue::Set(None),
+                    created_at: sea_orm::ActiveValue::Set(now),
+                }
+                .insert(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("写入通知失败: {e}")))?;
+
+                Ok(true)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_smallest_reached_offset_in_days() {
+        let end = DateTime::parse_from_rfc3339("2026-12-31T00:00:00Z").unwrap().with_timezone(&Utc);
+        let offsets = [30, 7, 1];
+
+        assert_eq!(reminder_days_for(end, end - Duration::days(31), &offsets), None);
+        assert_eq!(reminder_days_for(end, end - Duration::days(30), &offsets), Some(30));
+        assert_eq!(reminder_days_for(end, end - Duration::days(6), &offsets), Some(7));
+        assert_eq!(reminder_days_for(end, end - Duration::hours(3), &offsets), Some(1));
+        assert_eq!(reminder_days_for(end, end, &offsets), None);
+        assert_eq!(reminder_kind(7), "DUE_IN_7D");
+    }
+}
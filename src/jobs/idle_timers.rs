@@ -0,0 +1,240 @@
+//! 闲置计时检测与自动暂停
+//!
+//! - 单段计时（自开始/恢复起）运行超过 `TIMER_IDLE_MAX_MINUTES`（默认 240）即视为闲置
+//! - 用户状态为 AWAY / OFFLINE 且最后活动早于 `TIMER_IDLE_AWAY_GRACE_MINUTES`（默认 30）前，同样视为闲置
+//! - 闲置计时自动暂停在最后活动时间（不早于本段开始、不晚于本段上限），之后的闲置时段暂不计入时长，
+//!   记录在 `idlePausedAt` / `idleSeconds`，并发送 `TIMER_IDLE_PAUSED` 通知；
+//!   用户可通过 `POST /timelogs/:id/idle` 选择保留（计入时长）或丢弃
+//! - 事务内加锁复核状态与开始时间，避免与用户手动暂停/停止并发
+
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::AppState;
+use crate::entity::user::UserStatus;
+use crate::entity::{notification, time_log, user};
+use crate::error::{AppError, AppResult};
+
+const BATCH_SIZE: u64 = 500;
+
+/// 闲置判定：返回应暂停到的时间点；未闲置返回 None
+fn idle_pause_at(
+    segment_start: DateTime<Utc>,
+    last_active_at: Option<DateTime<Utc>>,
+    status: &UserStatus,
+    now: DateTime<Utc>,
+    max_segment: Duration,
+    away_grace: Duration,
+) -> Option<DateTime<Utc>> {
+    let segment_end = segment_start + max_segment;
+    let last_seen = last_active_at.unwrap_or(segment_start).clamp(segment_start, segment_end);
+
+    let over_max = now > segment_end;
+    let away = matches!(status, UserStatus::Away | UserStatus::Offline)
+        && last_active_at.map_or(true, |seen| now - seen > away_grace);
+
+    (over_max || away).then_some(last_seen.min(now))
+}
+
+pub fn spawn(state: Arc<AppState>) {
+    let interval_secs = state.config.timer_idle_sweep_interval_secs;
+    if interval_secs == 0 {
+        tracing::info!("⏸️ 闲置计时扫描已关闭（TIMER_IDLE_SWEEP_INTERVAL_SECS=0）");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            ticker.tick().await;
+            match run_once(&state, Utc::now()).await {
+                Ok(0) => {}
+                Ok(paused) => tracing::info!("⏱️ 已自动暂停闲置计时 {paused} 条"),
+                Err(e) => tracing::warn!("闲置计时扫描失败: {e}"),
+            }
+        }
+    });
+}
+
+/// 执行一轮扫描，返回本轮自动暂停的计时数
+///
+/// 按 (startTime, id) 游标分批扫描全部进行中的计时，避免最早的一批未闲置计时占满批次、挤掉其后的闲置计时
+pub async fn run_once(state: &AppState, now: DateTime<Utc>) -> AppResult<usize> {
+    let max_segment = Duration::minutes(state.config.timer_idle_max_minutes as i64);
+    let away_grace = Duration::minutes(state.config.timer_idle_away_grace_minutes as i64);
+
+    let mut paused = 0;
+    let mut cursor: Option<(DateTime<Utc>, String)> = None;
+    loop {
+        let mut select =
+            time_log::Entity::find().filter(time_log::Column::Status.eq(time_log::TimeLogStatus::Running));
+        if let Some((start_time, id)) = &cursor {
+            select = select.filter(
+                Condition::any().add(time_log::Column::StartTime.gt(*start_time)).add(
+                    Condition::all()
+                        .add(time_log::Column::StartTime.eq(*start_time))
+                        .add(time_log::Column::Id.gt(id.as_str())),
+                ),
+            );
+        }
+        let running = select
+            .order_by_asc(time_log::Column::StartTime)
+            .order_by_asc(time_log::Column::Id)
+            .limit(BATCH_SIZE)
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询进行中计时失败: {e}")))?;
+
+        let exhausted = (running.len() as u64) < BATCH_SIZE;
+        cursor = running.last().map(|t| (t.start_time, t.id.clone()));
+        paused += process_batch(state, now, max_segment, away_grace, running).await?;
+        if exhausted {
+            return Ok(paused);
+        }
+    }
+}
+
+async fn process_batch(
+    state: &AppState,
+    now: DateTime<Utc>,
+    max_segment: Duration,
+    away_grace: Duration,
+    running: Vec<time_log::Model>,
+) -> AppResult<usize> {
+    if running.is_empty() {
+        return Ok(0);
+    }
+
+    let user_ids: Vec<String> = running.iter().map(|t| t.user_id.clone()).collect();
+    let users: HashMap<String, user::Model> = user::Entity::find()
+        .filter(user::Column::Id.is_in(user_ids))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+        .into_iter()
+        .map(|u| (u.id.clone(), u))
+        .collect();
+
+    let mut paused = 0usize;
+    for timer in running {
+        let Some(owner) = users.get(&timer.user_id) else {
+            continue;
+        };
+        let Some(pause_at) =
+            idle_pause_at(timer.start_time, owner.last_active_at, &owner.status, now, max_segment, away_grace)
+        else {
+            continue;
+        };
+
+        let timer_id = timer.id.clone();
+        let start_time = timer.start_time;
+        let done = state
+            .db
+            .transaction::<_, bool, AppError>(|txn| {
+                Box::pin(async move {
+                    let locked = time_log::Entity::find_by_id(&timer_id)
+                        .lock_exclusive()
+                        .one(txn)
+                        .await
+                        .map_err(|e| AppError::Database(format!("查询计时记录失败: {e}")))?;
+                    let Some(locked) = locked.filter(|t| {
+                        t.status == time_log::TimeLogStatus::Running && t.start_time == start_time
+                    }) else {
+                        return Ok(false);
+                    };
+
+                    let worked = (pause_at - locked.start_time).num_seconds().clamp(0, i64::from(i32::MAX)) as i32;
+                    let idle = (now - pause_at).num_seconds().clamp(0, i64::from(i32::MAX)) as i32;
+                    let user_id = locked.user_id.clone();
+                    let description: String = locked.description.chars().take(100).collect();
+
+                    let mut active: time_log::ActiveModel = locked.clone().into();
+                    active.duration = sea_orm::ActiveValue::Set(locked.duration.saturating_add(worked));
+                    active.status = sea_orm::ActiveValue::Set(time_log::TimeLogStatus::Paused);
+                    active.idle_paused_at = sea_orm::ActiveValue::Set(Some(pause_at));
+                    active.idle_seconds = sea_orm::ActiveValue::Set(Some(idle));
+                    active.updated_at = sea_orm::ActiveValue::Set(now);
+                    active.update(txn).await.map_err(|e| AppError::Database(format!("暂停计时失败: {e}")))?;
+
+                    notification::ActiveModel {
+                        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                        user_id: sea_orm::ActiveValue::Set(user_id),
+                        actor_id: sea_orm::ActiveValue::Set(None),
+                        notification_type: sea_orm::ActiveValue::Set(notification::NotificationType::TimerIdlePaused),
+                        title: sea_orm::ActiveValue::Set("计时因闲置已自动暂停".to_string()),
+                        content: sea_orm::ActiveValue::Set(Some(format!(
+                            "{description}：闲置 {} 分钟未计入，可选择保留或丢弃",
+                            idle / 60
+                        ))),
+                        action_url: sea_orm::ActiveValue::Set(Some("/timelog".to_string())),
+                        metadata: sea_orm::ActiveValue::Set(Some(json!({
+                            "timeLogId": timer_id,
+                            "idlePausedAt": pause_at,
+                            "idleSeconds": idle,
+                            "actions": ["KEEP", "DISCARD"],
+                        }))),
+                        read_at: sea_orm::ActiveValue::Set(None),
+                        created_at: sea_orm::ActiveValue::Set(now),
+                    }
+                    .insert(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("写入通知失败: {e}")))?;
+
+                    Ok(true)
+                })
+            })
+            .await
+            .map_err(|e| match e {
+                sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+                sea_orm::TransactionError::Transaction(app) => app,
+            })?;
+        if done {
+            paused += 1;
+        }
+    }
+
+    Ok(paused)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn pauses_at_last_activity_within_segment() {
+        let start = at("2026-10-14T09:00:00Z");
+        let max = Duration::hours(4);
+        let grace = Duration::minutes(30);
+        let available = UserStatus::Available;
+
+        // 未超时且在线：不处理
+        assert_eq!(idle_pause_at(start, Some(at("2026-10-14T11:00:00Z")), &available, at("2026-10-14T12:00:00Z"), max, grace), None);
+
+        // 超过上限：停在最后活动时间；无本段活动时停在本段开始；活动晚于上限时停在上限
+        let overnight = at("2026-10-15T08:00:00Z");
+        let last_seen = at("2026-10-14T12:30:00Z");
+        assert_eq!(idle_pause_at(start, Some(last_seen), &available, overnight, max, grace), Some(last_seen));
+        assert_eq!(idle_pause_at(start, Some(at("2026-10-14T08:00:00Z")), &available, overnight, max, grace), Some(start));
+        assert_eq!(
+            idle_pause_at(start, Some(at("2026-10-14T20:00:00Z")), &available, overnight, max, grace),
+            Some(at("2026-10-14T13:00:00Z"))
+        );
+
+        // 离线超过宽限期：未到上限也暂停
+        let away_seen = at("2026-10-14T10:00:00Z");
+        let now = at("2026-10-14T10:45:00Z");
+        assert_eq!(idle_pause_at(start, Some(away_seen), &UserStatus::Offline, now, max, grace), Some(away_seen));
+        assert_eq!(idle_pause_at(start, Some(away_seen), &UserStatus::Away, at("2026-10-14T10:20:00Z"), max, grace), None);
+    }
+}
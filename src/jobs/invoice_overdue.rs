@@ -0,0 +1,49 @@
+//! 发票逾期标记
+//!
+//! - 付款截止日已过且未收清（PENDING / PARTIAL）的发票置为 OVERDUE
+//! - 收款登记/撤销时状态在同一事务内重新推导（见 `routes::payments`），本任务只负责随时间推移的逾期
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use std::sync::Arc;
+
+use crate::db::AppState;
+use crate::entity::invoice::{self, InvoiceStatus};
+use crate::error::{AppError, AppResult};
+
+pub fn spawn(state: Arc<AppState>) {
+    let interval_secs = state.config.invoice_overdue_interval_secs;
+    if interval_secs == 0 {
+        tracing::info!("⏸️ 发票逾期标记已关闭（INVOICE_OVERDUE_INTERVAL_SECS=0）");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            ticker.tick().await;
+            match run_once(&state, Utc::now()).await {
+                Ok(0) => {}
+                Ok(marked) => tracing::info!("🧾 已标记逾期发票 {marked} 张"),
+                Err(e) => tracing::warn!("发票逾期标记失败: {e}"),
+            }
+        }
+    });
+}
+
+/// 执行一轮标记，返回本轮置为逾期的发票数
+pub async fn run_once(state: &AppState, now: DateTime<Utc>) -> AppResult<u64> {
+    let result = invoice::Entity::update_many()
+        .set(invoice::ActiveModel {
+            status: sea_orm::ActiveValue::Set(InvoiceStatus::Overdue),
+            updated_at: sea_orm::ActiveValue::Set(now),
+            ..Default::default()
+        })
+        .filter(invoice::Column::Status.is_in(vec![InvoiceStatus::Pending, InvoiceStatus::Partial]))
+        .filter(invoice::Column::DueDate.lt(now))
+        .exec(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("标记逾期发票失败: {e}")))?;
+    Ok(result.rows_affected)
+}
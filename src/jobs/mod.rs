@@ -0,0 +1,21 @@
+//! 后台调度任务模块
+//!
+//! - 随 API 进程启动，按固定间隔轮询数据库
+//! - 多实例部署时依赖数据库唯一约束/行锁去重，任务本身可重入
+
+use std::sync::Arc;
+
+use crate::db::AppState;
+
+pub mod contract_expiry;
+pub mod idle_timers;
+pub mod invoice_overdue;
+pub mod task_reminders;
+
+/// 启动全部后台任务（不阻塞调用方）
+pub fn spawn_all(state: Arc<AppState>) {
+    contract_expiry::spawn(state.clone());
+    idle_timers::spawn(state.clone());
+    invoice_overdue::spawn(state.clone());
+    task_reminders::spawn(state);
+}
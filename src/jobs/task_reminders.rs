@@ -0,0 +1,336 @@
+//! 任务到期提醒与逾期升级
+//!
+//! - 到期前：按 `TASK_REMINDER_OFFSETS_MINUTES`（默认 24h / 1h）向执行人发送 `TASK_DUE_SOON`；
+//!   同一时刻只发送已到达的最小提前量档位（停机补偿时不会连发多档）
+//! - 逾期后：向执行人、案件承办人（项目任务为项目负责人）发送 `TASK_OVERDUE`；
+//!   `P0_URGENT` 任务额外升级到执行人的上级（`supervisorId`）
+//! - 去重：先插入 `TaskReminder`（taskId, recipientId, kind, dueDate 唯一，冲突即跳过）再写通知，
+//!   二者同一事务；重启或多实例并发不会重复发送，修改截止时间后按新时间重新提醒
+//! - 仅处理逾期不超过 7 天的任务，避免首次上线时对历史任务集中轰炸
+
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    TransactionTrait,
+};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::AppState;
+use crate::entity::{case, notification, project, task, task_reminder, user};
+use crate::error::{AppError, AppResult};
+
+const OVERDUE_LOOKBACK_DAYS: i64 = 7;
+const BATCH_SIZE: u64 = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReminderKind {
+    /// 距截止时间不足 N 分钟
+    DueIn(i64),
+    Overdue,
+}
+
+impl ReminderKind {
+    fn as_key(self) -> String {
+        match self {
+            Self::DueIn(minutes) => format!("DUE_IN_{minutes}"),
+            Self::Overdue => "OVERDUE".to_string(),
+        }
+    }
+}
+
+/// 当前时刻应发送的提醒档位；尚未进入任何提前量窗口时返回 None
+fn reminder_kind_for(due_date: DateTime<Utc>, now: DateTime<Utc>, offsets_minutes: &[i64]) -> Option<ReminderKind> {
+    if now >= due_date {
+        return Some(ReminderKind::Overdue);
+    }
+    offsets_minutes
+        .iter()
+        .copied()
+        .filter(|minutes| due_date - Duration::minutes(*minutes) <= now)
+        .min()
+        .map(ReminderKind::DueIn)
+}
+
+fn format_remaining(minutes: i64) -> String {
+    if minutes >= 60 && minutes % 60 == 0 {
+        format!("{} 小时", minutes / 60)
+    } else {
+        format!("{minutes} 分钟")
+    }
+}
+
+pub fn spawn(state: Arc<AppState>) {
+    let interval_secs = state.config.task_reminder_interval_secs;
+    if interval_secs == 0 {
+        tracing::info!("⏸️ 任务到期提醒调度已关闭（TASK_REMINDER_INTERVAL_SECS=0）");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            ticker.tick().await;
+            match run_once(&state, Utc::now()).await {
+                Ok(0) => {}
+                Ok(sent) => tracing::info!("🔔 已发送任务到期提醒 {sent} 条"),
+                Err(e) => tracing::warn!("任务到期提醒调度失败: {e}"),
+            }
+        }
+    });
+}
+
+/// 执行一轮扫描，返回本轮实际发送的通知数
+///
+/// 按 (dueDate, id) 游标分批扫描整个时间窗口，避免长期逾期的任务占满批次、挤掉即将到期的任务
+pub async fn run_once(state: &AppState, now: DateTime<Utc>) -> AppResult<usize> {
+    let offsets = &state.config.task_reminder_offsets_minutes;
+    let max_offset = offsets.iter().copied().max().unwrap_or(0);
+
+    let mut sent = 0;
+    let mut cursor: Option<(DateTime<Utc>, String)> = None;
+    loop {
+        let mut select = task::Entity::find()
+            .filter(task::Column::Status.ne(task::TaskStatus::Done))
+            .filter(task::Column::AssigneeId.is_not_null())
+            .filter(task::Column::DueDate.gte(now - Duration::days(OVERDUE_LOOKBACK_DAYS)))
+            .filter(task::Column::DueDate.lte(now + Duration::minutes(max_offset)));
+        if let Some((due_date, id)) = &cursor {
+            select = select.filter(
+                Condition::any().add(task::Column::DueDate.gt(*due_date)).add(
+                    Condition::all().add(task::Column::DueDate.eq(*due_date)).add(task::Column::Id.gt(id.as_str())),
+                ),
+            );
+        }
+        let candidates = select
+            .order_by_asc(task::Column::DueDate)
+            .order_by_asc(task::Column::Id)
+            .limit(BATCH_SIZE)
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询待提醒任务失败: {e}")))?;
+
+        let exhausted = (candidates.len() as u64) < BATCH_SIZE;
+        cursor = candidates.last().and_then(|t| t.due_date.map(|d| (d, t.id.clone())));
+        sent += process_batch(state, now, offsets, candidates).await?;
+        if exhausted || cursor.is_none() {
+            return Ok(sent);
+        }
+    }
+}
+
+async fn process_batch(
+    state: &AppState,
+    now: DateTime<Utc>,
+    offsets: &[i64],
+    candidates: Vec<task::Model>,
+) -> AppResult<usize> {
+    if candidates.is_empty() {
+        return Ok(0);
+    }
+
+    // 已发送记录：批量查出后跳过，避免对每个已提醒的任务逐条开事务
+    let task_ids: Vec<String> = candidates.iter().map(|t| t.id.clone()).collect();
+    let already_sent: HashSet<(String, String, String, DateTime<Utc>)> = task_reminder::Entity::find()
+        .filter(task_reminder::Column::TaskId.is_in(task_ids))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询提醒记录失败: {e}")))?
+        .into_iter()
+        .map(|r| (r.task_id, r.recipient_id, r.kind, r.due_date))
+        .collect();
+
+    // 归属信息：tenantId 与逾期升级对象（案件承办人 / 项目负责人）
+    let case_ids: Vec<String> = candidates.iter().filter_map(|t| t.case_id.clone()).collect();
+    let project_ids: Vec<String> = candidates.iter().filter_map(|t| t.project_id.clone()).collect();
+    let mut owners: HashMap<String, (String, Option<String>)> = HashMap::new();
+    if !case_ids.is_empty() {
+        for c in case::Entity::find()
+            .filter(case::Column::Id.is_in(case_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询案件失败: {e}")))?
+        {
+            owners.insert(c.id, (c.tenant_id, c.handler_id));
+        }
+    }
+    if !project_ids.is_empty() {
+        for p in project::Entity::find()
+            .filter(project::Column::Id.is_in(project_ids))
+            .filter(project::Column::DeletedAt.is_null())
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询项目失败: {e}")))?
+        {
+            owners.insert(p.id, (p.tenant_id, Some(p.owner_id)));
+        }
+    }
+
+    let assignee_ids: Vec<String> = candidates.iter().filter_map(|t| t.assignee_id.clone()).collect();
+    let supervisors: HashMap<String, Option<String>> = user::Entity::find()
+        .filter(user::Column::Id.is_in(assignee_ids))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询执行人失败: {e}")))?
+        .into_iter()
+        .map(|u| (u.id, u.supervisor_id))
+        .collect();
+
+    let mut sent = 0;
+    for model in candidates {
+        let (Some(due_date), Some(assignee_id)) = (model.due_date, model.assignee_id.clone()) else {
+            continue;
+        };
+        let Some(kind) = reminder_kind_for(due_date, now, offsets) else {
+            continue;
+        };
+        let owner_id = model.case_id.as_deref().or(model.project_id.as_deref()).unwrap_or_default();
+        let Some((tenant_id, escalate_to)) = owners.get(owner_id) else {
+            continue;
+        };
+
+        let mut recipients = vec![(assignee_id.clone(), false)];
+        if kind == ReminderKind::Overdue {
+            let mut escalations: Vec<Option<String>> = vec![escalate_to.clone()];
+            if model.priority == task::TaskPriority::P0Urgent {
+                escalations.push(supervisors.get(&assignee_id).cloned().flatten());
+            }
+            for user_id in escalations.into_iter().flatten() {
+                if !recipients.iter().any(|(id, _)| *id == user_id) {
+                    recipients.push((user_id, true));
+                }
+            }
+        }
+
+        for (recipient_id, escalation) in recipients {
+            if already_sent.contains(&(model.id.clone(), recipient_id.clone(), kind.as_key(), due_date)) {
+                continue;
+            }
+            if send_reminder(state, tenant_id, &model, due_date, kind, &recipient_id, escalation).await? {
+                sent += 1;
+            }
+        }
+    }
+    Ok(sent)
+}
+
+/// 写入去重记录并发送通知；已发送过（唯一约束冲突）时返回 false
+async fn send_reminder(
+    state: &AppState,
+    tenant_id: &str,
+    model: &task::Model,
+    due_date: DateTime<Utc>,
+    kind: ReminderKind,
+    recipient_id: &str,
+    escalation: bool,
+) -> AppResult<bool> {
+    let tenant_id = tenant_id.to_string();
+    let model = model.clone();
+    let recipient_id = recipient_id.to_string();
+
+    state
+        .db
+        .transaction::<_, bool, AppError>(|txn| {
+            Box::pin(async move {
+                let now = Utc::now();
+                let inserted = task_reminder::Entity::insert(task_reminder::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    tenant_id: sea_orm::ActiveValue::Set(tenant_id),
+                    task_id: sea_orm::ActiveValue::Set(model.id.clone()),
+                    recipient_id: sea_orm::ActiveValue::Set(recipient_id.clone()),
+                    kind: sea_orm::ActiveValue::Set(kind.as_key()),
+                    due_date: sea_orm::ActiveValue::Set(due_date),
+                    sent_at: sea_orm::ActiveValue::Set(now),
+                })
+                .on_conflict(
+                    OnConflict::columns([
+                        task_reminder::Column::TaskId,
+                        task_reminder::Column::RecipientId,
+                        task_reminder::Column::Kind,
+                        task_reminder::Column::DueDate,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .do_nothing()
+                .exec_without_returning(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("写入提醒记录失败: {e}")))?;
+
+                if !matches!(inserted, sea_orm::TryInsertResult::Inserted(n) if n > 0) {
+                    return Ok(false);
+                }
+
+                let due_text = due_date.format("%Y-%m-%d %H:%M UTC");
+                let (notification_type, title, content) = match kind {
+                    ReminderKind::DueIn(minutes) => (
+                        notification::NotificationType::TaskDueSoon,
+                        format!("任务即将到期：{}", model.title),
+                        format!("截止时间 {due_text}，剩余不足 {}", format_remaining(minutes)),
+                    ),
+                    ReminderKind::Overdue if escalation => (
+                        notification::NotificationType::TaskOverdue,
+                        format!("任务逾期升级：{}", model.title),
+                        format!("任务已于 {due_text} 到期仍未完成（当前状态 {}），请跟进", model.status.to_value()),
+                    ),
+                    ReminderKind::Overdue => (
+                        notification::NotificationType::TaskOverdue,
+                        format!("任务已逾期：{}", model.title),
+                        format!("任务已于 {due_text} 到期，请尽快处理"),
+                    ),
+                };
+
+                notification::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    user_id: sea_orm::ActiveValue::Set(recipient_id),
+                    actor_id: sea_orm::ActiveValue::Set(None),
+                    notification_type: sea_orm::ActiveValue::Set(notification_type),
+                    title: sea_orm::ActiveValue::Set(title),
+                    content: sea_orm::ActiveValue::Set(Some(content)),
+                    action_url: sea_orm::ActiveValue::Set(Some(format!("/tasks/{}", model.id))),
+                    metadata: sea_orm::ActiveValue::Set(Some(json!({
+                        "taskId": model.id,
+                        "caseId": model.case_id,
+                        "projectId": model.project_id,
+                        "assigneeId": model.assignee_id,
+                        "dueDate": due_date,
+                        "reminderKind": kind.as_key(),
+                        "escalation": escalation,
+                    }))),
+                    read_at: sea_orm::ActiveValue::Set(None),
+                    created_at: sea_orm::ActiveValue::Set(now),
+                }
+                .insert(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("写入通知失败: {e}")))?;
+
+                Ok(true)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_smallest_reached_offset() {
+        let due = DateTime::parse_from_rfc3339("2026-10-20T09:00:00Z").unwrap().with_timezone(&Utc);
+        let offsets = [1440, 60];
+
+        assert_eq!(reminder_kind_for(due, due - Duration::hours(30), &offsets), None);
+        assert_eq!(reminder_kind_for(due, due - Duration::hours(24), &offsets), Some(ReminderKind::DueIn(1440)));
+        assert_eq!(reminder_kind_for(due, due - Duration::minutes(30), &offsets), Some(ReminderKind::DueIn(60)));
+        assert_eq!(reminder_kind_for(due, due, &offsets), Some(ReminderKind::Overdue));
+        assert_eq!(ReminderKind::DueIn(60).as_key(), "DUE_IN_60");
+    }
+}
@@ -19,13 +19,18 @@ use tower_http::{
 };
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+mod billing;
 mod config;
 mod db;
 mod entity;
 mod error;
+mod jobs;
+mod realtime;
 mod routes;
+mod scheduling;
 mod security;
 mod storage;
+mod workflow;
 
 pub use config::AppConfig;
 pub use db::AppState;
@@ -79,6 +84,15 @@ async fn api_root() -> Json<ApiInfo> {
             "/api/v1/documents".to_string(),
             "/api/v1/events".to_string(),
             "/api/v1/notifications".to_string(),
+            "/api/v1/signals".to_string(),
+            "/api/v1/projects".to_string(),
+            "/api/v1/me".to_string(),
+            "/api/v1/billing".to_string(),
+            "/api/v1/invoices".to_string(),
+            "/api/v1/expenses".to_string(),
+            "/api/v1/trust".to_string(),
+            "/api/v1/contracts".to_string(),
+            "/api/v1/approvals".to_string(),
         ],
     })
 }
@@ -120,6 +134,15 @@ fn create_app(state: Arc<AppState>) -> Router {
         .nest("/api/v1/documents", routes::documents::router())
         .nest("/api/v1/events", routes::events::router())
         .nest("/api/v1/notifications", routes::notifications::router())
+        .nest("/api/v1/signals", routes::signals::router())
+        .nest("/api/v1/projects", routes::projects::router())
+        .nest("/api/v1/me", routes::me::router())
+        .nest("/api/v1/billing", routes::billing::router())
+        .nest("/api/v1/invoices", routes::invoices::router())
+        .nest("/api/v1/expenses", routes::expenses::router())
+        .nest("/api/v1/trust", routes::trust::router())
+        .nest("/api/v1/contracts", routes::contracts::router())
+        .nest("/api/v1/approvals", routes::approvals::router())
         // 中间件
         .layer(
             ServiceBuilder::new()
@@ -165,6 +188,9 @@ async fn main() {
         }
     };
 
+    // 后台调度（任务到期提醒等）
+    jobs::spawn_all(state.clone());
+
     // 创建应用
     let app = create_app(state);
 
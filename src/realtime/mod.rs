@@ -0,0 +1,6 @@
+//! 实时协作信号（与 Web 主线 `lawclick-next/src/lib/realtime/` 对齐）
+//!
+//! - 写入：任务等协作数据变更时，在同一事务内递增 `TenantSignal.version`
+//! - 读取：客户端轮询/长轮询/SSE 比较 version，发现变化后增量刷新看板
+
+pub mod tenant_signal;
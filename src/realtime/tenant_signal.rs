@@ -0,0 +1,66 @@
+//! TenantSignal 写入/读取
+//!
+//! 对齐 Web 主线 `lawclick-next/src/lib/realtime/tenant-signal.ts`：
+//! - upsert：不存在则以 version=1 创建，存在则 version+1 并刷新 updatedAt
+//! - 必须传入调用方的事务连接，确保“业务写入 + 信号递增”原子提交
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ActiveEnum, ConnectionTrait, DatabaseBackend, EntityTrait, Statement};
+use serde_json::Value as JsonValue;
+
+use crate::entity::tenant_signal::{self, TenantSignalKind};
+use crate::error::{AppError, AppResult};
+
+pub fn parse_signal_kind(raw: &str) -> AppResult<TenantSignalKind> {
+    match raw.trim().to_uppercase().as_str() {
+        "TASKS_CHANGED" => Ok(TenantSignalKind::TasksChanged),
+        _ => Err(AppError::Validation("无效的信号类型".to_string())),
+    }
+}
+
+/// 递增租户信号版本号，返回递增后的 version
+pub async fn touch_tenant_signal<C: ConnectionTrait>(
+    db: &C,
+    tenant_id: &str,
+    kind: TenantSignalKind,
+    payload: Option<JsonValue>,
+) -> AppResult<i32> {
+    let tenant_id = tenant_id.trim();
+    if tenant_id.is_empty() {
+        return Err(AppError::Internal("tenantId 不能为空".to_string()));
+    }
+
+    let now: DateTime<Utc> = Utc::now();
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"INSERT INTO "TenantSignal" ("tenantId", "kind", "payload", "version", "createdAt", "updatedAt")
+           VALUES ($1, CAST($2 AS "TenantSignalKind"), $3, 1, $4, $4)
+           ON CONFLICT ("tenantId", "kind") DO UPDATE
+           SET "version" = "TenantSignal"."version" + 1,
+               "updatedAt" = EXCLUDED."updatedAt",
+               "payload" = COALESCE(EXCLUDED."payload", "TenantSignal"."payload")
+           RETURNING "version""#,
+        vec![tenant_id.into(), kind.to_value().into(), payload.into(), now.into()],
+    );
+
+    let row = db
+        .query_one(stmt)
+        .await
+        .map_err(|e| AppError::Database(format!("更新实时信号失败: {e}")))?
+        .ok_or_else(|| AppError::Database("更新实时信号失败: 无返回行".to_string()))?;
+
+    row.try_get("", "version")
+        .map_err(|_| AppError::Database("解析 TenantSignal.version 失败".to_string()))
+}
+
+/// 读取当前信号（不存在时返回 None，视为 version=0）
+pub async fn find_tenant_signal<C: ConnectionTrait>(
+    db: &C,
+    tenant_id: &str,
+    kind: TenantSignalKind,
+) -> AppResult<Option<tenant_signal::Model>> {
+    tenant_signal::Entity::find_by_id((tenant_id.to_string(), kind))
+        .one(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询实时信号失败: {e}")))
+}
@@ -0,0 +1,1417 @@
+//! 审批路由（与 Web 主线 `approval-actions.ts` 口径对齐，并扩展为可配置的多级顺序审批）
+//!
+//! - 生命周期：草稿 → 提交（PENDING）→ 逐级通过（APPROVED）/ 驳回（REJECTED）；申请人可撤回草稿或审批中的申请（CANCELLED）
+//! - 创建需 `approval:create`；关联案件时需具备案件可见性；草稿仅申请人可修改、提交
+//! - 路由规则按审批类型配置（规则管理需 `admin:settings`），同一类型可按金额下限分档，
+//!   取下限不超过申请金额的最高一档；每档为若干顺序步骤，审批人可为案件承办人 / 申请人上级 / 指定角色 / 指定用户
+//! - 未配置规则时为单步审批：审批人为申请时指定的用户，缺省由任一具备 `approval:approve` 的成员审批
+//! - 提交时生成步骤快照（`ApprovalStep`），审批人即申请人的步骤自动跳过；申请人不能审批自己的申请
+//! - 每一步都会通知：待审批人收到 `APPROVAL_REQUESTED`，申请人收到进度 / 结果 `APPROVAL_UPDATED`，撤回时通知当前审批人
+//! - 外出代理：指定审批人在 `OutOfOffice` 时段内设置了代理人时，该步骤同时可由代理人审批（记为 actedBy 代 onBehalfOf），
+//!   并通知代理人；时段结束后自动交回（见 `security::delegation`）
+//! - 查看：申请人、各步骤审批人、当前步骤可审批的成员，或具备 `approval:view_all`
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{delete, get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::Decimal;
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Statement, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::billing::invoice::round_money;
+use crate::db::AppState;
+use crate::entity::approval_request::{self, ApprovalStatus, ApprovalType};
+use crate::entity::approval_rule_step::{self, ApproverKind};
+use crate::entity::approval_step::{self, ApprovalStepStatus};
+use crate::entity::notification::{self, NotificationType};
+use crate::entity::user::Role;
+use crate::entity::{approval_rule, case, user};
+use crate::error::{AppError, AppResult};
+use crate::security::case_access::require_case_access;
+use crate::security::current_user::CurrentUser;
+use crate::security::delegation::{active_delegate, active_delegators};
+use crate::security::permissions::{has_permission, parse_role, require_permission, Permission};
+use crate::security::validation::{require_non_empty, ValidatedJson};
+use crate::workflow::approval::{next_step, select_tier, step_skipped, PlannedStep};
+
+use super::cases::PaginatedResponse;
+
+/// 单条规则最多的审批步骤数
+const MAX_RULE_STEPS: usize = 10;
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApprovalRequest {
+    /// `LEAVE` | `EXPENSE` | `PURCHASE` | `CONTRACT` | `INVOICE` | `OTHER`
+    #[serde(rename = "type")]
+    pub approval_type: String,
+    pub title: String,
+    #[validate(length(max = 5000, message = "description 长度不合法"))]
+    pub description: Option<String>,
+    /// 金额（Decimal 字符串，最多 2 位小数），用于规则分档
+    pub amount: Option<String>,
+    /// 未配置路由规则时的审批人；缺省由任一具备 `approval:approve` 的成员审批
+    pub approver_id: Option<String>,
+    pub case_id: Option<String>,
+    /// 缺省取案件的委托人
+    pub client_id: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+    /// 创建后直接提交
+    pub submit: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateApprovalRequest {
+    pub title: Option<String>,
+    #[validate(length(max = 5000, message = "description 长度不合法"))]
+    pub description: Option<String>,
+    /// 传空字符串清空
+    pub amount: Option<String>,
+    /// 传空字符串清空
+    pub approver_id: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalDecisionRequest {
+    /// 审批意见（驳回时必填）
+    #[validate(length(max = 2000, message = "note 长度不合法"))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalListQuery {
+    /// `mine`（缺省，我发起的）| `pending`（待我审批）| `all`（需 `approval:view_all`）
+    pub filter: Option<String>,
+    pub status: Option<String>,
+    #[serde(rename = "type")]
+    pub approval_type: Option<String>,
+    pub case_id: Option<String>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleListQuery {
+    #[serde(rename = "type")]
+    pub approval_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleStepInput {
+    /// `CASE_HANDLER` | `SUPERVISOR` | `ROLE` | `USER`
+    pub kind: String,
+    /// kind 为 `ROLE` 时必填
+    pub role: Option<String>,
+    /// kind 为 `USER` 时必填
+    pub user_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertRuleRequest {
+    #[serde(rename = "type")]
+    pub approval_type: String,
+    /// 金额下限（含），缺省 0；同一类型 + 下限已存在时覆盖该档
+    pub min_amount: Option<String>,
+    #[validate(length(max = 100, message = "name 长度不合法"))]
+    pub name: Option<String>,
+    pub steps: Vec<RuleStepInput>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalStepResponse {
+    pub step_order: i32,
+    pub approver_id: Option<String>,
+    pub approver_name: Option<String>,
+    pub approver_role: Option<String>,
+    pub status: String,
+    pub note: Option<String>,
+    pub acted_by_id: Option<String>,
+    pub acted_by_name: Option<String>,
+    /// 代理审批时为被代理的审批人（actedBy 代 onBehalfOf 审批）
+    pub on_behalf_of_id: Option<String>,
+    pub on_behalf_of_name: Option<String>,
+    pub acted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalResponse {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub approval_type: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub case_id: Option<String>,
+    pub client_id: Option<String>,
+    pub requester_id: String,
+    pub requester_name: Option<String>,
+    pub approver_id: Option<String>,
+    pub approver_name: Option<String>,
+    pub status: String,
+    pub amount: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+    pub current_step: Option<i32>,
+    pub steps: Vec<ApprovalStepResponse>,
+    pub created_at: DateTime<Utc>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub approval_note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleStepResponse {
+    pub step_order: i32,
+    pub kind: String,
+    pub role: Option<String>,
+    pub user_id: Option<String>,
+    pub user_name: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleResponse {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub approval_type: String,
+    pub min_amount: String,
+    pub name: Option<String>,
+    pub steps: Vec<RuleStepResponse>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn parse_approval_type(raw: &str) -> AppResult<ApprovalType> {
+    match raw.trim() {
+        "LEAVE" => Ok(ApprovalType::Leave),
+        "EXPENSE" => Ok(ApprovalType::Expense),
+        "PURCHASE" => Ok(ApprovalType::Purchase),
+        "CONTRACT" => Ok(ApprovalType::Contract),
+        "INVOICE" => Ok(ApprovalType::Invoice),
+        "OTHER" => Ok(ApprovalType::Other),
+        other => Err(AppError::Validation(format!("type 无效: {other}"))),
+    }
+}
+
+fn parse_approval_status(raw: &str) -> AppResult<ApprovalStatus> {
+    match raw.trim() {
+        "DRAFT" => Ok(ApprovalStatus::Draft),
+        "PENDING" => Ok(ApprovalStatus::Pending),
+        "APPROVED" => Ok(ApprovalStatus::Approved),
+        "REJECTED" => Ok(ApprovalStatus::Rejected),
+        "CANCELLED" => Ok(ApprovalStatus::Cancelled),
+        other => Err(AppError::Validation(format!("status 无效: {other}"))),
+    }
+}
+
+fn parse_approver_kind(raw: &str) -> AppResult<ApproverKind> {
+    match raw.trim() {
+        "CASE_HANDLER" => Ok(ApproverKind::CaseHandler),
+        "SUPERVISOR" => Ok(ApproverKind::Supervisor),
+        "ROLE" => Ok(ApproverKind::Role),
+        "USER" => Ok(ApproverKind::User),
+        other => Err(AppError::Validation(format!("kind 无效: {other}"))),
+    }
+}
+
+fn parse_amount(field: &str, raw: &str) -> AppResult<Decimal> {
+    match Decimal::from_str(raw.trim()) {
+        Ok(v) if v >= Decimal::ZERO && round_money(v) == v => Ok(v),
+        _ => Err(AppError::Validation(format!("{field} 无效: {raw}（需为非负数，最多 2 位小数）"))),
+    }
+}
+
+fn trim_optional(value: Option<String>) -> Option<String> {
+    value.map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+fn type_label(approval_type: ApprovalType) -> &'static str {
+    match approval_type {
+        ApprovalType::Leave => "请假",
+        ApprovalType::Expense => "费用",
+        ApprovalType::Purchase => "采购",
+        ApprovalType::Contract => "合同",
+        ApprovalType::Invoice => "开票",
+        ApprovalType::Other => "其他",
+    }
+}
+
+/// 租户内在职成员（TenantMembership 为 ACTIVE 且账号启用）的 id 与角色；给定 `user_id` 时只查该用户
+async fn tenant_members(state: &AppState, tenant_id: &str, user_id: Option<&str>) -> AppResult<Vec<(String, Role)>> {
+    let rows = state
+        .db
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT u.id, u.role::text AS role FROM "User" u
+               JOIN "TenantMembership" m ON m."userId" = u.id
+               WHERE m."tenantId" = $1 AND m.status = 'ACTIVE' AND u."isActive" = true
+                 AND ($2::text IS NULL OR u.id = $2)"#,
+            [tenant_id.into(), user_id.map(str::to_string).into()],
+        ))
+        .await
+        .map_err(|e| AppError::Database(format!("查询租户成员失败: {e}")))?;
+
+    let mut members = Vec::with_capacity(rows.len());
+    for row in rows {
+        let id: String = row.try_get("", "id").map_err(|e| AppError::Database(format!("读取成员失败: {e}")))?;
+        let role: String = row.try_get("", "role").map_err(|e| AppError::Database(format!("读取成员失败: {e}")))?;
+        if let Some(role) = parse_role(&role) {
+            members.push((id, role));
+        }
+    }
+    Ok(members)
+}
+
+pub(crate) async fn ensure_member(state: &AppState, tenant_id: &str, user_id: &str, field: &str) -> AppResult<()> {
+    Uuid::parse_str(user_id).map_err(|_| AppError::Validation(format!("{field} 无效")))?;
+    if tenant_members(state, tenant_id, Some(user_id)).await?.is_empty() {
+        return Err(AppError::Validation(format!("{field} 对应的用户已停用或不在当前租户")));
+    }
+    Ok(())
+}
+
+async fn ensure_client_in_tenant(state: &AppState, tenant_id: &str, client_id: &str) -> AppResult<()> {
+    state
+        .db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT 1 FROM "Contact" WHERE id = $1 AND "tenantId" = $2 AND "deletedAt" IS NULL LIMIT 1"#,
+            [client_id.into(), tenant_id.into()],
+        ))
+        .await
+        .map_err(|e| AppError::Database(format!("查询客户失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("客户不存在或不在当前租户".to_string()))?;
+    Ok(())
+}
+
+async fn user_names(state: &AppState, ids: Vec<String>) -> AppResult<HashMap<String, String>> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    Ok(user::Entity::find()
+        .filter(user::Column::Id.is_in(ids))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+        .into_iter()
+        .map(|u| (u.id, u.name.unwrap_or(u.email)))
+        .collect())
+}
+
+async fn load_request(state: &AppState, current_user: &CurrentUser, id: &str) -> AppResult<approval_request::Model> {
+    Uuid::parse_str(id).map_err(|_| AppError::Validation("审批ID 无效".to_string()))?;
+    approval_request::Entity::find_by_id(id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询审批失败: {e}")))?
+        .filter(|m| m.tenant_id == current_user.model.active_tenant_id)
+        .ok_or_else(|| AppError::NotFound("审批不存在".to_string()))
+}
+
+async fn load_steps<C: ConnectionTrait>(db: &C, request_id: &str) -> AppResult<Vec<approval_step::Model>> {
+    approval_step::Entity::find()
+        .filter(approval_step::Column::RequestId.eq(request_id))
+        .order_by_asc(approval_step::Column::StepOrder)
+        .all(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询审批步骤失败: {e}")))
+}
+
+fn current_step<'a>(
+    model: &approval_request::Model,
+    steps: &'a [approval_step::Model],
+) -> Option<&'a approval_step::Model> {
+    steps.iter().find(|s| Some(s.step_order) == model.current_step)
+}
+
+/// 审批身份：本人审批，或代外出的指定审批人审批
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Acting {
+    Own,
+    OnBehalfOf(String),
+}
+
+/// 当前用户审批该步骤的身份：指定审批人本人或其外出代理人（`delegators`）；角色步骤的该角色成员；
+/// 未指定审批人时需 `approval:approve`。角色 / 不指定审批人的步骤本就由多人共担，不走代理
+fn acting_as(
+    current_user: &CurrentUser,
+    model: &approval_request::Model,
+    step: &approval_step::Model,
+    delegators: &[(String, Role)],
+) -> Option<Acting> {
+    if step.status != ApprovalStepStatus::Pending || model.requester_id == current_user.id() {
+        return None;
+    }
+    match (step.approver_id.as_deref(), step.approver_role.as_ref()) {
+        (Some(approver_id), _) if approver_id == current_user.id() => Some(Acting::Own),
+        (Some(approver_id), _) => delegators
+            .iter()
+            .any(|(id, _)| id == approver_id)
+            .then(|| Acting::OnBehalfOf(approver_id.to_string())),
+        (None, Some(role)) => (*role == current_user.model.role).then_some(Acting::Own),
+        (None, None) => {
+            has_permission(current_user.model.role.clone(), Permission::ApprovalApprove).then_some(Acting::Own)
+        }
+    }
+}
+
+async fn delegators_of(state: &AppState, current_user: &CurrentUser) -> AppResult<Vec<(String, Role)>> {
+    active_delegators(state, &current_user.model.active_tenant_id, current_user.id(), Utc::now()).await
+}
+
+fn require_viewer(
+    current_user: &CurrentUser,
+    model: &approval_request::Model,
+    steps: &[approval_step::Model],
+    delegators: &[(String, Role)],
+) -> AppResult<()> {
+    let me = current_user.id();
+    let involved = model.requester_id == me
+        || model.approver_id.as_deref() == Some(me)
+        || steps.iter().any(|s| {
+            [&s.approver_id, &s.acted_by_id, &s.on_behalf_of_id].iter().any(|id| id.as_deref() == Some(me))
+        })
+        || current_step(model, steps).is_some_and(|s| acting_as(current_user, model, s, delegators).is_some());
+    if involved || has_permission(current_user.model.role.clone(), Permission::ApprovalViewAll) {
+        return Ok(());
+    }
+    Err(AppError::Forbidden("无权查看该审批".to_string()))
+}
+
+fn require_requester(current_user: &CurrentUser, model: &approval_request::Model) -> AppResult<()> {
+    if model.requester_id != current_user.id() {
+        return Err(AppError::Forbidden("仅申请人可操作该审批".to_string()));
+    }
+    Ok(())
+}
+
+/// 某一步骤需要通知的待审批人（不含申请人）；指定审批人外出时一并通知其代理人
+async fn step_recipients(
+    state: &AppState,
+    model: &approval_request::Model,
+    approver_id: Option<&str>,
+    approver_role: Option<&Role>,
+) -> AppResult<Vec<String>> {
+    if let Some(approver_id) = approver_id {
+        let mut recipients = vec![approver_id.to_string()];
+        if let Some(delegate_id) = active_delegate(state, &model.tenant_id, approver_id, Utc::now()).await? {
+            if delegate_id != model.requester_id {
+                recipients.push(delegate_id);
+            }
+        }
+        return Ok(recipients);
+    }
+    Ok(tenant_members(state, &model.tenant_id, None)
+        .await?
+        .into_iter()
+        .filter(|(id, role)| {
+            *id != model.requester_id
+                && match approver_role {
+                    Some(step_role) => role == step_role,
+                    None => has_permission(role.clone(), Permission::ApprovalApprove),
+                }
+        })
+        .map(|(id, _)| id)
+        .collect())
+}
+
+fn approval_notice(
+    recipient_id: String,
+    actor_id: &str,
+    notification_type: NotificationType,
+    title: String,
+    content: String,
+    model: &approval_request::Model,
+    now: DateTime<Utc>,
+) -> notification::ActiveModel {
+    notification::ActiveModel {
+        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+        user_id: sea_orm::ActiveValue::Set(recipient_id),
+        actor_id: sea_orm::ActiveValue::Set(Some(actor_id.to_string())),
+        notification_type: sea_orm::ActiveValue::Set(notification_type),
+        title: sea_orm::ActiveValue::Set(title),
+        content: sea_orm::ActiveValue::Set(Some(content)),
+        action_url: sea_orm::ActiveValue::Set(Some(format!("/approvals/{}", model.id))),
+        metadata: sea_orm::ActiveValue::Set(Some(json!({
+            "approvalId": model.id,
+            "type": model.approval_type.to_value(),
+            "status": model.status.to_value(),
+            "currentStep": model.current_step,
+            "caseId": model.case_id,
+        }))),
+        read_at: sea_orm::ActiveValue::Set(None),
+        created_at: sea_orm::ActiveValue::Set(now),
+    }
+}
+
+async fn insert_notices<C: ConnectionTrait>(db: &C, notices: Vec<notification::ActiveModel>) -> AppResult<()> {
+    for notice in notices {
+        notice.insert(db).await.map_err(|e| AppError::Database(format!("写入通知失败: {e}")))?;
+    }
+    Ok(())
+}
+
+/// 事务内加锁重读申请，并确认状态与当前步骤未被并发修改
+async fn lock_request<C: ConnectionTrait>(
+    db: &C,
+    expected: &approval_request::Model,
+) -> AppResult<approval_request::Model> {
+    let locked = approval_request::Entity::find_by_id(&expected.id)
+        .lock_exclusive()
+        .one(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询审批失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("审批不存在".to_string()))?;
+    if locked.status != expected.status || locked.current_step != expected.current_step {
+        return Err(AppError::Validation("审批状态已变化，请刷新后重试".to_string()));
+    }
+    Ok(locked)
+}
+
+async fn to_responses(state: &AppState, models: Vec<approval_request::Model>) -> AppResult<Vec<ApprovalResponse>> {
+    let request_ids: Vec<String> = models.iter().map(|m| m.id.clone()).collect();
+    let mut steps_by_request: HashMap<String, Vec<approval_step::Model>> = HashMap::new();
+    if !request_ids.is_empty() {
+        let steps = approval_step::Entity::find()
+            .filter(approval_step::Column::RequestId.is_in(request_ids))
+            .order_by_asc(approval_step::Column::StepOrder)
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询审批步骤失败: {e}")))?;
+        for step in steps {
+            steps_by_request.entry(step.request_id.clone()).or_default().push(step);
+        }
+    }
+
+    let mut user_ids: Vec<String> = models
+        .iter()
+        .flat_map(|m| std::iter::once(m.requester_id.clone()).chain(m.approver_id.clone()))
+        .chain(
+            steps_by_request
+                .values()
+                .flatten()
+                .flat_map(|s| [s.approver_id.clone(), s.acted_by_id.clone(), s.on_behalf_of_id.clone()])
+                .flatten(),
+        )
+        .collect();
+    user_ids.sort();
+    user_ids.dedup();
+    let names = user_names(state, user_ids).await?;
+
+    Ok(models
+        .into_iter()
+        .map(|m| {
+            let steps = steps_by_request
+                .remove(&m.id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|s| ApprovalStepResponse {
+                    step_order: s.step_order,
+                    approver_name: s.approver_id.as_ref().and_then(|id| names.get(id)).cloned(),
+                    approver_id: s.approver_id,
+                    approver_role: s.approver_role.map(|r| r.to_value()),
+                    status: s.status.to_value(),
+                    note: s.note,
+                    acted_by_name: s.acted_by_id.as_ref().and_then(|id| names.get(id)).cloned(),
+                    acted_by_id: s.acted_by_id,
+                    on_behalf_of_name: s.on_behalf_of_id.as_ref().and_then(|id| names.get(id)).cloned(),
+                    on_behalf_of_id: s.on_behalf_of_id,
+                    acted_at: s.acted_at,
+                })
+                .collect();
+            ApprovalResponse {
+                requester_name: names.get(&m.requester_id).cloned(),
+                approver_name: m.approver_id.as_ref().and_then(|id| names.get(id)).cloned(),
+                id: m.id,
+                approval_type: m.approval_type.to_value(),
+                title: m.title,
+                description: m.description,
+                case_id: m.case_id,
+                client_id: m.client_id,
+                requester_id: m.requester_id,
+                approver_id: m.approver_id,
+                status: m.status.to_value(),
+                amount: m.amount.map(|a| a.to_string()),
+                metadata: m.metadata,
+                current_step: m.current_step,
+                steps,
+                created_at: m.created_at,
+                submitted_at: m.submitted_at,
+                resolved_at: m.resolved_at,
+                approval_note: m.approval_note,
+            }
+        })
+        .collect())
+}
+
+async fn to_response(state: &AppState, model: approval_request::Model) -> AppResult<ApprovalResponse> {
+    to_responses(state, vec![model])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::Internal("审批响应为空".to_string()))
+}
+
+/// 提交时解析出的一步审批人
+struct PlannedApprover {
+    approver_id: Option<String>,
+    approver_role: Option<Role>,
+}
+
+/// 按路由规则解析审批步骤；未配置规则时为单步审批
+async fn plan_approvers(
+    state: &AppState,
+    current_user: &CurrentUser,
+    model: &approval_request::Model,
+) -> AppResult<Vec<PlannedApprover>> {
+    let rules = approval_rule::Entity::find()
+        .filter(approval_rule::Column::TenantId.eq(&model.tenant_id))
+        .filter(approval_rule::Column::ApprovalType.eq(model.approval_type))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询审批规则失败: {e}")))?;
+    let min_amounts: Vec<Decimal> = rules.iter().map(|r| r.min_amount).collect();
+    let Some(index) = select_tier(&min_amounts, model.amount) else {
+        return Ok(vec![PlannedApprover { approver_id: model.approver_id.clone(), approver_role: None }]);
+    };
+
+    let rule = &rules[index];
+    let rule_steps = approval_rule_step::Entity::find()
+        .filter(approval_rule_step::Column::RuleId.eq(&rule.id))
+        .order_by_asc(approval_rule_step::Column::StepOrder)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询审批规则步骤失败: {e}")))?;
+    let incomplete = || AppError::Validation("审批规则配置不完整，请联系管理员".to_string());
+
+    let mut planned = Vec::with_capacity(rule_steps.len());
+    for step in rule_steps {
+        let approver = match step.approver_kind {
+            ApproverKind::CaseHandler => {
+                let case_id = model
+                    .case_id
+                    .as_deref()
+                    .ok_or_else(|| AppError::Validation("该类审批需由案件承办人审批，请先关联案件".to_string()))?;
+                let handler_id = case::Entity::find_by_id(case_id)
+                    .one(&state.db)
+                    .await
+                    .map_err(|e| AppError::Database(format!("查询案件失败: {e}")))?
+                    .and_then(|c| c.handler_id)
+                    .ok_or_else(|| AppError::Validation("关联案件未指定承办人".to_string()))?;
+                PlannedApprover { approver_id: Some(handler_id), approver_role: None }
+            }
+            ApproverKind::Supervisor => {
+                let supervisor_id = current_user
+                    .model
+                    .supervisor_id
+                    .clone()
+                    .ok_or_else(|| AppError::Validation("该类审批需由直属上级审批，但申请人未设置上级".to_string()))?;
+                PlannedApprover { approver_id: Some(supervisor_id), approver_role: None }
+            }
+            ApproverKind::Role => {
+                let role = step.approver_role.ok_or_else(incomplete)?;
+                PlannedApprover { approver_id: None, approver_role: Some(role) }
+            }
+            ApproverKind::User => {
+                let user_id = step.approver_user_id.ok_or_else(incomplete)?;
+                PlannedApprover { approver_id: Some(user_id), approver_role: None }
+            }
+        };
+        planned.push(approver);
+    }
+    if planned.is_empty() {
+        return Err(incomplete());
+    }
+    Ok(planned)
+}
+
+/// 提交草稿：生成步骤快照，进入第一个待审批步骤并通知审批人
+async fn submit_draft(
+    state: &AppState,
+    current_user: &CurrentUser,
+    model: approval_request::Model,
+) -> AppResult<approval_request::Model> {
+    require_requester(current_user, &model)?;
+    if model.status != ApprovalStatus::Draft {
+        return Err(AppError::Validation("仅草稿可提交".to_string()));
+    }
+
+    let approvers = plan_approvers(state, current_user, &model).await?;
+    for approver_id in approvers.iter().filter_map(|a| a.approver_id.as_deref()) {
+        if approver_id != model.requester_id {
+            ensure_member(state, &model.tenant_id, approver_id, "审批人").await?;
+        }
+    }
+    // 按角色（或审批权限）审批的步骤以当前租户成员为候选人；只有申请人本人时与"审批人即申请人"一样跳过
+    let members = if approvers.iter().any(|a| a.approver_id.is_none()) {
+        tenant_members(state, &model.tenant_id, None).await?
+    } else {
+        Vec::new()
+    };
+    let mut planned: Vec<PlannedStep> = Vec::with_capacity(approvers.len());
+    for (approver, order) in approvers.iter().zip(1..) {
+        let candidates: Vec<&str> = match (approver.approver_id.as_deref(), approver.approver_role.as_ref()) {
+            (Some(approver_id), _) => vec![approver_id],
+            (None, step_role) => members
+                .iter()
+                .filter(|(_, role)| match step_role {
+                    Some(step_role) => role == step_role,
+                    None => has_permission(role.clone(), Permission::ApprovalApprove),
+                })
+                .map(|(id, _)| id.as_str())
+                .collect(),
+        };
+        if candidates.is_empty() {
+            return Err(AppError::Validation(format!("审批流程第 {order} 步没有可审批的成员，请联系管理员")));
+        }
+        planned.push(PlannedStep { order, skipped: step_skipped(&model.requester_id, &candidates) });
+    }
+    let first = next_step(&planned, None)
+        .ok_or_else(|| AppError::Validation("审批流程中没有申请人以外的审批人".to_string()))?;
+    let first_approver = &approvers[(first - 1) as usize];
+    let recipients =
+        step_recipients(state, &model, first_approver.approver_id.as_deref(), first_approver.approver_role.as_ref())
+            .await?;
+
+    let actor_id = current_user.id().to_string();
+    let requester_name = current_user.model.name.clone().unwrap_or_else(|| current_user.model.email.clone());
+    state
+        .db
+        .transaction::<_, approval_request::Model, AppError>(|txn| {
+            Box::pin(async move {
+                let locked = lock_request(txn, &model).await?;
+                let now = Utc::now();
+                approval_step::Entity::delete_many()
+                    .filter(approval_step::Column::RequestId.eq(&locked.id))
+                    .exec(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("清理审批步骤失败: {e}")))?;
+
+                let mut current_approver = None;
+                for (step, approver) in planned.iter().zip(approvers) {
+                    let status = if step.skipped {
+                        ApprovalStepStatus::Skipped
+                    } else if step.order == first {
+                        current_approver = approver.approver_id.clone();
+                        ApprovalStepStatus::Pending
+                    } else {
+                        ApprovalStepStatus::Waiting
+                    };
+                    approval_step::ActiveModel {
+                        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                        tenant_id: sea_orm::ActiveValue::Set(locked.tenant_id.clone()),
+                        request_id: sea_orm::ActiveValue::Set(locked.id.clone()),
+                        step_order: sea_orm::ActiveValue::Set(step.order),
+                        approver_id: sea_orm::ActiveValue::Set(approver.approver_id),
+                        approver_role: sea_orm::ActiveValue::Set(approver.approver_role),
+                        status: sea_orm::ActiveValue::Set(status),
+                        note: sea_orm::ActiveValue::Set(None),
+                        acted_by_id: sea_orm::ActiveValue::Set(None),
+                        on_behalf_of_id: sea_orm::ActiveValue::Set(None),
+                        acted_at: sea_orm::ActiveValue::Set(step.skipped.then_some(now)),
+                        created_at: sea_orm::ActiveValue::Set(now),
+                    }
+                    .insert(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("写入审批步骤失败: {e}")))?;
+                }
+
+                let mut active: approval_request::ActiveModel = locked.into();
+                active.status = sea_orm::ActiveValue::Set(ApprovalStatus::Pending);
+                active.submitted_at = sea_orm::ActiveValue::Set(Some(now));
+                active.current_step = sea_orm::ActiveValue::Set(Some(first));
+                active.approver_id = sea_orm::ActiveValue::Set(current_approver);
+                let updated = active.update(txn).await.map_err(|e| AppError::Database(format!("提交审批失败: {e}")))?;
+
+                let title = format!("待审批：{}", updated.title);
+                let content = format!("{requester_name} 提交了{}审批，请处理", type_label(updated.approval_type));
+                let notices = recipients
+                    .into_iter()
+                    .map(|r| {
+                        let kind = NotificationType::ApprovalRequested;
+                        approval_notice(r, &actor_id, kind, title.clone(), content.clone(), &updated, now)
+                    })
+                    .collect();
+                insert_notices(txn, notices).await?;
+                Ok(updated)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })
+}
+
+/// 审批当前步骤：通过则推进到下一步（或完成），驳回则结束并取消剩余步骤
+async fn decide(
+    state: &AppState,
+    current_user: &CurrentUser,
+    model: approval_request::Model,
+    approve: bool,
+    note: Option<String>,
+) -> AppResult<approval_request::Model> {
+    if model.status != ApprovalStatus::Pending {
+        return Err(AppError::Validation("仅审批中的申请可审批".to_string()));
+    }
+    let steps = load_steps(&state.db, &model.id).await?;
+    let step = current_step(&model, &steps).ok_or_else(|| AppError::Internal("审批步骤缺失".to_string()))?;
+    let delegators = delegators_of(state, current_user).await?;
+    let acting = acting_as(current_user, &model, step, &delegators)
+        .ok_or_else(|| AppError::Forbidden("当前步骤不由你审批".to_string()))?;
+
+    let planned: Vec<PlannedStep> = steps
+        .iter()
+        .map(|s| PlannedStep { order: s.step_order, skipped: s.status == ApprovalStepStatus::Skipped })
+        .collect();
+    let next = if approve {
+        next_step(&planned, model.current_step).and_then(|order| steps.iter().find(|s| s.step_order == order)).cloned()
+    } else {
+        None
+    };
+    let next_recipients = match &next {
+        Some(s) => step_recipients(state, &model, s.approver_id.as_deref(), s.approver_role.as_ref()).await?,
+        None => Vec::new(),
+    };
+
+    let step = step.clone();
+    let actor_id = current_user.id().to_string();
+    let delegate_name = current_user.model.name.clone().unwrap_or_else(|| current_user.model.email.clone());
+    let (actor_name, on_behalf_of) = match acting {
+        Acting::Own => (delegate_name.clone(), None),
+        Acting::OnBehalfOf(principal_id) => {
+            let names = user_names(state, vec![principal_id.clone()]).await?;
+            let principal_name = names.get(&principal_id).cloned().unwrap_or_else(|| principal_id.clone());
+            (format!("{delegate_name}（代 {principal_name}）"), Some(principal_id))
+        }
+    };
+    state
+        .db
+        .transaction::<_, approval_request::Model, AppError>(|txn| {
+            Box::pin(async move {
+                let locked = lock_request(txn, &model).await?;
+                let now = Utc::now();
+
+                let step_order = step.step_order;
+                let mut active_step: approval_step::ActiveModel = step.into();
+                let step_status = if approve { ApprovalStepStatus::Approved } else { ApprovalStepStatus::Rejected };
+                active_step.status = sea_orm::ActiveValue::Set(step_status);
+                active_step.note = sea_orm::ActiveValue::Set(note.clone());
+                active_step.acted_by_id = sea_orm::ActiveValue::Set(Some(actor_id.clone()));
+                active_step.on_behalf_of_id = sea_orm::ActiveValue::Set(on_behalf_of.clone());
+                active_step.acted_at = sea_orm::ActiveValue::Set(Some(now));
+                active_step.update(txn).await.map_err(|e| AppError::Database(format!("更新审批步骤失败: {e}")))?;
+
+                let requester_id = locked.requester_id.clone();
+                let verb = if approve { "通过" } else { "驳回" };
+                let mut notices = Vec::new();
+                if let Some(principal_id) = on_behalf_of {
+                    notices.push(approval_notice(
+                        principal_id,
+                        &actor_id,
+                        NotificationType::ApprovalUpdated,
+                        format!("已代为审批：{}", locked.title),
+                        format!("你外出期间，{delegate_name} 代你{verb}了第 {step_order} 步审批"),
+                        &locked,
+                        now,
+                    ));
+                }
+                let mut active: approval_request::ActiveModel = locked.into();
+                match next {
+                    Some(next) => {
+                        let next_order = next.step_order;
+                        active.current_step = sea_orm::ActiveValue::Set(Some(next_order));
+                        active.approver_id = sea_orm::ActiveValue::Set(next.approver_id.clone());
+                        let mut active_next: approval_step::ActiveModel = next.into();
+                        active_next.status = sea_orm::ActiveValue::Set(ApprovalStepStatus::Pending);
+                        active_next
+                            .update(txn)
+                            .await
+                            .map_err(|e| AppError::Database(format!("更新审批步骤失败: {e}")))?;
+                        let updated =
+                            active.update(txn).await.map_err(|e| AppError::Database(format!("更新审批失败: {e}")))?;
+
+                        let title = format!("待审批：{}", updated.title);
+                        let content = format!("{}审批已进入第 {next_order} 步，请处理", type_label(updated.approval_type));
+                        notices.extend(next_recipients.into_iter().map(|r| {
+                            let kind = NotificationType::ApprovalRequested;
+                            approval_notice(r, &actor_id, kind, title.clone(), content.clone(), &updated, now)
+                        }));
+                        notices.push(approval_notice(
+                            requester_id,
+                            &actor_id,
+                            NotificationType::ApprovalUpdated,
+                            format!("审批进度：{}", updated.title),
+                            format!("{actor_name} 已通过第 {step_order} 步，等待第 {next_order} 步审批"),
+                            &updated,
+                            now,
+                        ));
+                        insert_notices(txn, notices).await?;
+                        Ok(updated)
+                    }
+                    None => {
+                        if !approve {
+                            approval_step::Entity::update_many()
+                                .set(approval_step::ActiveModel {
+                                    status: sea_orm::ActiveValue::Set(ApprovalStepStatus::Cancelled),
+                                    ..Default::default()
+                                })
+                                .filter(approval_step::Column::RequestId.eq(&model.id))
+                                .filter(approval_step::Column::Status.eq(ApprovalStepStatus::Waiting))
+                                .exec(txn)
+                                .await
+                                .map_err(|e| AppError::Database(format!("取消剩余审批步骤失败: {e}")))?;
+                        }
+                        let status = if approve { ApprovalStatus::Approved } else { ApprovalStatus::Rejected };
+                        active.status = sea_orm::ActiveValue::Set(status);
+                        active.current_step = sea_orm::ActiveValue::Set(None);
+                        active.approver_id = sea_orm::ActiveValue::Set(Some(actor_id.clone()));
+                        active.resolved_at = sea_orm::ActiveValue::Set(Some(now));
+                        active.approval_note = sea_orm::ActiveValue::Set(note.clone());
+                        let updated =
+                            active.update(txn).await.map_err(|e| AppError::Database(format!("更新审批失败: {e}")))?;
+
+                        let title = if approve { "审批已通过" } else { "审批被驳回" };
+                        let mut content = format!("{actor_name} {verb}了你的{}审批", type_label(updated.approval_type));
+                        if let Some(note) = note.as_deref() {
+                            content.push_str(&format!("：{note}"));
+                        }
+                        notices.push(approval_notice(
+                            requester_id,
+                            &actor_id,
+                            NotificationType::ApprovalUpdated,
+                            format!("{title}：{}", updated.title),
+                            content,
+                            &updated,
+                            now,
+                        ));
+                        insert_notices(txn, notices).await?;
+                        Ok(updated)
+                    }
+                }
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })
+}
+
+/// GET /api/v1/approvals
+async fn list_approvals(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<ApprovalListQuery>,
+) -> AppResult<Json<PaginatedResponse<ApprovalResponse>>> {
+    let role = current_user.model.role.clone();
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
+    let tenant_id = &current_user.model.active_tenant_id;
+    let me = current_user.id();
+
+    let mut select = approval_request::Entity::find()
+        .filter(approval_request::Column::TenantId.eq(tenant_id))
+        .order_by_desc(approval_request::Column::CreatedAt);
+    match query.filter.as_deref().map(str::trim).unwrap_or("mine") {
+        "mine" => {
+            require_permission(role, Permission::ApprovalCreate)?;
+            select = select.filter(approval_request::Column::RequesterId.eq(me));
+        }
+        "pending" => {
+            let mut actionable = Condition::any().add(approval_step::Column::ApproverId.eq(me)).add(
+                Condition::all()
+                    .add(approval_step::Column::ApproverId.is_null())
+                    .add(approval_step::Column::ApproverRole.eq(role.clone())),
+            );
+            if has_permission(role, Permission::ApprovalApprove) {
+                actionable = actionable.add(
+                    Condition::all()
+                        .add(approval_step::Column::ApproverId.is_null())
+                        .add(approval_step::Column::ApproverRole.is_null()),
+                );
+            }
+            // 外出委托人的指定审批步骤，时段内由我代为审批
+            let delegator_ids: Vec<String> =
+                delegators_of(&state, &current_user).await?.into_iter().map(|(id, _)| id).collect();
+            if !delegator_ids.is_empty() {
+                actionable = actionable.add(approval_step::Column::ApproverId.is_in(delegator_ids));
+            }
+            let request_ids: Vec<String> = approval_step::Entity::find()
+                .filter(approval_step::Column::TenantId.eq(tenant_id))
+                .filter(approval_step::Column::Status.eq(ApprovalStepStatus::Pending))
+                .filter(actionable)
+                .all(&state.db)
+                .await
+                .map_err(|e| AppError::Database(format!("查询待审批步骤失败: {e}")))?
+                .into_iter()
+                .map(|s| s.request_id)
+                .collect();
+            select = select
+                .filter(approval_request::Column::Id.is_in(request_ids))
+                .filter(approval_request::Column::Status.eq(ApprovalStatus::Pending))
+                .filter(approval_request::Column::RequesterId.ne(me));
+        }
+        "all" => require_permission(role, Permission::ApprovalViewAll)?,
+        other => return Err(AppError::Validation(format!("filter 无效: {other}"))),
+    }
+    if let Some(status) = query.status.as_deref() {
+        select = select.filter(approval_request::Column::Status.eq(parse_approval_status(status)?));
+    }
+    if let Some(approval_type) = query.approval_type.as_deref() {
+        select = select.filter(approval_request::Column::ApprovalType.eq(parse_approval_type(approval_type)?));
+    }
+    if let Some(case_id) = query.case_id.as_deref() {
+        Uuid::parse_str(case_id).map_err(|_| AppError::Validation("caseId 无效".to_string()))?;
+        select = select.filter(approval_request::Column::CaseId.eq(case_id));
+    }
+
+    let paginator = select.paginate(&state.db, page_size);
+    let total = paginator.num_items().await.map_err(|e| AppError::Database(format!("计数失败: {e}")))?;
+    let total_pages = paginator.num_pages().await.map_err(|e| AppError::Database(format!("分页失败: {e}")))?;
+    let models = paginator
+        .fetch_page(page - 1)
+        .await
+        .map_err(|e| AppError::Database(format!("查询审批失败: {e}")))?;
+
+    Ok(Json(PaginatedResponse { data: to_responses(&state, models).await?, total, page, page_size, total_pages }))
+}
+
+/// POST /api/v1/approvals
+async fn create_approval(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(req): ValidatedJson<CreateApprovalRequest>,
+) -> AppResult<Json<ApprovalResponse>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::ApprovalCreate)?;
+    let tenant_id = current_user.model.active_tenant_id.clone();
+
+    let approval_type = parse_approval_type(&req.approval_type)?;
+    let title = require_non_empty(&req.title, "title", 200)?;
+    let amount = trim_optional(req.amount).as_deref().map(|a| parse_amount("amount", a)).transpose()?;
+    let approver_id = trim_optional(req.approver_id);
+    if let Some(approver_id) = approver_id.as_deref() {
+        if approver_id == current_user.id() {
+            return Err(AppError::Validation("不能指定自己为审批人".to_string()));
+        }
+        ensure_member(&state, &tenant_id, approver_id, "approverId").await?;
+    }
+
+    let case_id = trim_optional(req.case_id);
+    let mut client_id = trim_optional(req.client_id);
+    if let Some(id) = case_id.as_deref() {
+        Uuid::parse_str(id).map_err(|_| AppError::Validation("caseId 无效".to_string()))?;
+        let case_model = require_case_access(&state, id, current_user.id(), role, Permission::CaseView).await?;
+        if case_model.tenant_id != tenant_id {
+            return Err(AppError::NotFound(format!("案件 {id} 不存在")));
+        }
+        client_id.get_or_insert(case_model.client_id);
+    }
+    if let Some(client_id) = client_id.as_deref() {
+        ensure_client_in_tenant(&state, &tenant_id, client_id).await?;
+    }
+
+    let created = approval_request::ActiveModel {
+        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+        tenant_id: sea_orm::ActiveValue::Set(tenant_id),
+        approval_type: sea_orm::ActiveValue::Set(approval_type),
+        title: sea_orm::ActiveValue::Set(title),
+        description: sea_orm::ActiveValue::Set(trim_optional(req.description)),
+        case_id: sea_orm::ActiveValue::Set(case_id),
+        client_id: sea_orm::ActiveValue::Set(client_id),
+        requester_id: sea_orm::ActiveValue::Set(current_user.id().to_string()),
+        approver_id: sea_orm::ActiveValue::Set(approver_id),
+        status: sea_orm::ActiveValue::Set(ApprovalStatus::Draft),
+        amount: sea_orm::ActiveValue::Set(amount),
+        metadata: sea_orm::ActiveValue::Set(Some(req.metadata.unwrap_or_else(|| json!({})))),
+        created_at: sea_orm::ActiveValue::Set(Utc::now()),
+        submitted_at: sea_orm::ActiveValue::Set(None),
+        resolved_at: sea_orm::ActiveValue::Set(None),
+        approval_note: sea_orm::ActiveValue::Set(None),
+        current_step: sea_orm::ActiveValue::Set(None),
+    }
+    .insert(&state.db)
+    .await
+    .map_err(|e| AppError::Database(format!("创建审批失败: {e}")))?;
+
+    let model = if req.submit.unwrap_or(false) { submit_draft(&state, &current_user, created).await? } else { created };
+    Ok(Json(to_response(&state, model).await?))
+}
+
+/// GET /api/v1/approvals/:id
+async fn get_approval(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> AppResult<Json<ApprovalResponse>> {
+    let model = load_request(&state, &current_user, &id).await?;
+    let steps = load_steps(&state.db, &model.id).await?;
+    let delegators = delegators_of(&state, &current_user).await?;
+    require_viewer(&current_user, &model, &steps, &delegators)?;
+    Ok(Json(to_response(&state, model).await?))
+}
+
+/// PATCH /api/v1/approvals/:id（仅草稿）
+async fn update_approval(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+    ValidatedJson(req): ValidatedJson<UpdateApprovalRequest>,
+) -> AppResult<Json<ApprovalResponse>> {
+    let model = load_request(&state, &current_user, &id).await?;
+    require_requester(&current_user, &model)?;
+    if model.status != ApprovalStatus::Draft {
+        return Err(AppError::Validation("仅草稿可修改".to_string()));
+    }
+
+    let mut active: approval_request::ActiveModel = model.into();
+    if let Some(title) = req.title.as_deref() {
+        active.title = sea_orm::ActiveValue::Set(require_non_empty(title, "title", 200)?);
+    }
+    if let Some(description) = req.description {
+        active.description = sea_orm::ActiveValue::Set(trim_optional(Some(description)));
+    }
+    if let Some(amount) = req.amount {
+        let amount = trim_optional(Some(amount)).as_deref().map(|a| parse_amount("amount", a)).transpose()?;
+        active.amount = sea_orm::ActiveValue::Set(amount);
+    }
+    if let Some(approver_id) = req.approver_id {
+        let approver_id = trim_optional(Some(approver_id));
+        if let Some(approver_id) = approver_id.as_deref() {
+            if approver_id == current_user.id() {
+                return Err(AppError::Validation("不能指定自己为审批人".to_string()));
+            }
+            ensure_member(&state, &current_user.model.active_tenant_id, approver_id, "approverId").await?;
+        }
+        active.approver_id = sea_orm::ActiveValue::Set(approver_id);
+    }
+    if let Some(metadata) = req.metadata {
+        active.metadata = sea_orm::ActiveValue::Set(Some(metadata));
+    }
+    let updated = active.update(&state.db).await.map_err(|e| AppError::Database(format!("更新审批失败: {e}")))?;
+    Ok(Json(to_response(&state, updated).await?))
+}
+
+/// POST /api/v1/approvals/:id/submit
+async fn submit_approval(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> AppResult<Json<ApprovalResponse>> {
+    let model = load_request(&state, &current_user, &id).await?;
+    let submitted = submit_draft(&state, &current_user, model).await?;
+    Ok(Json(to_response(&state, submitted).await?))
+}
+
+/// POST /api/v1/approvals/:id/approve
+async fn approve_approval(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+    ValidatedJson(req): ValidatedJson<ApprovalDecisionRequest>,
+) -> AppResult<Json<ApprovalResponse>> {
+    let model = load_request(&state, &current_user, &id).await?;
+    let updated = decide(&state, &current_user, model, true, trim_optional(req.note)).await?;
+    Ok(Json(to_response(&state, updated).await?))
+}
+
+/// POST /api/v1/approvals/:id/reject
+async fn reject_approval(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+    ValidatedJson(req): ValidatedJson<ApprovalDecisionRequest>,
+) -> AppResult<Json<ApprovalResponse>> {
+    let note = trim_optional(req.note).ok_or_else(|| AppError::Validation("驳回需填写审批意见".to_string()))?;
+    let model = load_request(&state, &current_user, &id).await?;
+    let updated = decide(&state, &current_user, model, false, Some(note)).await?;
+    Ok(Json(to_response(&state, updated).await?))
+}
+
+/// POST /api/v1/approvals/:id/withdraw（草稿或审批中）
+async fn withdraw_approval(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> AppResult<Json<ApprovalResponse>> {
+    let model = load_request(&state, &current_user, &id).await?;
+    require_requester(&current_user, &model)?;
+    if !matches!(model.status, ApprovalStatus::Draft | ApprovalStatus::Pending) {
+        return Err(AppError::Validation("仅草稿或审批中的申请可撤回".to_string()));
+    }
+    let recipients = if model.status == ApprovalStatus::Pending {
+        let steps = load_steps(&state.db, &model.id).await?;
+        match current_step(&model, &steps) {
+            Some(s) => step_recipients(&state, &model, s.approver_id.as_deref(), s.approver_role.as_ref()).await?,
+            None => Vec::new(),
+        }
+    } else {
+        Vec::new()
+    };
+
+    let actor_id = current_user.id().to_string();
+    let actor_name = current_user.model.name.clone().unwrap_or_else(|| current_user.model.email.clone());
+    let updated = state
+        .db
+        .transaction::<_, approval_request::Model, AppError>(|txn| {
+            Box::pin(async move {
+                let locked = lock_request(txn, &model).await?;
+                let now = Utc::now();
+                approval_step::Entity::update_many()
+                    .set(approval_step::ActiveModel {
+                        status: sea_orm::ActiveValue::Set(ApprovalStepStatus::Cancelled),
+                        ..Default::default()
+                    })
+                    .filter(approval_step::Column::RequestId.eq(&locked.id))
+                    .filter(
+                        approval_step::Column::Status.is_in([ApprovalStepStatus::Pending, ApprovalStepStatus::Waiting]),
+                    )
+                    .exec(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("取消审批步骤失败: {e}")))?;
+
+                let mut active: approval_request::ActiveModel = locked.into();
+                active.status = sea_orm::ActiveValue::Set(ApprovalStatus::Cancelled);
+                active.current_step = sea_orm::ActiveValue::Set(None);
+                active.resolved_at = sea_orm::ActiveValue::Set(Some(now));
+                let updated = active.update(txn).await.map_err(|e| AppError::Database(format!("撤回审批失败: {e}")))?;
+
+                let title = format!("审批已撤回：{}", updated.title);
+                let content = format!("{actor_name} 撤回了{}审批，无需再处理", type_label(updated.approval_type));
+                let notices = recipients
+                    .into_iter()
+                    .map(|r| {
+                        let kind = NotificationType::ApprovalUpdated;
+                        approval_notice(r, &actor_id, kind, title.clone(), content.clone(), &updated, now)
+                    })
+                    .collect();
+                insert_notices(txn, notices).await?;
+                Ok(updated)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+    Ok(Json(to_response(&state, updated).await?))
+}
+
+async fn to_rule_responses(state: &AppState, rules: Vec<approval_rule::Model>) -> AppResult<Vec<RuleResponse>> {
+    let rule_ids: Vec<String> = rules.iter().map(|r| r.id.clone()).collect();
+    let mut steps_by_rule: HashMap<String, Vec<approval_rule_step::Model>> = HashMap::new();
+    if !rule_ids.is_empty() {
+        let steps = approval_rule_step::Entity::find()
+            .filter(approval_rule_step::Column::RuleId.is_in(rule_ids))
+            .order_by_asc(approval_rule_step::Column::StepOrder)
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询审批规则步骤失败: {e}")))?;
+        for step in steps {
+            steps_by_rule.entry(step.rule_id.clone()).or_default().push(step);
+        }
+    }
+    let names = user_names(state, steps_by_rule.values().flatten().filter_map(|s| s.approver_user_id.clone()).collect())
+        .await?;
+
+    Ok(rules
+        .into_iter()
+        .map(|r| RuleResponse {
+            steps: steps_by_rule
+                .remove(&r.id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|s| RuleStepResponse {
+                    step_order: s.step_order,
+                    kind: s.approver_kind.to_value(),
+                    role: s.approver_role.map(|role| role.to_value()),
+                    user_name: s.approver_user_id.as_ref().and_then(|id| names.get(id)).cloned(),
+                    user_id: s.approver_user_id,
+                })
+                .collect(),
+            id: r.id,
+            approval_type: r.approval_type.to_value(),
+            min_amount: r.min_amount.to_string(),
+            name: r.name,
+            updated_at: r.updated_at,
+        })
+        .collect())
+}
+
+/// GET /api/v1/approvals/rules
+async fn list_rules(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<RuleListQuery>,
+) -> AppResult<Json<Vec<RuleResponse>>> {
+    require_permission(current_user.model.role.clone(), Permission::AdminSettings)?;
+    let mut select = approval_rule::Entity::find()
+        .filter(approval_rule::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .order_by_asc(approval_rule::Column::ApprovalType)
+        .order_by_asc(approval_rule::Column::MinAmount);
+    if let Some(approval_type) = query.approval_type.as_deref() {
+        select = select.filter(approval_rule::Column::ApprovalType.eq(parse_approval_type(approval_type)?));
+    }
+    let rules = select.all(&state.db).await.map_err(|e| AppError::Database(format!("查询审批规则失败: {e}")))?;
+    Ok(Json(to_rule_responses(&state, rules).await?))
+}
+
+/// PUT /api/v1/approvals/rules（按 类型 + 金额下限 覆盖写入，步骤整体替换）
+async fn upsert_rule(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(req): ValidatedJson<UpsertRuleRequest>,
+) -> AppResult<Json<RuleResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::AdminSettings)?;
+    let tenant_id = current_user.model.active_tenant_id.clone();
+
+    let approval_type = parse_approval_type(&req.approval_type)?;
+    let min_amount = match trim_optional(req.min_amount) {
+        Some(raw) => parse_amount("minAmount", &raw)?,
+        None => Decimal::ZERO,
+    };
+    if req.steps.is_empty() || req.steps.len() > MAX_RULE_STEPS {
+        return Err(AppError::Validation(format!("steps 需为 1-{MAX_RULE_STEPS} 个")));
+    }
+    let mut steps = Vec::with_capacity(req.steps.len());
+    for (index, step) in req.steps.iter().enumerate() {
+        let kind = parse_approver_kind(&step.kind)?;
+        let (role, user_id) = match kind {
+            ApproverKind::Role => {
+                let raw = step.role.as_deref().unwrap_or_default();
+                let role = parse_role(raw)
+                    .ok_or_else(|| AppError::Validation(format!("第 {} 步 role 无效: {raw}", index + 1)))?;
+                (Some(role), None)
+            }
+            ApproverKind::User => {
+                let user_id = trim_optional(step.user_id.clone())
+                    .ok_or_else(|| AppError::Validation(format!("第 {} 步需指定 userId", index + 1)))?;
+                ensure_member(&state, &tenant_id, &user_id, "userId").await?;
+                (None, Some(user_id))
+            }
+            ApproverKind::CaseHandler | ApproverKind::Supervisor => (None, None),
+        };
+        steps.push((kind, role, user_id));
+    }
+    let name = trim_optional(req.name);
+
+    let rule = state
+        .db
+        .transaction::<_, approval_rule::Model, AppError>(|txn| {
+            Box::pin(async move {
+                let now = Utc::now();
+                let existing = approval_rule::Entity::find()
+                    .filter(approval_rule::Column::TenantId.eq(&tenant_id))
+                    .filter(approval_rule::Column::ApprovalType.eq(approval_type))
+                    .filter(approval_rule::Column::MinAmount.eq(min_amount))
+                    .lock_exclusive()
+                    .one(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("查询审批规则失败: {e}")))?;
+                let rule = match existing {
+                    Some(rule) => {
+                        approval_rule_step::Entity::delete_many()
+                            .filter(approval_rule_step::Column::RuleId.eq(&rule.id))
+                            .exec(txn)
+                            .await
+                            .map_err(|e| AppError::Database(format!("清理审批规则步骤失败: {e}")))?;
+                        let mut active: approval_rule::ActiveModel = rule.into();
+                        active.name = sea_orm::ActiveValue::Set(name);
+                        active.updated_at = sea_orm::ActiveValue::Set(now);
+                        active.update(txn).await.map_err(|e| AppError::Database(format!("更新审批规则失败: {e}")))?
+                    }
+                    None => approval_rule::ActiveModel {
+                        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                        tenant_id: sea_orm::ActiveValue::Set(tenant_id),
+                        approval_type: sea_orm::ActiveValue::Set(approval_type),
+                        min_amount: sea_orm::ActiveValue::Set(min_amount),
+                        name: sea_orm::ActiveValue::Set(name),
+                        created_at: sea_orm::ActiveValue::Set(now),
+                        updated_at: sea_orm::ActiveValue::Set(now),
+                    }
+                    .insert(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("创建审批规则失败: {e}")))?,
+                };
+
+                for ((kind, role, user_id), order) in steps.into_iter().zip(1..) {
+                    approval_rule_step::ActiveModel {
+                        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                        rule_id: sea_orm::ActiveValue::Set(rule.id.clone()),
+                        step_order: sea_orm::ActiveValue::Set(order),
+                        approver_kind: sea_orm::ActiveValue::Set(kind),
+                        approver_role: sea_orm::ActiveValue::Set(role),
+                        approver_user_id: sea_orm::ActiveValue::Set(user_id),
+                    }
+                    .insert(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("写入审批规则步骤失败: {e}")))?;
+                }
+                Ok(rule)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    to_rule_responses(&state, vec![rule])
+        .await?
+        .pop()
+        .map(Json)
+        .ok_or_else(|| AppError::Internal("审批规则响应为空".to_string()))
+}
+
+/// DELETE /api/v1/approvals/rules/:id（已提交的审批使用步骤快照，不受影响）
+async fn delete_rule(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(rule_id): Path<String>,
+) -> AppResult<Json<serde_json::Value>> {
+    require_permission(current_user.model.role.clone(), Permission::AdminSettings)?;
+    Uuid::parse_str(&rule_id).map_err(|_| AppError::Validation("规则ID 无效".to_string()))?;
+    let rule = approval_rule::Entity::find_by_id(&rule_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询审批规则失败: {e}")))?
+        .filter(|r| r.tenant_id == current_user.model.active_tenant_id)
+        .ok_or_else(|| AppError::NotFound("审批规则不存在".to_string()))?;
+    approval_rule::Entity::delete_by_id(rule.id)
+        .exec(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("删除审批规则失败: {e}")))?;
+    Ok(Json(json!({ "success": true })))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_approvals).post(create_approval))
+        .route("/rules", get(list_rules).put(upsert_rule))
+        .route("/rules/:id", delete(delete_rule))
+        .route("/:id", get(get_approval).patch(update_approval))
+        .route("/:id/submit", post(submit_approval))
+        .route("/:id/approve", post(approve_approval))
+        .route("/:id/reject", post(reject_approval))
+        .route("/:id/withdraw", post(withdraw_approval))
+}
@@ -0,0 +1,306 @@
+//! 计费配置路由模块
+//!
+//! - 计费取整规则：租户默认（`caseId` 为空）+ 案件级覆盖；未配置时按实际秒数计费
+//! - 查看需 `billing:view`；租户默认规则需 `admin:settings`；案件规则需 `billing:edit` 且可见该案件
+//! - 规则在停止计时/补录/修改/审核调整时生效，已生成的工时金额不追溯重算
+//! - 费率卡见 `rate_cards`，费用加收规则见 `expense_markups`，UTBMS 代码映射见 `utbms_codes`（均合并到本路由）
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    routing::{get, put},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter,
+    QueryOrder, Statement, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::billing::increments::{IncrementRule, Rounding};
+use crate::db::AppState;
+use crate::entity::billing_rule::{self, BillingRoundingMode};
+use crate::error::{AppError, AppResult};
+use crate::security::case_access::require_case_access;
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+use crate::security::validation::ValidatedJson;
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertBillingRuleRequest {
+    #[validate(range(min = 1, max = 60, message = "incrementMinutes 需在 1-60 之间"))]
+    pub increment_minutes: i32,
+    /// `UP` | `NEAREST`，缺省 `UP`
+    pub rounding_mode: Option<String>,
+    #[validate(range(min = 0, max = 480, message = "minimumMinutes 需在 0-480 之间"))]
+    pub minimum_minutes: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingRuleResponse {
+    pub id: String,
+    pub case_id: Option<String>,
+    pub increment_minutes: i32,
+    pub rounding_mode: String,
+    pub minimum_minutes: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<billing_rule::Model> for BillingRuleResponse {
+    fn from(m: billing_rule::Model) -> Self {
+        Self {
+            id: m.id,
+            case_id: m.case_id,
+            increment_minutes: m.increment_minutes,
+            rounding_mode: m.rounding_mode.to_value(),
+            minimum_minutes: m.minimum_minutes,
+            updated_at: m.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingRulesResponse {
+    pub default: Option<BillingRuleResponse>,
+    pub cases: Vec<BillingRuleResponse>,
+}
+
+/// 案件实际生效的规则；source: CASE | TENANT | NONE
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveBillingRuleResponse {
+    pub source: &'static str,
+    pub rule: Option<BillingRuleResponse>,
+}
+
+fn parse_rounding_mode(raw: Option<&str>) -> AppResult<BillingRoundingMode> {
+    match raw.map(str::trim) {
+        None | Some("") | Some("UP") => Ok(BillingRoundingMode::Up),
+        Some("NEAREST") => Ok(BillingRoundingMode::Nearest),
+        Some(other) => Err(AppError::Validation(format!("roundingMode 无效: {other}"))),
+    }
+}
+
+impl From<&billing_rule::Model> for IncrementRule {
+    fn from(m: &billing_rule::Model) -> Self {
+        let rounding = match m.rounding_mode {
+            BillingRoundingMode::Up => Rounding::Up,
+            BillingRoundingMode::Nearest => Rounding::Nearest,
+        };
+        IncrementRule::from_minutes(m.increment_minutes, rounding, m.minimum_minutes)
+    }
+}
+
+/// 案件规则优先，其次租户默认规则
+async fn find_effective_rule<C: ConnectionTrait>(
+    db: &C,
+    tenant_id: &str,
+    case_id: Option<&str>,
+) -> AppResult<Option<billing_rule::Model>> {
+    let mut scope = Condition::any().add(billing_rule::Column::CaseId.is_null());
+    if let Some(case_id) = case_id {
+        scope = scope.add(billing_rule::Column::CaseId.eq(case_id));
+    }
+    let rules = billing_rule::Entity::find()
+        .filter(billing_rule::Column::TenantId.eq(tenant_id))
+        .filter(scope)
+        .all(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询计费规则失败: {e}")))?;
+
+    let (case_rules, default_rules): (Vec<_>, Vec<_>) = rules.into_iter().partition(|r| r.case_id.is_some());
+    Ok(case_rules.into_iter().next().or_else(|| default_rules.into_iter().next()))
+}
+
+/// 计算工时计费时长所用的取整规则
+pub(crate) async fn resolve_increment_rule<C: ConnectionTrait>(
+    db: &C,
+    tenant_id: &str,
+    case_id: Option<&str>,
+) -> AppResult<IncrementRule> {
+    Ok(find_effective_rule(db, tenant_id, case_id)
+        .await?
+        .as_ref()
+        .map(IncrementRule::from)
+        .unwrap_or(IncrementRule::EXACT))
+}
+
+async fn upsert_rule(
+    state: &AppState,
+    tenant_id: &str,
+    case_id: Option<&str>,
+    payload: &UpsertBillingRuleRequest,
+) -> AppResult<billing_rule::Model> {
+    let rounding_mode = parse_rounding_mode(payload.rounding_mode.as_deref())?;
+    let minimum_minutes = payload.minimum_minutes.unwrap_or(0);
+
+    let tenant_id = tenant_id.to_string();
+    let case_id = case_id.map(str::to_string);
+    let increment_minutes = payload.increment_minutes;
+    state
+        .db
+        .transaction::<_, billing_rule::Model, AppError>(|txn| {
+            Box::pin(async move {
+                // 同一租户 / 案件的规则串行写入，避免并发首次保存时插入两条
+                let scope = case_id.as_deref().unwrap_or("default");
+                txn.execute(Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    "SELECT pg_advisory_xact_lock(hashtext($1))",
+                    [format!("billing-rule:{tenant_id}:{scope}").into()],
+                ))
+                .await
+                .map_err(|e| AppError::Database(format!("获取计费规则锁失败: {e}")))?;
+
+                let existing = billing_rule::Entity::find()
+                    .filter(billing_rule::Column::TenantId.eq(&tenant_id))
+                    .filter(match case_id.as_deref() {
+                        Some(case_id) => billing_rule::Column::CaseId.eq(case_id),
+                        None => billing_rule::Column::CaseId.is_null(),
+                    })
+                    .one(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("查询计费规则失败: {e}")))?;
+
+                let now = Utc::now();
+                let saved = match existing {
+                    Some(model) => {
+                        let mut active: billing_rule::ActiveModel = model.into();
+                        active.increment_minutes = sea_orm::ActiveValue::Set(increment_minutes);
+                        active.rounding_mode = sea_orm::ActiveValue::Set(rounding_mode);
+                        active.minimum_minutes = sea_orm::ActiveValue::Set(minimum_minutes);
+                        active.updated_at = sea_orm::ActiveValue::Set(now);
+                        active.update(txn).await
+                    }
+                    None => {
+                        billing_rule::ActiveModel {
+                            id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                            tenant_id: sea_orm::ActiveValue::Set(tenant_id),
+                            case_id: sea_orm::ActiveValue::Set(case_id),
+                            increment_minutes: sea_orm::ActiveValue::Set(increment_minutes),
+                            rounding_mode: sea_orm::ActiveValue::Set(rounding_mode),
+                            minimum_minutes: sea_orm::ActiveValue::Set(minimum_minutes),
+                            created_at: sea_orm::ActiveValue::Set(now),
+                            updated_at: sea_orm::ActiveValue::Set(now),
+                        }
+                        .insert(txn)
+                        .await
+                    }
+                };
+                saved.map_err(|e| AppError::Database(format!("保存计费规则失败: {e}")))
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })
+}
+
+/// GET /api/v1/billing/rules
+async fn list_rules(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+) -> AppResult<Json<BillingRulesResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingView)?;
+
+    let rules = billing_rule::Entity::find()
+        .filter(billing_rule::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .order_by_desc(billing_rule::Column::UpdatedAt)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询计费规则失败: {e}")))?;
+
+    let mut default = None;
+    let mut cases = Vec::new();
+    for rule in rules {
+        if rule.case_id.is_some() {
+            cases.push(BillingRuleResponse::from(rule));
+        } else {
+            default = Some(BillingRuleResponse::from(rule));
+        }
+    }
+    Ok(Json(BillingRulesResponse { default, cases }))
+}
+
+/// PUT /api/v1/billing/rules/default
+async fn put_default_rule(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(payload): ValidatedJson<UpsertBillingRuleRequest>,
+) -> AppResult<Json<BillingRuleResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::AdminSettings)?;
+    let saved = upsert_rule(&state, &current_user.model.active_tenant_id, None, &payload).await?;
+    Ok(Json(BillingRuleResponse::from(saved)))
+}
+
+/// GET /api/v1/billing/cases/:case_id/rule
+async fn get_case_rule(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(case_id): Path<String>,
+) -> AppResult<Json<EffectiveBillingRuleResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingView)?;
+    let case_model =
+        require_case_access(&state, &case_id, current_user.id(), current_user.model.role.clone(), Permission::CaseView)
+            .await?;
+
+    let rule = find_effective_rule(&state.db, &case_model.tenant_id, Some(&case_model.id)).await?;
+    let source = match &rule {
+        Some(r) if r.case_id.is_some() => "CASE",
+        Some(_) => "TENANT",
+        None => "NONE",
+    };
+    Ok(Json(EffectiveBillingRuleResponse { source, rule: rule.map(BillingRuleResponse::from) }))
+}
+
+/// PUT /api/v1/billing/cases/:case_id/rule
+async fn put_case_rule(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(case_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<UpsertBillingRuleRequest>,
+) -> AppResult<Json<BillingRuleResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    let case_model =
+        require_case_access(&state, &case_id, current_user.id(), current_user.model.role.clone(), Permission::CaseView)
+            .await?;
+
+    let saved = upsert_rule(&state, &case_model.tenant_id, Some(&case_model.id), &payload).await?;
+    Ok(Json(BillingRuleResponse::from(saved)))
+}
+
+/// DELETE /api/v1/billing/cases/:case_id/rule（恢复为租户默认规则）
+async fn delete_case_rule(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(case_id): Path<String>,
+) -> AppResult<Json<()>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    let case_model =
+        require_case_access(&state, &case_id, current_user.id(), current_user.model.role.clone(), Permission::CaseView)
+            .await?;
+
+    billing_rule::Entity::delete_many()
+        .filter(billing_rule::Column::CaseId.eq(&case_model.id))
+        .exec(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("删除计费规则失败: {e}")))?;
+    Ok(Json(()))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/rules", get(list_rules))
+        .route("/rules/default", put(put_default_rule))
+        .route("/cases/:case_id/rule", get(get_case_rule).put(put_case_rule).delete(delete_case_rule))
+        .merge(super::rate_cards::router())
+        .merge(super::expense_markups::router())
+        .merge(super::utbms_codes::router())
+}
@@ -0,0 +1,232 @@
+//! 案件预算（收费模式）路由与计费拆分
+//!
+//! - CAPPED：同一案件累计计费金额不超过合同金额，超出部分记为核销（writeOffAmount）；
+//!   累计金额跨过预警阈值（`CASE_BUDGET_WARN_PERCENTS`，默认 80,100）时通知承办律师（无承办人时通知案源律师）
+//! - FIXED：工时只作为投入记录，计费金额为 0，工时价值全部记为核销，供利润分析
+//! - HOURLY 或 CAPPED 未填写合同金额：按工时金额全额计费
+//! - `GET /cases/:id/budget`：案件预算使用情况
+//!
+//! 拆分在写入工时的事务内进行，并按案件加 advisory lock，避免并发写入同时占用剩余额度。
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use chrono::Utc;
+use sea_orm::prelude::Decimal;
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QuerySelect,
+    Statement,
+};
+use serde::Serialize;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::billing::budget::{crossed_thresholds, used_percent, BudgetMode};
+use crate::db::AppState;
+use crate::entity::case::{self, BillingMode};
+use crate::entity::{notification, time_log};
+use crate::error::{AppError, AppResult};
+use crate::security::case_access::require_case_access;
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+
+use super::timelogs::BillingSnapshot;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaseBudgetResponse {
+    pub case_id: String,
+    pub billing_mode: String,
+    pub contract_value: Option<String>,
+    /// 已计费金额（COMPLETED / APPROVED / BILLED）
+    pub billed_amount: String,
+    /// 核销金额（封顶超额 / 固定收费工时价值）
+    pub write_off_amount: String,
+    /// 剩余额度（仅 CAPPED 且有合同金额时）
+    pub remaining_amount: Option<String>,
+    /// 已用比例（百分比，保留 2 位；仅 CAPPED 且有合同金额时）
+    pub used_percent: Option<String>,
+    /// OK | WARNING | EXCEEDED | UNCAPPED
+    pub status: &'static str,
+    pub warn_percents: Vec<u32>,
+    /// 工时总时长（秒）
+    pub total_duration: i64,
+}
+
+fn budget_mode_of(case_model: &case::Model) -> BudgetMode {
+    match (&case_model.billing_mode, case_model.contract_value) {
+        (BillingMode::Fixed, _) => BudgetMode::Fixed,
+        (BillingMode::Capped, Some(cap)) => BudgetMode::Capped { cap },
+        _ => BudgetMode::Hourly,
+    }
+}
+
+fn settled_statuses() -> Vec<time_log::TimeLogStatus> {
+    vec![time_log::TimeLogStatus::Completed, time_log::TimeLogStatus::Approved, time_log::TimeLogStatus::Billed]
+}
+
+/// 案件已计费金额合计（可排除正在重算的工时）
+async fn billed_total<C: ConnectionTrait>(db: &C, case_id: &str, exclude_id: Option<&str>) -> AppResult<Decimal> {
+    let mut select = time_log::Entity::find()
+        .filter(time_log::Column::CaseId.eq(case_id))
+        .filter(time_log::Column::IsBillable.eq(true))
+        .filter(time_log::Column::Status.is_in(settled_statuses()));
+    if let Some(id) = exclude_id {
+        select = select.filter(time_log::Column::Id.ne(id));
+    }
+    let total = select
+        .select_only()
+        .column_as(time_log::Column::BillingAmount.sum(), "total")
+        .into_tuple::<Option<Decimal>>()
+        .one(db)
+        .await
+        .map_err(|e| AppError::Database(format!("汇总案件计费金额失败: {e}")))?
+        .flatten();
+    Ok(total.unwrap_or_default())
+}
+
+/// 按案件收费模式拆分本条工时的计费/核销金额；需在写入工时的事务内调用
+pub(crate) async fn apply_case_billing_mode<C: ConnectionTrait>(
+    db: &C,
+    warn_percents: &[u32],
+    actor_id: &str,
+    case_id: Option<&str>,
+    exclude_log_id: Option<&str>,
+    billing: BillingSnapshot,
+) -> AppResult<BillingSnapshot> {
+    let (Some(case_id), Some(amount)) = (case_id, billing.amount) else {
+        return Ok(billing);
+    };
+    let case_model = case::Entity::find_by_id(case_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询案件失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound(format!("案件 {} 不存在", case_id)))?;
+
+    let cap = match budget_mode_of(&case_model) {
+        BudgetMode::Hourly => return Ok(billing),
+        BudgetMode::Fixed => {
+            return Ok(BillingSnapshot { amount: Some(Decimal::ZERO), write_off_amount: Some(amount), ..billing })
+        }
+        BudgetMode::Capped { cap } => cap,
+    };
+
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT pg_advisory_xact_lock(hashtext($1))",
+        [format!("case-budget:{case_id}").into()],
+    ))
+    .await
+    .map_err(|e| AppError::Database(format!("获取案件预算锁失败: {e}")))?;
+
+    let billed_so_far = billed_total(db, case_id, exclude_log_id).await?;
+    let split = BudgetMode::Capped { cap }.split(billed_so_far, amount);
+    let billed_after = billed_so_far + split.billable;
+
+    let recipient = case_model.handler_id.clone().or_else(|| case_model.originator_id.clone());
+    if let Some(recipient) = recipient {
+        let now = Utc::now();
+        for percent in crossed_thresholds(cap, billed_so_far, billed_after, warn_percents) {
+            notification::ActiveModel {
+                id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                user_id: sea_orm::ActiveValue::Set(recipient.clone()),
+                actor_id: sea_orm::ActiveValue::Set(Some(actor_id.to_string())),
+                notification_type: sea_orm::ActiveValue::Set(notification::NotificationType::CaseBudgetThreshold),
+                title: sea_orm::ActiveValue::Set(format!("案件计费已达合同金额 {percent}%")),
+                content: sea_orm::ActiveValue::Set(Some(format!(
+                    "{}（{}）：已计费 {} / 合同金额 {}",
+                    case_model.title,
+                    case_model.case_code,
+                    billed_after.round_dp(2),
+                    cap.round_dp(2)
+                ))),
+                action_url: sea_orm::ActiveValue::Set(Some(format!("/cases/{}", case_model.id))),
+                metadata: sea_orm::ActiveValue::Set(Some(json!({
+                    "caseId": case_model.id,
+                    "percent": percent,
+                    "billedAmount": billed_after.to_string(),
+                    "contractValue": cap.to_string(),
+                }))),
+                read_at: sea_orm::ActiveValue::Set(None),
+                created_at: sea_orm::ActiveValue::Set(now),
+            }
+            .insert(db)
+            .await
+            .map_err(|e| AppError::Database(format!("写入通知失败: {e}")))?;
+        }
+    }
+
+    Ok(BillingSnapshot {
+        amount: Some(split.billable),
+        write_off_amount: (split.write_off > Decimal::ZERO).then_some(split.write_off),
+        ..billing
+    })
+}
+
+/// GET /api/v1/cases/:id/budget
+pub async fn get_case_budget(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(case_id): Path<String>,
+) -> AppResult<Json<CaseBudgetResponse>> {
+    Uuid::parse_str(&case_id).map_err(|_| AppError::Validation("案件ID 无效".to_string()))?;
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::BillingView)?;
+    let case_model = require_case_access(&state, &case_id, current_user.id(), role, Permission::CaseView).await?;
+
+    let logs: Vec<(bool, Option<Decimal>, Option<Decimal>, i32)> = time_log::Entity::find()
+        .filter(time_log::Column::CaseId.eq(&case_model.id))
+        .filter(time_log::Column::Status.is_in(settled_statuses()))
+        .select_only()
+        .column(time_log::Column::IsBillable)
+        .column(time_log::Column::BillingAmount)
+        .column(time_log::Column::WriteOffAmount)
+        .column(time_log::Column::Duration)
+        .into_tuple()
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询案件工时失败: {e}")))?;
+
+    let mut billed = Decimal::ZERO;
+    let mut write_off = Decimal::ZERO;
+    let mut total_duration = 0i64;
+    for (is_billable, amount, write_off_amount, duration) in logs {
+        if is_billable {
+            billed += amount.unwrap_or_default();
+        }
+        write_off += write_off_amount.unwrap_or_default();
+        total_duration += i64::from(duration);
+    }
+
+    let warn_percents = state.config.case_budget_warn_percents.clone();
+    let (remaining, percent, status) = match budget_mode_of(&case_model) {
+        BudgetMode::Capped { cap } => {
+            let percent = used_percent(cap, billed);
+            let warn_from = warn_percents.first().copied().map(Decimal::from);
+            let status = if billed >= cap {
+                "EXCEEDED"
+            } else if warn_from.is_some_and(|w| percent >= w) {
+                "WARNING"
+            } else {
+                "OK"
+            };
+            (Some((cap - billed).max(Decimal::ZERO)), Some(percent), status)
+        }
+        _ => (None, None, "UNCAPPED"),
+    };
+
+    Ok(Json(CaseBudgetResponse {
+        case_id: case_model.id,
+        billing_mode: case_model.billing_mode.to_value(),
+        contract_value: case_model.contract_value.map(|v| v.to_string()),
+        billed_amount: billed.to_string(),
+        write_off_amount: write_off.to_string(),
+        remaining_amount: remaining.map(|v| v.to_string()),
+        used_percent: percent.map(|v| v.round_dp(2).to_string()),
+        status,
+        warn_percents,
+        total_duration,
+    }))
+}
@@ -435,7 +435,6 @@ async fn create_case(
             let claims_sub = current_user.id().to_string();
             let title = title.clone();
             let description = description.clone();
-            let contract_value = contract_value.clone();
             let service_type = service_type.clone();
             let billing_mode = billing_mode.clone();
             let handler_id = handler_id.clone();
@@ -610,4 +609,6 @@ pub fn router() -> Router<Arc<AppState>> {
     Router::new()
         .route("/", get(list_cases).post(create_case))
         .route("/:id", get(get_case))
+        .route("/:id/tasks/critical-path", get(super::task_dependencies::case_critical_path))
+        .route("/:id/budget", get(super::case_budget::get_case_budget))
 }
@@ -0,0 +1,619 @@
+//! 合同台账路由（与 Web 主线 `contract-actions.ts` 口径对齐）
+//!
+//! - 列表/详情需 `billing:view`；创建需 `billing:create`；编辑、删除、关联文件需 `billing:edit`
+//! - 关联案件的合同按案件可见性控制；未关联案件的合同仅 `admin:access` 可操作（列表中对其它角色不可见）
+//! - 合同编号缺省按 `CTR-{yyyyMMdd}-{序号}` 生成（按租户加 advisory lock），租户内唯一
+//! - 合同文件复用案件文档：文档须属于当前租户，且与合同所属案件一致（合同未关联案件时随文档归入其案件）；
+//!   一份文档只能关联一份合同
+//! - 删除为软删除（写入 deletedAt/deletedById 并置为 CANCELLED）
+//! - 到期提醒与到期标记由后台任务 `jobs::contract_expiry` 负责
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{get, put},
+    Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::prelude::Decimal;
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, Statement, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::billing::invoice::round_money;
+use crate::db::AppState;
+use crate::entity::contract::{self, ContractStatus};
+use crate::entity::{case, document};
+use crate::error::{AppError, AppResult};
+use crate::security::case_access::{require_case_access, visible_case_ids};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+use crate::security::validation::{require_non_empty, ValidatedJson};
+
+use super::cases::PaginatedResponse;
+use super::receivables::contact_names;
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateContractRequest {
+    /// 缺省自动生成
+    #[validate(length(max = 120, message = "contractNo 长度不合法"))]
+    pub contract_no: Option<String>,
+    pub title: String,
+    /// `DRAFT`（缺省）| `SIGNED` | `ACTIVE` | `EXPIRED` | `CANCELLED`
+    pub status: Option<String>,
+    /// 合同金额（Decimal 字符串，最多 2 位小数）
+    pub amount: Option<String>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub signed_at: Option<DateTime<Utc>>,
+    #[validate(length(max = 10000, message = "notes 长度不合法"))]
+    pub notes: Option<String>,
+    pub case_id: Option<String>,
+    /// 缺省取案件的委托人
+    pub client_id: Option<String>,
+    pub document_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateContractRequest {
+    #[validate(length(max = 120, message = "contractNo 长度不合法"))]
+    pub contract_no: Option<String>,
+    pub title: Option<String>,
+    pub status: Option<String>,
+    /// 传空字符串清空
+    pub amount: Option<String>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub signed_at: Option<DateTime<Utc>>,
+    #[validate(length(max = 10000, message = "notes 长度不合法"))]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkContractDocumentRequest {
+    pub document_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractListQuery {
+    pub case_id: Option<String>,
+    pub client_id: Option<String>,
+    pub status: Option<String>,
+    /// 按合同编号/标题模糊搜索
+    pub search: Option<String>,
+    /// 仅列出 N 天内到期的已签署/生效中合同
+    pub expiring_within_days: Option<i64>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractResponse {
+    pub id: String,
+    pub contract_no: String,
+    pub title: String,
+    pub status: String,
+    pub amount: Option<String>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub signed_at: Option<DateTime<Utc>>,
+    pub notes: Option<String>,
+    pub case_id: Option<String>,
+    pub case_code: Option<String>,
+    pub client_id: Option<String>,
+    pub client_name: Option<String>,
+    pub document_id: Option<String>,
+    pub document_title: Option<String>,
+    pub creator_id: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn parse_contract_status(raw: &str) -> AppResult<ContractStatus> {
+    match raw.trim() {
+        "DRAFT" => Ok(ContractStatus::Draft),
+        "SIGNED" => Ok(ContractStatus::Signed),
+        "ACTIVE" => Ok(ContractStatus::Active),
+        "EXPIRED" => Ok(ContractStatus::Expired),
+        "CANCELLED" => Ok(ContractStatus::Cancelled),
+        other => Err(AppError::Validation(format!("status 无效: {other}"))),
+    }
+}
+
+fn parse_contract_amount(raw: &str) -> AppResult<Decimal> {
+    match Decimal::from_str(raw.trim()) {
+        Ok(v) if v >= Decimal::ZERO && round_money(v) == v => Ok(v),
+        _ => Err(AppError::Validation(format!("amount 无效: {raw}（需为非负数，最多 2 位小数）"))),
+    }
+}
+
+fn validate_period(start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) -> AppResult<()> {
+    match (start, end) {
+        (Some(start), Some(end)) if end < start => Err(AppError::Validation("endDate 不能早于 startDate".to_string())),
+        _ => Ok(()),
+    }
+}
+
+fn trim_optional(value: Option<String>) -> Option<String> {
+    value.map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/// 查询合同并校验租户与可见性（关联案件按案件可见性，否则需 `admin:access`）
+async fn require_contract_access(
+    state: &AppState,
+    current_user: &CurrentUser,
+    contract_id: &str,
+) -> AppResult<contract::Model> {
+    Uuid::parse_str(contract_id).map_err(|_| AppError::Validation("合同ID 无效".to_string()))?;
+    let model = contract::Entity::find_by_id(contract_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询合同失败: {e}")))?
+        .filter(|c| c.tenant_id == current_user.model.active_tenant_id && c.deleted_at.is_none())
+        .ok_or_else(|| AppError::NotFound("合同不存在".to_string()))?;
+    let role = current_user.model.role.clone();
+    match model.case_id.as_deref() {
+        Some(case_id) => {
+            require_case_access(state, case_id, current_user.id(), role, Permission::CaseView).await?;
+        }
+        None => require_permission(role, Permission::AdminAccess)?,
+    }
+    Ok(model)
+}
+
+async fn ensure_client_in_tenant(state: &AppState, tenant_id: &str, client_id: &str) -> AppResult<()> {
+    state
+        .db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT 1 FROM "Contact" WHERE id = $1 AND "tenantId" = $2 AND "deletedAt" IS NULL LIMIT 1"#,
+            [client_id.into(), tenant_id.into()],
+        ))
+        .await
+        .map_err(|e| AppError::Database(format!("查询客户失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("客户不存在或不在当前租户".to_string()))?;
+    Ok(())
+}
+
+/// 校验文档属于当前租户且未被其它合同关联；`case_id` 给定时须与文档所属案件一致
+async fn resolve_document(
+    state: &AppState,
+    tenant_id: &str,
+    document_id: &str,
+    case_id: Option<&str>,
+    contract_id: Option<&str>,
+) -> AppResult<document::Model> {
+    Uuid::parse_str(document_id).map_err(|_| AppError::Validation("documentId 无效".to_string()))?;
+    let doc = document::Entity::find_by_id(document_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询文档失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("文档不存在".to_string()))?;
+    let doc_case = case::Entity::find_by_id(&doc.case_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询案件失败: {e}")))?;
+    if doc_case.map_or(true, |c| c.tenant_id != tenant_id) {
+        return Err(AppError::NotFound("文档不存在".to_string()));
+    }
+    if case_id.is_some_and(|id| id != doc.case_id) {
+        return Err(AppError::Validation("合同与文档所属案件不一致".to_string()));
+    }
+
+    let mut taken = contract::Entity::find().filter(contract::Column::DocumentId.eq(document_id));
+    if let Some(contract_id) = contract_id {
+        taken = taken.filter(contract::Column::Id.ne(contract_id));
+    }
+    let taken = taken.one(&state.db).await.map_err(|e| AppError::Database(format!("查询合同失败: {e}")))?;
+    if taken.is_some() {
+        return Err(AppError::Validation("该文档已关联其他合同".to_string()));
+    }
+    Ok(doc)
+}
+
+/// 租户内合同编号是否已被占用（含已删除合同）
+async fn contract_no_taken<C: ConnectionTrait>(
+    db: &C,
+    tenant_id: &str,
+    contract_no: &str,
+    exclude_id: Option<&str>,
+) -> AppResult<bool> {
+    let mut select = contract::Entity::find()
+        .filter(contract::Column::TenantId.eq(tenant_id))
+        .filter(contract::Column::ContractNo.eq(contract_no));
+    if let Some(id) = exclude_id {
+        select = select.filter(contract::Column::Id.ne(id));
+    }
+    let existing = select.one(db).await.map_err(|e| AppError::Database(format!("查询合同编号失败: {e}")))?;
+    Ok(existing.is_some())
+}
+
+async fn to_responses(state: &AppState, models: Vec<contract::Model>) -> AppResult<Vec<ContractResponse>> {
+    let case_ids: Vec<String> = models.iter().filter_map(|m| m.case_id.clone()).collect();
+    let case_codes: HashMap<String, String> = if case_ids.is_empty() {
+        HashMap::new()
+    } else {
+        case::Entity::find()
+            .filter(case::Column::Id.is_in(case_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询案件失败: {e}")))?
+            .into_iter()
+            .map(|c| (c.id, c.case_code))
+            .collect()
+    };
+    let document_ids: Vec<String> = models.iter().filter_map(|m| m.document_id.clone()).collect();
+    let document_titles: HashMap<String, String> = if document_ids.is_empty() {
+        HashMap::new()
+    } else {
+        document::Entity::find()
+            .filter(document::Column::Id.is_in(document_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询文档失败: {e}")))?
+            .into_iter()
+            .map(|d| (d.id, d.title))
+            .collect()
+    };
+    let client_names = contact_names(state, models.iter().filter_map(|m| m.client_id.clone()).collect()).await?;
+
+    Ok(models
+        .into_iter()
+        .map(|m| ContractResponse {
+            case_code: m.case_id.as_ref().and_then(|id| case_codes.get(id)).cloned(),
+            client_name: m.client_id.as_ref().and_then(|id| client_names.get(id)).cloned(),
+            document_title: m.document_id.as_ref().and_then(|id| document_titles.get(id)).cloned(),
+            id: m.id,
+            contract_no: m.contract_no,
+            title: m.title,
+            status: m.status.to_value(),
+            amount: m.amount.map(|a| a.to_string()),
+            start_date: m.start_date,
+            end_date: m.end_date,
+            signed_at: m.signed_at,
+            notes: m.notes,
+            case_id: m.case_id,
+            client_id: m.client_id,
+            document_id: m.document_id,
+            creator_id: m.creator_id,
+            created_at: m.created_at,
+            updated_at: m.updated_at,
+        })
+        .collect())
+}
+
+async fn to_response(state: &AppState, model: contract::Model) -> AppResult<ContractResponse> {
+    to_responses(state, vec![model])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::Internal("合同响应为空".to_string()))
+}
+
+/// GET /api/v1/contracts
+async fn list_contracts(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<ContractListQuery>,
+) -> AppResult<Json<PaginatedResponse<ContractResponse>>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::BillingView)?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
+    let tenant_id = &current_user.model.active_tenant_id;
+
+    let mut select = contract::Entity::find()
+        .filter(contract::Column::TenantId.eq(tenant_id))
+        .filter(contract::Column::DeletedAt.is_null())
+        .order_by_desc(contract::Column::UpdatedAt);
+    match query.case_id.as_deref() {
+        Some(case_id) => {
+            Uuid::parse_str(case_id).map_err(|_| AppError::Validation("caseId 无效".to_string()))?;
+            require_case_access(&state, case_id, current_user.id(), role, Permission::CaseView).await?;
+            select = select.filter(contract::Column::CaseId.eq(case_id));
+        }
+        None => {
+            if let Some(ids) = visible_case_ids(&state, current_user.id(), &role).await? {
+                select = select.filter(contract::Column::CaseId.is_in(ids));
+            }
+        }
+    }
+    if let Some(client_id) = query.client_id.as_deref() {
+        ensure_client_in_tenant(&state, tenant_id, client_id).await?;
+        select = select.filter(contract::Column::ClientId.eq(client_id));
+    }
+    if let Some(status) = query.status.as_deref() {
+        select = select.filter(contract::Column::Status.eq(parse_contract_status(status)?));
+    }
+    if let Some(q) = query.search.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        select = select.filter(
+            Condition::any().add(contract::Column::ContractNo.contains(q)).add(contract::Column::Title.contains(q)),
+        );
+    }
+    if let Some(days) = query.expiring_within_days {
+        if !(1..=3650).contains(&days) {
+            return Err(AppError::Validation("expiringWithinDays 需在 1-3650 之间".to_string()));
+        }
+        let now = Utc::now();
+        select = select
+            .filter(contract::Column::Status.is_in(vec![ContractStatus::Signed, ContractStatus::Active]))
+            .filter(contract::Column::EndDate.gt(now))
+            .filter(contract::Column::EndDate.lte(now + Duration::days(days)));
+    }
+
+    let paginator = select.paginate(&state.db, page_size);
+    let total = paginator.num_items().await.map_err(|e| AppError::Database(format!("计数失败: {e}")))?;
+    let total_pages = paginator.num_pages().await.map_err(|e| AppError::Database(format!("分页失败: {e}")))?;
+    let models = paginator
+        .fetch_page(page - 1)
+        .await
+        .map_err(|e| AppError::Database(format!("查询合同失败: {e}")))?;
+
+    Ok(Json(PaginatedResponse { data: to_responses(&state, models).await?, total, page, page_size, total_pages }))
+}
+
+/// POST /api/v1/contracts
+async fn create_contract(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(req): ValidatedJson<CreateContractRequest>,
+) -> AppResult<Json<ContractResponse>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::BillingCreate)?;
+    let tenant_id = current_user.model.active_tenant_id.clone();
+
+    let title = require_non_empty(&req.title, "title", 200)?;
+    let status = req.status.as_deref().map(parse_contract_status).transpose()?.unwrap_or(ContractStatus::Draft);
+    let amount = trim_optional(req.amount).as_deref().map(parse_contract_amount).transpose()?;
+    validate_period(req.start_date, req.end_date)?;
+
+    let mut case_id = trim_optional(req.case_id);
+    let mut client_id = trim_optional(req.client_id);
+    let document_id = trim_optional(req.document_id);
+    if let Some(document_id) = document_id.as_deref() {
+        let doc = resolve_document(&state, &tenant_id, document_id, case_id.as_deref(), None).await?;
+        case_id.get_or_insert(doc.case_id);
+    }
+    match case_id.as_deref() {
+        Some(id) => {
+            Uuid::parse_str(id).map_err(|_| AppError::Validation("caseId 无效".to_string()))?;
+            let case_model = require_case_access(&state, id, current_user.id(), role, Permission::CaseView).await?;
+            if case_model.tenant_id != tenant_id {
+                return Err(AppError::NotFound(format!("案件 {id} 不存在")));
+            }
+            if client_id.as_deref().is_some_and(|c| c != case_model.client_id) {
+                return Err(AppError::Validation("案件不属于该客户".to_string()));
+            }
+            client_id.get_or_insert(case_model.client_id);
+        }
+        None => require_permission(role, Permission::AdminAccess)?,
+    }
+    if let Some(client_id) = client_id.as_deref() {
+        ensure_client_in_tenant(&state, &tenant_id, client_id).await?;
+    }
+
+    let contract_no = trim_optional(req.contract_no);
+    let creator_id = current_user.id().to_string();
+    let notes = trim_optional(req.notes);
+    let created = state
+        .db
+        .transaction::<_, contract::Model, AppError>(|txn| {
+            Box::pin(async move {
+                txn.execute(Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    "SELECT pg_advisory_xact_lock(hashtext($1))",
+                    [format!("contract-no:{tenant_id}").into()],
+                ))
+                .await
+                .map_err(|e| AppError::Database(format!("获取合同编号锁失败: {e}")))?;
+
+                let now = Utc::now();
+                let contract_no = match contract_no {
+                    Some(no) => {
+                        if contract_no_taken(txn, &tenant_id, &no, None).await? {
+                            return Err(AppError::Validation(format!("合同编号已存在: {no}")));
+                        }
+                        no
+                    }
+                    None => {
+                        let prefix = format!("CTR-{}", now.format("%Y%m%d"));
+                        let last = contract::Entity::find()
+                            .filter(contract::Column::TenantId.eq(&tenant_id))
+                            .filter(contract::Column::ContractNo.starts_with(&prefix))
+                            .order_by_desc(contract::Column::ContractNo)
+                            .one(txn)
+                            .await
+                            .map_err(|e| AppError::Database(format!("生成合同编号失败: {e}")))?;
+                        let next_num = last
+                            .and_then(|c| c.contract_no.rsplit('-').next().and_then(|n| n.parse::<u32>().ok()))
+                            .unwrap_or(0)
+                            + 1;
+                        format!("{prefix}-{next_num:04}")
+                    }
+                };
+
+                contract::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    tenant_id: sea_orm::ActiveValue::Set(tenant_id),
+                    contract_no: sea_orm::ActiveValue::Set(contract_no),
+                    title: sea_orm::ActiveValue::Set(title),
+                    status: sea_orm::ActiveValue::Set(status),
+                    amount: sea_orm::ActiveValue::Set(amount),
+                    start_date: sea_orm::ActiveValue::Set(req.start_date),
+                    end_date: sea_orm::ActiveValue::Set(req.end_date),
+                    signed_at: sea_orm::ActiveValue::Set(req.signed_at),
+                    notes: sea_orm::ActiveValue::Set(notes),
+                    case_id: sea_orm::ActiveValue::Set(case_id),
+                    client_id: sea_orm::ActiveValue::Set(client_id),
+                    document_id: sea_orm::ActiveValue::Set(document_id),
+                    creator_id: sea_orm::ActiveValue::Set(creator_id),
+                    deleted_at: sea_orm::ActiveValue::Set(None),
+                    deleted_by_id: sea_orm::ActiveValue::Set(None),
+                    created_at: sea_orm::ActiveValue::Set(now),
+                    updated_at: sea_orm::ActiveValue::Set(now),
+                }
+                .insert(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("创建合同失败: {e}")))
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    Ok(Json(to_response(&state, created).await?))
+}
+
+/// GET /api/v1/contracts/:id
+async fn get_contract(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> AppResult<Json<ContractResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingView)?;
+    let model = require_contract_access(&state, &current_user, &id).await?;
+    Ok(Json(to_response(&state, model).await?))
+}
+
+/// PATCH /api/v1/contracts/:id
+async fn update_contract(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+    ValidatedJson(req): ValidatedJson<UpdateContractRequest>,
+) -> AppResult<Json<ContractResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    let existing = require_contract_access(&state, &current_user, &id).await?;
+    validate_period(req.start_date.or(existing.start_date), req.end_date.or(existing.end_date))?;
+
+    let contract_no = trim_optional(req.contract_no).filter(|no| *no != existing.contract_no);
+    if let Some(no) = contract_no.as_deref() {
+        if contract_no_taken(&state.db, &existing.tenant_id, no, Some(&existing.id)).await? {
+            return Err(AppError::Validation(format!("合同编号已存在: {no}")));
+        }
+    }
+
+    let mut active: contract::ActiveModel = existing.into();
+    if let Some(no) = contract_no {
+        active.contract_no = sea_orm::ActiveValue::Set(no);
+    }
+    if let Some(title) = req.title.as_deref() {
+        active.title = sea_orm::ActiveValue::Set(require_non_empty(title, "title", 200)?);
+    }
+    if let Some(status) = req.status.as_deref() {
+        active.status = sea_orm::ActiveValue::Set(parse_contract_status(status)?);
+    }
+    if let Some(amount) = req.amount {
+        let amount = trim_optional(Some(amount)).as_deref().map(parse_contract_amount).transpose()?;
+        active.amount = sea_orm::ActiveValue::Set(amount);
+    }
+    if let Some(start_date) = req.start_date {
+        active.start_date = sea_orm::ActiveValue::Set(Some(start_date));
+    }
+    if let Some(end_date) = req.end_date {
+        active.end_date = sea_orm::ActiveValue::Set(Some(end_date));
+    }
+    if let Some(signed_at) = req.signed_at {
+        active.signed_at = sea_orm::ActiveValue::Set(Some(signed_at));
+    }
+    if let Some(notes) = req.notes {
+        active.notes = sea_orm::ActiveValue::Set(trim_optional(Some(notes)));
+    }
+    active.updated_at = sea_orm::ActiveValue::Set(Utc::now());
+
+    let updated = active.update(&state.db).await.map_err(|e| AppError::Database(format!("更新合同失败: {e}")))?;
+    Ok(Json(to_response(&state, updated).await?))
+}
+
+/// DELETE /api/v1/contracts/:id
+///
+/// 软删除：写入 deletedAt/deletedById 并置为 CANCELLED，合同文件保留。
+async fn delete_contract(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> AppResult<Json<serde_json::Value>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    let existing = require_contract_access(&state, &current_user, &id).await?;
+
+    let now = Utc::now();
+    let mut active: contract::ActiveModel = existing.into();
+    active.deleted_at = sea_orm::ActiveValue::Set(Some(now));
+    active.deleted_by_id = sea_orm::ActiveValue::Set(Some(current_user.id().to_string()));
+    active.status = sea_orm::ActiveValue::Set(ContractStatus::Cancelled);
+    active.updated_at = sea_orm::ActiveValue::Set(now);
+    active.update(&state.db).await.map_err(|e| AppError::Database(format!("删除合同失败: {e}")))?;
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// PUT /api/v1/contracts/:id/document
+async fn link_document(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+    ValidatedJson(req): ValidatedJson<LinkContractDocumentRequest>,
+) -> AppResult<Json<ContractResponse>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::BillingEdit)?;
+    let existing = require_contract_access(&state, &current_user, &id).await?;
+    let doc = resolve_document(
+        &state,
+        &existing.tenant_id,
+        req.document_id.trim(),
+        existing.case_id.as_deref(),
+        Some(&existing.id),
+    )
+    .await?;
+    // 未关联案件的合同随文档归入其案件，须同时可见该案件
+    if existing.case_id.is_none() {
+        require_case_access(&state, &doc.case_id, current_user.id(), role, Permission::CaseView).await?;
+    }
+
+    let mut active: contract::ActiveModel = existing.into();
+    active.case_id = sea_orm::ActiveValue::Set(Some(doc.case_id));
+    active.document_id = sea_orm::ActiveValue::Set(Some(doc.id));
+    active.updated_at = sea_orm::ActiveValue::Set(Utc::now());
+    let updated = active.update(&state.db).await.map_err(|e| AppError::Database(format!("关联合同文件失败: {e}")))?;
+    Ok(Json(to_response(&state, updated).await?))
+}
+
+/// DELETE /api/v1/contracts/:id/document
+async fn unlink_document(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> AppResult<Json<ContractResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    let existing = require_contract_access(&state, &current_user, &id).await?;
+    if existing.document_id.is_none() {
+        return Ok(Json(to_response(&state, existing).await?));
+    }
+
+    let mut active: contract::ActiveModel = existing.into();
+    active.document_id = sea_orm::ActiveValue::Set(None);
+    active.updated_at = sea_orm::ActiveValue::Set(Utc::now());
+    let updated = active.update(&state.db).await.map_err(|e| AppError::Database(format!("解除合同文件失败: {e}")))?;
+    Ok(Json(to_response(&state, updated).await?))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_contracts).post(create_contract))
+        .route("/:id", get(get_contract).patch(update_contract).delete(delete_contract))
+        .route("/:id/document", put(link_document).delete(unlink_document))
+}
@@ -88,7 +88,7 @@ pub struct DownloadQuery {
     pub version: Option<i32>,
 }
 
-fn sanitize_filename(filename: &str) -> String {
+pub(crate) fn sanitize_filename(filename: &str) -> String {
     filename
         .replace(['\\', '/', ':', '*', '?', '"', '<', '>', '|'], "-")
         .split_whitespace()
@@ -98,7 +98,7 @@ fn sanitize_filename(filename: &str) -> String {
         .to_string()
 }
 
-fn build_object_key(case_id: &str, document_id: &str, version: i32, filename: &str) -> String {
+pub(crate) fn build_object_key(case_id: &str, document_id: &str, version: i32, filename: &str) -> String {
     let safe = sanitize_filename(if filename.trim().is_empty() { "document" } else { filename });
     let ts = Utc::now()
         .to_rfc3339_opts(SecondsFormat::Millis, true)
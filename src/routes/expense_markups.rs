@@ -0,0 +1,142 @@
+//! 费用加收规则路由（合并到 `/billing`）
+//!
+//! - 按费用类别配置加收百分比，预开票/开票时计入费用明细金额；未配置的类别按原金额开票
+//! - 查看需 `billing:view`；维护需 `billing:edit`
+//! - 规则只影响此后的开票，已开票明细金额不追溯
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    routing::{delete, get},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::Decimal;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::db::AppState;
+use crate::entity::expense_markup_rule;
+use crate::error::{AppError, AppResult};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+use crate::security::validation::{require_non_empty, ValidatedJson};
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertExpenseMarkupRequest {
+    pub category: String,
+    /// 加收百分比（Decimal 字符串，0-100）
+    pub markup_percent: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpenseMarkupResponse {
+    pub id: String,
+    pub category: String,
+    pub markup_percent: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<expense_markup_rule::Model> for ExpenseMarkupResponse {
+    fn from(m: expense_markup_rule::Model) -> Self {
+        Self { id: m.id, category: m.category, markup_percent: m.markup_percent.to_string(), updated_at: m.updated_at }
+    }
+}
+
+fn parse_markup_percent(raw: &str) -> AppResult<Decimal> {
+    match Decimal::from_str(raw.trim()) {
+        Ok(v) if v >= Decimal::ZERO && v <= Decimal::from(100) => Ok(v),
+        _ => Err(AppError::Validation(format!("markupPercent 无效: {raw}（需为 0-100）"))),
+    }
+}
+
+/// GET /api/v1/billing/expense-markups
+async fn list_markups(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+) -> AppResult<Json<Vec<ExpenseMarkupResponse>>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingView)?;
+    let rules = expense_markup_rule::Entity::find()
+        .filter(expense_markup_rule::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .order_by_asc(expense_markup_rule::Column::Category)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询费用加收规则失败: {e}")))?;
+    Ok(Json(rules.into_iter().map(ExpenseMarkupResponse::from).collect()))
+}
+
+/// PUT /api/v1/billing/expense-markups（按类别新增或更新）
+async fn upsert_markup(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(payload): ValidatedJson<UpsertExpenseMarkupRequest>,
+) -> AppResult<Json<ExpenseMarkupResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    let category = require_non_empty(&payload.category, "category", 100)?;
+    let markup_percent = parse_markup_percent(&payload.markup_percent)?;
+    let tenant_id = current_user.model.active_tenant_id.clone();
+
+    let existing = expense_markup_rule::Entity::find()
+        .filter(expense_markup_rule::Column::TenantId.eq(&tenant_id))
+        .filter(expense_markup_rule::Column::Category.eq(&category))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询费用加收规则失败: {e}")))?;
+
+    let now = Utc::now();
+    let saved = match existing {
+        Some(model) => {
+            let mut active: expense_markup_rule::ActiveModel = model.into();
+            active.markup_percent = sea_orm::ActiveValue::Set(markup_percent);
+            active.updated_at = sea_orm::ActiveValue::Set(now);
+            active.update(&state.db).await
+        }
+        None => {
+            expense_markup_rule::ActiveModel {
+                id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                tenant_id: sea_orm::ActiveValue::Set(tenant_id),
+                category: sea_orm::ActiveValue::Set(category),
+                markup_percent: sea_orm::ActiveValue::Set(markup_percent),
+                created_at: sea_orm::ActiveValue::Set(now),
+                updated_at: sea_orm::ActiveValue::Set(now),
+            }
+            .insert(&state.db)
+            .await
+        }
+    };
+    let saved = saved.map_err(|e| AppError::Database(format!("保存费用加收规则失败: {e}")))?;
+    Ok(Json(ExpenseMarkupResponse::from(saved)))
+}
+
+/// DELETE /api/v1/billing/expense-markups/:id
+async fn delete_markup(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(rule_id): Path<String>,
+) -> AppResult<Json<()>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    Uuid::parse_str(&rule_id).map_err(|_| AppError::Validation("规则ID 无效".to_string()))?;
+
+    let result = expense_markup_rule::Entity::delete_many()
+        .filter(expense_markup_rule::Column::Id.eq(&rule_id))
+        .filter(expense_markup_rule::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .exec(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("删除费用加收规则失败: {e}")))?;
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound("费用加收规则不存在".to_string()));
+    }
+    Ok(Json(()))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/expense-markups", get(list_markups).put(upsert_markup))
+        .route("/expense-markups/:id", delete(delete_markup))
+}
@@ -0,0 +1,841 @@
+//! 费用路由模块（录入 → 收据 → 审批 → 开票）
+//!
+//! - 录入/修改/删除：仅本人；PENDING 或 REJECTED 可修改（修改 REJECTED 费用即重新提交为 PENDING），APPROVED 起不可变
+//! - 收据：`POST /expenses/:id/receipts`（multipart `file`）上传到对象存储，key 记入 `attachments`；
+//!   单个不超过 10MB，每笔费用最多 10 个，仅允许图片 / PDF
+//! - 审批：具备 `billing:approve` 或为案件承办人；非 PARTNER / ADMIN 不可审批本人费用。
+//!   通过时确认是否向客户计费（`billable`，仅关联案件的费用可计费），退回需填写原因；结果通知费用所属人
+//! - 报销：`POST /expenses/:id/reimburse`（`billing:edit`）将已审批费用标记为 REIMBURSED，不影响开票
+//! - 确认计费的费用由预开票按类别加收规则计入发票（见 `invoices`、`expense_markups`）
+
+use axum::{
+    body::Body,
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::{get, patch, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::Decimal;
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::billing::invoice::round_money;
+use crate::db::AppState;
+use crate::entity::expense::{self, ExpenseStatus};
+use crate::entity::{case, notification};
+use crate::entity::user::Role;
+use crate::error::{AppError, AppResult};
+use crate::security::case_access::{require_case_access, visible_case_ids};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{has_permission, require_permission, Permission};
+use crate::security::validation::{require_non_empty, ValidatedJson};
+
+use super::cases::PaginatedResponse;
+use super::documents::sanitize_filename;
+use super::timelog_reports::require_user_report_access;
+
+const MAX_RECEIPT_BYTES: usize = 10 * 1024 * 1024;
+const MAX_RECEIPTS_PER_EXPENSE: usize = 10;
+const MAX_PENDING: u64 = 200;
+const RECEIPT_CONTENT_TYPES: &[&str] =
+    &["image/jpeg", "image/png", "image/webp", "image/heic", "image/heif", "application/pdf"];
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateExpenseRequest {
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub case_id: Option<String>,
+    /// 费用类别（交通/住宿/餐饮/复印等）
+    pub category: String,
+    /// 金额（Decimal 字符串，最多 2 位小数）
+    pub amount: String,
+    pub expense_date: DateTime<Utc>,
+    #[validate(length(max = 2000, message = "description 长度不合法"))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateExpenseRequest {
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub case_id: Option<String>,
+    pub category: Option<String>,
+    pub amount: Option<String>,
+    pub expense_date: Option<DateTime<Utc>>,
+    #[validate(length(max = 2000, message = "description 长度不合法"))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ApproveExpenseRequest {
+    /// 是否向客户计费；缺省为“关联案件即计费”
+    pub billable: Option<bool>,
+    #[validate(length(max = 2000, message = "note 长度不合法"))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectExpenseRequest {
+    #[validate(length(min = 1, max = 2000, message = "reason 长度不合法"))]
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpenseListQuery {
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+    pub user_id: Option<String>,
+    pub case_id: Option<String>,
+    pub status: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptResponse {
+    pub index: usize,
+    pub file_name: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpenseResponse {
+    pub id: String,
+    pub user_id: String,
+    pub case_id: Option<String>,
+    pub category: String,
+    pub amount: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub is_billable: bool,
+    pub reviewer_id: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub review_note: Option<String>,
+    pub expense_date: DateTime<Utc>,
+    pub receipts: Vec<ReceiptResponse>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 收据 key 形如 `expenses/{expenseId}/{uuid}/{文件名}`
+fn receipt_file_name(key: &str) -> String {
+    key.rsplit('/').next().unwrap_or(key).to_string()
+}
+
+impl From<expense::Model> for ExpenseResponse {
+    fn from(m: expense::Model) -> Self {
+        Self {
+            id: m.id,
+            user_id: m.user_id,
+            case_id: m.case_id,
+            category: m.category,
+            amount: m.amount.to_string(),
+            description: m.description,
+            status: m.status.to_value(),
+            is_billable: m.is_billable,
+            reviewer_id: m.reviewer_id,
+            reviewed_at: m.reviewed_at,
+            review_note: m.review_note,
+            expense_date: m.expense_date,
+            receipts: m
+                .attachments
+                .iter()
+                .enumerate()
+                .map(|(index, key)| ReceiptResponse { index, file_name: receipt_file_name(key) })
+                .collect(),
+            created_at: m.created_at,
+            updated_at: m.updated_at,
+        }
+    }
+}
+
+fn parse_amount(raw: &str) -> AppResult<Decimal> {
+    match Decimal::from_str(raw.trim()) {
+        Ok(v) if v > Decimal::ZERO && round_money(v) == v => Ok(v),
+        _ => Err(AppError::Validation(format!("amount 无效: {raw}（需为正数，最多 2 位小数）"))),
+    }
+}
+
+fn parse_expense_status(raw: &str) -> AppResult<ExpenseStatus> {
+    match raw.trim() {
+        "PENDING" => Ok(ExpenseStatus::Pending),
+        "APPROVED" => Ok(ExpenseStatus::Approved),
+        "REJECTED" => Ok(ExpenseStatus::Rejected),
+        "REIMBURSED" => Ok(ExpenseStatus::Reimbursed),
+        other => Err(AppError::Validation(format!("status 无效: {other}"))),
+    }
+}
+
+fn is_admin(role: &Role) -> bool {
+    matches!(role, Role::Partner | Role::Admin)
+}
+
+async fn load_expense(state: &AppState, current_user: &CurrentUser, expense_id: &str) -> AppResult<expense::Model> {
+    Uuid::parse_str(expense_id).map_err(|_| AppError::Validation("费用ID 无效".to_string()))?;
+    expense::Entity::find_by_id(expense_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询费用失败: {e}")))?
+        .filter(|e| e.tenant_id == current_user.model.active_tenant_id)
+        .ok_or_else(|| AppError::NotFound("费用不存在".to_string()))
+}
+
+/// 事务内锁定费用行，状态校验与写入基于锁定后的最新数据
+async fn lock_expense<C: ConnectionTrait>(db: &C, expense_id: &str) -> AppResult<expense::Model> {
+    expense::Entity::find_by_id(expense_id)
+        .lock_exclusive()
+        .one(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询费用失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("费用不存在".to_string()))
+}
+
+/// 本人且仍可修改（PENDING / REJECTED）
+fn require_editable(current_user: &CurrentUser, model: &expense::Model) -> AppResult<()> {
+    if model.user_id != current_user.id() {
+        return Err(AppError::Forbidden("无权操作".to_string()));
+    }
+    if !matches!(model.status, ExpenseStatus::Pending | ExpenseStatus::Rejected) {
+        return Err(AppError::Validation("费用已审批，不可修改".to_string()));
+    }
+    Ok(())
+}
+
+/// 校验当前用户可审批该费用
+async fn require_reviewer(state: &AppState, current_user: &CurrentUser, model: &expense::Model) -> AppResult<()> {
+    let role = current_user.model.role.clone();
+    if model.user_id == current_user.id() && !is_admin(&role) {
+        return Err(AppError::Forbidden("不能审批本人的费用".to_string()));
+    }
+    let Some(case_id) = model.case_id.as_deref() else {
+        return if is_admin(&role) { Ok(()) } else { Err(AppError::Forbidden("无权审批该费用".to_string())) };
+    };
+
+    let case_model = require_case_access(state, case_id, current_user.id(), role.clone(), Permission::CaseView).await?;
+    if has_permission(role, Permission::BillingApprove) || case_model.handler_id.as_deref() == Some(current_user.id())
+    {
+        return Ok(());
+    }
+    Err(AppError::Forbidden("缺少权限：billing:approve（或非案件承办人）".to_string()))
+}
+
+/// 可审批费用的案件范围；PARTNER / ADMIN 返回 None（不限）
+async fn reviewable_case_ids(state: &AppState, user_id: &str, role: &Role) -> AppResult<Option<Vec<String>>> {
+    if is_admin(role) {
+        return Ok(None);
+    }
+    if has_permission(role.clone(), Permission::BillingApprove) {
+        return visible_case_ids(state, user_id, role).await;
+    }
+    // 无 billing:approve 时仅承办案件
+    let case_ids = case::Entity::find()
+        .filter(case::Column::HandlerId.eq(user_id))
+        .select_only()
+        .column(case::Column::Id)
+        .into_values::<String, case::Column>()
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询承办案件失败: {e}")))?;
+    Ok(Some(case_ids))
+}
+
+/// 本人、审批人或可查看该案件计费的用户可查看费用
+async fn require_viewer(state: &AppState, current_user: &CurrentUser, model: &expense::Model) -> AppResult<()> {
+    if model.user_id == current_user.id() {
+        return Ok(());
+    }
+    let role = current_user.model.role.clone();
+    match model.case_id.as_deref() {
+        Some(case_id) => {
+            require_permission(role.clone(), Permission::BillingView)?;
+            require_case_access(state, case_id, current_user.id(), role, Permission::CaseView).await?;
+            Ok(())
+        }
+        None if is_admin(&role) => Ok(()),
+        None => Err(AppError::Forbidden("无权查看该费用".to_string())),
+    }
+}
+
+async fn resolve_case_id(
+    state: &AppState,
+    current_user: &CurrentUser,
+    case_id: Option<&str>,
+) -> AppResult<Option<String>> {
+    let Some(case_id) = case_id else {
+        return Ok(None);
+    };
+    let case_model =
+        require_case_access(state, case_id, current_user.id(), current_user.model.role.clone(), Permission::CaseView)
+            .await?;
+    Ok(Some(case_model.id))
+}
+
+/// GET /api/v1/expenses
+async fn list_expenses(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<ExpenseListQuery>,
+) -> AppResult<Json<PaginatedResponse<ExpenseResponse>>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::CaseView)?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
+
+    let mut select = expense::Entity::find()
+        .filter(expense::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .order_by_desc(expense::Column::ExpenseDate);
+
+    if let Some(case_id) = query.case_id.as_deref() {
+        Uuid::parse_str(case_id).map_err(|_| AppError::Validation("caseId 无效".to_string()))?;
+        require_permission(role.clone(), Permission::BillingView)?;
+        require_case_access(&state, case_id, current_user.id(), role.clone(), Permission::CaseView).await?;
+        select = select.filter(expense::Column::CaseId.eq(case_id));
+    }
+    match query.user_id.as_deref() {
+        Some(user_id) => {
+            if query.case_id.is_none() {
+                require_user_report_access(&state, &current_user, user_id).await?;
+            }
+            select = select.filter(expense::Column::UserId.eq(user_id));
+        }
+        None if query.case_id.is_none() => {
+            select = select.filter(expense::Column::UserId.eq(current_user.id()));
+        }
+        None => {}
+    }
+    if let Some(status) = query.status.as_deref() {
+        select = select.filter(expense::Column::Status.eq(parse_expense_status(status)?));
+    }
+    if let Some(from) = query.from {
+        select = select.filter(expense::Column::ExpenseDate.gte(from));
+    }
+    if let Some(to) = query.to {
+        select = select.filter(expense::Column::ExpenseDate.lt(to));
+    }
+
+    let paginator = select.paginate(&state.db, page_size);
+    let total = paginator.num_items().await.map_err(|e| AppError::Database(format!("计数失败: {e}")))?;
+    let total_pages = paginator.num_pages().await.map_err(|e| AppError::Database(format!("分页失败: {e}")))?;
+    let expenses = paginator
+        .fetch_page(page - 1)
+        .await
+        .map_err(|e| AppError::Database(format!("查询费用失败: {e}")))?;
+
+    Ok(Json(PaginatedResponse {
+        data: expenses.into_iter().map(ExpenseResponse::from).collect(),
+        total,
+        page,
+        page_size,
+        total_pages,
+    }))
+}
+
+/// GET /api/v1/expenses/approvals（待审批队列：可见案件内他人的 PENDING 费用）
+async fn list_pending_approvals(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+) -> AppResult<Json<Vec<ExpenseResponse>>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::CaseView)?;
+
+    // 审批范围与 `require_reviewer` 一致，直接在 SQL 中限定，避免逐条校验
+    let mut select = expense::Entity::find()
+        .filter(expense::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .filter(expense::Column::Status.eq(ExpenseStatus::Pending))
+        .order_by_asc(expense::Column::CreatedAt);
+    if !is_admin(&role) {
+        select =
+            select.filter(expense::Column::UserId.ne(current_user.id())).filter(expense::Column::CaseId.is_not_null());
+    }
+    if let Some(ids) = reviewable_case_ids(&state, current_user.id(), &role).await? {
+        if ids.is_empty() {
+            return Ok(Json(Vec::new()));
+        }
+        select = select.filter(expense::Column::CaseId.is_in(ids));
+    }
+
+    let pending = select
+        .limit(MAX_PENDING)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询费用失败: {e}")))?;
+    Ok(Json(pending.into_iter().map(ExpenseResponse::from).collect()))
+}
+
+/// POST /api/v1/expenses
+async fn create_expense(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(payload): ValidatedJson<CreateExpenseRequest>,
+) -> AppResult<Json<ExpenseResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::CaseView)?;
+    let case_id = resolve_case_id(&state, &current_user, payload.case_id.as_deref()).await?;
+    let category = require_non_empty(&payload.category, "category", 100)?;
+    let amount = parse_amount(&payload.amount)?;
+
+    let now = Utc::now();
+    let inserted = expense::ActiveModel {
+        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+        tenant_id: sea_orm::ActiveValue::Set(current_user.model.active_tenant_id.clone()),
+        case_id: sea_orm::ActiveValue::Set(case_id),
+        user_id: sea_orm::ActiveValue::Set(current_user.id().to_string()),
+        category: sea_orm::ActiveValue::Set(category),
+        amount: sea_orm::ActiveValue::Set(amount),
+        description: sea_orm::ActiveValue::Set(
+            payload.description.map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+        ),
+        status: sea_orm::ActiveValue::Set(ExpenseStatus::Pending),
+        is_billable: sea_orm::ActiveValue::Set(false),
+        reviewer_id: sea_orm::ActiveValue::Set(None),
+        reviewed_at: sea_orm::ActiveValue::Set(None),
+        review_note: sea_orm::ActiveValue::Set(None),
+        expense_date: sea_orm::ActiveValue::Set(payload.expense_date),
+        attachments: sea_orm::ActiveValue::Set(Vec::new()),
+        created_at: sea_orm::ActiveValue::Set(now),
+        updated_at: sea_orm::ActiveValue::Set(now),
+    }
+    .insert(&state.db)
+    .await
+    .map_err(|e| AppError::Database(format!("添加费用失败: {e}")))?;
+
+    Ok(Json(ExpenseResponse::from(inserted)))
+}
+
+/// GET /api/v1/expenses/:id
+async fn get_expense(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(expense_id): Path<String>,
+) -> AppResult<Json<ExpenseResponse>> {
+    let model = load_expense(&state, &current_user, &expense_id).await?;
+    require_viewer(&state, &current_user, &model).await?;
+    Ok(Json(ExpenseResponse::from(model)))
+}
+
+/// PATCH /api/v1/expenses/:id
+async fn update_expense(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(expense_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<UpdateExpenseRequest>,
+) -> AppResult<Json<ExpenseResponse>> {
+    let model = load_expense(&state, &current_user, &expense_id).await?;
+    require_editable(&current_user, &model)?;
+
+    let case_id = match payload.case_id.as_deref() {
+        Some(case_id) => Some(resolve_case_id(&state, &current_user, Some(case_id)).await?),
+        None => None,
+    };
+    let category = payload.category.as_deref().map(|c| require_non_empty(c, "category", 100)).transpose()?;
+    let amount = payload.amount.as_deref().map(parse_amount).transpose()?;
+    let description = payload.description.map(|d| d.trim().to_string()).map(|d| (!d.is_empty()).then_some(d));
+
+    let updated = state
+        .db
+        .transaction::<_, expense::Model, AppError>(|txn| {
+            Box::pin(async move {
+                let locked = lock_expense(txn, &model.id).await?;
+                require_editable(&current_user, &locked)?;
+
+                let mut active: expense::ActiveModel = locked.into();
+                if let Some(case_id) = case_id {
+                    active.case_id = sea_orm::ActiveValue::Set(case_id);
+                }
+                if let Some(category) = category {
+                    active.category = sea_orm::ActiveValue::Set(category);
+                }
+                if let Some(amount) = amount {
+                    active.amount = sea_orm::ActiveValue::Set(amount);
+                }
+                if let Some(expense_date) = payload.expense_date {
+                    active.expense_date = sea_orm::ActiveValue::Set(expense_date);
+                }
+                if let Some(description) = description {
+                    active.description = sea_orm::ActiveValue::Set(description);
+                }
+                // 修改即重新提交
+                active.status = sea_orm::ActiveValue::Set(ExpenseStatus::Pending);
+                active.reviewer_id = sea_orm::ActiveValue::Set(None);
+                active.reviewed_at = sea_orm::ActiveValue::Set(None);
+                active.review_note = sea_orm::ActiveValue::Set(None);
+                active.updated_at = sea_orm::ActiveValue::Set(Utc::now());
+                active.update(txn).await.map_err(|e| AppError::Database(format!("修改费用失败: {e}")))
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+    Ok(Json(ExpenseResponse::from(updated)))
+}
+
+/// DELETE /api/v1/expenses/:id
+async fn delete_expense(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(expense_id): Path<String>,
+) -> AppResult<Json<()>> {
+    let model = load_expense(&state, &current_user, &expense_id).await?;
+    require_editable(&current_user, &model)?;
+
+    let deleted = state
+        .db
+        .transaction::<_, expense::Model, AppError>(|txn| {
+            Box::pin(async move {
+                let locked = lock_expense(txn, &model.id).await?;
+                require_editable(&current_user, &locked)?;
+                expense::Entity::delete_by_id(&locked.id)
+                    .exec(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("删除费用失败: {e}")))?;
+                Ok(locked)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    for key in &deleted.attachments {
+        if let Err(e) = state.storage.delete_object(key).await {
+            tracing::warn!("删除收据文件失败（{key}）: {e}");
+        }
+    }
+    Ok(Json(()))
+}
+
+/// POST /api/v1/expenses/:id/receipts
+async fn upload_receipt(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(expense_id): Path<String>,
+    mut multipart: Multipart,
+) -> AppResult<Json<ExpenseResponse>> {
+    let model = load_expense(&state, &current_user, &expense_id).await?;
+    require_editable(&current_user, &model)?;
+    if model.attachments.len() >= MAX_RECEIPTS_PER_EXPENSE {
+        return Err(AppError::Validation(format!("每笔费用最多上传 {MAX_RECEIPTS_PER_EXPENSE} 个收据")));
+    }
+
+    let mut file: Option<(Vec<u8>, Option<String>, Option<String>)> = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::Validation(format!("multipart 解析失败: {e}")))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let file_name = field.file_name().map(|v| v.to_string());
+        let content_type = field.content_type().map(|v| v.to_string());
+        let bytes = field.bytes().await.map_err(|e| AppError::Validation(format!("读取文件失败: {e}")))?;
+        file = Some((bytes.to_vec(), file_name, content_type));
+    }
+
+    let (bytes, file_name, content_type) = file.ok_or_else(|| AppError::Validation("缺少文件".to_string()))?;
+    if bytes.is_empty() {
+        return Err(AppError::Validation("文件为空".to_string()));
+    }
+    if bytes.len() > MAX_RECEIPT_BYTES {
+        return Err(AppError::Validation("收据文件不能超过 10MB".to_string()));
+    }
+    let content_type = content_type.unwrap_or_default();
+    if !RECEIPT_CONTENT_TYPES.contains(&content_type.as_str()) {
+        return Err(AppError::Validation(format!("不支持的收据文件类型: {content_type}")));
+    }
+
+    let safe_name = sanitize_filename(file_name.as_deref().unwrap_or("receipt"));
+    let safe_name = if safe_name.is_empty() { "receipt".to_string() } else { safe_name };
+    let key = format!("expenses/{}/{}/{safe_name}", model.id, Uuid::new_v4());
+    state.storage.put_object(&key, bytes, Some(&content_type)).await?;
+
+    // 上传期间可能有并发上传 / 删除：锁定后基于最新的 attachments 追加，并重新校验数量上限
+    let new_key = key.clone();
+    let saved = state
+        .db
+        .transaction::<_, expense::Model, AppError>(|txn| {
+            Box::pin(async move {
+                let locked = lock_expense(txn, &model.id).await?;
+                require_editable(&current_user, &locked)?;
+                if locked.attachments.len() >= MAX_RECEIPTS_PER_EXPENSE {
+                    return Err(AppError::Validation(format!(
+                        "每笔费用最多上传 {MAX_RECEIPTS_PER_EXPENSE} 个收据"
+                    )));
+                }
+                let mut attachments = locked.attachments.clone();
+                attachments.push(new_key);
+                let mut active: expense::ActiveModel = locked.into();
+                active.attachments = sea_orm::ActiveValue::Set(attachments);
+                active.updated_at = sea_orm::ActiveValue::Set(Utc::now());
+                active.update(txn).await.map_err(|e| AppError::Database(format!("保存收据失败: {e}")))
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        });
+    match saved {
+        Ok(updated) => Ok(Json(ExpenseResponse::from(updated))),
+        Err(e) => {
+            if let Err(cleanup) = state.storage.delete_object(&key).await {
+                tracing::warn!("回滚收据文件失败（{key}）: {cleanup}");
+            }
+            Err(e)
+        }
+    }
+}
+
+/// GET /api/v1/expenses/:id/receipts/:index
+async fn download_receipt(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path((expense_id, index)): Path<(String, usize)>,
+) -> AppResult<Response> {
+    let model = load_expense(&state, &current_user, &expense_id).await?;
+    require_viewer(&state, &current_user, &model).await?;
+    let key = model.attachments.get(index).ok_or_else(|| AppError::NotFound("收据不存在".to_string()))?;
+
+    let obj = state.storage.get_object(key).await?;
+    let mut headers = HeaderMap::new();
+    let ct = obj.content_type.unwrap_or_else(|| "application/octet-stream".to_string());
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_str(&ct).unwrap_or(HeaderValue::from_static("application/octet-stream")),
+    );
+    let disposition = format!("attachment; filename=\"{}\"", receipt_file_name(key));
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&disposition).unwrap_or(HeaderValue::from_static("attachment")),
+    );
+    Ok((StatusCode::OK, headers, Body::from(obj.bytes)).into_response())
+}
+
+/// DELETE /api/v1/expenses/:id/receipts/:index
+async fn delete_receipt(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path((expense_id, index)): Path<(String, usize)>,
+) -> AppResult<Json<ExpenseResponse>> {
+    let model = load_expense(&state, &current_user, &expense_id).await?;
+    require_editable(&current_user, &model)?;
+
+    let (updated, key) = state
+        .db
+        .transaction::<_, (expense::Model, String), AppError>(|txn| {
+            Box::pin(async move {
+                let locked = lock_expense(txn, &model.id).await?;
+                require_editable(&current_user, &locked)?;
+                if index >= locked.attachments.len() {
+                    return Err(AppError::NotFound("收据不存在".to_string()));
+                }
+                let mut attachments = locked.attachments.clone();
+                let key = attachments.remove(index);
+                let mut active: expense::ActiveModel = locked.into();
+                active.attachments = sea_orm::ActiveValue::Set(attachments);
+                active.updated_at = sea_orm::ActiveValue::Set(Utc::now());
+                let updated =
+                    active.update(txn).await.map_err(|e| AppError::Database(format!("删除收据失败: {e}")))?;
+                Ok((updated, key))
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    if let Err(e) = state.storage.delete_object(&key).await {
+        tracing::warn!("删除收据文件失败（{key}）: {e}");
+    }
+    Ok(Json(ExpenseResponse::from(updated)))
+}
+
+/// 写入审批结果并通知费用所属人
+///
+/// 事务内锁定费用行并重新校验：仍为 PENDING，且自审批人读取后未被所属人修改（金额 / 案件等）
+async fn apply_review(
+    state: &AppState,
+    reviewer_id: &str,
+    model: expense::Model,
+    status: ExpenseStatus,
+    is_billable: bool,
+    note: Option<String>,
+) -> AppResult<expense::Model> {
+    let now = Utc::now();
+    let reviewer_id = reviewer_id.to_string();
+
+    let updated = state
+        .db
+        .transaction::<_, expense::Model, AppError>(|txn| {
+            Box::pin(async move {
+                let locked = lock_expense(txn, &model.id).await?;
+                if locked.status != ExpenseStatus::Pending {
+                    return Err(AppError::Validation("费用已被审批或退回".to_string()));
+                }
+                if locked.updated_at != model.updated_at {
+                    return Err(AppError::Validation("费用已被修改，请刷新后重新审批".to_string()));
+                }
+
+                let (title, content) = match status {
+                    ExpenseStatus::Approved if is_billable => {
+                        ("费用已审批（向客户计费）", format!("{} {}", locked.category, locked.amount))
+                    }
+                    ExpenseStatus::Approved => ("费用已审批", format!("{} {}", locked.category, locked.amount)),
+                    _ => (
+                        "费用被退回",
+                        format!("{} {}：{}", locked.category, locked.amount, note.as_deref().unwrap_or_default()),
+                    ),
+                };
+                let owner_id = locked.user_id.clone();
+                let mut active: expense::ActiveModel = locked.into();
+                active.status = sea_orm::ActiveValue::Set(status);
+                active.is_billable = sea_orm::ActiveValue::Set(is_billable);
+                active.reviewer_id = sea_orm::ActiveValue::Set(Some(reviewer_id.clone()));
+                active.reviewed_at = sea_orm::ActiveValue::Set(Some(now));
+                active.review_note = sea_orm::ActiveValue::Set(note);
+                active.updated_at = sea_orm::ActiveValue::Set(now);
+                let updated =
+                    active.update(txn).await.map_err(|e| AppError::Database(format!("审批费用失败: {e}")))?;
+
+                notification::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    user_id: sea_orm::ActiveValue::Set(owner_id),
+                    actor_id: sea_orm::ActiveValue::Set(Some(reviewer_id)),
+                    notification_type: sea_orm::ActiveValue::Set(notification::NotificationType::ExpenseReviewed),
+                    title: sea_orm::ActiveValue::Set(title.to_string()),
+                    content: sea_orm::ActiveValue::Set(Some(content)),
+                    action_url: sea_orm::ActiveValue::Set(Some("/expenses".to_string())),
+                    metadata: sea_orm::ActiveValue::Set(Some(json!({
+                        "expenseId": updated.id,
+                        "caseId": updated.case_id,
+                        "status": updated.status.to_value(),
+                        "isBillable": updated.is_billable,
+                    }))),
+                    read_at: sea_orm::ActiveValue::Set(None),
+                    created_at: sea_orm::ActiveValue::Set(now),
+                }
+                .insert(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("写入通知失败: {e}")))?;
+
+                Ok(updated)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+    Ok(updated)
+}
+
+/// POST /api/v1/expenses/:id/approve
+async fn approve_expense(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(expense_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<ApproveExpenseRequest>,
+) -> AppResult<Json<ExpenseResponse>> {
+    let model = load_expense(&state, &current_user, &expense_id).await?;
+    require_reviewer(&state, &current_user, &model).await?;
+    if model.status != ExpenseStatus::Pending {
+        return Err(AppError::Validation("仅待审批的费用可审批".to_string()));
+    }
+    let billable = payload.billable.unwrap_or(model.case_id.is_some());
+    if billable && model.case_id.is_none() {
+        return Err(AppError::Validation("未关联案件的费用不能向客户计费".to_string()));
+    }
+    let note = payload.note.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+
+    let updated = apply_review(&state, current_user.id(), model, ExpenseStatus::Approved, billable, note).await?;
+    Ok(Json(ExpenseResponse::from(updated)))
+}
+
+/// POST /api/v1/expenses/:id/reject
+async fn reject_expense(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(expense_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<RejectExpenseRequest>,
+) -> AppResult<Json<ExpenseResponse>> {
+    let model = load_expense(&state, &current_user, &expense_id).await?;
+    require_reviewer(&state, &current_user, &model).await?;
+    if model.status != ExpenseStatus::Pending {
+        return Err(AppError::Validation("仅待审批的费用可退回".to_string()));
+    }
+    let reason = require_non_empty(&payload.reason, "reason", 2000)?;
+
+    let updated = apply_review(&state, current_user.id(), model, ExpenseStatus::Rejected, false, Some(reason)).await?;
+    Ok(Json(ExpenseResponse::from(updated)))
+}
+
+/// POST /api/v1/expenses/:id/reimburse
+async fn reimburse_expense(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(expense_id): Path<String>,
+) -> AppResult<Json<ExpenseResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    let model = load_expense(&state, &current_user, &expense_id).await?;
+    if let Some(case_id) = model.case_id.as_deref() {
+        require_case_access(&state, case_id, current_user.id(), current_user.model.role.clone(), Permission::CaseView)
+            .await?;
+    }
+    if model.status != ExpenseStatus::Approved {
+        return Err(AppError::Validation("仅已审批的费用可标记报销".to_string()));
+    }
+
+    let updated = state
+        .db
+        .transaction::<_, expense::Model, AppError>(|txn| {
+            Box::pin(async move {
+                let locked = lock_expense(txn, &model.id).await?;
+                if locked.status != ExpenseStatus::Approved {
+                    return Err(AppError::Validation("仅已审批的费用可标记报销".to_string()));
+                }
+                let mut active: expense::ActiveModel = locked.into();
+                active.status = sea_orm::ActiveValue::Set(ExpenseStatus::Reimbursed);
+                active.updated_at = sea_orm::ActiveValue::Set(Utc::now());
+                active.update(txn).await.map_err(|e| AppError::Database(format!("标记报销失败: {e}")))
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+    Ok(Json(ExpenseResponse::from(updated)))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_expenses).post(create_expense))
+        .route("/approvals", get(list_pending_approvals))
+        .route("/:id", patch(update_expense).get(get_expense).delete(delete_expense))
+        .route(
+            "/:id/receipts",
+            post(upload_receipt).layer(DefaultBodyLimit::max(MAX_RECEIPT_BYTES + 64 * 1024)),
+        )
+        .route("/:id/receipts/:index", get(download_receipt).delete(delete_receipt))
+        .route("/:id/approve", post(approve_expense))
+        .route("/:id/reject", post(reject_expense))
+        .route("/:id/reimburse", post(reimburse_expense))
+}
@@ -0,0 +1,261 @@
+//! 发票 PDF 账单（合并到 `/invoices`）
+//!
+//! - `POST /invoices/:id/pdf?tzOffsetMinutes=`：按发票明细快照渲染 PDF（律所抬头、客户、案件编号、明细、税额、
+//!   合计及大写金额），经 `StorageProvider` 存为案件文档（`documentType = INVOICE`）
+//! - 首次生成创建文档并记入 `Invoice.documentId`；再次生成追加文档版本，便于留存历次发出的账单
+//! - 需 `billing:view` 与 `document:upload`，且可见该案件；下载走文档接口 `GET /documents/:id/file`
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::post,
+    Router,
+};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    Statement, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::billing::invoice_pdf::{render_invoice_pdf, PdfInvoice, PdfLine};
+use crate::db::AppState;
+use crate::entity::invoice_item::{self, InvoiceItemType};
+use crate::entity::{case, document, document_version, invoice};
+use crate::error::{AppError, AppResult};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+
+use super::documents::build_object_key;
+use super::invoices::require_invoice_access;
+use super::timelog_reports::parse_offset;
+
+const PDF_CONTENT_TYPE: &str = "application/pdf";
+const INVOICE_DOCUMENT_TYPE: &str = "INVOICE";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoicePdfQuery {
+    pub tz_offset_minutes: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoicePdfResponse {
+    pub invoice_id: String,
+    pub document_id: String,
+    pub version: i32,
+    pub file_size: i32,
+}
+
+fn local_date(at: DateTime<Utc>, offset: FixedOffset) -> NaiveDate {
+    at.with_timezone(&offset).date_naive()
+}
+
+async fn query_name(state: &AppState, sql: &str, id: &str, what: &str) -> AppResult<Option<String>> {
+    let row = state
+        .db
+        .query_one(Statement::from_sql_and_values(DatabaseBackend::Postgres, sql, [id.into()]))
+        .await
+        .map_err(|e| AppError::Database(format!("查询{what}失败: {e}")))?;
+    row.map(|r| r.try_get::<String>("", "name").map_err(|e| AppError::Database(format!("读取{what}失败: {e}"))))
+        .transpose()
+}
+
+/// POST /api/v1/invoices/:id/pdf
+async fn generate_invoice_pdf(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(invoice_id): Path<String>,
+    Query(query): Query<InvoicePdfQuery>,
+) -> AppResult<Json<InvoicePdfResponse>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::BillingView)?;
+    require_permission(role, Permission::DocumentUpload)?;
+    let offset = parse_offset(query.tz_offset_minutes)?;
+    let model = require_invoice_access(&state, &current_user, &invoice_id).await?;
+    let case_id = model.case_id.clone().ok_or_else(|| AppError::Validation("发票未关联案件，无法存档".to_string()))?;
+
+    let case_model = case::Entity::find_by_id(&case_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询案件失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("案件不存在".to_string()))?;
+    let items = invoice_item::Entity::find()
+        .filter(invoice_item::Column::InvoiceId.eq(&model.id))
+        .order_by_asc(invoice_item::Column::Date)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询发票明细失败: {e}")))?;
+
+    let firm_name = query_name(
+        &state,
+        r#"SELECT COALESCE(f.name, t.name) AS name
+           FROM "Tenant" t LEFT JOIN "Firm" f ON f.id = t."firmId"
+           WHERE t.id = $1"#,
+        &model.tenant_id,
+        "律所",
+    )
+    .await?
+    .unwrap_or_default();
+    let client_name = match model.client_id.as_deref() {
+        Some(client_id) => query_name(&state, r#"SELECT name FROM "Contact" WHERE id = $1"#, client_id, "客户").await?,
+        None => None,
+    };
+
+    let lines: Vec<PdfLine> = items
+        .iter()
+        .map(|item| PdfLine {
+            date: local_date(item.date, offset),
+            kind: match item.item_type {
+                InvoiceItemType::Time => "工时".to_string(),
+                InvoiceItemType::Expense => "费用".to_string(),
+            },
+            description: item.description.clone(),
+            quantity: item.quantity,
+            rate: item.rate,
+            amount: item.amount,
+        })
+        .collect();
+    // periodEnd 为开区间
+    let period = match (model.period_start, model.period_end) {
+        (Some(from), Some(to)) => Some((local_date(from, offset), local_date(to - Duration::seconds(1), offset))),
+        _ => None,
+    };
+    let bytes = render_invoice_pdf(
+        &PdfInvoice {
+            firm_name,
+            invoice_no: model.invoice_no.clone(),
+            issued_on: model.issued_at.map(|d| local_date(d, offset)),
+            due_on: model.due_date.map(|d| local_date(d, offset)),
+            client_name,
+            case_code: Some(case_model.case_code.clone()),
+            case_title: Some(case_model.title.clone()),
+            period,
+            amount: model.amount,
+            tax: model.tax,
+            total: model.total_amount,
+            notes: model.notes.clone(),
+        },
+        &lines,
+    )?;
+    let file_size = i32::try_from(bytes.len()).map_err(|_| AppError::Internal("PDF 文件过大".to_string()))?;
+
+    // 先按当前版本写入对象存储，事务内锁定发票后确认版本未被并发生成占用
+    let existing_document = match model.document_id.as_deref() {
+        Some(document_id) => document::Entity::find_by_id(document_id)
+            .one(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询文档失败: {e}")))?,
+        None => None,
+    };
+    let document_id = existing_document.as_ref().map_or_else(|| Uuid::new_v4().to_string(), |d| d.id.clone());
+    let version = existing_document.as_ref().map_or(1, |d| d.version.saturating_add(1));
+    let file_name = format!("{}.pdf", model.invoice_no);
+    let key = build_object_key(&case_id, &document_id, version, &file_name);
+    state.storage.put_object(&key, bytes, Some(PDF_CONTENT_TYPE)).await?;
+
+    let uploader_id = current_user.id().to_string();
+    let expected_document_id = model.document_id.clone();
+    let title = format!("账单 {}", model.invoice_no);
+    let tx_document_id = document_id.clone();
+    let tx_key = key.clone();
+    let result = state
+        .db
+        .transaction::<_, (), AppError>(|txn| {
+            Box::pin(async move {
+                let locked = invoice::Entity::find_by_id(&model.id)
+                    .lock_exclusive()
+                    .one(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("查询发票失败: {e}")))?
+                    .ok_or_else(|| AppError::NotFound("发票不存在".to_string()))?;
+                let current = match locked.document_id.as_deref() {
+                    Some(id) => document::Entity::find_by_id(id)
+                        .one(txn)
+                        .await
+                        .map_err(|e| AppError::Database(format!("查询文档失败: {e}")))?,
+                    None => None,
+                };
+                let current_version = current.as_ref().map_or(0, |d| d.version);
+                if locked.document_id != expected_document_id || current_version + 1 != version {
+                    return Err(AppError::Validation("账单 PDF 正在被其他请求生成，请稍后重试".to_string()));
+                }
+
+                let now = Utc::now();
+                match current {
+                    Some(existing) => {
+                        let mut active: document::ActiveModel = existing.into();
+                        active.file_url = sea_orm::ActiveValue::Set(Some(tx_key.clone()));
+                        active.file_type = sea_orm::ActiveValue::Set(Some(PDF_CONTENT_TYPE.to_string()));
+                        active.file_size = sea_orm::ActiveValue::Set(file_size);
+                        active.version = sea_orm::ActiveValue::Set(version);
+                        active.uploader_id = sea_orm::ActiveValue::Set(Some(uploader_id.clone()));
+                        active.updated_at = sea_orm::ActiveValue::Set(now);
+                        active.update(txn).await.map_err(|e| AppError::Database(format!("更新文档失败: {e}")))?;
+                    }
+                    None => {
+                        document::ActiveModel {
+                            id: sea_orm::ActiveValue::Set(tx_document_id.clone()),
+                            title: sea_orm::ActiveValue::Set(title),
+                            file_url: sea_orm::ActiveValue::Set(Some(tx_key.clone())),
+                            file_type: sea_orm::ActiveValue::Set(Some(PDF_CONTENT_TYPE.to_string())),
+                            file_size: sea_orm::ActiveValue::Set(file_size),
+                            version: sea_orm::ActiveValue::Set(version),
+                            document_type: sea_orm::ActiveValue::Set(Some(INVOICE_DOCUMENT_TYPE.to_string())),
+                            category: sea_orm::ActiveValue::Set(Some("账单".to_string())),
+                            tags: sea_orm::ActiveValue::Set(vec![]),
+                            uploader_id: sea_orm::ActiveValue::Set(Some(uploader_id.clone())),
+                            created_at: sea_orm::ActiveValue::Set(now),
+                            updated_at: sea_orm::ActiveValue::Set(now),
+                            case_id: sea_orm::ActiveValue::Set(case_id),
+                            ..Default::default()
+                        }
+                        .insert(txn)
+                        .await
+                        .map_err(|e| AppError::Database(format!("创建文档失败: {e}")))?;
+
+                        let mut active: invoice::ActiveModel = locked.into();
+                        active.document_id = sea_orm::ActiveValue::Set(Some(tx_document_id.clone()));
+                        active.updated_at = sea_orm::ActiveValue::Set(now);
+                        active.update(txn).await.map_err(|e| AppError::Database(format!("更新发票失败: {e}")))?;
+                    }
+                }
+
+                document_version::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    document_id: sea_orm::ActiveValue::Set(tx_document_id),
+                    version: sea_orm::ActiveValue::Set(version),
+                    file_key: sea_orm::ActiveValue::Set(tx_key),
+                    file_type: sea_orm::ActiveValue::Set(PDF_CONTENT_TYPE.to_string()),
+                    file_size: sea_orm::ActiveValue::Set(file_size),
+                    uploader_id: sea_orm::ActiveValue::Set(Some(uploader_id)),
+                    created_at: sea_orm::ActiveValue::Set(now),
+                }
+                .insert(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("写入文档版本失败: {e}")))?;
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        });
+
+    if let Err(e) = result {
+        if let Err(cleanup) = state.storage.delete_object(&key).await {
+            tracing::warn!("回滚账单 PDF 文件失败（{key}）: {cleanup}");
+        }
+        return Err(e);
+    }
+
+    Ok(Json(InvoicePdfResponse { invoice_id, document_id, version, file_size }))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/:id/pdf", post(generate_invoice_pdf))
+}
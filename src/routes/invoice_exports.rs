@@ -0,0 +1,282 @@
+//! 发票电子账单导出（合并到 `/invoices`）
+//!
+//! - `GET /invoices/:id/export?format=ledes|csv&tzOffsetMinutes=`：按发票明细快照导出 LEDES 1998B 或 CSV（`billing:view`）
+//! - 工时行：计时人取工时所属律师，级别按角色映射（PARTNER → PT，律师 → AS，实习/秘书 → LA，其余 OT）；
+//!   任务代码按工时关联任务的 `taskType`、活动代码按工时描述映射（见 `utbms_codes`）
+//! - 费用行：费用代码按费用类别映射；数量为 1、单价为加收后的金额
+//! - 缺少必填字段时拒绝导出并列出全部缺失项；日期按 `tzOffsetMinutes`（缺省 UTC）换算为本地日期
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, Utc};
+use sea_orm::prelude::Decimal;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::billing::invoice::round_money;
+use crate::billing::ledes::{
+    csv_records, render_ledes_1998b, validate, ExportFormat, ExportInvoice, ExportLine, LineKind, UtbmsCodes,
+    CSV_HEADER,
+};
+use crate::db::AppState;
+use crate::entity::invoice_item::{self, InvoiceItemType};
+use crate::entity::user::{self, Role};
+use crate::entity::utbms_code_mapping::{self, UtbmsCodeKind};
+use crate::entity::{case, expense, task, time_log};
+use crate::error::{AppError, AppResult};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+
+use super::invoices::require_invoice_access;
+use super::timelog_reports::{csv_field, parse_offset};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportQuery {
+    /// `ledes`（LEDES 1998B，缺省）| `csv`
+    pub format: Option<String>,
+    pub tz_offset_minutes: Option<i32>,
+}
+
+fn parse_format(raw: Option<&str>) -> AppResult<ExportFormat> {
+    match raw.map(|v| v.trim().to_lowercase()).as_deref() {
+        None | Some("") | Some("ledes") | Some("ledes1998b") => Ok(ExportFormat::Ledes1998b),
+        Some("csv") => Ok(ExportFormat::Csv),
+        Some(other) => Err(AppError::Validation(format!("format 无效: {other}（支持 ledes / csv）"))),
+    }
+}
+
+/// 角色 → LEDES 计时人级别
+fn timekeeper_classification(role: &Role) -> &'static str {
+    match role {
+        Role::Partner => "PT",
+        Role::SeniorLawyer | Role::Lawyer => "AS",
+        Role::Trainee | Role::LegalSecretary => "LA",
+        _ => "OT",
+    }
+}
+
+fn local_date(at: DateTime<Utc>, offset: FixedOffset) -> NaiveDate {
+    at.with_timezone(&offset).date_naive()
+}
+
+async fn load_utbms_codes(state: &AppState, tenant_id: &str) -> AppResult<UtbmsCodes> {
+    let mappings = utbms_code_mapping::Entity::find()
+        .filter(utbms_code_mapping::Column::TenantId.eq(tenant_id))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询 UTBMS 代码映射失败: {e}")))?;
+    let mut codes = UtbmsCodes::default();
+    for m in &mappings {
+        match m.kind {
+            UtbmsCodeKind::Task => codes.add_task(&m.match_value, &m.code),
+            UtbmsCodeKind::Activity => codes.add_activity(&m.match_value, &m.code),
+            UtbmsCodeKind::Expense => codes.add_expense(&m.match_value, &m.code),
+        }
+    }
+    Ok(codes)
+}
+
+/// GET /api/v1/invoices/:id/export
+async fn export_invoice(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(invoice_id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> AppResult<Response> {
+    require_permission(current_user.model.role.clone(), Permission::BillingView)?;
+    let format = parse_format(query.format.as_deref())?;
+    let offset = parse_offset(query.tz_offset_minutes)?;
+    let model = require_invoice_access(&state, &current_user, &invoice_id).await?;
+
+    let items = invoice_item::Entity::find()
+        .filter(invoice_item::Column::InvoiceId.eq(&model.id))
+        .order_by_asc(invoice_item::Column::Date)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询发票明细失败: {e}")))?;
+
+    let log_ids: Vec<String> = items.iter().filter_map(|i| i.time_log_id.clone()).collect();
+    let logs: HashMap<String, time_log::Model> = if log_ids.is_empty() {
+        HashMap::new()
+    } else {
+        time_log::Entity::find()
+            .filter(time_log::Column::Id.is_in(log_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询工时失败: {e}")))?
+            .into_iter()
+            .map(|l| (l.id.clone(), l))
+            .collect()
+    };
+    let expense_ids: Vec<String> = items.iter().filter_map(|i| i.expense_id.clone()).collect();
+    let expenses: HashMap<String, expense::Model> = if expense_ids.is_empty() {
+        HashMap::new()
+    } else {
+        expense::Entity::find()
+            .filter(expense::Column::Id.is_in(expense_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询费用失败: {e}")))?
+            .into_iter()
+            .map(|e| (e.id.clone(), e))
+            .collect()
+    };
+    let task_ids: Vec<String> = logs.values().filter_map(|l| l.task_id.clone()).collect();
+    let task_types: HashMap<String, Option<String>> = if task_ids.is_empty() {
+        HashMap::new()
+    } else {
+        task::Entity::find()
+            .filter(task::Column::Id.is_in(task_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
+            .into_iter()
+            .map(|t| (t.id, t.task_type))
+            .collect()
+    };
+    let user_ids: Vec<String> =
+        logs.values().map(|l| l.user_id.clone()).chain(expenses.values().map(|e| e.user_id.clone())).collect();
+    let users: HashMap<String, user::Model> = if user_ids.is_empty() {
+        HashMap::new()
+    } else {
+        user::Entity::find()
+            .filter(user::Column::Id.is_in(user_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+            .into_iter()
+            .map(|u| (u.id.clone(), u))
+            .collect()
+    };
+    let case_model = match model.case_id.as_deref() {
+        Some(case_id) => case::Entity::find_by_id(case_id)
+            .one(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询案件失败: {e}")))?,
+        None => None,
+    };
+    let codes = load_utbms_codes(&state, &model.tenant_id).await?;
+
+    let lines: Vec<ExportLine> = items
+        .iter()
+        .map(|item| {
+            let (kind, units, unit_cost, owner_id, task_code, activity_code, expense_code) = match item.item_type {
+                InvoiceItemType::Time => {
+                    let log = item.time_log_id.as_ref().and_then(|id| logs.get(id));
+                    let units = item.quantity.unwrap_or(Decimal::ONE);
+                    let unit_cost = item.rate.unwrap_or_else(|| {
+                        if units > Decimal::ZERO {
+                            round_money(item.original_amount / units)
+                        } else {
+                            item.original_amount
+                        }
+                    });
+                    let task_type = log
+                        .and_then(|l| l.task_id.as_ref())
+                        .and_then(|id| task_types.get(id))
+                        .and_then(|t| t.as_deref());
+                    let activity_source = log.map_or(item.description.as_str(), |l| l.description.as_str());
+                    (
+                        LineKind::Fee,
+                        units,
+                        unit_cost,
+                        log.map(|l| l.user_id.clone()),
+                        codes.task_code(task_type),
+                        codes.activity_code(activity_source),
+                        None,
+                    )
+                }
+                InvoiceItemType::Expense => {
+                    let e = item.expense_id.as_ref().and_then(|id| expenses.get(id));
+                    (
+                        LineKind::Expense,
+                        Decimal::ONE,
+                        item.original_amount,
+                        e.map(|e| e.user_id.clone()),
+                        None,
+                        None,
+                        e.and_then(|e| codes.expense_code(&e.category)),
+                    )
+                }
+            };
+            let owner = owner_id.as_ref().and_then(|id| users.get(id));
+            ExportLine {
+                kind,
+                date: local_date(item.date, offset),
+                units,
+                unit_cost,
+                total: item.amount,
+                task_code,
+                activity_code,
+                expense_code,
+                timekeeper_id: owner_id.clone(),
+                timekeeper_name: owner.map(|u| u.name.clone().unwrap_or_else(|| u.email.clone())),
+                timekeeper_classification: owner.map(|u| timekeeper_classification(&u.role).to_string()),
+                description: item.description.clone(),
+            }
+        })
+        .collect();
+
+    // 未记录计费期间时以明细日期范围代替；periodEnd 为开区间
+    let billing_start = model
+        .period_start
+        .map(|d| local_date(d, offset))
+        .or_else(|| lines.iter().map(|l| l.date).min());
+    let billing_end = model
+        .period_end
+        .map(|d| local_date(d - Duration::seconds(1), offset))
+        .or_else(|| lines.iter().map(|l| l.date).max());
+
+    let export = ExportInvoice {
+        invoice_no: model.invoice_no.clone(),
+        invoice_date: model.issued_at.map(|d| local_date(d, offset)),
+        client_id: model.client_id.clone(),
+        matter_id: case_model.map(|c| c.case_code),
+        law_firm_id: state.config.ledes_law_firm_id.clone(),
+        total: model.amount,
+        billing_start,
+        billing_end,
+        description: model.description.clone(),
+    };
+
+    let errors = validate(&export, &lines, format);
+    if !errors.is_empty() {
+        return Err(AppError::Validation(format!("发票无法导出：{}", errors.join("；"))));
+    }
+
+    let (body, content_type, extension) = match format {
+        ExportFormat::Ledes1998b => (render_ledes_1998b(&export, &lines), "text/plain; charset=utf-8", "txt"),
+        ExportFormat::Csv => {
+            let mut out = String::from("\u{feff}");
+            out.push_str(&CSV_HEADER.join(","));
+            out.push('\n');
+            for record in csv_records(&export, &lines) {
+                let fields: Vec<String> = record.iter().map(|v| csv_field(v)).collect();
+                out.push_str(&fields.join(","));
+                out.push('\n');
+            }
+            (out, "text/csv; charset=utf-8", "csv")
+        }
+    };
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    let disposition = format!("attachment; filename=\"{}.{extension}\"", model.invoice_no);
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&disposition).unwrap_or(HeaderValue::from_static("attachment")),
+    );
+    Ok((StatusCode::OK, headers, body).into_response())
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/:id/export", get(export_invoice))
+}
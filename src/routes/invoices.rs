@@ -0,0 +1,714 @@
+//! 发票路由模块（预开票 → 开票）
+//!
+//! - `GET /invoices/prebill`：汇总案件在计费期间内已审批（APPROVED）的可计费工时与已审批、确认计费且未开票的费用，
+//!   费用按类别加收规则（`ExpenseMarkupRule`）计算金额，按默认税率试算，供合伙人审核调整；不落库
+//! - `POST /invoices`：按审核后的明细开票（可逐条调整金额/描述），事务内分配租户内唯一发票号
+//!   （`INV-YYYYMM-NNNN`）、计算税额与总额、写入明细快照，并将工时置为 BILLED
+//! - 预开票需 `billing:create`；开票需 `billing:approve`；均需可见该案件
+//! - 工时/费用各自只能开票一次（`InvoiceItem.timeLogId` / `expenseId` 唯一）
+//! - 收款见 `payments`，账龄报表见 `receivables`，电子账单导出见 `invoice_exports`，
+//!   PDF 账单见 `invoice_documents`（合并到本路由）
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::prelude::Decimal;
+use sea_orm::sea_query::Query as SeaQuery;
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Statement, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::billing::invoice::{invoice_totals, marked_up, next_invoice_no, round_money};
+use crate::db::AppState;
+use crate::entity::expense::{self, ExpenseStatus};
+use crate::entity::expense_markup_rule;
+use crate::entity::invoice::{self, InvoiceStatus};
+use crate::entity::invoice_item::{self, InvoiceItemType};
+use crate::entity::time_log;
+use crate::error::{AppError, AppResult};
+use crate::security::case_access::{require_case_access, visible_case_ids};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+use crate::security::validation::ValidatedJson;
+
+use super::cases::PaginatedResponse;
+
+/// 单张发票明细上限
+const MAX_INVOICE_LINES: usize = 500;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrebillQuery {
+    pub case_id: String,
+    /// 计费期间起（含）；按工时开始时间 / 费用发生日期
+    pub from: Option<DateTime<Utc>>,
+    /// 计费期间止（不含）
+    pub to: Option<DateTime<Utc>>,
+    /// 税率（Decimal 字符串，缺省 `INVOICE_TAX_RATE`）
+    pub tax_rate: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrebillLine {
+    /// 工时/费用 ID
+    pub id: String,
+    /// TIME | EXPENSE
+    pub item_type: String,
+    pub date: DateTime<Utc>,
+    pub description: String,
+    pub user_id: String,
+    /// 计费小时数（工时）
+    pub quantity: Option<String>,
+    pub rate: Option<String>,
+    pub amount: String,
+    /// 核销金额（封顶/固定收费，不计入开票）
+    pub write_off_amount: Option<String>,
+    /// 费用加收百分比（已计入 amount）
+    pub markup_percent: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrebillResponse {
+    pub case_id: String,
+    pub case_code: String,
+    pub case_title: String,
+    pub client_id: String,
+    pub period_start: Option<DateTime<Utc>>,
+    pub period_end: Option<DateTime<Utc>>,
+    pub time_logs: Vec<PrebillLine>,
+    pub expenses: Vec<PrebillLine>,
+    pub amount: String,
+    pub tax_rate: String,
+    pub tax: String,
+    pub total_amount: String,
+}
+
+/// 开票明细：id 为工时/费用 ID；amount/description 为合伙人调整后的值（缺省沿用原值）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceLineInput {
+    pub id: String,
+    pub amount: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueInvoiceRequest {
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub case_id: String,
+    pub period_start: Option<DateTime<Utc>>,
+    pub period_end: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub time_logs: Vec<InvoiceLineInput>,
+    #[serde(default)]
+    pub expenses: Vec<InvoiceLineInput>,
+    pub tax_rate: Option<String>,
+    /// 付款截止日期（缺省开票后 `INVOICE_DUE_DAYS` 天）
+    pub due_date: Option<DateTime<Utc>>,
+    #[validate(length(max = 2000, message = "description 长度不合法"))]
+    pub description: Option<String>,
+    #[validate(length(max = 5000, message = "notes 长度不合法"))]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceListQuery {
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+    pub case_id: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceResponse {
+    pub id: String,
+    pub invoice_no: String,
+    pub case_id: Option<String>,
+    pub client_id: Option<String>,
+    pub amount: String,
+    pub tax: String,
+    pub total_amount: String,
+    pub status: String,
+    pub issued_at: Option<DateTime<Utc>>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub period_start: Option<DateTime<Utc>>,
+    pub period_end: Option<DateTime<Utc>>,
+    pub issued_by_id: Option<String>,
+    pub document_id: Option<String>,
+    pub description: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<invoice::Model> for InvoiceResponse {
+    fn from(m: invoice::Model) -> Self {
+        Self {
+            id: m.id,
+            invoice_no: m.invoice_no,
+            case_id: m.case_id,
+            client_id: m.client_id,
+            amount: m.amount.to_string(),
+            tax: m.tax.to_string(),
+            total_amount: m.total_amount.to_string(),
+            status: m.status.to_value(),
+            issued_at: m.issued_at,
+            due_date: m.due_date,
+            period_start: m.period_start,
+            period_end: m.period_end,
+            issued_by_id: m.issued_by_id,
+            document_id: m.document_id,
+            description: m.description,
+            notes: m.notes,
+            created_at: m.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceItemResponse {
+    pub id: String,
+    pub item_type: String,
+    pub time_log_id: Option<String>,
+    pub expense_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub description: String,
+    pub quantity: Option<String>,
+    pub rate: Option<String>,
+    pub original_amount: String,
+    pub amount: String,
+}
+
+impl From<invoice_item::Model> for InvoiceItemResponse {
+    fn from(m: invoice_item::Model) -> Self {
+        Self {
+            id: m.id,
+            item_type: m.item_type.to_value(),
+            time_log_id: m.time_log_id,
+            expense_id: m.expense_id,
+            date: m.date,
+            description: m.description,
+            quantity: m.quantity.map(|v| v.to_string()),
+            rate: m.rate.map(|v| v.to_string()),
+            original_amount: m.original_amount.to_string(),
+            amount: m.amount.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceDetailResponse {
+    #[serde(flatten)]
+    pub invoice: InvoiceResponse,
+    pub items: Vec<InvoiceItemResponse>,
+}
+
+fn parse_tax_rate(raw: Option<&str>, default: Decimal) -> AppResult<Decimal> {
+    let Some(raw) = raw.map(str::trim).filter(|s| !s.is_empty()) else {
+        return Ok(default);
+    };
+    match Decimal::from_str(raw) {
+        Ok(v) if v >= Decimal::ZERO && v < Decimal::ONE => Ok(v),
+        _ => Err(AppError::Validation(format!("taxRate 无效: {raw}（需为 0-1 之间的小数）"))),
+    }
+}
+
+fn parse_invoice_status(raw: &str) -> AppResult<InvoiceStatus> {
+    match raw.trim() {
+        "DRAFT" => Ok(InvoiceStatus::Draft),
+        "PENDING" => Ok(InvoiceStatus::Pending),
+        "PAID" => Ok(InvoiceStatus::Paid),
+        "PARTIAL" => Ok(InvoiceStatus::Partial),
+        "OVERDUE" => Ok(InvoiceStatus::Overdue),
+        "CANCELLED" => Ok(InvoiceStatus::Cancelled),
+        other => Err(AppError::Validation(format!("status 无效: {other}"))),
+    }
+}
+
+/// 计费小时数（优先取整后的计费时长）
+fn billed_hours(log: &time_log::Model) -> Decimal {
+    let secs = log.billed_duration.unwrap_or(log.duration);
+    (Decimal::from(secs) / Decimal::from(3600)).round_dp(2)
+}
+
+fn expense_description(e: &expense::Model) -> String {
+    match e.description.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(desc) => format!("{}：{}", e.category, desc),
+        None => e.category.clone(),
+    }
+}
+
+fn time_log_line(log: &time_log::Model) -> PrebillLine {
+    PrebillLine {
+        id: log.id.clone(),
+        item_type: InvoiceItemType::Time.to_value(),
+        date: log.start_time,
+        description: log.description.clone(),
+        user_id: log.user_id.clone(),
+        quantity: Some(billed_hours(log).to_string()),
+        rate: log.billing_rate.map(|v| v.to_string()),
+        amount: round_money(log.billing_amount.unwrap_or_default()).to_string(),
+        write_off_amount: log.write_off_amount.map(|v| v.to_string()),
+        markup_percent: None,
+    }
+}
+
+/// 租户费用加收规则：类别 → 加收百分比
+async fn expense_markups<C: ConnectionTrait>(db: &C, tenant_id: &str) -> AppResult<HashMap<String, Decimal>> {
+    Ok(expense_markup_rule::Entity::find()
+        .filter(expense_markup_rule::Column::TenantId.eq(tenant_id))
+        .all(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询费用加收规则失败: {e}")))?
+        .into_iter()
+        .map(|r| (r.category, r.markup_percent))
+        .collect())
+}
+
+/// 费用开票金额（含类别加收）
+fn expense_amount(e: &expense::Model, markups: &HashMap<String, Decimal>) -> Decimal {
+    marked_up(e.amount, markups.get(&e.category).copied().unwrap_or_default())
+}
+
+fn expense_line(e: &expense::Model, markups: &HashMap<String, Decimal>) -> PrebillLine {
+    PrebillLine {
+        id: e.id.clone(),
+        item_type: InvoiceItemType::Expense.to_value(),
+        date: e.expense_date,
+        description: expense_description(e),
+        user_id: e.user_id.clone(),
+        quantity: None,
+        rate: None,
+        amount: expense_amount(e, markups).to_string(),
+        write_off_amount: None,
+        markup_percent: markups.get(&e.category).map(|v| v.to_string()),
+    }
+}
+
+/// 已开票的费用 ID 子查询
+fn invoiced_expense_ids() -> sea_orm::sea_query::SelectStatement {
+    SeaQuery::select()
+        .column(invoice_item::Column::ExpenseId)
+        .from(invoice_item::Entity)
+        .and_where(invoice_item::Column::ExpenseId.is_not_null())
+        .to_owned()
+}
+
+fn billable_expense_statuses() -> Vec<ExpenseStatus> {
+    vec![ExpenseStatus::Approved, ExpenseStatus::Reimbursed]
+}
+
+/// GET /api/v1/invoices/prebill
+async fn prebill(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<PrebillQuery>,
+) -> AppResult<Json<PrebillResponse>> {
+    Uuid::parse_str(&query.case_id).map_err(|_| AppError::Validation("caseId 无效".to_string()))?;
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::BillingCreate)?;
+    let case_model = require_case_access(&state, &query.case_id, current_user.id(), role, Permission::CaseView).await?;
+    let tax_rate = parse_tax_rate(query.tax_rate.as_deref(), state.config.invoice_tax_rate)?;
+
+    let mut logs_select = time_log::Entity::find()
+        .filter(time_log::Column::CaseId.eq(&case_model.id))
+        .filter(time_log::Column::Status.eq(time_log::TimeLogStatus::Approved))
+        .filter(time_log::Column::IsBillable.eq(true))
+        .order_by_asc(time_log::Column::StartTime);
+    let mut expenses_select = expense::Entity::find()
+        .filter(expense::Column::CaseId.eq(&case_model.id))
+        .filter(expense::Column::Status.is_in(billable_expense_statuses()))
+        .filter(expense::Column::IsBillable.eq(true))
+        .filter(expense::Column::Id.not_in_subquery(invoiced_expense_ids()))
+        .order_by_asc(expense::Column::ExpenseDate);
+    if let Some(from) = query.from {
+        logs_select = logs_select.filter(time_log::Column::StartTime.gte(from));
+        expenses_select = expenses_select.filter(expense::Column::ExpenseDate.gte(from));
+    }
+    if let Some(to) = query.to {
+        logs_select = logs_select.filter(time_log::Column::StartTime.lt(to));
+        expenses_select = expenses_select.filter(expense::Column::ExpenseDate.lt(to));
+    }
+
+    let logs = logs_select
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询待开票工时失败: {e}")))?;
+    let expenses = expenses_select
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询待开票费用失败: {e}")))?;
+
+    let markups = expense_markups(&state.db, &case_model.tenant_id).await?;
+
+    let amounts = logs
+        .iter()
+        .map(|l| l.billing_amount.unwrap_or_default())
+        .chain(expenses.iter().map(|e| expense_amount(e, &markups)));
+    let totals = invoice_totals(amounts, tax_rate);
+
+    Ok(Json(PrebillResponse {
+        case_id: case_model.id,
+        case_code: case_model.case_code,
+        case_title: case_model.title,
+        client_id: case_model.client_id,
+        period_start: query.from,
+        period_end: query.to,
+        time_logs: logs.iter().map(time_log_line).collect(),
+        expenses: expenses.iter().map(|e| expense_line(e, &markups)).collect(),
+        amount: totals.amount.to_string(),
+        tax_rate: tax_rate.to_string(),
+        tax: totals.tax.to_string(),
+        total_amount: totals.total.to_string(),
+    }))
+}
+
+/// 明细 ID 校验：UUID 且不重复
+fn collect_line_ids(lines: &[InvoiceLineInput], field: &str) -> AppResult<Vec<String>> {
+    let mut seen = HashSet::new();
+    for line in lines {
+        Uuid::parse_str(&line.id).map_err(|_| AppError::Validation(format!("{field} 含无效 ID: {}", line.id)))?;
+        if !seen.insert(line.id.as_str()) {
+            return Err(AppError::Validation(format!("{field} 含重复 ID: {}", line.id)));
+        }
+    }
+    Ok(lines.iter().map(|l| l.id.clone()).collect())
+}
+
+/// 调整后的明细金额（非负）与描述
+fn adjusted_line(input: &InvoiceLineInput, original: Decimal, description: &str) -> AppResult<(Decimal, String)> {
+    let amount = match input.amount.as_deref().map(str::trim) {
+        None | Some("") => original,
+        Some(raw) => match Decimal::from_str(raw) {
+            Ok(v) if v >= Decimal::ZERO => v,
+            _ => return Err(AppError::Validation(format!("明细 {} 的 amount 无效: {raw}", input.id))),
+        },
+    };
+    let description = match input.description.as_deref().map(str::trim) {
+        None | Some("") => description.to_string(),
+        Some(desc) if desc.chars().count() <= 5000 => desc.to_string(),
+        Some(_) => return Err(AppError::Validation(format!("明细 {} 的 description 过长", input.id))),
+    };
+    Ok((round_money(amount), description))
+}
+
+/// POST /api/v1/invoices
+async fn issue_invoice(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(payload): ValidatedJson<IssueInvoiceRequest>,
+) -> AppResult<Json<InvoiceDetailResponse>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::BillingApprove)?;
+    let case_model =
+        require_case_access(&state, &payload.case_id, current_user.id(), role, Permission::CaseView).await?;
+    let tax_rate = parse_tax_rate(payload.tax_rate.as_deref(), state.config.invoice_tax_rate)?;
+
+    let log_ids = collect_line_ids(&payload.time_logs, "timeLogs")?;
+    let expense_ids = collect_line_ids(&payload.expenses, "expenses")?;
+    let line_count = log_ids.len() + expense_ids.len();
+    if line_count == 0 {
+        return Err(AppError::Validation("开票明细不能为空".to_string()));
+    }
+    if line_count > MAX_INVOICE_LINES {
+        return Err(AppError::Validation(format!("单张发票明细最多 {MAX_INVOICE_LINES} 条")));
+    }
+    if let (Some(start), Some(end)) = (payload.period_start, payload.period_end) {
+        if start >= end {
+            return Err(AppError::Validation("periodStart 必须早于 periodEnd".to_string()));
+        }
+    }
+
+    let now = Utc::now();
+    let due_date = payload.due_date.unwrap_or(now + Duration::days(state.config.invoice_due_days as i64));
+    let issuer_id = current_user.id().to_string();
+
+    let (saved, items) = state
+        .db
+        .transaction::<_, (invoice::Model, Vec<invoice_item::Model>), AppError>(|txn| {
+            Box::pin(async move {
+                txn.execute(Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    "SELECT pg_advisory_xact_lock(hashtext($1))",
+                    [format!("invoice-no:{}", case_model.tenant_id).into()],
+                ))
+                .await
+                .map_err(|e| AppError::Database(format!("获取发票号锁失败: {e}")))?;
+
+                let invoice_id = Uuid::new_v4().to_string();
+                let mut items: Vec<invoice_item::ActiveModel> = Vec::with_capacity(line_count);
+                let new_item = |item_type, date, description, amounts: (Decimal, Decimal)| invoice_item::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    tenant_id: sea_orm::ActiveValue::Set(case_model.tenant_id.clone()),
+                    invoice_id: sea_orm::ActiveValue::Set(invoice_id.clone()),
+                    item_type: sea_orm::ActiveValue::Set(item_type),
+                    time_log_id: sea_orm::ActiveValue::Set(None),
+                    expense_id: sea_orm::ActiveValue::Set(None),
+                    date: sea_orm::ActiveValue::Set(date),
+                    description: sea_orm::ActiveValue::Set(description),
+                    quantity: sea_orm::ActiveValue::Set(None),
+                    rate: sea_orm::ActiveValue::Set(None),
+                    original_amount: sea_orm::ActiveValue::Set(amounts.0),
+                    amount: sea_orm::ActiveValue::Set(amounts.1),
+                    created_at: sea_orm::ActiveValue::Set(now),
+                };
+
+                if !log_ids.is_empty() {
+                    let logs = time_log::Entity::find()
+                        .filter(time_log::Column::Id.is_in(log_ids.clone()))
+                        .lock_exclusive()
+                        .all(txn)
+                        .await
+                        .map_err(|e| AppError::Database(format!("查询工时失败: {e}")))?;
+                    for input in &payload.time_logs {
+                        let log = logs
+                            .iter()
+                            .find(|l| l.id == input.id)
+                            .ok_or_else(|| AppError::NotFound(format!("工时 {} 不存在", input.id)))?;
+                        if log.case_id.as_deref() != Some(case_model.id.as_str()) {
+                            return Err(AppError::Validation(format!("工时 {} 不属于该案件", log.id)));
+                        }
+                        if log.status != time_log::TimeLogStatus::Approved || !log.is_billable {
+                            return Err(AppError::Validation(format!("工时 {} 不是已审批的可计费工时", log.id)));
+                        }
+                        let original = log.billing_amount.unwrap_or_default();
+                        let (amount, description) = adjusted_line(input, original, &log.description)?;
+                        let mut item = new_item(InvoiceItemType::Time, log.start_time, description, (original, amount));
+                        item.time_log_id = sea_orm::ActiveValue::Set(Some(log.id.clone()));
+                        item.quantity = sea_orm::ActiveValue::Set(Some(billed_hours(log)));
+                        item.rate = sea_orm::ActiveValue::Set(log.billing_rate);
+                        items.push(item);
+                    }
+                }
+
+                if !expense_ids.is_empty() {
+                    let expenses = expense::Entity::find()
+                        .filter(expense::Column::Id.is_in(expense_ids.clone()))
+                        .lock_exclusive()
+                        .all(txn)
+                        .await
+                        .map_err(|e| AppError::Database(format!("查询费用失败: {e}")))?;
+                    // 先锁定费用行再查开票记录：并发开票同一费用时后到者等待前者提交后能看到其明细
+                    let invoiced = invoice_item::Entity::find()
+                        .filter(invoice_item::Column::ExpenseId.is_in(expense_ids.clone()))
+                        .count(txn)
+                        .await
+                        .map_err(|e| AppError::Database(format!("查询已开票费用失败: {e}")))?;
+                    if invoiced > 0 {
+                        return Err(AppError::Validation("部分费用已开票".to_string()));
+                    }
+                    let markups = expense_markups(txn, &case_model.tenant_id).await?;
+                    for input in &payload.expenses {
+                        let exp = expenses
+                            .iter()
+                            .find(|e| e.id == input.id)
+                            .ok_or_else(|| AppError::NotFound(format!("费用 {} 不存在", input.id)))?;
+                        if exp.case_id.as_deref() != Some(case_model.id.as_str()) {
+                            return Err(AppError::Validation(format!("费用 {} 不属于该案件", exp.id)));
+                        }
+                        if !billable_expense_statuses().contains(&exp.status) {
+                            return Err(AppError::Validation(format!("费用 {} 尚未审批", exp.id)));
+                        }
+                        if !exp.is_billable {
+                            return Err(AppError::Validation(format!("费用 {} 未确认向客户计费", exp.id)));
+                        }
+                        let original = expense_amount(exp, &markups);
+                        let (amount, description) = adjusted_line(input, original, &expense_description(exp))?;
+                        let mut item =
+                            new_item(InvoiceItemType::Expense, exp.expense_date, description, (original, amount));
+                        item.expense_id = sea_orm::ActiveValue::Set(Some(exp.id.clone()));
+                        items.push(item);
+                    }
+                }
+
+                let totals = invoice_totals(items.iter().map(|i| *i.amount.as_ref()), tax_rate);
+
+                let prefix = format!("INV-{}", now.format("%Y%m"));
+                let existing: Vec<String> = invoice::Entity::find()
+                    .filter(invoice::Column::TenantId.eq(&case_model.tenant_id))
+                    .filter(invoice::Column::InvoiceNo.starts_with(format!("{prefix}-")))
+                    .select_only()
+                    .column(invoice::Column::InvoiceNo)
+                    .into_tuple()
+                    .all(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("查询发票号失败: {e}")))?;
+                let invoice_no = next_invoice_no(&prefix, existing.iter().map(String::as_str));
+
+                let saved = invoice::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(invoice_id.clone()),
+                    tenant_id: sea_orm::ActiveValue::Set(case_model.tenant_id.clone()),
+                    invoice_no: sea_orm::ActiveValue::Set(invoice_no),
+                    case_id: sea_orm::ActiveValue::Set(Some(case_model.id.clone())),
+                    client_id: sea_orm::ActiveValue::Set(Some(case_model.client_id.clone())),
+                    amount: sea_orm::ActiveValue::Set(totals.amount),
+                    tax: sea_orm::ActiveValue::Set(totals.tax),
+                    total_amount: sea_orm::ActiveValue::Set(totals.total),
+                    status: sea_orm::ActiveValue::Set(InvoiceStatus::Pending),
+                    issued_at: sea_orm::ActiveValue::Set(Some(now)),
+                    due_date: sea_orm::ActiveValue::Set(Some(due_date)),
+                    period_start: sea_orm::ActiveValue::Set(payload.period_start),
+                    period_end: sea_orm::ActiveValue::Set(payload.period_end),
+                    issued_by_id: sea_orm::ActiveValue::Set(Some(issuer_id)),
+                    document_id: sea_orm::ActiveValue::Set(None),
+                    description: sea_orm::ActiveValue::Set(payload.description.clone()),
+                    notes: sea_orm::ActiveValue::Set(payload.notes.clone()),
+                    created_at: sea_orm::ActiveValue::Set(now),
+                    updated_at: sea_orm::ActiveValue::Set(now),
+                }
+                .insert(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("创建发票失败: {e}")))?;
+
+                let mut saved_items = Vec::with_capacity(items.len());
+                for item in items {
+                    saved_items.push(
+                        item.insert(txn).await.map_err(|e| AppError::Database(format!("写入发票明细失败: {e}")))?,
+                    );
+                }
+
+                if !log_ids.is_empty() {
+                    time_log::Entity::update_many()
+                        .set(time_log::ActiveModel {
+                            status: sea_orm::ActiveValue::Set(time_log::TimeLogStatus::Billed),
+                            updated_at: sea_orm::ActiveValue::Set(now),
+                            ..Default::default()
+                        })
+                        .filter(time_log::Column::Id.is_in(log_ids))
+                        .exec(txn)
+                        .await
+                        .map_err(|e| AppError::Database(format!("更新工时状态失败: {e}")))?;
+                }
+
+                Ok((saved, saved_items))
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    Ok(Json(InvoiceDetailResponse {
+        invoice: InvoiceResponse::from(saved),
+        items: items.into_iter().map(InvoiceItemResponse::from).collect(),
+    }))
+}
+
+/// GET /api/v1/invoices
+async fn list_invoices(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<InvoiceListQuery>,
+) -> AppResult<Json<PaginatedResponse<InvoiceResponse>>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::BillingView)?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
+
+    let mut select = invoice::Entity::find()
+        .filter(invoice::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .order_by_desc(invoice::Column::CreatedAt);
+    match query.case_id.as_deref() {
+        Some(case_id) => {
+            Uuid::parse_str(case_id).map_err(|_| AppError::Validation("caseId 无效".to_string()))?;
+            require_case_access(&state, case_id, current_user.id(), role, Permission::CaseView).await?;
+            select = select.filter(invoice::Column::CaseId.eq(case_id));
+        }
+        None => {
+            if let Some(ids) = visible_case_ids(&state, current_user.id(), &role).await? {
+                select = select.filter(invoice::Column::CaseId.is_in(ids));
+            }
+        }
+    }
+    if let Some(status) = query.status.as_deref() {
+        select = select.filter(invoice::Column::Status.eq(parse_invoice_status(status)?));
+    }
+
+    let paginator = select.paginate(&state.db, page_size);
+    let total = paginator.num_items().await.map_err(|e| AppError::Database(format!("计数失败: {e}")))?;
+    let total_pages = paginator.num_pages().await.map_err(|e| AppError::Database(format!("分页失败: {e}")))?;
+    let invoices = paginator
+        .fetch_page(page - 1)
+        .await
+        .map_err(|e| AppError::Database(format!("查询发票失败: {e}")))?;
+
+    Ok(Json(PaginatedResponse {
+        data: invoices.into_iter().map(InvoiceResponse::from).collect(),
+        total,
+        page,
+        page_size,
+        total_pages,
+    }))
+}
+
+/// 查询发票并校验租户与案件可见性
+pub(crate) async fn require_invoice_access(
+    state: &AppState,
+    current_user: &CurrentUser,
+    invoice_id: &str,
+) -> AppResult<invoice::Model> {
+    Uuid::parse_str(invoice_id).map_err(|_| AppError::Validation("发票ID 无效".to_string()))?;
+    let model = invoice::Entity::find_by_id(invoice_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询发票失败: {e}")))?
+        .filter(|i| i.tenant_id == current_user.model.active_tenant_id)
+        .ok_or_else(|| AppError::NotFound("发票不存在".to_string()))?;
+    if let Some(case_id) = model.case_id.as_deref() {
+        require_case_access(state, case_id, current_user.id(), current_user.model.role.clone(), Permission::CaseView)
+            .await?;
+    }
+    Ok(model)
+}
+
+/// GET /api/v1/invoices/:id
+async fn get_invoice(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(invoice_id): Path<String>,
+) -> AppResult<Json<InvoiceDetailResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingView)?;
+    let model = require_invoice_access(&state, &current_user, &invoice_id).await?;
+
+    let items = invoice_item::Entity::find()
+        .filter(invoice_item::Column::InvoiceId.eq(&model.id))
+        .order_by_asc(invoice_item::Column::Date)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询发票明细失败: {e}")))?;
+
+    Ok(Json(InvoiceDetailResponse {
+        invoice: InvoiceResponse::from(model),
+        items: items.into_iter().map(InvoiceItemResponse::from).collect(),
+    }))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_invoices).post(issue_invoice))
+        .route("/prebill", get(prebill))
+        .route("/:id", get(get_invoice))
+        .merge(super::payments::router())
+        .merge(super::receivables::router())
+        .merge(super::invoice_exports::router())
+        .merge(super::invoice_documents::router())
+}
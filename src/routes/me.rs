@@ -0,0 +1,303 @@
+//! 个人工作台路由模块（“我的工作”）
+//!
+//! - `GET /me/work`：聚合当前用户被指派的任务（跨所有可访问的案件/项目），
+//!   按截止时间分组为 已逾期 / 今天到期 / 本周 / 以后（无截止时间归入“以后”）
+//! - 同时返回未来 7 天内的日程（本人创建或受邀且未拒绝）与当前进行中的计时
+//! - 分组内排序：优先级（P0 → P3）→ 截止时间（早 → 晚，无截止时间在后）→ 最近更新
+//! - “今天/本周”按 `tzOffsetMinutes`（相对 UTC 的分钟偏移，默认 0）计算自然日与自然周（周一起）
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveTime, TimeZone, Utc};
+use sea_orm::{ActiveEnum, ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::db::AppState;
+use crate::entity::{event, event_participant, task};
+use crate::error::{AppError, AppResult};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+
+use super::tasks::{parse_task_priority, parse_task_status, require_task_owner_access, TaskOwner, TaskResponse};
+use super::timelogs::{find_active_timer, ActiveTimerResponse};
+
+const MAX_WORK_TASKS: u64 = 500;
+const MAX_UPCOMING_EVENTS: u64 = 50;
+const UPCOMING_EVENT_DAYS: i64 = 7;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyWorkQuery {
+    /// 逗号分隔，如 `P0_URGENT,P1_HIGH`
+    pub priority: Option<String>,
+    /// 逗号分隔，如 `TODO,IN_PROGRESS`；缺省时返回除 DONE 外的全部状态
+    pub status: Option<String>,
+    pub tz_offset_minutes: Option<i32>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyWorkTasks {
+    pub overdue: Vec<TaskResponse>,
+    pub due_today: Vec<TaskResponse>,
+    pub this_week: Vec<TaskResponse>,
+    pub later: Vec<TaskResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyUpcomingEvent {
+    pub id: String,
+    pub title: String,
+    pub r#type: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub location: Option<String>,
+    pub case_id: Option<String>,
+    pub task_id: Option<String>,
+    /// 本人参与状态；本人创建且未列为参与人时为 None
+    pub my_status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyWorkResponse {
+    pub tasks: MyWorkTasks,
+    pub total_tasks: usize,
+    pub upcoming_events: Vec<MyUpcomingEvent>,
+    pub active_timer: Option<ActiveTimerResponse>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WorkBucket {
+    Overdue,
+    DueToday,
+    ThisWeek,
+    Later,
+}
+
+/// 按本地自然日/自然周（周一为一周开始）划分截止时间
+fn bucket_of(due_date: Option<DateTime<Utc>>, now: DateTime<Utc>, offset: FixedOffset) -> WorkBucket {
+    let Some(due_date) = due_date else {
+        return WorkBucket::Later;
+    };
+
+    let today = now.with_timezone(&offset).date_naive();
+    let start_of = |date: chrono::NaiveDate| {
+        offset
+            .from_local_datetime(&date.and_time(NaiveTime::MIN))
+            .single()
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or(now)
+    };
+    let start_of_today = start_of(today);
+    let start_of_tomorrow = start_of(today + Duration::days(1));
+    let start_of_next_week = start_of(today + Duration::days(7 - i64::from(today.weekday().num_days_from_monday())));
+
+    if due_date < start_of_today {
+        WorkBucket::Overdue
+    } else if due_date < start_of_tomorrow {
+        WorkBucket::DueToday
+    } else if due_date < start_of_next_week {
+        WorkBucket::ThisWeek
+    } else {
+        WorkBucket::Later
+    }
+}
+
+fn priority_rank(priority: &task::TaskPriority) -> u8 {
+    match priority {
+        task::TaskPriority::P0Urgent => 0,
+        task::TaskPriority::P1High => 1,
+        task::TaskPriority::P2Medium => 2,
+        task::TaskPriority::P3Low => 3,
+    }
+}
+
+fn compare_work_items(a: &task::Model, b: &task::Model) -> Ordering {
+    priority_rank(&a.priority)
+        .cmp(&priority_rank(&b.priority))
+        .then_with(|| match (a.due_date, b.due_date) {
+            (Some(x), Some(y)) => x.cmp(&y),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        })
+        .then_with(|| b.updated_at.cmp(&a.updated_at))
+}
+
+fn split_csv(raw: Option<&str>) -> Vec<&str> {
+    raw.map(|s| s.split(',').map(str::trim).filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+async fn upcoming_events(state: &AppState, user_id: &str, now: DateTime<Utc>) -> AppResult<Vec<MyUpcomingEvent>> {
+    let participations = event_participant::Entity::find()
+        .filter(event_participant::Column::UserId.eq(user_id))
+        .filter(event_participant::Column::Status.ne(event_participant::EventParticipantStatus::Declined))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询参与事件失败: {e}")))?;
+
+    let my_status: HashMap<String, String> =
+        participations.into_iter().map(|p| (p.event_id, p.status.to_value())).collect();
+
+    let mut visibility = Condition::any().add(event::Column::CreatorId.eq(user_id));
+    if !my_status.is_empty() {
+        visibility = visibility.add(event::Column::Id.is_in(my_status.keys().cloned().collect::<Vec<_>>()));
+    }
+
+    let events = event::Entity::find()
+        .filter(event::Column::Status.eq(event::EventStatus::Scheduled))
+        .filter(event::Column::EndTime.gt(now))
+        .filter(event::Column::StartTime.lt(now + Duration::days(UPCOMING_EVENT_DAYS)))
+        .filter(visibility)
+        .order_by_asc(event::Column::StartTime)
+        .limit(MAX_UPCOMING_EVENTS)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询事件失败: {e}")))?;
+
+    Ok(events
+        .into_iter()
+        .map(|e| MyUpcomingEvent {
+            my_status: my_status.get(&e.id).cloned(),
+            id: e.id,
+            title: e.title,
+            r#type: e.event_type.to_value(),
+            start_time: e.start_time,
+            end_time: e.end_time,
+            location: e.location,
+            case_id: e.case_id,
+            task_id: e.task_id,
+        })
+        .collect())
+}
+
+/// GET /api/v1/me/work
+async fn get_my_work(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<MyWorkQuery>,
+) -> AppResult<Json<MyWorkResponse>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::TaskView)?;
+
+    let tz_offset_minutes = query.tz_offset_minutes.unwrap_or(0);
+    let offset = FixedOffset::east_opt(tz_offset_minutes.saturating_mul(60))
+        .ok_or_else(|| AppError::Validation("tzOffsetMinutes 超出范围".to_string()))?;
+
+    let statuses = split_csv(query.status.as_deref())
+        .into_iter()
+        .map(|s| parse_task_status(Some(s)))
+        .collect::<AppResult<Vec<_>>>()?;
+    let priorities = split_csv(query.priority.as_deref())
+        .into_iter()
+        .map(|s| parse_task_priority(Some(s)))
+        .collect::<AppResult<Vec<_>>>()?;
+
+    let mut select = task::Entity::find().filter(task::Column::AssigneeId.eq(current_user.id()));
+    select = if statuses.is_empty() {
+        select.filter(task::Column::Status.ne(task::TaskStatus::Done))
+    } else {
+        select.filter(task::Column::Status.is_in(statuses))
+    };
+    if !priorities.is_empty() {
+        select = select.filter(task::Column::Priority.is_in(priorities));
+    }
+
+    let candidates = select
+        .order_by_asc(task::Column::DueDate)
+        .limit(MAX_WORK_TASKS)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询我的任务失败: {e}")))?;
+
+    // 指派给本人但已失去案件/项目访问权（或项目已删除）的任务不返回
+    let mut access: HashMap<TaskOwner, bool> = HashMap::new();
+    let mut visible: Vec<task::Model> = Vec::with_capacity(candidates.len());
+    for model in candidates {
+        let owner = TaskOwner::of(&model)?;
+        let allowed = match access.get(&owner) {
+            Some(allowed) => *allowed,
+            None => {
+                let allowed = match require_task_owner_access(
+                    &state,
+                    &owner,
+                    current_user.id(),
+                    &current_user.model.active_tenant_id,
+                    role.clone(),
+                    Permission::TaskView,
+                )
+                .await
+                {
+                    Ok(_) => true,
+                    Err(AppError::Forbidden(_) | AppError::NotFound(_)) => false,
+                    Err(err) => return Err(err),
+                };
+                access.insert(owner, allowed);
+                allowed
+            }
+        };
+        if allowed {
+            visible.push(model);
+        }
+    }
+    visible.sort_by(compare_work_items);
+
+    let now = Utc::now();
+    let total_tasks = visible.len();
+    let mut tasks = MyWorkTasks::default();
+    for model in visible {
+        let bucket = match bucket_of(model.due_date, now, offset) {
+            WorkBucket::Overdue => &mut tasks.overdue,
+            WorkBucket::DueToday => &mut tasks.due_today,
+            WorkBucket::ThisWeek => &mut tasks.this_week,
+            WorkBucket::Later => &mut tasks.later,
+        };
+        bucket.push(TaskResponse::from(model));
+    }
+
+    let upcoming_events = upcoming_events(&state, current_user.id(), now).await?;
+    let active_timer = find_active_timer(&state, current_user.id()).await?;
+
+    Ok(Json(MyWorkResponse { tasks, total_tasks, upcoming_events, active_timer }))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/work", get(get_my_work)).merge(super::out_of_office::router())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn buckets_follow_local_day_and_week() {
+        // 2026-10-14 是周三；UTC+8 下本地时间为 2026-10-14 18:00
+        let now = at("2026-10-14T10:00:00Z");
+        let cst = FixedOffset::east_opt(8 * 3600).unwrap();
+
+        assert_eq!(bucket_of(None, now, cst), WorkBucket::Later);
+        assert_eq!(bucket_of(Some(at("2026-10-13T15:59:00Z")), now, cst), WorkBucket::Overdue);
+        assert_eq!(bucket_of(Some(at("2026-10-13T16:00:00Z")), now, cst), WorkBucket::DueToday);
+        assert_eq!(bucket_of(Some(at("2026-10-14T16:00:00Z")), now, cst), WorkBucket::ThisWeek);
+        assert_eq!(bucket_of(Some(at("2026-10-18T15:59:00Z")), now, cst), WorkBucket::ThisWeek);
+        assert_eq!(bucket_of(Some(at("2026-10-18T16:00:00Z")), now, cst), WorkBucket::Later);
+
+        // 同一时刻按 UTC 计算，10-13 20:00Z 已属于前一天
+        let utc = FixedOffset::east_opt(0).unwrap();
+        assert_eq!(bucket_of(Some(at("2026-10-13T20:00:00Z")), now, utc), WorkBucket::Overdue);
+    }
+}
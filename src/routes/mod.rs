@@ -8,3 +8,28 @@ pub mod timelogs;
 pub mod documents;
 pub mod events;
 pub mod notifications;
+pub mod signals;
+pub mod task_checklist;
+pub mod task_dependencies;
+pub mod task_recurrence;
+pub mod task_comments;
+pub mod task_bulk;
+pub mod projects;
+pub mod me;
+pub mod timelog_reports;
+pub mod timelog_approvals;
+pub mod billing;
+pub mod rate_cards;
+pub mod case_budget;
+pub mod invoices;
+pub mod payments;
+pub mod receivables;
+pub mod expenses;
+pub mod expense_markups;
+pub mod invoice_exports;
+pub mod utbms_codes;
+pub mod invoice_documents;
+pub mod trust;
+pub mod contracts;
+pub mod approvals;
+pub mod out_of_office;
@@ -0,0 +1,360 @@
+//! 外出 / 休假与审批代理路由（合并到 `/me`）
+//!
+//! - `GET/POST /me/out-of-office`、`PATCH/DELETE /me/out-of-office/:id`：维护本人的外出时段，可指定审批代理人
+//! - 代理人须为当前租户的在职成员且不能是本人；同一用户设置了代理人的外出时段不得重叠
+//! - 时段内本人的待审批事项（审批申请的指定步骤、工时审核）由代理人代为处理，结束后自动交回（见 `security::delegation`）
+//! - 设置 / 更换 / 取消代理人时通知相关代理人（`APPROVAL_DELEGATED`）
+//! - `GET /me/delegations`：当前及将来由我代为审批的外出时段
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{get, patch},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::db::AppState;
+use crate::entity::{notification, out_of_office, user};
+use crate::error::{AppError, AppResult};
+use crate::security::current_user::CurrentUser;
+use crate::security::validation::ValidatedJson;
+
+use super::approvals::ensure_member;
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOutOfOfficeRequest {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    #[validate(length(max = 500, message = "reason 长度不合法"))]
+    pub reason: Option<String>,
+    /// 审批代理人
+    pub delegate_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateOutOfOfficeRequest {
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    /// 传空字符串清空
+    #[validate(length(max = 500, message = "reason 长度不合法"))]
+    pub reason: Option<String>,
+    /// 传空字符串取消代理
+    pub delegate_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutOfOfficeListQuery {
+    /// 是否包含已结束的时段（默认否）
+    pub include_past: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutOfOfficeResponse {
+    pub id: String,
+    pub user_id: String,
+    pub user_name: Option<String>,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub reason: Option<String>,
+    pub delegate_id: Option<String>,
+    pub delegate_name: Option<String>,
+    /// 当前是否处于该时段内
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn trim_optional(value: Option<String>) -> Option<String> {
+    value.map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+fn validate_period(start: DateTime<Utc>, end: DateTime<Utc>) -> AppResult<()> {
+    if end <= start {
+        return Err(AppError::Validation("endTime 必须晚于 startTime".to_string()));
+    }
+    Ok(())
+}
+
+fn display_name(model: &user::Model) -> String {
+    model.name.clone().unwrap_or_else(|| model.email.clone())
+}
+
+async fn load_own_entry(state: &AppState, current_user: &CurrentUser, id: &str) -> AppResult<out_of_office::Model> {
+    Uuid::parse_str(id).map_err(|_| AppError::Validation("外出记录ID 无效".to_string()))?;
+    out_of_office::Entity::find_by_id(id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询外出记录失败: {e}")))?
+        .filter(|o| o.tenant_id == current_user.model.active_tenant_id && o.user_id == current_user.id())
+        .ok_or_else(|| AppError::NotFound("外出记录不存在".to_string()))
+}
+
+/// 校验代理人，并确保本人设置了代理人的外出时段互不重叠
+async fn validate_delegation(
+    state: &AppState,
+    current_user: &CurrentUser,
+    delegate_id: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    exclude_id: Option<&str>,
+) -> AppResult<()> {
+    if delegate_id == current_user.id() {
+        return Err(AppError::Validation("不能指定自己为代理人".to_string()));
+    }
+    let tenant_id = &current_user.model.active_tenant_id;
+    ensure_member(state, tenant_id, delegate_id, "delegateId").await?;
+
+    let mut overlapping = out_of_office::Entity::find()
+        .filter(out_of_office::Column::TenantId.eq(tenant_id))
+        .filter(out_of_office::Column::UserId.eq(current_user.id()))
+        .filter(out_of_office::Column::DelegateId.is_not_null())
+        .filter(out_of_office::Column::StartTime.lt(end))
+        .filter(out_of_office::Column::EndTime.gt(start));
+    if let Some(id) = exclude_id {
+        overlapping = overlapping.filter(out_of_office::Column::Id.ne(id));
+    }
+    let overlapping =
+        overlapping.one(&state.db).await.map_err(|e| AppError::Database(format!("查询外出记录失败: {e}")))?;
+    if overlapping.is_some() {
+        return Err(AppError::Validation("该时段与已设置代理人的外出时段重叠".to_string()));
+    }
+    Ok(())
+}
+
+/// 通知代理人（设置或取消代理）；已结束的时段不通知
+async fn notify_delegate(
+    state: &AppState,
+    current_user: &CurrentUser,
+    entry: &out_of_office::Model,
+    delegate_id: &str,
+    assigned: bool,
+) -> AppResult<()> {
+    let now = Utc::now();
+    if entry.end_time <= now {
+        return Ok(());
+    }
+    let name = display_name(&current_user.model);
+    let period = format!("{} 至 {}", entry.start_time.format("%Y-%m-%d %H:%M"), entry.end_time.format("%Y-%m-%d %H:%M"));
+    let (title, content) = if assigned {
+        (
+            format!("审批代理：{name} 外出期间由你代为审批"),
+            format!("{period}（UTC），{name} 的待审批事项（审批申请、工时审核）由你代为处理，结束后自动交回"),
+        )
+    } else {
+        (format!("审批代理已取消：{name}"), format!("{period}（UTC）的审批代理已取消"))
+    };
+
+    notification::ActiveModel {
+        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+        user_id: sea_orm::ActiveValue::Set(delegate_id.to_string()),
+        actor_id: sea_orm::ActiveValue::Set(Some(current_user.id().to_string())),
+        notification_type: sea_orm::ActiveValue::Set(notification::NotificationType::ApprovalDelegated),
+        title: sea_orm::ActiveValue::Set(title),
+        content: sea_orm::ActiveValue::Set(Some(content)),
+        action_url: sea_orm::ActiveValue::Set(Some("/approvals".to_string())),
+        metadata: sea_orm::ActiveValue::Set(Some(json!({
+            "outOfOfficeId": entry.id,
+            "principalId": entry.user_id,
+            "startTime": entry.start_time,
+            "endTime": entry.end_time,
+            "assigned": assigned,
+        }))),
+        read_at: sea_orm::ActiveValue::Set(None),
+        created_at: sea_orm::ActiveValue::Set(now),
+    }
+    .insert(&state.db)
+    .await
+    .map_err(|e| AppError::Database(format!("写入通知失败: {e}")))?;
+    Ok(())
+}
+
+async fn to_responses(state: &AppState, entries: Vec<out_of_office::Model>) -> AppResult<Vec<OutOfOfficeResponse>> {
+    let mut user_ids: Vec<String> =
+        entries.iter().flat_map(|o| std::iter::once(o.user_id.clone()).chain(o.delegate_id.clone())).collect();
+    user_ids.sort();
+    user_ids.dedup();
+    let names: HashMap<String, String> = if user_ids.is_empty() {
+        HashMap::new()
+    } else {
+        user::Entity::find()
+            .filter(user::Column::Id.is_in(user_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+            .into_iter()
+            .map(|u| (u.id.clone(), display_name(&u)))
+            .collect()
+    };
+
+    let now = Utc::now();
+    Ok(entries
+        .into_iter()
+        .map(|o| OutOfOfficeResponse {
+            user_name: names.get(&o.user_id).cloned(),
+            delegate_name: o.delegate_id.as_ref().and_then(|id| names.get(id)).cloned(),
+            active: o.start_time <= now && now < o.end_time,
+            id: o.id,
+            user_id: o.user_id,
+            start_time: o.start_time,
+            end_time: o.end_time,
+            reason: o.reason,
+            delegate_id: o.delegate_id,
+            created_at: o.created_at,
+            updated_at: o.updated_at,
+        })
+        .collect())
+}
+
+async fn to_response(state: &AppState, entry: out_of_office::Model) -> AppResult<OutOfOfficeResponse> {
+    to_responses(state, vec![entry])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::Internal("外出记录响应为空".to_string()))
+}
+
+/// GET /api/v1/me/out-of-office
+async fn list_out_of_office(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<OutOfOfficeListQuery>,
+) -> AppResult<Json<Vec<OutOfOfficeResponse>>> {
+    let mut select = out_of_office::Entity::find()
+        .filter(out_of_office::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .filter(out_of_office::Column::UserId.eq(current_user.id()))
+        .order_by_asc(out_of_office::Column::StartTime);
+    if !query.include_past.unwrap_or(false) {
+        select = select.filter(out_of_office::Column::EndTime.gt(Utc::now()));
+    }
+    let entries = select.all(&state.db).await.map_err(|e| AppError::Database(format!("查询外出记录失败: {e}")))?;
+    Ok(Json(to_responses(&state, entries).await?))
+}
+
+/// POST /api/v1/me/out-of-office
+async fn create_out_of_office(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(req): ValidatedJson<CreateOutOfOfficeRequest>,
+) -> AppResult<Json<OutOfOfficeResponse>> {
+    validate_period(req.start_time, req.end_time)?;
+    let delegate_id = trim_optional(req.delegate_id);
+    if let Some(delegate_id) = delegate_id.as_deref() {
+        validate_delegation(&state, &current_user, delegate_id, req.start_time, req.end_time, None).await?;
+    }
+
+    let now = Utc::now();
+    let created = out_of_office::ActiveModel {
+        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+        tenant_id: sea_orm::ActiveValue::Set(current_user.model.active_tenant_id.clone()),
+        user_id: sea_orm::ActiveValue::Set(current_user.id().to_string()),
+        start_time: sea_orm::ActiveValue::Set(req.start_time),
+        end_time: sea_orm::ActiveValue::Set(req.end_time),
+        reason: sea_orm::ActiveValue::Set(trim_optional(req.reason)),
+        delegate_id: sea_orm::ActiveValue::Set(delegate_id),
+        created_at: sea_orm::ActiveValue::Set(now),
+        updated_at: sea_orm::ActiveValue::Set(now),
+    }
+    .insert(&state.db)
+    .await
+    .map_err(|e| AppError::Database(format!("创建外出记录失败: {e}")))?;
+
+    if let Some(delegate_id) = created.delegate_id.as_deref() {
+        notify_delegate(&state, &current_user, &created, delegate_id, true).await?;
+    }
+    Ok(Json(to_response(&state, created).await?))
+}
+
+/// PATCH /api/v1/me/out-of-office/:id
+async fn update_out_of_office(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+    ValidatedJson(req): ValidatedJson<UpdateOutOfOfficeRequest>,
+) -> AppResult<Json<OutOfOfficeResponse>> {
+    let existing = load_own_entry(&state, &current_user, &id).await?;
+    let start_time = req.start_time.unwrap_or(existing.start_time);
+    let end_time = req.end_time.unwrap_or(existing.end_time);
+    validate_period(start_time, end_time)?;
+    let delegate_id = match req.delegate_id {
+        Some(raw) => trim_optional(Some(raw)),
+        None => existing.delegate_id.clone(),
+    };
+    if let Some(delegate_id) = delegate_id.as_deref() {
+        validate_delegation(&state, &current_user, delegate_id, start_time, end_time, Some(&existing.id)).await?;
+    }
+
+    let previous = existing.clone();
+    let mut active: out_of_office::ActiveModel = existing.into();
+    active.start_time = sea_orm::ActiveValue::Set(start_time);
+    active.end_time = sea_orm::ActiveValue::Set(end_time);
+    if let Some(reason) = req.reason {
+        active.reason = sea_orm::ActiveValue::Set(trim_optional(Some(reason)));
+    }
+    active.delegate_id = sea_orm::ActiveValue::Set(delegate_id);
+    active.updated_at = sea_orm::ActiveValue::Set(Utc::now());
+    let updated = active.update(&state.db).await.map_err(|e| AppError::Database(format!("更新外出记录失败: {e}")))?;
+
+    let period_changed = previous.start_time != updated.start_time || previous.end_time != updated.end_time;
+    if previous.delegate_id != updated.delegate_id {
+        if let Some(old_delegate) = previous.delegate_id.as_deref() {
+            notify_delegate(&state, &current_user, &previous, old_delegate, false).await?;
+        }
+    }
+    if let Some(delegate_id) = updated.delegate_id.as_deref() {
+        if previous.delegate_id != updated.delegate_id || period_changed {
+            notify_delegate(&state, &current_user, &updated, delegate_id, true).await?;
+        }
+    }
+    Ok(Json(to_response(&state, updated).await?))
+}
+
+/// DELETE /api/v1/me/out-of-office/:id
+async fn delete_out_of_office(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> AppResult<Json<serde_json::Value>> {
+    let existing = load_own_entry(&state, &current_user, &id).await?;
+    out_of_office::Entity::delete_by_id(existing.id.clone())
+        .exec(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("删除外出记录失败: {e}")))?;
+    if let Some(delegate_id) = existing.delegate_id.as_deref() {
+        notify_delegate(&state, &current_user, &existing, delegate_id, false).await?;
+    }
+    Ok(Json(json!({ "success": true })))
+}
+
+/// GET /api/v1/me/delegations（当前及将来由我代为审批的外出时段）
+async fn list_delegations(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+) -> AppResult<Json<Vec<OutOfOfficeResponse>>> {
+    let entries = out_of_office::Entity::find()
+        .filter(out_of_office::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .filter(out_of_office::Column::DelegateId.eq(current_user.id()))
+        .filter(out_of_office::Column::EndTime.gt(Utc::now()))
+        .order_by_asc(out_of_office::Column::StartTime)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询外出代理失败: {e}")))?;
+    Ok(Json(to_responses(&state, entries).await?))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/out-of-office", get(list_out_of_office).post(create_out_of_office))
+        .route("/out-of-office/:id", patch(update_out_of_office).delete(delete_out_of_office))
+        .route("/delegations", get(list_delegations))
+}
@@ -0,0 +1,303 @@
+//! 发票收款路由（合并到 `/invoices`）
+//!
+//! - `GET /invoices/:id/payments`：收款记录与已收/未收金额（`billing:view`）
+//! - `POST /invoices/:id/payments`：登记收款（`billing:edit`），支持部分收款；累计收款不可超过发票总额
+//! - `DELETE /invoices/:id/payments/:payment_id`：撤销误登记的收款（`billing:edit`）；信托划转的收款须在信托账中冲正
+//! - 发票状态由收款推导（PENDING / PARTIAL / PAID / OVERDUE），在收款变动时于同一事务内刷新；
+//!   到期未收清的发票由后台任务 `jobs::invoice_overdue` 置为 OVERDUE
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    routing::{delete, get},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::Decimal;
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::billing::invoice::round_money;
+use crate::billing::receivables::{receivable_state, ReceivableState};
+use crate::db::AppState;
+use crate::entity::invoice::{self, InvoiceStatus};
+use crate::entity::payment::{self, PaymentMethod};
+use crate::error::{AppError, AppResult};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+use crate::security::validation::ValidatedJson;
+
+use super::invoices::{require_invoice_access, InvoiceResponse};
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePaymentRequest {
+    /// 收款金额（Decimal 字符串，最多 2 位小数）
+    pub amount: String,
+    /// `BANK` | `CASH` | `CHECK` | `ONLINE` | `OTHER`，缺省 `BANK`
+    pub method: Option<String>,
+    pub received_at: DateTime<Utc>,
+    #[validate(length(max = 200, message = "reference 长度不合法"))]
+    pub reference: Option<String>,
+    #[validate(length(max = 2000, message = "note 长度不合法"))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentResponse {
+    pub id: String,
+    pub invoice_id: String,
+    pub amount: String,
+    pub method: String,
+    pub received_at: DateTime<Utc>,
+    pub reference: Option<String>,
+    pub note: Option<String>,
+    pub recorder_id: Option<String>,
+    pub voided_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<payment::Model> for PaymentResponse {
+    fn from(m: payment::Model) -> Self {
+        Self {
+            id: m.id,
+            invoice_id: m.invoice_id,
+            amount: m.amount.to_string(),
+            method: m.method.to_value(),
+            received_at: m.received_at,
+            reference: m.reference,
+            note: m.note,
+            recorder_id: m.recorder_id,
+            voided_at: m.voided_at,
+            created_at: m.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoicePaymentsResponse {
+    pub invoice: InvoiceResponse,
+    pub paid_amount: String,
+    pub outstanding_amount: String,
+    pub payments: Vec<PaymentResponse>,
+}
+
+fn parse_payment_method(raw: Option<&str>) -> AppResult<PaymentMethod> {
+    match raw.map(str::trim) {
+        None | Some("") | Some("BANK") => Ok(PaymentMethod::Bank),
+        Some("CASH") => Ok(PaymentMethod::Cash),
+        Some("CHECK") => Ok(PaymentMethod::Check),
+        Some("ONLINE") => Ok(PaymentMethod::Online),
+        Some("OTHER") => Ok(PaymentMethod::Other),
+        Some(other) => Err(AppError::Validation(format!("method 无效: {other}"))),
+    }
+}
+
+fn parse_payment_amount(raw: &str) -> AppResult<Decimal> {
+    match Decimal::from_str(raw.trim()) {
+        Ok(v) if v > Decimal::ZERO && round_money(v) == v => Ok(v),
+        _ => Err(AppError::Validation(format!("amount 无效: {raw}（需为正数，最多 2 位小数）"))),
+    }
+}
+
+impl From<ReceivableState> for InvoiceStatus {
+    fn from(state: ReceivableState) -> Self {
+        match state {
+            ReceivableState::Pending => InvoiceStatus::Pending,
+            ReceivableState::Partial => InvoiceStatus::Partial,
+            ReceivableState::Paid => InvoiceStatus::Paid,
+            ReceivableState::Overdue => InvoiceStatus::Overdue,
+        }
+    }
+}
+
+/// 发票累计收款（不含已作废的收款）
+pub(crate) async fn paid_total<C: ConnectionTrait>(db: &C, invoice_id: &str) -> AppResult<Decimal> {
+    let total = payment::Entity::find()
+        .filter(payment::Column::InvoiceId.eq(invoice_id))
+        .filter(payment::Column::VoidedAt.is_null())
+        .select_only()
+        .column_as(payment::Column::Amount.sum(), "total")
+        .into_tuple::<Option<Decimal>>()
+        .one(db)
+        .await
+        .map_err(|e| AppError::Database(format!("汇总收款失败: {e}")))?
+        .flatten();
+    Ok(total.unwrap_or_default())
+}
+
+/// 加锁读取可收款的发票（已取消/草稿发票不可收款）
+pub(crate) async fn lock_receivable_invoice<C: ConnectionTrait>(db: &C, invoice_id: &str) -> AppResult<invoice::Model> {
+    let model = invoice::Entity::find_by_id(invoice_id)
+        .lock_exclusive()
+        .one(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询发票失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("发票不存在".to_string()))?;
+    if matches!(model.status, InvoiceStatus::Draft | InvoiceStatus::Cancelled) {
+        return Err(AppError::Validation("草稿或已取消的发票不可登记收款".to_string()));
+    }
+    Ok(model)
+}
+
+/// 按累计收款刷新发票状态
+pub(crate) async fn refresh_status<C: ConnectionTrait>(
+    db: &C,
+    model: invoice::Model,
+    paid: Decimal,
+    now: DateTime<Utc>,
+) -> AppResult<invoice::Model> {
+    let status = InvoiceStatus::from(receivable_state(model.total_amount, paid, model.due_date, now));
+    if status == model.status {
+        return Ok(model);
+    }
+    let mut active: invoice::ActiveModel = model.into();
+    active.status = sea_orm::ActiveValue::Set(status);
+    active.updated_at = sea_orm::ActiveValue::Set(now);
+    active.update(db).await.map_err(|e| AppError::Database(format!("更新发票状态失败: {e}")))
+}
+
+async fn payments_response(state: &AppState, model: invoice::Model) -> AppResult<InvoicePaymentsResponse> {
+    let payments = payment::Entity::find()
+        .filter(payment::Column::InvoiceId.eq(&model.id))
+        .order_by_asc(payment::Column::ReceivedAt)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询收款记录失败: {e}")))?;
+    let paid: Decimal = payments.iter().filter(|p| p.voided_at.is_none()).map(|p| p.amount).sum();
+    let outstanding = (model.total_amount - paid).max(Decimal::ZERO);
+
+    Ok(InvoicePaymentsResponse {
+        invoice: InvoiceResponse::from(model),
+        paid_amount: paid.to_string(),
+        outstanding_amount: outstanding.to_string(),
+        payments: payments.into_iter().map(PaymentResponse::from).collect(),
+    })
+}
+
+/// GET /api/v1/invoices/:id/payments
+async fn list_payments(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(invoice_id): Path<String>,
+) -> AppResult<Json<InvoicePaymentsResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingView)?;
+    let model = require_invoice_access(&state, &current_user, &invoice_id).await?;
+    Ok(Json(payments_response(&state, model).await?))
+}
+
+/// POST /api/v1/invoices/:id/payments
+async fn create_payment(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(invoice_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<CreatePaymentRequest>,
+) -> AppResult<Json<InvoicePaymentsResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    let model = require_invoice_access(&state, &current_user, &invoice_id).await?;
+    let amount = parse_payment_amount(&payload.amount)?;
+    let method = parse_payment_method(payload.method.as_deref())?;
+    let recorder_id = current_user.id().to_string();
+
+    let updated = state
+        .db
+        .transaction::<_, invoice::Model, AppError>(|txn| {
+            Box::pin(async move {
+                let locked = lock_receivable_invoice(txn, &model.id).await?;
+                let paid = paid_total(txn, &locked.id).await?;
+                let outstanding = locked.total_amount - paid;
+                if amount > outstanding {
+                    return Err(AppError::Validation(format!(
+                        "收款金额超过未收金额 {}",
+                        outstanding.max(Decimal::ZERO)
+                    )));
+                }
+
+                let now = Utc::now();
+                payment::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    tenant_id: sea_orm::ActiveValue::Set(locked.tenant_id.clone()),
+                    invoice_id: sea_orm::ActiveValue::Set(locked.id.clone()),
+                    amount: sea_orm::ActiveValue::Set(amount),
+                    method: sea_orm::ActiveValue::Set(method),
+                    received_at: sea_orm::ActiveValue::Set(payload.received_at),
+                    reference: sea_orm::ActiveValue::Set(
+                        payload.reference.map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+                    ),
+                    note: sea_orm::ActiveValue::Set(payload.note),
+                    recorder_id: sea_orm::ActiveValue::Set(Some(recorder_id)),
+                    voided_at: sea_orm::ActiveValue::Set(None),
+                    created_at: sea_orm::ActiveValue::Set(now),
+                }
+                .insert(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("登记收款失败: {e}")))?;
+
+                refresh_status(txn, locked, paid + amount, now).await
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    Ok(Json(payments_response(&state, updated).await?))
+}
+
+/// DELETE /api/v1/invoices/:id/payments/:payment_id
+async fn delete_payment(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path((invoice_id, payment_id)): Path<(String, String)>,
+) -> AppResult<Json<InvoicePaymentsResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    let model = require_invoice_access(&state, &current_user, &invoice_id).await?;
+    Uuid::parse_str(&payment_id).map_err(|_| AppError::Validation("收款ID 无效".to_string()))?;
+
+    let updated = state
+        .db
+        .transaction::<_, invoice::Model, AppError>(|txn| {
+            Box::pin(async move {
+                let locked = lock_receivable_invoice(txn, &model.id).await?;
+                let existing = payment::Entity::find_by_id(&payment_id)
+                    .filter(payment::Column::InvoiceId.eq(&locked.id))
+                    .one(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("查询收款失败: {e}")))?
+                    .ok_or_else(|| AppError::NotFound("收款记录不存在".to_string()))?;
+                if existing.method == PaymentMethod::Trust {
+                    return Err(AppError::Validation("信托划转的收款请在信托账中冲正".to_string()));
+                }
+                payment::Entity::delete_by_id(existing.id)
+                    .exec(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("删除收款失败: {e}")))?;
+
+                let paid = paid_total(txn, &locked.id).await?;
+                refresh_status(txn, locked, paid, Utc::now()).await
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    Ok(Json(payments_response(&state, updated).await?))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/:id/payments", get(list_payments).post(create_payment))
+        .route("/:id/payments/:payment_id", delete(delete_payment))
+}
@@ -0,0 +1,643 @@
+//! 非案件项目路由模块（与 Web 主线 `projects-crud.ts` 口径对齐）
+//!
+//! - 列表/详情：PARTNER / ADMIN 可见全部未删除项目；其它角色仅可见自己负责或参与的项目
+//! - 创建：需 `task:create`；在事务内生成 `PRJ-{年份}-{类型码}-{序号}` 编号，并把创建者写入 OWNER 成员
+//! - 编辑/删除/成员管理：仅项目负责人与 PARTNER / ADMIN（删除为软删除并归档）
+//! - 成员可自行退出项目；项目负责人不可被移除
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{delete, get},
+    Router,
+};
+use chrono::{DateTime, Datelike, Utc};
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Statement, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::db::AppState;
+use crate::entity::project::{self, ProjectStatus, ProjectType};
+use crate::entity::project_member::{self, ProjectRole};
+use crate::entity::task::{self, TaskStatus};
+use crate::entity::user::{self, Role};
+use crate::error::{AppError, AppResult};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+use crate::security::project_access::{require_project_access, require_project_manage};
+use crate::security::validation::{require_non_empty, ValidatedJson};
+
+const MAX_INITIAL_MEMBERS: usize = 50;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectResponse {
+    pub id: String,
+    pub project_code: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    #[serde(rename = "type")]
+    pub project_type: String,
+    pub owner_id: String,
+    pub open_tasks_count: u64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectMemberResponse {
+    pub user_id: String,
+    pub name: Option<String>,
+    pub email: String,
+    pub avatar_url: Option<String>,
+    pub role: String,
+    pub joined_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDetailResponse {
+    #[serde(flatten)]
+    pub project: ProjectResponse,
+    pub members: Vec<ProjectMemberResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectListQuery {
+    pub query: Option<String>,
+    pub status: Option<String>,
+    #[serde(rename = "type")]
+    pub project_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateProjectRequest {
+    #[validate(length(max = 200))]
+    pub title: Option<String>,
+    #[validate(length(max = 10000))]
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub project_type: Option<String>,
+    pub status: Option<String>,
+    #[serde(default)]
+    pub member_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProjectRequest {
+    #[validate(length(max = 200))]
+    pub title: Option<String>,
+    #[validate(length(max = 10000))]
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub project_type: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct AddProjectMemberRequest {
+    pub user_id: Option<String>,
+    #[validate(email)]
+    pub email: Option<String>,
+    pub role: Option<String>,
+}
+
+fn parse_project_status(value: &str) -> AppResult<ProjectStatus> {
+    match value.trim().to_uppercase().as_str() {
+        "PLANNED" => Ok(ProjectStatus::Planned),
+        "ACTIVE" => Ok(ProjectStatus::Active),
+        "ON_HOLD" => Ok(ProjectStatus::OnHold),
+        "COMPLETED" => Ok(ProjectStatus::Completed),
+        "ARCHIVED" => Ok(ProjectStatus::Archived),
+        other => Err(AppError::Validation(format!("无效的项目状态: {other}"))),
+    }
+}
+
+fn parse_project_type(value: &str) -> AppResult<ProjectType> {
+    match value.trim().to_uppercase().as_str() {
+        "ADMIN" => Ok(ProjectType::Admin),
+        "HR" => Ok(ProjectType::Hr),
+        "MARKETING" => Ok(ProjectType::Marketing),
+        "IT" => Ok(ProjectType::It),
+        "BUSINESS" => Ok(ProjectType::Business),
+        "OTHER" => Ok(ProjectType::Other),
+        other => Err(AppError::Validation(format!("无效的项目类型: {other}"))),
+    }
+}
+
+fn parse_project_role(value: &str) -> AppResult<ProjectRole> {
+    match value.trim().to_uppercase().as_str() {
+        "OWNER" => Ok(ProjectRole::Owner),
+        "MEMBER" => Ok(ProjectRole::Member),
+        "VIEWER" => Ok(ProjectRole::Viewer),
+        other => Err(AppError::Validation(format!("无效的项目成员角色: {other}"))),
+    }
+}
+
+/// 在同一前缀已有项目编号的基础上生成下一个编号（按序号数值取最大值，超过 999 后字典序不再可靠）
+fn next_project_code<'a>(prefix: &str, existing: impl IntoIterator<Item = &'a str>) -> String {
+    let last_seq = existing
+        .into_iter()
+        .filter_map(|code| code.strip_prefix(prefix)?.strip_prefix('-')?.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0);
+    format!("{prefix}-{:03}", last_seq.saturating_add(1))
+}
+
+fn project_type_code(project_type: &ProjectType) -> &'static str {
+    match project_type {
+        ProjectType::Admin => "AD",
+        ProjectType::Hr => "HR",
+        ProjectType::Marketing => "MK",
+        ProjectType::It => "IT",
+        ProjectType::Business => "BZ",
+        ProjectType::Other => "OT",
+    }
+}
+
+async fn open_tasks_count(state: &AppState, project_id: &str) -> AppResult<u64> {
+    task::Entity::find()
+        .filter(task::Column::ProjectId.eq(project_id))
+        .filter(task::Column::Status.ne(TaskStatus::Done))
+        .count(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("统计项目任务失败: {e}")))
+}
+
+async fn to_response(state: &AppState, model: project::Model) -> AppResult<ProjectResponse> {
+    let open_tasks_count = open_tasks_count(state, &model.id).await?;
+    Ok(ProjectResponse {
+        id: model.id,
+        project_code: model.project_code,
+        title: model.title,
+        description: model.description,
+        status: model.status.to_value(),
+        project_type: model.project_type.to_value(),
+        owner_id: model.owner_id,
+        open_tasks_count,
+        created_at: model.created_at,
+        updated_at: model.updated_at,
+    })
+}
+
+async fn list_members(state: &AppState, project_id: &str) -> AppResult<Vec<ProjectMemberResponse>> {
+    let members = project_member::Entity::find()
+        .filter(project_member::Column::ProjectId.eq(project_id))
+        .order_by_asc(project_member::Column::JoinedAt)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询项目成员失败: {e}")))?;
+
+    let user_ids: Vec<String> = members.iter().map(|m| m.user_id.clone()).collect();
+    let users = if user_ids.is_empty() {
+        Vec::new()
+    } else {
+        user::Entity::find()
+            .filter(user::Column::Id.is_in(user_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+    };
+
+    Ok(members
+        .into_iter()
+        .filter_map(|m| {
+            let u = users.iter().find(|u| u.id == m.user_id)?;
+            Some(ProjectMemberResponse {
+                user_id: m.user_id,
+                name: u.name.clone(),
+                email: u.email.clone(),
+                avatar_url: u.avatar_url.clone(),
+                role: m.role.to_value(),
+                joined_at: m.joined_at,
+            })
+        })
+        .collect())
+}
+
+async fn project_detail(state: &AppState, model: project::Model) -> AppResult<ProjectDetailResponse> {
+    let members = list_members(state, &model.id).await?;
+    Ok(ProjectDetailResponse { project: to_response(state, model).await?, members })
+}
+
+/// GET /api/v1/projects
+async fn list_projects(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<ProjectListQuery>,
+) -> AppResult<Json<Vec<ProjectResponse>>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::TaskView)?;
+
+    let mut select = project::Entity::find()
+        .filter(project::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .filter(project::Column::DeletedAt.is_null())
+        .order_by_desc(project::Column::UpdatedAt);
+
+    if !matches!(role, Role::Partner | Role::Admin) {
+        let member_project_ids: Vec<String> = project_member::Entity::find()
+            .filter(project_member::Column::UserId.eq(current_user.id()))
+            .select_only()
+            .column(project_member::Column::ProjectId)
+            .into_values::<String, project_member::Column>()
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询项目成员失败: {e}")))?;
+
+        let mut visibility = Condition::any().add(project::Column::OwnerId.eq(current_user.id()));
+        if !member_project_ids.is_empty() {
+            visibility = visibility.add(project::Column::Id.is_in(member_project_ids));
+        }
+        select = select.filter(visibility);
+    }
+
+    if let Some(status) = query.status.as_deref() {
+        select = select.filter(project::Column::Status.eq(parse_project_status(status)?));
+    }
+    if let Some(project_type) = query.project_type.as_deref() {
+        select = select.filter(project::Column::ProjectType.eq(parse_project_type(project_type)?));
+    }
+    if let Some(q) = query.query.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        select = select.filter(
+            Condition::any()
+                .add(project::Column::Title.contains(q))
+                .add(project::Column::ProjectCode.contains(q)),
+        );
+    }
+
+    let models = select
+        .limit(200)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询项目失败: {e}")))?;
+
+    let mut data = Vec::with_capacity(models.len());
+    for model in models {
+        data.push(to_response(&state, model).await?);
+    }
+    Ok(Json(data))
+}
+
+/// POST /api/v1/projects
+async fn create_project(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(req): ValidatedJson<CreateProjectRequest>,
+) -> AppResult<Json<ProjectDetailResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::TaskCreate)?;
+
+    let title = match req.title.as_deref() {
+        Some(title) => require_non_empty(title, "title", 200)?,
+        None => "未命名项目".to_string(),
+    };
+    let description = req.description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+    let project_type = req.project_type.as_deref().map(parse_project_type).transpose()?.unwrap_or(ProjectType::Other);
+    let status = req.status.as_deref().map(parse_project_status).transpose()?.unwrap_or(ProjectStatus::Active);
+
+    let tenant_id = current_user.model.active_tenant_id.clone();
+    let owner_id = current_user.id().to_string();
+
+    let mut member_ids: Vec<String> = Vec::new();
+    for id in req.member_ids {
+        if Uuid::parse_str(&id).is_err() {
+            return Err(AppError::Validation(format!("无效的成员ID: {id}")));
+        }
+        if id != owner_id && !member_ids.contains(&id) {
+            member_ids.push(id);
+        }
+    }
+    if member_ids.len() > MAX_INITIAL_MEMBERS {
+        return Err(AppError::Validation(format!("创建项目时最多添加 {MAX_INITIAL_MEMBERS} 名成员")));
+    }
+    if !member_ids.is_empty() {
+        let found = user::Entity::find()
+            .filter(user::Column::Id.is_in(member_ids.clone()))
+            .filter(user::Column::TenantId.eq(&tenant_id))
+            .filter(user::Column::IsActive.eq(true))
+            .count(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询项目成员失败: {e}")))?;
+        if found != member_ids.len() as u64 {
+            return Err(AppError::Validation("项目成员不存在或不在当前租户".to_string()));
+        }
+    }
+
+    let created = state
+        .db
+        .transaction::<_, project::Model, AppError>(|txn| {
+            Box::pin(async move {
+                let now = Utc::now();
+                txn.execute(Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    "SELECT pg_advisory_xact_lock(hashtext($1))",
+                    [format!("project-code:{tenant_id}").into()],
+                ))
+                .await
+                .map_err(|e| AppError::Database(format!("获取项目编号锁失败: {e}")))?;
+
+                let prefix = format!("PRJ-{}-{}", now.year(), project_type_code(&project_type));
+                let existing: Vec<String> = project::Entity::find()
+                    .filter(project::Column::TenantId.eq(&tenant_id))
+                    .filter(project::Column::ProjectCode.starts_with(format!("{prefix}-")))
+                    .select_only()
+                    .column(project::Column::ProjectCode)
+                    .into_tuple()
+                    .all(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("生成项目编号失败: {e}")))?;
+                let project_code = next_project_code(&prefix, existing.iter().map(String::as_str));
+
+                let created = project::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    tenant_id: sea_orm::ActiveValue::Set(tenant_id),
+                    project_code: sea_orm::ActiveValue::Set(project_code),
+                    title: sea_orm::ActiveValue::Set(title),
+                    description: sea_orm::ActiveValue::Set(description),
+                    status: sea_orm::ActiveValue::Set(status),
+                    project_type: sea_orm::ActiveValue::Set(project_type),
+                    owner_id: sea_orm::ActiveValue::Set(owner_id.clone()),
+                    deleted_at: sea_orm::ActiveValue::Set(None),
+                    deleted_by_id: sea_orm::ActiveValue::Set(None),
+                    created_at: sea_orm::ActiveValue::Set(now),
+                    updated_at: sea_orm::ActiveValue::Set(now),
+                }
+                .insert(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("创建项目失败: {e}")))?;
+
+                let memberships = std::iter::once((owner_id, ProjectRole::Owner))
+                    .chain(member_ids.into_iter().map(|id| (id, ProjectRole::Member)));
+                for (user_id, role) in memberships {
+                    project_member::ActiveModel {
+                        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                        project_id: sea_orm::ActiveValue::Set(created.id.clone()),
+                        user_id: sea_orm::ActiveValue::Set(user_id),
+                        role: sea_orm::ActiveValue::Set(role),
+                        joined_at: sea_orm::ActiveValue::Set(now),
+                    }
+                    .insert(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("添加项目成员失败: {e}")))?;
+                }
+
+                Ok(created)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    Ok(Json(project_detail(&state, created).await?))
+}
+
+/// GET /api/v1/projects/:id
+async fn get_project(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> AppResult<Json<ProjectDetailResponse>> {
+    let model = require_project_access(
+        &state,
+        &id,
+        current_user.id(),
+        &current_user.model.active_tenant_id,
+        current_user.model.role.clone(),
+        Permission::TaskView,
+    )
+    .await?;
+    Ok(Json(project_detail(&state, model).await?))
+}
+
+/// PATCH /api/v1/projects/:id
+async fn update_project(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+    ValidatedJson(req): ValidatedJson<UpdateProjectRequest>,
+) -> AppResult<Json<ProjectDetailResponse>> {
+    let existing = require_project_manage(
+        &state,
+        &id,
+        current_user.id(),
+        &current_user.model.active_tenant_id,
+        current_user.model.role.clone(),
+    )
+    .await?;
+
+    let mut active: project::ActiveModel = existing.into();
+    if let Some(title) = req.title.as_deref() {
+        active.title = sea_orm::ActiveValue::Set(require_non_empty(title, "title", 200)?);
+    }
+    if let Some(description) = req.description {
+        let description = description.trim().to_string();
+        active.description = sea_orm::ActiveValue::Set((!description.is_empty()).then_some(description));
+    }
+    if let Some(project_type) = req.project_type.as_deref() {
+        active.project_type = sea_orm::ActiveValue::Set(parse_project_type(project_type)?);
+    }
+    if let Some(status) = req.status.as_deref() {
+        active.status = sea_orm::ActiveValue::Set(parse_project_status(status)?);
+    }
+    active.updated_at = sea_orm::ActiveValue::Set(Utc::now());
+
+    let updated = active
+        .update(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("更新项目失败: {e}")))?;
+
+    Ok(Json(project_detail(&state, updated).await?))
+}
+
+/// DELETE /api/v1/projects/:id
+///
+/// 软删除：写入 deletedAt/deletedById 并归档，项目下任务保留。
+async fn delete_project(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> AppResult<Json<serde_json::Value>> {
+    let existing = require_project_manage(
+        &state,
+        &id,
+        current_user.id(),
+        &current_user.model.active_tenant_id,
+        current_user.model.role.clone(),
+    )
+    .await?;
+
+    let now = Utc::now();
+    let mut active: project::ActiveModel = existing.into();
+    active.deleted_at = sea_orm::ActiveValue::Set(Some(now));
+    active.deleted_by_id = sea_orm::ActiveValue::Set(Some(current_user.id().to_string()));
+    active.status = sea_orm::ActiveValue::Set(ProjectStatus::Archived);
+    active.updated_at = sea_orm::ActiveValue::Set(now);
+    active
+        .update(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("删除项目失败: {e}")))?;
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// GET /api/v1/projects/:id/members
+async fn get_project_members(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> AppResult<Json<Vec<ProjectMemberResponse>>> {
+    require_project_access(
+        &state,
+        &id,
+        current_user.id(),
+        &current_user.model.active_tenant_id,
+        current_user.model.role.clone(),
+        Permission::TaskView,
+    )
+    .await?;
+    Ok(Json(list_members(&state, &id).await?))
+}
+
+/// POST /api/v1/projects/:id/members
+///
+/// 按 userId 或 email 添加成员；已是成员时更新其角色。
+async fn add_project_member(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+    ValidatedJson(req): ValidatedJson<AddProjectMemberRequest>,
+) -> AppResult<Json<Vec<ProjectMemberResponse>>> {
+    let project_model = require_project_manage(
+        &state,
+        &id,
+        current_user.id(),
+        &current_user.model.active_tenant_id,
+        current_user.model.role.clone(),
+    )
+    .await?;
+
+    let role = req.role.as_deref().map(parse_project_role).transpose()?.unwrap_or(ProjectRole::Member);
+
+    let mut select = user::Entity::find()
+        .filter(user::Column::TenantId.eq(&project_model.tenant_id))
+        .filter(user::Column::IsActive.eq(true));
+    select = match (req.user_id.as_deref(), req.email.as_deref()) {
+        (Some(user_id), _) => select.filter(user::Column::Id.eq(user_id)),
+        (None, Some(email)) => select.filter(user::Column::Email.eq(email.trim().to_lowercase())),
+        (None, None) => return Err(AppError::Validation("userId 与 email 至少提供一个".to_string())),
+    };
+    let target = select
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("用户不存在或不在当前租户".to_string()))?;
+
+    if target.id == project_model.owner_id && role != ProjectRole::Owner {
+        return Err(AppError::Validation("不能修改项目负责人的成员角色".to_string()));
+    }
+
+    let existing = project_member::Entity::find()
+        .filter(project_member::Column::ProjectId.eq(&project_model.id))
+        .filter(project_member::Column::UserId.eq(&target.id))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询项目成员失败: {e}")))?;
+
+    match existing {
+        Some(member) => {
+            let mut active: project_member::ActiveModel = member.into();
+            active.role = sea_orm::ActiveValue::Set(role);
+            active.update(&state.db).await
+        }
+        None => {
+            project_member::ActiveModel {
+                id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                project_id: sea_orm::ActiveValue::Set(project_model.id.clone()),
+                user_id: sea_orm::ActiveValue::Set(target.id.clone()),
+                role: sea_orm::ActiveValue::Set(role),
+                joined_at: sea_orm::ActiveValue::Set(Utc::now()),
+            }
+            .insert(&state.db)
+            .await
+        }
+    }
+    .map_err(|e| AppError::Database(format!("保存项目成员失败: {e}")))?;
+
+    Ok(Json(list_members(&state, &project_model.id).await?))
+}
+
+/// DELETE /api/v1/projects/:id/members/:user_id
+async fn remove_project_member(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path((id, user_id)): Path<(String, String)>,
+) -> AppResult<Json<Vec<ProjectMemberResponse>>> {
+    let project_model = if user_id == current_user.id() {
+        require_project_access(
+            &state,
+            &id,
+            current_user.id(),
+            &current_user.model.active_tenant_id,
+            current_user.model.role.clone(),
+            Permission::TaskView,
+        )
+        .await?
+    } else {
+        require_project_manage(
+            &state,
+            &id,
+            current_user.id(),
+            &current_user.model.active_tenant_id,
+            current_user.model.role.clone(),
+        )
+        .await?
+    };
+
+    if user_id == project_model.owner_id {
+        return Err(AppError::Validation("不能移除项目负责人".to_string()));
+    }
+
+    let result = project_member::Entity::delete_many()
+        .filter(project_member::Column::ProjectId.eq(&project_model.id))
+        .filter(project_member::Column::UserId.eq(&user_id))
+        .exec(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("移除项目成员失败: {e}")))?;
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound("项目成员不存在".to_string()));
+    }
+
+    Ok(Json(list_members(&state, &project_model.id).await?))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_projects).post(create_project))
+        .route("/:id", get(get_project).patch(update_project).delete(delete_project))
+        .route("/:id/members", get(get_project_members).post(add_project_member))
+        .route("/:id/members/:user_id", delete(remove_project_member))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_code_sequence_is_numeric_past_999() {
+        assert_eq!(next_project_code("PRJ-2026-IT", []), "PRJ-2026-IT-001");
+        assert_eq!(next_project_code("PRJ-2026-IT", ["PRJ-2026-IT-999", "PRJ-2026-IT-1000"]), "PRJ-2026-IT-1001");
+        assert_eq!(next_project_code("PRJ-2026-IT", ["PRJ-2026-IT-X"]), "PRJ-2026-IT-001");
+    }
+}
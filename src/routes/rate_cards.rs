@@ -0,0 +1,527 @@
+//! 费率卡路由（合并到 `/billing`）
+//!
+//! - 优先级：任务级覆盖 > 案件内指定律师 > 案件内指定角色 > 客户协议费率 > 用户默认小时费率
+//! - 查看需 `billing:view`；新增/修改/删除需 `billing:edit`，案件/任务范围还需可见对应案件
+//! - 同一范围（同一任务 / 案件+律师 / 案件+角色 / 租户内客户）至多一张费率卡，重复创建返回 409；范围与对象创建后不可改
+//! - `GET /billing/rate-cards/preview`：预览某律师在某案件/任务下将适用的费率及来源
+//! - 费率在停止计时/补录时快照到工时记录，修改费率卡不追溯已有工时
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{get, patch},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::Decimal;
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Statement,
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::billing::rates::{RateCandidates, RateSource};
+use crate::db::AppState;
+use crate::entity::rate_card::{self, RateCardScope};
+use crate::entity::{case, task, user};
+use crate::error::{AppError, AppResult};
+use crate::security::case_access::require_case_access;
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{parse_role, require_permission, Permission};
+use crate::security::validation::ValidatedJson;
+
+const MAX_RATE_CARDS: u64 = 500;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRateCardsQuery {
+    pub scope: Option<String>,
+    pub case_id: Option<String>,
+    pub client_id: Option<String>,
+    pub user_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRateCardRequest {
+    /// `TASK` | `CASE_USER` | `CASE_ROLE` | `CLIENT`
+    pub scope: String,
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub task_id: Option<String>,
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub case_id: Option<String>,
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub user_id: Option<String>,
+    pub role: Option<String>,
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub client_id: Option<String>,
+    /// 小时费率（Decimal 字符串）
+    pub hourly_rate: String,
+    #[validate(length(max = 500, message = "note 长度不合法"))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRateCardRequest {
+    pub hourly_rate: Option<String>,
+    #[validate(length(max = 500, message = "note 长度不合法"))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RatePreviewQuery {
+    /// 缺省为当前用户
+    pub user_id: Option<String>,
+    pub case_id: Option<String>,
+    pub task_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateCardResponse {
+    pub id: String,
+    pub scope: String,
+    pub task_id: Option<String>,
+    pub case_id: Option<String>,
+    pub user_id: Option<String>,
+    pub role: Option<String>,
+    pub client_id: Option<String>,
+    pub hourly_rate: String,
+    pub note: Option<String>,
+    pub created_by_id: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<rate_card::Model> for RateCardResponse {
+    fn from(m: rate_card::Model) -> Self {
+        Self {
+            id: m.id,
+            scope: m.scope.to_value(),
+            task_id: m.task_id,
+            case_id: m.case_id,
+            user_id: m.user_id,
+            role: m.role.map(|r| r.to_value()),
+            client_id: m.client_id,
+            hourly_rate: m.hourly_rate.to_string(),
+            note: m.note,
+            created_by_id: m.created_by_id,
+            created_at: m.created_at,
+            updated_at: m.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RatePreviewResponse {
+    pub user_id: String,
+    pub case_id: Option<String>,
+    pub task_id: Option<String>,
+    pub hourly_rate: String,
+    /// TASK | CASE_USER | CASE_ROLE | CLIENT | USER_DEFAULT
+    pub source: &'static str,
+    pub rate_card_id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ResolvedRate {
+    pub rate: Decimal,
+    pub source: RateSource,
+    pub rate_card_id: Option<String>,
+}
+
+fn parse_scope(raw: &str) -> AppResult<RateCardScope> {
+    match raw.trim() {
+        "TASK" => Ok(RateCardScope::Task),
+        "CASE_USER" => Ok(RateCardScope::CaseUser),
+        "CASE_ROLE" => Ok(RateCardScope::CaseRole),
+        "CLIENT" => Ok(RateCardScope::Client),
+        other => Err(AppError::Validation(format!("scope 无效: {other}"))),
+    }
+}
+
+fn parse_hourly_rate(raw: &str) -> AppResult<Decimal> {
+    let rate = Decimal::from_str(raw.trim()).map_err(|_| AppError::Validation("hourlyRate 无效".to_string()))?;
+    if rate.is_sign_negative() {
+        return Err(AppError::Validation("hourlyRate 不能为负数".to_string()));
+    }
+    Ok(rate)
+}
+
+/// 解析某律师在指定案件/任务下适用的小时费率（任务所属案件优先于传入的 caseId）
+pub(crate) async fn resolve_billing_rate<C: ConnectionTrait>(
+    db: &C,
+    user_model: &user::Model,
+    case_id: Option<&str>,
+    task_id: Option<&str>,
+) -> AppResult<ResolvedRate> {
+    let mut case_id = case_id.map(str::to_string);
+    if let Some(task_id) = task_id {
+        let task_case_id = task::Entity::find_by_id(task_id)
+            .select_only()
+            .column(task::Column::CaseId)
+            .into_tuple::<Option<String>>()
+            .one(db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
+            .flatten();
+        if task_case_id.is_some() {
+            case_id = task_case_id;
+        }
+    }
+
+    let case_model = match case_id.as_deref() {
+        Some(case_id) => case::Entity::find_by_id(case_id)
+            .one(db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询案件失败: {e}")))?,
+        None => None,
+    };
+
+    let mut scope = Condition::any();
+    if let Some(task_id) = task_id {
+        scope = scope.add(rate_card::Column::TaskId.eq(task_id));
+    }
+    if let Some(case_model) = &case_model {
+        scope = scope
+            .add(
+                Condition::all()
+                    .add(rate_card::Column::CaseId.eq(&case_model.id))
+                    .add(rate_card::Column::UserId.eq(&user_model.id)),
+            )
+            .add(
+                Condition::all()
+                    .add(rate_card::Column::CaseId.eq(&case_model.id))
+                    .add(rate_card::Column::Role.eq(user_model.role.clone())),
+            )
+            .add(rate_card::Column::ClientId.eq(&case_model.client_id));
+    }
+
+    let cards = if scope.is_empty() {
+        Vec::new()
+    } else {
+        let mut select = rate_card::Entity::find().filter(scope);
+        if let Some(case_model) = &case_model {
+            select = select.filter(rate_card::Column::TenantId.eq(&case_model.tenant_id));
+        }
+        select.all(db).await.map_err(|e| AppError::Database(format!("查询费率卡失败: {e}")))?
+    };
+
+    let card_of = |scope: RateCardScope| cards.iter().find(|c| c.scope == scope);
+    let candidates = RateCandidates {
+        task: card_of(RateCardScope::Task).map(|c| c.hourly_rate),
+        case_user: card_of(RateCardScope::CaseUser).map(|c| c.hourly_rate),
+        case_role: card_of(RateCardScope::CaseRole).map(|c| c.hourly_rate),
+        client: card_of(RateCardScope::Client).map(|c| c.hourly_rate),
+    };
+    let (rate, source) = candidates.pick(user_model.hourly_rate);
+    let rate_card_id = match source {
+        RateSource::Task => card_of(RateCardScope::Task),
+        RateSource::CaseUser => card_of(RateCardScope::CaseUser),
+        RateSource::CaseRole => card_of(RateCardScope::CaseRole),
+        RateSource::Client => card_of(RateCardScope::Client),
+        RateSource::UserDefault => None,
+    }
+    .map(|c| c.id.clone());
+
+    Ok(ResolvedRate { rate, source, rate_card_id })
+}
+
+/// 费率卡绑定案件（含任务所属案件）时校验案件可见性
+async fn require_rate_card_access(
+    state: &AppState,
+    current_user: &CurrentUser,
+    model: &rate_card::Model,
+) -> AppResult<()> {
+    let case_id = match (&model.case_id, &model.task_id) {
+        (Some(case_id), _) => Some(case_id.clone()),
+        (None, Some(task_id)) => task::Entity::find_by_id(task_id)
+            .one(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
+            .and_then(|t| t.case_id),
+        (None, None) => None,
+    };
+    if let Some(case_id) = case_id {
+        require_case_access(state, &case_id, current_user.id(), current_user.model.role.clone(), Permission::CaseView)
+            .await?;
+    }
+    Ok(())
+}
+
+/// 按 ID 查找当前租户的费率卡（其它租户的视为不存在）
+async fn find_rate_card(state: &AppState, tenant_id: &str, id: &str) -> AppResult<rate_card::Model> {
+    Uuid::parse_str(id).map_err(|_| AppError::Validation("rateCardId 无效".to_string()))?;
+    rate_card::Entity::find_by_id(id)
+        .filter(rate_card::Column::TenantId.eq(tenant_id))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询费率卡失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("费率卡不存在".to_string()))
+}
+
+/// GET /api/v1/billing/rate-cards
+async fn list_rate_cards(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<ListRateCardsQuery>,
+) -> AppResult<Json<Vec<RateCardResponse>>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingView)?;
+
+    let mut select = rate_card::Entity::find()
+        .filter(rate_card::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .order_by_desc(rate_card::Column::UpdatedAt);
+    if let Some(scope) = query.scope.as_deref() {
+        select = select.filter(rate_card::Column::Scope.eq(parse_scope(scope)?));
+    }
+    if let Some(case_id) = query.case_id.as_deref() {
+        select = select.filter(rate_card::Column::CaseId.eq(case_id));
+    }
+    if let Some(client_id) = query.client_id.as_deref() {
+        select = select.filter(rate_card::Column::ClientId.eq(client_id));
+    }
+    if let Some(user_id) = query.user_id.as_deref() {
+        select = select.filter(rate_card::Column::UserId.eq(user_id));
+    }
+
+    let cards = select
+        .limit(MAX_RATE_CARDS)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询费率卡失败: {e}")))?;
+    Ok(Json(cards.into_iter().map(RateCardResponse::from).collect()))
+}
+
+/// POST /api/v1/billing/rate-cards
+async fn create_rate_card(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(payload): ValidatedJson<CreateRateCardRequest>,
+) -> AppResult<Json<RateCardResponse>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::BillingEdit)?;
+
+    let scope = parse_scope(&payload.scope)?;
+    let hourly_rate = parse_hourly_rate(&payload.hourly_rate)?;
+
+    let (present, expected): (Vec<&str>, &[&str]) = (
+        [
+            payload.task_id.as_ref().map(|_| "taskId"),
+            payload.case_id.as_ref().map(|_| "caseId"),
+            payload.user_id.as_ref().map(|_| "userId"),
+            payload.role.as_ref().map(|_| "role"),
+            payload.client_id.as_ref().map(|_| "clientId"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        match scope {
+            RateCardScope::Task => &["taskId"],
+            RateCardScope::CaseUser => &["caseId", "userId"],
+            RateCardScope::CaseRole => &["caseId", "role"],
+            RateCardScope::Client => &["clientId"],
+        },
+    );
+    if present != expected {
+        return Err(AppError::Validation(format!("scope={} 需且仅需提供 {}", payload.scope.trim(), expected.join(", "))));
+    }
+
+    // 费率卡归属调用方当前租户；案件 / 客户须属于该租户
+    let tenant_id = current_user.model.active_tenant_id.clone();
+    let mut card_role = None;
+    let mut existing = rate_card::Entity::find().filter(rate_card::Column::TenantId.eq(&tenant_id));
+    let case_tenant_id = match scope {
+        RateCardScope::Task => {
+            let task_id = payload.task_id.as_deref().unwrap_or_default();
+            let task_model = task::Entity::find_by_id(task_id)
+                .one(&state.db)
+                .await
+                .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
+                .ok_or_else(|| AppError::NotFound("任务不存在".to_string()))?;
+            let case_id =
+                task_model.case_id.ok_or_else(|| AppError::Validation("项目任务不支持费率卡".to_string()))?;
+            let case_model =
+                require_case_access(&state, &case_id, current_user.id(), role.clone(), Permission::CaseView).await?;
+            existing = existing.filter(rate_card::Column::TaskId.eq(task_id));
+            Some(case_model.tenant_id)
+        }
+        RateCardScope::CaseUser | RateCardScope::CaseRole => {
+            let case_id = payload.case_id.as_deref().unwrap_or_default();
+            let case_model =
+                require_case_access(&state, case_id, current_user.id(), role.clone(), Permission::CaseView).await?;
+            existing = existing.filter(rate_card::Column::CaseId.eq(case_id));
+            if let Some(user_id) = payload.user_id.as_deref() {
+                user::Entity::find_by_id(user_id)
+                    .one(&state.db)
+                    .await
+                    .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+                    .ok_or_else(|| AppError::NotFound("用户不存在".to_string()))?;
+                existing = existing.filter(rate_card::Column::UserId.eq(user_id));
+            }
+            if let Some(raw) = payload.role.as_deref() {
+                let parsed =
+                    parse_role(raw.trim()).ok_or_else(|| AppError::Validation(format!("role 无效: {raw}")))?;
+                existing = existing.filter(rate_card::Column::Role.eq(parsed.clone()));
+                card_role = Some(parsed);
+            }
+            Some(case_model.tenant_id)
+        }
+        RateCardScope::Client => {
+            let client_id = payload.client_id.as_deref().unwrap_or_default();
+            state
+                .db
+                .query_one(Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    r#"SELECT 1 FROM "Contact" WHERE id = $1 AND "tenantId" = $2 AND "deletedAt" IS NULL LIMIT 1"#,
+                    [client_id.into(), tenant_id.as_str().into()],
+                ))
+                .await
+                .map_err(|e| AppError::Database(format!("查询客户失败: {e}")))?
+                .ok_or_else(|| AppError::NotFound("客户不存在".to_string()))?;
+            existing = existing.filter(rate_card::Column::ClientId.eq(client_id));
+            None
+        }
+    };
+    if case_tenant_id.is_some_and(|id| id != tenant_id) {
+        return Err(AppError::NotFound("案件不存在".to_string()));
+    }
+
+    let duplicate = existing
+        .filter(rate_card::Column::Scope.eq(scope))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询费率卡失败: {e}")))?;
+    if duplicate.is_some() {
+        return Err(AppError::Conflict("该范围已存在费率卡，请直接修改".to_string()));
+    }
+
+    let now = Utc::now();
+    let inserted = rate_card::ActiveModel {
+        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+        tenant_id: sea_orm::ActiveValue::Set(tenant_id),
+        scope: sea_orm::ActiveValue::Set(scope),
+        task_id: sea_orm::ActiveValue::Set(payload.task_id.clone()),
+        case_id: sea_orm::ActiveValue::Set(payload.case_id.clone()),
+        user_id: sea_orm::ActiveValue::Set(payload.user_id.clone()),
+        role: sea_orm::ActiveValue::Set(card_role),
+        client_id: sea_orm::ActiveValue::Set(payload.client_id.clone()),
+        hourly_rate: sea_orm::ActiveValue::Set(hourly_rate),
+        note: sea_orm::ActiveValue::Set(
+            payload.note.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string),
+        ),
+        created_by_id: sea_orm::ActiveValue::Set(current_user.id().to_string()),
+        created_at: sea_orm::ActiveValue::Set(now),
+        updated_at: sea_orm::ActiveValue::Set(now),
+    }
+    .insert(&state.db)
+    .await
+    .map_err(|e| match e.sql_err() {
+        // 并发创建同一范围时由唯一索引兜底
+        Some(sea_orm::SqlErr::UniqueConstraintViolation(_)) => {
+            AppError::Conflict("该范围已存在费率卡，请直接修改".to_string())
+        }
+        _ => AppError::Database(format!("创建费率卡失败: {e}")),
+    })?;
+
+    Ok(Json(RateCardResponse::from(inserted)))
+}
+
+/// PATCH /api/v1/billing/rate-cards/:id
+async fn update_rate_card(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<UpdateRateCardRequest>,
+) -> AppResult<Json<RateCardResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    let existing = find_rate_card(&state, &current_user.model.active_tenant_id, &id).await?;
+    require_rate_card_access(&state, &current_user, &existing).await?;
+
+    let mut active: rate_card::ActiveModel = existing.into();
+    if let Some(raw) = payload.hourly_rate.as_deref() {
+        active.hourly_rate = sea_orm::ActiveValue::Set(parse_hourly_rate(raw)?);
+    }
+    if let Some(note) = payload.note.as_deref() {
+        let note = note.trim();
+        active.note = sea_orm::ActiveValue::Set((!note.is_empty()).then(|| note.to_string()));
+    }
+    active.updated_at = sea_orm::ActiveValue::Set(Utc::now());
+
+    let updated = active.update(&state.db).await.map_err(|e| AppError::Database(format!("更新费率卡失败: {e}")))?;
+    Ok(Json(RateCardResponse::from(updated)))
+}
+
+/// DELETE /api/v1/billing/rate-cards/:id
+async fn delete_rate_card(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> AppResult<Json<()>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    let existing = find_rate_card(&state, &current_user.model.active_tenant_id, &id).await?;
+    require_rate_card_access(&state, &current_user, &existing).await?;
+
+    rate_card::Entity::delete_by_id(existing.id)
+        .exec(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("删除费率卡失败: {e}")))?;
+    Ok(Json(()))
+}
+
+/// GET /api/v1/billing/rate-cards/preview
+async fn preview_rate(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<RatePreviewQuery>,
+) -> AppResult<Json<RatePreviewResponse>> {
+    let role = current_user.model.role.clone();
+    let user_id = query.user_id.clone().unwrap_or_else(|| current_user.id().to_string());
+    if user_id != current_user.id() {
+        require_permission(role.clone(), Permission::BillingView)?;
+    }
+
+    let mut case_id = query.case_id.clone();
+    if let Some(task_id) = query.task_id.as_deref() {
+        let task_model = task::Entity::find_by_id(task_id)
+            .one(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
+            .ok_or_else(|| AppError::NotFound("任务不存在".to_string()))?;
+        if task_model.case_id.is_some() {
+            case_id = task_model.case_id;
+        }
+    }
+    if let Some(case_id) = case_id.as_deref() {
+        require_case_access(&state, case_id, current_user.id(), role, Permission::CaseView).await?;
+    }
+
+    let user_model = user::Entity::find_by_id(&user_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("用户不存在".to_string()))?;
+    let resolved = resolve_billing_rate(&state.db, &user_model, case_id.as_deref(), query.task_id.as_deref()).await?;
+
+    Ok(Json(RatePreviewResponse {
+        user_id,
+        case_id,
+        task_id: query.task_id,
+        hourly_rate: resolved.rate.to_string(),
+        source: resolved.source.as_str(),
+        rate_card_id: resolved.rate_card_id,
+    }))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/rate-cards", get(list_rate_cards).post(create_rate_card))
+        .route("/rate-cards/preview", get(preview_rate))
+        .route("/rate-cards/:id", patch(update_rate_card).delete(delete_rate_card))
+}
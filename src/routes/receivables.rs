@@ -0,0 +1,247 @@
+//! 应收账款账龄报表（合并到 `/invoices`）
+//!
+//! - `GET /invoices/aging?asOf=`：截至 `asOf`（默认当前时间）已开票、未收清的发票，
+//!   按开票日起算的账龄分段（0-30 / 31-60 / 61-90 / 90+）汇总未收金额
+//! - 分组：客户、承办律师（handler）、案源律师（originator），供合伙人分配讨论；未指定的归入 `id = null`
+//! - 需 `billing:approve`；非 PARTNER / ADMIN 仅统计可见案件的发票
+//! - 未收金额 = 发票总额 - `asOf` 前的收款；全程 Decimal 计算
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::Decimal;
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QuerySelect, Statement,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::billing::receivables::{AgingAmounts, AgingBucket};
+use crate::db::AppState;
+use crate::entity::invoice::{self, InvoiceStatus};
+use crate::entity::{case, payment, user};
+use crate::error::{AppError, AppResult};
+use crate::security::case_access::visible_case_ids;
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgingQuery {
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgingAmountsResponse {
+    pub days_0_30: String,
+    pub days_31_60: String,
+    pub days_61_90: String,
+    pub over_90: String,
+    pub total: String,
+}
+
+impl From<&AgingAmounts> for AgingAmountsResponse {
+    fn from(a: &AgingAmounts) -> Self {
+        Self {
+            days_0_30: a.days_0_30.to_string(),
+            days_31_60: a.days_31_60.to_string(),
+            days_61_90: a.days_61_90.to_string(),
+            over_90: a.over_90.to_string(),
+            total: a.total().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgingRow {
+    /// 客户/律师 ID；未指定为 null
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub invoice_count: u64,
+    #[serde(flatten)]
+    pub amounts: AgingAmountsResponse,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgingReportResponse {
+    pub as_of: DateTime<Utc>,
+    pub invoice_count: u64,
+    pub totals: AgingAmountsResponse,
+    pub by_client: Vec<AgingRow>,
+    pub by_handler: Vec<AgingRow>,
+    pub by_originator: Vec<AgingRow>,
+}
+
+#[derive(Default)]
+struct GroupTotals {
+    invoice_count: u64,
+    amounts: AgingAmounts,
+}
+
+type Groups = BTreeMap<Option<String>, GroupTotals>;
+
+fn add_to_group(groups: &mut Groups, key: Option<&String>, bucket: AgingBucket, amount: Decimal) {
+    let group = groups.entry(key.cloned()).or_default();
+    group.invoice_count += 1;
+    group.amounts.add(bucket, amount);
+}
+
+/// 按未收总额降序输出
+fn into_rows(groups: Groups, names: &HashMap<String, String>) -> Vec<AgingRow> {
+    let mut rows: Vec<(Decimal, AgingRow)> = groups
+        .into_iter()
+        .map(|(id, g)| {
+            let row = AgingRow {
+                name: id.as_ref().and_then(|id| names.get(id).cloned()),
+                id,
+                invoice_count: g.invoice_count,
+                amounts: AgingAmountsResponse::from(&g.amounts),
+            };
+            (g.amounts.total(), row)
+        })
+        .collect();
+    rows.sort_by_key(|(total, _)| std::cmp::Reverse(*total));
+    rows.into_iter().map(|(_, row)| row).collect()
+}
+
+pub(crate) async fn contact_names(state: &AppState, ids: Vec<String>) -> AppResult<HashMap<String, String>> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let rows = state
+        .db
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT id, name FROM "Contact" WHERE id = ANY($1)"#,
+            [ids.into()],
+        ))
+        .await
+        .map_err(|e| AppError::Database(format!("查询客户失败: {e}")))?;
+    rows.into_iter()
+        .map(|row| {
+            let id: String = row.try_get("", "id").map_err(|e| AppError::Database(format!("读取客户失败: {e}")))?;
+            let name: String =
+                row.try_get("", "name").map_err(|e| AppError::Database(format!("读取客户失败: {e}")))?;
+            Ok((id, name))
+        })
+        .collect()
+}
+
+/// GET /api/v1/invoices/aging
+async fn get_aging_report(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<AgingQuery>,
+) -> AppResult<Json<AgingReportResponse>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::BillingApprove)?;
+    let as_of = query.as_of.unwrap_or_else(Utc::now);
+
+    let mut select = invoice::Entity::find()
+        .filter(invoice::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .filter(invoice::Column::Status.is_not_in(vec![InvoiceStatus::Draft, InvoiceStatus::Cancelled]))
+        .filter(invoice::Column::IssuedAt.lte(as_of));
+    if let Some(ids) = visible_case_ids(&state, current_user.id(), &role).await? {
+        select = select.filter(invoice::Column::CaseId.is_in(ids));
+    }
+    let invoices = select
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询发票失败: {e}")))?;
+
+    let invoice_ids: Vec<String> = invoices.iter().map(|i| i.id.clone()).collect();
+    let paid: HashMap<String, Decimal> = if invoice_ids.is_empty() {
+        HashMap::new()
+    } else {
+        payment::Entity::find()
+            .filter(payment::Column::InvoiceId.is_in(invoice_ids))
+            .filter(payment::Column::ReceivedAt.lte(as_of))
+            .filter(
+                Condition::any().add(payment::Column::VoidedAt.is_null()).add(payment::Column::VoidedAt.gt(as_of)),
+            )
+            .select_only()
+            .column(payment::Column::InvoiceId)
+            .column_as(payment::Column::Amount.sum(), "total")
+            .group_by(payment::Column::InvoiceId)
+            .into_tuple::<(String, Option<Decimal>)>()
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("汇总收款失败: {e}")))?
+            .into_iter()
+            .map(|(id, total)| (id, total.unwrap_or_default()))
+            .collect()
+    };
+
+    let outstanding: Vec<(invoice::Model, Decimal)> = invoices
+        .into_iter()
+        .filter_map(|i| {
+            let due = i.total_amount - paid.get(&i.id).copied().unwrap_or_default();
+            (due > Decimal::ZERO).then_some((i, due))
+        })
+        .collect();
+
+    let case_ids: Vec<String> = outstanding.iter().filter_map(|(i, _)| i.case_id.clone()).collect();
+    let cases: HashMap<String, case::Model> = if case_ids.is_empty() {
+        HashMap::new()
+    } else {
+        case::Entity::find()
+            .filter(case::Column::Id.is_in(case_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询案件失败: {e}")))?
+            .into_iter()
+            .map(|c| (c.id.clone(), c))
+            .collect()
+    };
+
+    let mut totals = AgingAmounts::default();
+    let mut by_client = Groups::new();
+    let mut by_handler = Groups::new();
+    let mut by_originator = Groups::new();
+    for (inv, due) in &outstanding {
+        let issued = inv.issued_at.unwrap_or(inv.created_at);
+        let bucket = AgingBucket::for_age_days((as_of - issued).num_days());
+        let case_model = inv.case_id.as_ref().and_then(|id| cases.get(id));
+
+        totals.add(bucket, *due);
+        add_to_group(&mut by_client, inv.client_id.as_ref(), bucket, *due);
+        add_to_group(&mut by_handler, case_model.and_then(|c| c.handler_id.as_ref()), bucket, *due);
+        add_to_group(&mut by_originator, case_model.and_then(|c| c.originator_id.as_ref()), bucket, *due);
+    }
+
+    let client_names = contact_names(&state, by_client.keys().flatten().cloned().collect()).await?;
+    let lawyer_ids: Vec<String> = by_handler.keys().chain(by_originator.keys()).flatten().cloned().collect();
+    let lawyer_names: HashMap<String, String> = if lawyer_ids.is_empty() {
+        HashMap::new()
+    } else {
+        user::Entity::find()
+            .filter(user::Column::Id.is_in(lawyer_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+            .into_iter()
+            .map(|u| (u.id, u.name.unwrap_or(u.email)))
+            .collect()
+    };
+
+    Ok(Json(AgingReportResponse {
+        as_of,
+        invoice_count: outstanding.len() as u64,
+        totals: AgingAmountsResponse::from(&totals),
+        by_client: into_rows(by_client, &client_names),
+        by_handler: into_rows(by_handler, &lawyer_names),
+        by_originator: into_rows(by_originator, &lawyer_names),
+    }))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/aging", get(get_aging_report))
+}
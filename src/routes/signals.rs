@@ -0,0 +1,197 @@
+//! 实时信号路由模块（真实闭环）
+//!
+//! 对齐 Web 主线 `lawclick-next/src/app/api/realtime/signals/route.ts`：
+//! - 读取：`GET /signals/:kind` 返回当前租户信号快照（version/updatedAt/payload）
+//! - 长轮询：`GET /signals/:kind/wait?sinceVersion=N` 在 version > N 时立即返回，超时返回未变化
+//! - SSE：`GET /signals/:kind/stream` 推送 `signal` 事件（id=version，支持 Last-Event-ID 续传）
+//! - 权限：`task:view`；租户取当前用户的 activeTenantId
+
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Json,
+    },
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, Stream};
+use sea_orm::ActiveEnum;
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::db::AppState;
+use crate::entity::tenant_signal::{self, TenantSignalKind};
+use crate::error::{AppError, AppResult};
+use crate::realtime::tenant_signal::{find_tenant_signal, parse_signal_kind};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+
+const LONG_POLL_INTERVAL_MS: u64 = 1000;
+const LONG_POLL_DEFAULT_TIMEOUT_MS: u64 = 25_000;
+const LONG_POLL_MAX_TIMEOUT_MS: u64 = 30_000;
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalWaitQuery {
+    pub since_version: Option<i32>,
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalStreamQuery {
+    pub since_version: Option<i32>,
+    pub poll_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalResponse {
+    pub tenant_id: String,
+    pub kind: String,
+    pub version: i32,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub payload: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalWaitResponse {
+    pub changed: bool,
+    #[serde(flatten)]
+    pub signal: SignalResponse,
+}
+
+fn to_signal_response(tenant_id: &str, kind: &TenantSignalKind, model: Option<tenant_signal::Model>) -> SignalResponse {
+    match model {
+        Some(m) => SignalResponse {
+            tenant_id: m.tenant_id,
+            kind: m.kind.to_value(),
+            version: m.version,
+            updated_at: Some(m.updated_at),
+            payload: m.payload,
+        },
+        None => SignalResponse {
+            tenant_id: tenant_id.to_string(),
+            kind: kind.to_value(),
+            version: 0,
+            updated_at: None,
+            payload: None,
+        },
+    }
+}
+
+fn parse_last_event_id(headers: &HeaderMap) -> Option<i32> {
+    headers
+        .get("Last-Event-ID")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<i32>().ok())
+        .filter(|v| *v >= 0)
+}
+
+async fn get_signal(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(kind): Path<String>,
+) -> AppResult<Json<SignalResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::TaskView)?;
+    let kind = parse_signal_kind(&kind)?;
+    let tenant_id = current_user.model.active_tenant_id.clone();
+
+    let model = find_tenant_signal(&state.db, &tenant_id, kind.clone()).await?;
+    Ok(Json(to_signal_response(&tenant_id, &kind, model)))
+}
+
+async fn wait_signal(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(kind): Path<String>,
+    Query(query): Query<SignalWaitQuery>,
+) -> AppResult<Json<SignalWaitResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::TaskView)?;
+    let kind = parse_signal_kind(&kind)?;
+    let tenant_id = current_user.model.active_tenant_id.clone();
+
+    let since_version = query.since_version.unwrap_or(0);
+    if since_version < 0 {
+        return Err(AppError::Validation("sinceVersion 不能为负".to_string()));
+    }
+    let timeout_ms = query
+        .timeout_ms
+        .unwrap_or(LONG_POLL_DEFAULT_TIMEOUT_MS)
+        .clamp(LONG_POLL_INTERVAL_MS, LONG_POLL_MAX_TIMEOUT_MS);
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(timeout_ms);
+
+    loop {
+        let model = find_tenant_signal(&state.db, &tenant_id, kind.clone()).await?;
+        let signal = to_signal_response(&tenant_id, &kind, model);
+        if signal.version > since_version {
+            return Ok(Json(SignalWaitResponse { changed: true, signal }));
+        }
+
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            return Ok(Json(SignalWaitResponse { changed: false, signal }));
+        }
+        let wait = Duration::from_millis(LONG_POLL_INTERVAL_MS).min(deadline - now);
+        tokio::time::sleep(wait).await;
+    }
+}
+
+async fn stream_signal(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(kind): Path<String>,
+    Query(query): Query<SignalStreamQuery>,
+    headers: HeaderMap,
+) -> AppResult<Sse<impl Stream<Item = Result<Event, Infallible>>>> {
+    require_permission(current_user.model.role.clone(), Permission::TaskView)?;
+    let kind = parse_signal_kind(&kind)?;
+    let tenant_id = current_user.model.active_tenant_id.clone();
+
+    // Last-Event-ID（断线重连）优先于 query.sinceVersion
+    let since_version = parse_last_event_id(&headers).or(query.since_version).unwrap_or(0).max(0);
+    let poll = Duration::from_millis(query.poll_ms.unwrap_or(3000).clamp(1000, 10_000));
+
+    let events = stream::unfold(since_version, move |last_version| {
+        let state = state.clone();
+        let tenant_id = tenant_id.clone();
+        let kind = kind.clone();
+        async move {
+            loop {
+                match find_tenant_signal(&state.db, &tenant_id, kind.clone()).await {
+                    Ok(model) => {
+                        let signal = to_signal_response(&tenant_id, &kind, model);
+                        if signal.version > last_version {
+                            let version = signal.version;
+                            let event = Event::default()
+                                .event("signal")
+                                .id(version.to_string())
+                                .json_data(&signal)
+                                .unwrap_or_else(|_| Event::default().event("signal").id(version.to_string()));
+                            return Some((Ok(event), version));
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!("实时信号轮询失败: {e}");
+                    }
+                }
+                tokio::time::sleep(poll).await;
+            }
+        }
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::new().interval(Duration::from_secs(15)).text("ping")))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/:kind", get(get_signal))
+        .route("/:kind/wait", get(wait_signal))
+        .route("/:kind/stream", get(stream_signal))
+}
@@ -0,0 +1,343 @@
+//! 任务批量操作路由模块
+//!
+//! `POST /tasks/bulk`：对一组任务执行同一操作（指派/状态/优先级/截止日期/泳道/删除）
+//! - 权限：功能权限校验一次；可见性按去重后的案件/项目各校验一次
+//! - 事务：所有可执行条目在同一事务内提交，并按租户递增一次 `TenantSignal(TASKS_CHANGED)`
+//! - 结果：逐条返回成功/失败（不存在、无权限、被前置任务阻塞等不影响其它条目）
+
+use axum::{extract::State, response::Json, routing::post, Router};
+use chrono::{DateTime, Utc};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::db::AppState;
+use crate::entity::task;
+use crate::entity::tenant_signal::TenantSignalKind;
+use crate::error::{AppError, AppResult};
+use crate::realtime::tenant_signal::touch_tenant_signal;
+use crate::routes::tasks::{
+    next_task_order, parse_task_priority, parse_task_status, require_task_owner_access, TaskOwner,
+};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+use crate::security::validation::ValidatedJson;
+
+const BULK_MAX_TASKS: usize = 200;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE", rename_all_fields = "camelCase")]
+pub enum BulkOperation {
+    /// assigneeId 为空表示取消指派
+    Assign { assignee_id: Option<String> },
+    SetStatus { status: String },
+    SetPriority { priority: String },
+    /// dueDate 为空表示清除截止日期
+    SetDueDate { due_date: Option<DateTime<Utc>> },
+    /// swimlane 为空表示移回默认泳道
+    MoveSwimlane { swimlane: Option<String> },
+    Delete,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkTaskRequest {
+    #[validate(length(min = 1, max = 200, message = "taskIds 数量需在 1-200 之间"))]
+    pub task_ids: Vec<String>,
+    pub operation: BulkOperation,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkItemResult {
+    pub task_id: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkTaskResponse {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkItemResult>,
+}
+
+/// 单个租户内本次批量操作涉及的任务与所属案件/项目（用于合并推送一条 signal）
+#[derive(Debug, Default)]
+struct TouchedTasks {
+    task_ids: Vec<String>,
+    case_ids: Vec<String>,
+    project_ids: Vec<String>,
+}
+
+/// 已校验的操作（字符串字段已解析为枚举/规范化）
+#[derive(Debug, Clone)]
+enum ResolvedOperation {
+    Update(FieldUpdate),
+    Delete,
+}
+
+/// 非删除类的字段修改
+#[derive(Debug, Clone)]
+enum FieldUpdate {
+    Assign(Option<String>),
+    SetStatus(task::TaskStatus),
+    SetPriority(task::TaskPriority),
+    SetDueDate(Option<DateTime<Utc>>),
+    MoveSwimlane(Option<String>),
+}
+
+fn normalize_optional(raw: Option<&str>) -> Option<String> {
+    raw.map(|s| s.trim()).filter(|s| !s.is_empty()).map(|s| s.to_string())
+}
+
+fn resolve_operation(op: &BulkOperation) -> AppResult<ResolvedOperation> {
+    Ok(match op {
+        BulkOperation::Assign { assignee_id } => {
+            let assignee_id = normalize_optional(assignee_id.as_deref());
+            if let Some(id) = assignee_id.as_deref() {
+                Uuid::parse_str(id).map_err(|_| AppError::Validation("assigneeId 无效".to_string()))?;
+            }
+            ResolvedOperation::Update(FieldUpdate::Assign(assignee_id))
+        }
+        BulkOperation::SetStatus { status } => {
+            if status.trim().is_empty() {
+                return Err(AppError::Validation("status 不能为空".to_string()));
+            }
+            ResolvedOperation::Update(FieldUpdate::SetStatus(parse_task_status(Some(status))?))
+        }
+        BulkOperation::SetPriority { priority } => {
+            if priority.trim().is_empty() {
+                return Err(AppError::Validation("priority 不能为空".to_string()));
+            }
+            ResolvedOperation::Update(FieldUpdate::SetPriority(parse_task_priority(Some(priority))?))
+        }
+        BulkOperation::SetDueDate { due_date } => ResolvedOperation::Update(FieldUpdate::SetDueDate(*due_date)),
+        BulkOperation::MoveSwimlane { swimlane } => {
+            let swimlane = normalize_optional(swimlane.as_deref());
+            if swimlane.as_ref().is_some_and(|s| s.chars().count() > 64) {
+                return Err(AppError::Validation("swimlane 长度不合法".to_string()));
+            }
+            ResolvedOperation::Update(FieldUpdate::MoveSwimlane(swimlane))
+        }
+        BulkOperation::Delete => ResolvedOperation::Delete,
+    })
+}
+
+fn failure(task_id: &str, err: &AppError) -> BulkItemResult {
+    let (code, message) = match err {
+        AppError::Unauthorized(m) => ("unauthorized", m.clone()),
+        AppError::Forbidden(m) => ("forbidden", m.clone()),
+        AppError::NotFound(m) => ("not_found", m.clone()),
+        AppError::Validation(m) => ("validation", m.clone()),
+        AppError::ValidationWithDetails { message, .. } => ("validation", message.clone()),
+        AppError::Conflict(m) => ("conflict", m.clone()),
+        AppError::Database(m) => ("database", m.clone()),
+        AppError::Internal(m) => ("internal", m.clone()),
+    };
+    BulkItemResult { task_id: task_id.to_string(), ok: false, error: Some(code.to_string()), message: Some(message) }
+}
+
+/// 对单个任务执行操作（在事务内调用）；业务校验失败返回 Err 由调用方记为该条失败
+async fn apply_one(
+    txn: &sea_orm::DatabaseTransaction,
+    existing: task::Model,
+    op: &ResolvedOperation,
+) -> AppResult<()> {
+    let now = Utc::now();
+    let update = match op {
+        ResolvedOperation::Delete => {
+            task::Entity::delete_by_id(&existing.id)
+                .exec(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("删除任务失败: {e}")))?;
+            return Ok(());
+        }
+        ResolvedOperation::Update(update) => update,
+    };
+
+    let mut completed_now = false;
+    let mut active: task::ActiveModel = existing.clone().into();
+    match update {
+        FieldUpdate::Assign(assignee_id) => active.assignee_id = sea_orm::ActiveValue::Set(assignee_id.clone()),
+        FieldUpdate::SetStatus(status) => {
+            if *status == existing.status {
+                return Ok(());
+            }
+            if matches!(status, task::TaskStatus::InProgress | task::TaskStatus::Done) {
+                super::task_dependencies::ensure_no_open_blockers(txn, &existing).await?;
+            }
+            completed_now = *status == task::TaskStatus::Done;
+            active.status = sea_orm::ActiveValue::Set(status.clone());
+        }
+        FieldUpdate::SetPriority(priority) => active.priority = sea_orm::ActiveValue::Set(priority.clone()),
+        FieldUpdate::SetDueDate(due_date) => active.due_date = sea_orm::ActiveValue::Set(*due_date),
+        FieldUpdate::MoveSwimlane(swimlane) => {
+            if *swimlane == existing.swimlane {
+                return Ok(());
+            }
+            let order =
+                next_task_order(txn, &TaskOwner::of(&existing)?, &existing.status, swimlane.as_deref()).await?;
+            active.swimlane = sea_orm::ActiveValue::Set(swimlane.clone());
+            active.order = sea_orm::ActiveValue::Set(order);
+        }
+    }
+    active.updated_at = sea_orm::ActiveValue::Set(now);
+
+    let updated = active
+        .update(txn)
+        .await
+        .map_err(|e| AppError::Database(format!("更新任务失败: {e}")))?;
+
+    if completed_now {
+        super::task_recurrence::spawn_next_occurrence(txn, &updated).await?;
+    }
+    Ok(())
+}
+
+async fn bulk_tasks(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(payload): ValidatedJson<BulkTaskRequest>,
+) -> AppResult<Json<BulkTaskResponse>> {
+    let op = resolve_operation(&payload.operation)?;
+    let role = current_user.model.role.clone();
+    let permission = match op {
+        ResolvedOperation::Delete => Permission::TaskDelete,
+        ResolvedOperation::Update(_) => Permission::TaskEdit,
+    };
+    require_permission(role.clone(), permission)?;
+
+    // 去重并保持请求顺序
+    let mut task_ids: Vec<String> = Vec::with_capacity(payload.task_ids.len());
+    for raw in &payload.task_ids {
+        let id = raw.trim().to_string();
+        Uuid::parse_str(&id).map_err(|_| AppError::Validation(format!("任务ID 无效: {id}")))?;
+        if !task_ids.contains(&id) {
+            task_ids.push(id);
+        }
+    }
+    if task_ids.len() > BULK_MAX_TASKS {
+        return Err(AppError::Validation(format!("单次最多操作 {BULK_MAX_TASKS} 个任务")));
+    }
+
+    let mut tasks: HashMap<String, task::Model> = task::Entity::find()
+        .filter(task::Column::Id.is_in(task_ids.clone()))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
+        .into_iter()
+        .map(|t| (t.id.clone(), t))
+        .collect();
+
+    // 可见性：每个案件/项目仅校验一次（owner -> Ok(tenant_id) / Err）
+    let mut owner_access: HashMap<TaskOwner, Result<String, AppError>> = HashMap::new();
+    for t in tasks.values() {
+        let owner = TaskOwner::of(t)?;
+        if owner_access.contains_key(&owner) {
+            continue;
+        }
+        let tenant_id = &current_user.model.active_tenant_id;
+        let access = require_task_owner_access(&state, &owner, current_user.id(), tenant_id, role.clone(), permission)
+            .await
+            .map(|scope| scope.tenant_id().to_string());
+        owner_access.insert(owner, access);
+    }
+
+    let mut results: BTreeMap<usize, BulkItemResult> = BTreeMap::new();
+    let mut runnable: Vec<(usize, task::Model, String)> = Vec::new();
+    for (idx, id) in task_ids.iter().enumerate() {
+        let Some(t) = tasks.remove(id) else {
+            results.insert(idx, failure(id, &AppError::NotFound(format!("任务 {} 不存在", id))));
+            continue;
+        };
+        match owner_access.get(&TaskOwner::of(&t)?) {
+            Some(Ok(tenant_id)) => runnable.push((idx, t, tenant_id.clone())),
+            Some(Err(err)) => {
+                results.insert(idx, failure(id, err));
+            }
+            None => {
+                results.insert(idx, failure(id, &AppError::Forbidden("无案件访问权限".to_string())));
+            }
+        }
+    }
+
+    let action = if matches!(op, ResolvedOperation::Delete) { "bulkDeleted" } else { "bulkUpdated" };
+    let applied = state
+        .db
+        .transaction(|txn| {
+            let op = op.clone();
+            Box::pin(async move {
+                let mut outcomes: Vec<(usize, String, Result<(), AppError>)> = Vec::with_capacity(runnable.len());
+                let mut touched: BTreeMap<String, TouchedTasks> = BTreeMap::new();
+
+                for (idx, existing, tenant_id) in runnable {
+                    let task_id = existing.id.clone();
+                    let owner = TaskOwner::of(&existing)?;
+                    match apply_one(txn, existing, &op).await {
+                        Ok(()) => {
+                            let entry = touched.entry(tenant_id).or_default();
+                            entry.task_ids.push(task_id.clone());
+                            let ids = match &owner {
+                                TaskOwner::Case(_) => &mut entry.case_ids,
+                                TaskOwner::Project(_) => &mut entry.project_ids,
+                            };
+                            if !ids.iter().any(|id| id == owner.id()) {
+                                ids.push(owner.id().to_string());
+                            }
+                            outcomes.push((idx, task_id, Ok(())));
+                        }
+                        // 数据库错误整体回滚；业务校验失败仅记为该条失败
+                        Err(err @ (AppError::Database(_) | AppError::Internal(_))) => return Err(err),
+                        Err(err) => outcomes.push((idx, task_id, Err(err))),
+                    }
+                }
+
+                for (tenant_id, TouchedTasks { task_ids, case_ids, project_ids }) in touched {
+                    touch_tenant_signal(
+                        txn,
+                        &tenant_id,
+                        TenantSignalKind::TasksChanged,
+                        Some(json!({
+                            "action": action,
+                            "taskIds": task_ids,
+                            "caseIds": case_ids,
+                            "projectIds": project_ids,
+                        })),
+                    )
+                    .await?;
+                }
+
+                Ok(outcomes)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    for (idx, task_id, outcome) in applied {
+        let item = match outcome {
+            Ok(()) => BulkItemResult { task_id, ok: true, error: None, message: None },
+            Err(err) => failure(&task_id, &err),
+        };
+        results.insert(idx, item);
+    }
+
+    let results: Vec<BulkItemResult> = results.into_values().collect();
+    let succeeded = results.iter().filter(|r| r.ok).count();
+    Ok(Json(BulkTaskResponse { succeeded, failed: results.len() - succeeded, results }))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/bulk", post(bulk_tasks))
+}
@@ -0,0 +1,301 @@
+//! 任务检查清单路由模块（条目级原子操作）
+//!
+//! `Task.checklist` 为 JSON 数组，整体覆盖写会导致多人同时勾选时互相覆盖。
+//! 本模块对单个条目提供新增/勾选/排序/删除，全部以单条 `UPDATE ... jsonb` 语句完成：
+//! - 行锁保证并发写串行化，且每次都基于最新的 checklist 计算
+//! - 历史条目缺失 id 时按下标补 `legacy-{n}`，首次写入即固化
+//! - 每次变更同事务递增 `TenantSignal(TASKS_CHANGED)`
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    routing::{delete, patch, post},
+    Router,
+};
+use chrono::{SecondsFormat, Utc};
+use sea_orm::{DatabaseBackend, EntityTrait, Statement, TransactionTrait};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::db::AppState;
+use crate::entity::task;
+use crate::entity::tenant_signal::TenantSignalKind;
+use crate::error::{AppError, AppResult};
+use crate::realtime::tenant_signal::touch_tenant_signal;
+use crate::routes::tasks::{find_task_with_access, tasks_changed_payload, TaskResponse};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::Permission;
+use crate::security::validation::{require_non_empty, ValidatedJson};
+
+const MAX_CHECKLIST_ITEMS: i64 = 200;
+
+/// 展开 checklist 数组（非数组视为空数组）
+const ITEMS_FROM: &str = r#"jsonb_array_elements(CASE WHEN jsonb_typeof(t."checklist") = 'array' THEN t."checklist" ELSE '[]'::jsonb END) WITH ORDINALITY AS x(elem, ord)"#;
+/// 条目 id（历史条目按下标补 legacy id，与 `task::parse_checklist` 口径一致）
+const ITEM_ID: &str = r#"COALESCE(x.elem->>'id', 'legacy-' || (x.ord - 1))"#;
+
+fn item_normalized() -> String {
+    format!("(x.elem || jsonb_build_object('id', {ITEM_ID}))")
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct AddChecklistItemRequest {
+    #[validate(length(min = 1, max = 500, message = "text 长度不合法"))]
+    pub text: String,
+
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub assignee_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ToggleChecklistItemRequest {
+    /// 目标状态；缺省则取反
+    pub done: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderChecklistRequest {
+    #[validate(length(max = 200, message = "itemIds 数量超出上限"))]
+    pub item_ids: Vec<String>,
+}
+
+fn validate_item_id(item_id: &str) -> AppResult<String> {
+    let trimmed = item_id.trim();
+    if trimmed.is_empty() || trimmed.len() > 64 {
+        return Err(AppError::Validation("itemId 无效".to_string()));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// 在事务内执行 checklist UPDATE 并递增信号；`None` 表示前置条件（WHERE）未满足
+async fn apply_checklist_update(
+    state: &AppState,
+    tenant_id: &str,
+    stmt: Statement,
+) -> AppResult<Option<task::Model>> {
+    let tenant_id = tenant_id.to_string();
+    state
+        .db
+        .transaction(|txn| {
+            Box::pin(async move {
+                let updated = task::Entity::find()
+                    .from_raw_sql(stmt)
+                    .one(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("更新检查清单失败: {e}")))?;
+
+                if let Some(model) = updated.as_ref() {
+                    touch_tenant_signal(
+                        txn,
+                        &tenant_id,
+                        TenantSignalKind::TasksChanged,
+                        Some(tasks_changed_payload("updated", model)),
+                    )
+                    .await?;
+                }
+
+                Ok(updated)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })
+}
+
+async fn add_checklist_item(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(task_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<AddChecklistItemRequest>,
+) -> AppResult<Json<TaskResponse>> {
+    let (task_model, scope) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskEdit).await?;
+
+    let item = task::ChecklistItem {
+        id: Uuid::new_v4().to_string(),
+        text: require_non_empty(&payload.text, "text", 500)?,
+        done: false,
+        done_by: None,
+        done_at: None,
+        assignee_id: payload.assignee_id.as_deref().map(|s| s.trim().to_string()),
+    };
+    let item_json =
+        serde_json::to_value(&item).map_err(|_| AppError::Internal("序列化检查清单条目失败".to_string()))?;
+
+    let sql = format!(
+        r#"UPDATE "Task" AS t
+           SET "checklist" = COALESCE(
+                 (SELECT jsonb_agg({normalized} ORDER BY x.ord) FROM {ITEMS_FROM} WHERE jsonb_typeof(x.elem) = 'object'),
+                 '[]'::jsonb
+               ) || jsonb_build_array($2::jsonb),
+               "updatedAt" = $3
+           WHERE t."id" = $1
+             AND (SELECT count(*) FROM {ITEMS_FROM}) < $4
+           RETURNING t.*"#,
+        normalized = item_normalized(),
+    );
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        sql,
+        vec![task_model.id.clone().into(), item_json.into(), Utc::now().into(), MAX_CHECKLIST_ITEMS.into()],
+    );
+
+    let updated = apply_checklist_update(&state, scope.tenant_id(), stmt)
+        .await?
+        .ok_or_else(|| AppError::Validation(format!("检查清单条目不能超过 {MAX_CHECKLIST_ITEMS} 条")))?;
+
+    Ok(Json(TaskResponse::from(updated)))
+}
+
+async fn toggle_checklist_item(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path((task_id, item_id)): Path<(String, String)>,
+    ValidatedJson(payload): ValidatedJson<ToggleChecklistItemRequest>,
+) -> AppResult<Json<TaskResponse>> {
+    let (task_model, scope) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskEdit).await?;
+    let item_id = validate_item_id(&item_id)?;
+
+    let now = Utc::now();
+    let new_done = "COALESCE($3::boolean, NOT COALESCE((x.elem->>'done')::boolean, false))";
+    let sql = format!(
+        r#"UPDATE "Task" AS t
+           SET "checklist" = (
+                 SELECT jsonb_agg(
+                   CASE WHEN {ITEM_ID} = $2
+                     THEN {normalized} || jsonb_build_object(
+                       'done', {new_done},
+                       'doneBy', CASE WHEN {new_done} THEN $4::text END,
+                       'doneAt', CASE WHEN {new_done} THEN $5::text END
+                     )
+                     ELSE {normalized}
+                   END
+                   ORDER BY x.ord
+                 )
+                 FROM {ITEMS_FROM}
+                 WHERE jsonb_typeof(x.elem) = 'object'
+               ),
+               "updatedAt" = $6
+           WHERE t."id" = $1
+             AND EXISTS (SELECT 1 FROM {ITEMS_FROM} WHERE jsonb_typeof(x.elem) = 'object' AND {ITEM_ID} = $2)
+           RETURNING t.*"#,
+        normalized = item_normalized(),
+    );
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        sql,
+        vec![
+            task_model.id.clone().into(),
+            item_id.clone().into(),
+            payload.done.into(),
+            current_user.id().to_string().into(),
+            now.to_rfc3339_opts(SecondsFormat::Millis, true).into(),
+            now.into(),
+        ],
+    );
+
+    let updated = apply_checklist_update(&state, scope.tenant_id(), stmt)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("检查清单条目 {item_id} 不存在")))?;
+
+    Ok(Json(TaskResponse::from(updated)))
+}
+
+async fn reorder_checklist(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(task_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<ReorderChecklistRequest>,
+) -> AppResult<Json<TaskResponse>> {
+    let (task_model, scope) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskEdit).await?;
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut item_ids: Vec<String> = Vec::with_capacity(payload.item_ids.len());
+    for raw in &payload.item_ids {
+        let id = validate_item_id(raw)?;
+        if !seen.insert(id.clone()) {
+            return Err(AppError::Validation("itemIds 包含重复条目".to_string()));
+        }
+        item_ids.push(id);
+    }
+
+    // itemIds 必须与现有条目集合完全一致（防止基于过期快照的排序吞掉他人新增的条目）
+    let sql = format!(
+        r#"UPDATE "Task" AS t
+           SET "checklist" = COALESCE(
+                 (SELECT jsonb_agg({normalized} ORDER BY p.ord)
+                  FROM {ITEMS_FROM}
+                  JOIN jsonb_array_elements_text($2::jsonb) WITH ORDINALITY AS p(v, ord) ON p.v = {ITEM_ID}
+                  WHERE jsonb_typeof(x.elem) = 'object'),
+                 '[]'::jsonb
+               ),
+               "updatedAt" = $3
+           WHERE t."id" = $1
+             AND (SELECT COALESCE(array_agg(s.i ORDER BY s.i), '{{}}')
+                  FROM (SELECT {ITEM_ID} AS i FROM {ITEMS_FROM} WHERE jsonb_typeof(x.elem) = 'object') s)
+               = (SELECT COALESCE(array_agg(v ORDER BY v), '{{}}') FROM jsonb_array_elements_text($2::jsonb) AS v)
+           RETURNING t.*"#,
+        normalized = item_normalized(),
+    );
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        sql,
+        vec![task_model.id.clone().into(), serde_json::json!(item_ids).into(), Utc::now().into()],
+    );
+
+    let updated = apply_checklist_update(&state, scope.tenant_id(), stmt)
+        .await?
+        .ok_or_else(|| AppError::Validation("itemIds 与当前检查清单不一致，请刷新后重试".to_string()))?;
+
+    Ok(Json(TaskResponse::from(updated)))
+}
+
+async fn delete_checklist_item(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path((task_id, item_id)): Path<(String, String)>,
+) -> AppResult<Json<TaskResponse>> {
+    let (task_model, scope) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskEdit).await?;
+    let item_id = validate_item_id(&item_id)?;
+
+    let sql = format!(
+        r#"UPDATE "Task" AS t
+           SET "checklist" = COALESCE(
+                 (SELECT jsonb_agg({normalized} ORDER BY x.ord)
+                  FROM {ITEMS_FROM}
+                  WHERE jsonb_typeof(x.elem) = 'object' AND {ITEM_ID} <> $2),
+                 '[]'::jsonb
+               ),
+               "updatedAt" = $3
+           WHERE t."id" = $1
+             AND EXISTS (SELECT 1 FROM {ITEMS_FROM} WHERE jsonb_typeof(x.elem) = 'object' AND {ITEM_ID} = $2)
+           RETURNING t.*"#,
+        normalized = item_normalized(),
+    );
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        sql,
+        vec![task_model.id.clone().into(), item_id.clone().into(), Utc::now().into()],
+    );
+
+    let updated = apply_checklist_update(&state, scope.tenant_id(), stmt)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("检查清单条目 {item_id} 不存在")))?;
+
+    Ok(Json(TaskResponse::from(updated)))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/:id/checklist", post(add_checklist_item))
+        .route("/:id/checklist/reorder", patch(reorder_checklist))
+        .route("/:id/checklist/:item_id", delete(delete_checklist_item))
+        .route("/:id/checklist/:item_id/toggle", post(toggle_checklist_item))
+}
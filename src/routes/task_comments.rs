@@ -0,0 +1,412 @@
+//! 任务评论路由模块（评论 + @提及 + 任务动态）
+//!
+//! - 评论：`GET/POST /tasks/:id/comments`，`PATCH/DELETE /tasks/:id/comments/:comment_id`（仅作者本人）
+//! - 提及：content 中 `@<userId>` 或 `@<email>` 解析为提及；被提及人须可访问任务所属案件/项目
+//!   （复用 `require_case_access` / `require_project_access` 口径），并收到 `TASK_MENTIONED` 通知（编辑时仅通知新增提及）
+//! - 动态：`GET /tasks/:id/activity` 汇总任务创建、评论、工时记录，按时间倒序
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, patch},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::db::AppState;
+use crate::entity::{notification, task, task_comment, time_log, user};
+use crate::error::{AppError, AppResult};
+use crate::routes::tasks::{find_task_with_access, require_task_owner_access, TaskOwner};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::Permission;
+use crate::security::validation::{require_non_empty, ValidatedJson};
+
+const COMMENT_MAX_LEN: usize = 5000;
+const MAX_MENTIONS: usize = 20;
+const ACTIVITY_LIMIT: u64 = 200;
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentRequest {
+    #[validate(length(min = 1, max = 5000, message = "评论内容长度不合法"))]
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentResponse {
+    pub id: String,
+    pub task_id: String,
+    pub author_id: String,
+    pub content: String,
+    pub mentioned_user_ids: Vec<String>,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<task_comment::Model> for CommentResponse {
+    fn from(model: task_comment::Model) -> Self {
+        Self {
+            id: model.id,
+            task_id: model.task_id,
+            author_id: model.author_id,
+            content: model.content,
+            mentioned_user_ids: model.mentioned_user_ids,
+            edited_at: model.edited_at,
+            created_at: model.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityItem {
+    /// TASK_CREATED | COMMENT | TIME_LOG
+    pub kind: String,
+    pub at: DateTime<Utc>,
+    pub actor_id: Option<String>,
+    pub ref_id: String,
+    pub summary: String,
+}
+
+/// 解析 `@token` 提及（token 为 ASCII 字母数字及 `-_.+@`；`@` 前为字母数字时视为普通邮箱文本而非提及）
+///
+/// 结果按出现顺序去重。
+pub fn extract_mentions(content: &str) -> Vec<String> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut out: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] != '@' || (i > 0 && chars[i - 1].is_ascii_alphanumeric()) {
+            i += 1;
+            continue;
+        }
+        let start = i + 1;
+        let mut end = start;
+        while end < chars.len() && (chars[end].is_ascii_alphanumeric() || "-_.+@".contains(chars[end])) {
+            end += 1;
+        }
+        let token: String = chars[start..end].iter().collect();
+        let token = token.trim_end_matches(['.', '@']).to_string();
+        if !token.is_empty() && !out.contains(&token) {
+            out.push(token);
+        }
+        i = end.max(start);
+    }
+    out
+}
+
+/// 将提及 token 解析为用户，并校验其对案件的可见性；任何一个无法解析/无权限即整体报错
+async fn resolve_mentions(state: &AppState, owner: &TaskOwner, content: &str) -> AppResult<Vec<user::Model>> {
+    let tokens = extract_mentions(content);
+    if tokens.len() > MAX_MENTIONS {
+        return Err(AppError::Validation(format!("单条评论最多提及 {MAX_MENTIONS} 人")));
+    }
+
+    let mut users: Vec<user::Model> = Vec::new();
+    let mut invalid: Vec<String> = Vec::new();
+    for token in tokens {
+        let column = if Uuid::parse_str(&token).is_ok() { user::Column::Id } else { user::Column::Email };
+        let found = user::Entity::find()
+            .filter(column.eq(&token))
+            .filter(user::Column::IsActive.eq(true))
+            .one(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询被提及用户失败: {e}")))?;
+
+        let Some(found) = found else {
+            invalid.push(token);
+            continue;
+        };
+        let (tenant_id, role) = (&found.active_tenant_id, found.role.clone());
+        let access = require_task_owner_access(state, owner, &found.id, tenant_id, role, Permission::TaskView).await;
+        if access.is_err() {
+            invalid.push(token);
+            continue;
+        }
+        if !users.iter().any(|u| u.id == found.id) {
+            users.push(found);
+        }
+    }
+
+    if !invalid.is_empty() {
+        return Err(AppError::ValidationWithDetails {
+            message: "被提及用户不存在或无权访问该案件".to_string(),
+            details: json!({ "invalidMentions": invalid }),
+        });
+    }
+    Ok(users)
+}
+
+fn excerpt(content: &str) -> String {
+    let mut text: String = content.chars().take(200).collect();
+    if content.chars().count() > 200 {
+        text.push('…');
+    }
+    text
+}
+
+async fn notify_mentions<C: ConnectionTrait>(
+    db: &C,
+    actor_id: &str,
+    task_model: &task::Model,
+    comment: &task_comment::Model,
+    recipients: &[String],
+) -> AppResult<()> {
+    let now = Utc::now();
+    for user_id in recipients.iter().filter(|id| id.as_str() != actor_id) {
+        notification::ActiveModel {
+            id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+            user_id: sea_orm::ActiveValue::Set(user_id.clone()),
+            actor_id: sea_orm::ActiveValue::Set(Some(actor_id.to_string())),
+            notification_type: sea_orm::ActiveValue::Set(notification::NotificationType::TaskMentioned),
+            title: sea_orm::ActiveValue::Set(format!("任务评论中提到了你：{}", task_model.title)),
+            content: sea_orm::ActiveValue::Set(Some(excerpt(&comment.content))),
+            action_url: sea_orm::ActiveValue::Set(Some(format!(
+                "/tasks/{}?commentId={}",
+                task_model.id, comment.id
+            ))),
+            metadata: sea_orm::ActiveValue::Set(Some(json!({
+                "taskId": task_model.id,
+                "caseId": task_model.case_id,
+                "projectId": task_model.project_id,
+                "commentId": comment.id,
+            }))),
+            read_at: sea_orm::ActiveValue::Set(None),
+            created_at: sea_orm::ActiveValue::Set(now),
+        }
+        .insert(db)
+        .await
+        .map_err(|e| AppError::Database(format!("写入通知失败: {e}")))?;
+    }
+    Ok(())
+}
+
+async fn find_comment(state: &AppState, task_id: &str, comment_id: &str) -> AppResult<task_comment::Model> {
+    Uuid::parse_str(comment_id).map_err(|_| AppError::Validation("评论ID 无效".to_string()))?;
+    task_comment::Entity::find_by_id(comment_id)
+        .filter(task_comment::Column::TaskId.eq(task_id))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询评论失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound(format!("评论 {} 不存在", comment_id)))
+}
+
+async fn list_comments(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(task_id): Path<String>,
+) -> AppResult<Json<Vec<CommentResponse>>> {
+    let (task_model, _) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskView).await?;
+
+    let comments = task_comment::Entity::find()
+        .filter(task_comment::Column::TaskId.eq(&task_model.id))
+        .order_by_asc(task_comment::Column::CreatedAt)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询评论失败: {e}")))?;
+
+    Ok(Json(comments.into_iter().map(CommentResponse::from).collect()))
+}
+
+async fn create_comment(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(task_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<CommentRequest>,
+) -> AppResult<Json<CommentResponse>> {
+    let (task_model, scope) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskView).await?;
+    let content = require_non_empty(&payload.content, "content", COMMENT_MAX_LEN)?;
+    let mentioned = resolve_mentions(&state, &TaskOwner::of(&task_model)?, &content).await?;
+    let mentioned_ids: Vec<String> = mentioned.into_iter().map(|u| u.id).collect();
+    let author_id = current_user.id().to_string();
+
+    let created = state
+        .db
+        .transaction(|txn| {
+            Box::pin(async move {
+                let now = Utc::now();
+                let created = task_comment::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    tenant_id: sea_orm::ActiveValue::Set(scope.tenant_id().to_string()),
+                    task_id: sea_orm::ActiveValue::Set(task_model.id.clone()),
+                    author_id: sea_orm::ActiveValue::Set(author_id.clone()),
+                    content: sea_orm::ActiveValue::Set(content),
+                    mentioned_user_ids: sea_orm::ActiveValue::Set(mentioned_ids.clone()),
+                    edited_at: sea_orm::ActiveValue::Set(None),
+                    created_at: sea_orm::ActiveValue::Set(now),
+                    updated_at: sea_orm::ActiveValue::Set(now),
+                }
+                .insert(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("创建评论失败: {e}")))?;
+
+                notify_mentions(txn, &author_id, &task_model, &created, &mentioned_ids).await?;
+
+                Ok(created)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    Ok(Json(CommentResponse::from(created)))
+}
+
+async fn update_comment(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path((task_id, comment_id)): Path<(String, String)>,
+    ValidatedJson(payload): ValidatedJson<CommentRequest>,
+) -> AppResult<Json<CommentResponse>> {
+    let (task_model, _) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskView).await?;
+    let existing = find_comment(&state, &task_model.id, &comment_id).await?;
+    if existing.author_id != current_user.id() {
+        return Err(AppError::Forbidden("仅评论作者可编辑".to_string()));
+    }
+
+    let content = require_non_empty(&payload.content, "content", COMMENT_MAX_LEN)?;
+    let mentioned = resolve_mentions(&state, &TaskOwner::of(&task_model)?, &content).await?;
+    let mentioned_ids: Vec<String> = mentioned.into_iter().map(|u| u.id).collect();
+    let newly_mentioned: Vec<String> =
+        mentioned_ids.iter().filter(|id| !existing.mentioned_user_ids.contains(id)).cloned().collect();
+    let author_id = current_user.id().to_string();
+
+    let updated = state
+        .db
+        .transaction(|txn| {
+            Box::pin(async move {
+                let now = Utc::now();
+                let mut active: task_comment::ActiveModel = existing.into();
+                active.content = sea_orm::ActiveValue::Set(content);
+                active.mentioned_user_ids = sea_orm::ActiveValue::Set(mentioned_ids);
+                active.edited_at = sea_orm::ActiveValue::Set(Some(now));
+                active.updated_at = sea_orm::ActiveValue::Set(now);
+                let updated = active
+                    .update(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("更新评论失败: {e}")))?;
+
+                notify_mentions(txn, &author_id, &task_model, &updated, &newly_mentioned).await?;
+
+                Ok(updated)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    Ok(Json(CommentResponse::from(updated)))
+}
+
+async fn delete_comment(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path((task_id, comment_id)): Path<(String, String)>,
+) -> AppResult<StatusCode> {
+    let (task_model, _) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskView).await?;
+    let existing = find_comment(&state, &task_model.id, &comment_id).await?;
+    if existing.author_id != current_user.id() {
+        return Err(AppError::Forbidden("仅评论作者可删除".to_string()));
+    }
+
+    task_comment::Entity::delete_by_id(existing.id)
+        .exec(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("删除评论失败: {e}")))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn task_activity(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(task_id): Path<String>,
+) -> AppResult<Json<Vec<ActivityItem>>> {
+    let (task_model, _) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskView).await?;
+
+    let comments = task_comment::Entity::find()
+        .filter(task_comment::Column::TaskId.eq(&task_model.id))
+        .order_by_desc(task_comment::Column::CreatedAt)
+        .limit(ACTIVITY_LIMIT)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询评论失败: {e}")))?;
+
+    let time_logs = time_log::Entity::find()
+        .filter(time_log::Column::TaskId.eq(&task_model.id))
+        .order_by_desc(time_log::Column::StartTime)
+        .limit(ACTIVITY_LIMIT)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询工时记录失败: {e}")))?;
+
+    let mut items: Vec<ActivityItem> = Vec::with_capacity(comments.len() + time_logs.len() + 1);
+    items.push(ActivityItem {
+        kind: "TASK_CREATED".to_string(),
+        at: task_model.created_at,
+        actor_id: None,
+        ref_id: task_model.id.clone(),
+        summary: format!("创建任务：{}", task_model.title),
+    });
+    items.extend(comments.into_iter().map(|c| ActivityItem {
+        kind: "COMMENT".to_string(),
+        at: c.created_at,
+        actor_id: Some(c.author_id),
+        ref_id: c.id,
+        summary: excerpt(&c.content),
+    }));
+    items.extend(time_logs.into_iter().map(|t| ActivityItem {
+        kind: "TIME_LOG".to_string(),
+        at: t.start_time,
+        actor_id: Some(t.user_id),
+        ref_id: t.id,
+        summary: format!("记录工时 {} 分钟：{}", t.duration / 60, t.description),
+    }));
+
+    items.sort_by_key(|item| std::cmp::Reverse(item.at));
+    items.truncate(ACTIVITY_LIMIT as usize);
+
+    Ok(Json(items))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/:id/comments", get(list_comments).post(create_comment))
+        .route("/:id/comments/:comment_id", patch(update_comment).delete(delete_comment))
+        .route("/:id/activity", get(task_activity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_mentions_in_order_without_duplicates() {
+        let content = "请 @alice@firm.com 与 @3f1c2b4e-0000-4000-8000-000000000001 复核，@alice@firm.com 已知悉。";
+        assert_eq!(
+            extract_mentions(content),
+            vec!["alice@firm.com".to_string(), "3f1c2b4e-0000-4000-8000-000000000001".to_string()]
+        );
+    }
+
+    #[test]
+    fn ignores_plain_emails_and_bare_at() {
+        assert!(extract_mentions("联系 bob@firm.com 或 @ 一下").is_empty());
+        assert_eq!(extract_mentions("(@carol)."), vec!["carol".to_string()]);
+    }
+}
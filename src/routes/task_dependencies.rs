@@ -0,0 +1,553 @@
+//! 任务依赖路由模块（前置/阻塞关系 + 关键路径）
+//!
+//! - 关联：`POST /tasks/:id/dependencies`（本任务依赖 dependsOnId）/ `DELETE .../:depends_on_id`
+//! - 约束：仅允许同一案件/项目内的任务互相依赖；写入前在事务内做环检测（按案件/项目加 advisory lock）
+//! - 状态门禁：存在未完成前置任务时，禁止将任务推进到 IN_PROGRESS / DONE
+//! - 关键路径：`GET /cases/:id/tasks/critical-path`，按 estimatedHours 计算未完成任务的最长依赖链，
+//!   并以当前时间起算的预计完成时间对比 dueDate 标记风险
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get},
+    Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QuerySelect,
+    Statement, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::db::AppState;
+use crate::entity::{task, task_dependency};
+use crate::entity::tenant_signal::TenantSignalKind;
+use crate::error::{AppError, AppResult};
+use crate::realtime::tenant_signal::touch_tenant_signal;
+use crate::routes::tasks::{find_task_with_access, tasks_changed_payload, TaskOwner};
+use crate::security::case_access::require_case_access;
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::Permission;
+use crate::security::validation::ValidatedJson;
+
+/// 关键路径换算：每个工作日按 8 小时折算为日历时间
+const WORK_HOURS_PER_DAY: f64 = 8.0;
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct AddDependencyRequest {
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub depends_on_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyTaskItem {
+    pub task_id: String,
+    pub title: String,
+    pub status: String,
+    pub due_date: Option<DateTime<Utc>>,
+}
+
+impl From<task::Model> for DependencyTaskItem {
+    fn from(model: task::Model) -> Self {
+        Self {
+            task_id: model.id,
+            title: model.title,
+            status: model.status.to_value(),
+            due_date: model.due_date,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDependenciesResponse {
+    pub task_id: String,
+    /// 是否仍被未完成的前置任务阻塞
+    pub blocked: bool,
+    /// 本任务依赖的前置任务
+    pub blockers: Vec<DependencyTaskItem>,
+    /// 依赖本任务的后续任务
+    pub dependents: Vec<DependencyTaskItem>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalPathItem {
+    pub task_id: String,
+    pub title: String,
+    pub status: String,
+    pub estimated_hours: f64,
+    pub cumulative_hours: f64,
+    pub due_date: Option<DateTime<Utc>>,
+    pub projected_finish: DateTime<Utc>,
+    /// 预计完成时间晚于 dueDate
+    pub at_risk: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalPathResponse {
+    pub case_id: String,
+    pub total_hours: f64,
+    pub projected_finish: Option<DateTime<Utc>>,
+    pub at_risk: bool,
+    pub chain: Vec<CriticalPathItem>,
+}
+
+/// 判断新增依赖 `task_id -> depends_on_id` 是否成环
+///
+/// `edges` 为既有依赖 `(task_id, depends_on_id)`；若 depends_on_id 已（传递）依赖 task_id 则成环。
+pub fn would_create_cycle(edges: &[(String, String)], task_id: &str, depends_on_id: &str) -> bool {
+    if task_id == depends_on_id {
+        return true;
+    }
+
+    let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (from, to) in edges {
+        adjacency.entry(from.as_str()).or_default().push(to.as_str());
+    }
+
+    let mut visited: HashSet<&str> = HashSet::new();
+    let mut queue: VecDeque<&str> = VecDeque::from([depends_on_id]);
+    while let Some(current) = queue.pop_front() {
+        if current == task_id {
+            return true;
+        }
+        if !visited.insert(current) {
+            continue;
+        }
+        if let Some(next) = adjacency.get(current) {
+            queue.extend(next.iter().copied());
+        }
+    }
+    false
+}
+
+/// 计算最长依赖链（按小时加权）；返回自最早前置任务到末端任务的 id 序列
+///
+/// `nodes` 为 `(task_id, hours)`（保持输入顺序以保证结果稳定），`edges` 为 `(task_id, depends_on_id)`。
+/// 不在 nodes 内的边端点会被忽略；若图中存在环，则环上节点不参与计算。
+pub fn longest_chain(nodes: &[(String, f64)], edges: &[(String, String)]) -> Vec<String> {
+    let index: HashMap<&str, usize> = nodes.iter().enumerate().map(|(i, (id, _))| (id.as_str(), i)).collect();
+
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    let mut in_degree: Vec<usize> = vec![0; nodes.len()];
+    for (task_id, depends_on_id) in edges {
+        let (Some(&to), Some(&from)) = (index.get(task_id.as_str()), index.get(depends_on_id.as_str())) else {
+            continue;
+        };
+        successors[from].push(to);
+        in_degree[to] += 1;
+    }
+
+    let mut best: Vec<f64> = nodes.iter().map(|(_, h)| h.max(0.0)).collect();
+    let mut prev: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut queue: VecDeque<usize> = (0..nodes.len()).filter(|&i| in_degree[i] == 0).collect();
+    let mut visited: Vec<bool> = vec![false; nodes.len()];
+
+    while let Some(current) = queue.pop_front() {
+        visited[current] = true;
+        for &next in &successors[current] {
+            let candidate = best[current] + nodes[next].1.max(0.0);
+            if prev[next].is_none() || candidate > best[next] {
+                best[next] = candidate;
+                prev[next] = Some(current);
+            }
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    let Some(end) = (0..nodes.len())
+        .filter(|&i| visited[i])
+        .fold(None, |acc: Option<usize>, i| match acc {
+            Some(j) if best[j] >= best[i] => Some(j),
+            _ => Some(i),
+        })
+    else {
+        return vec![];
+    };
+
+    let mut chain = vec![nodes[end].0.clone()];
+    let mut cursor = prev[end];
+    while let Some(i) = cursor {
+        chain.push(nodes[i].0.clone());
+        cursor = prev[i];
+    }
+    chain.reverse();
+    chain
+}
+
+/// 状态门禁：存在未完成（非 DONE）的前置任务时返回错误
+///
+/// 需在写入状态的事务内调用：与依赖写入共用 advisory lock，并以共享锁锁定前置任务，
+/// 避免检查后、提交前有前置任务被新增或重新打开
+pub(crate) async fn ensure_no_open_blockers<C: ConnectionTrait>(db: &C, task_model: &task::Model) -> AppResult<()> {
+    let owner = TaskOwner::of(task_model)?;
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT pg_advisory_xact_lock(hashtext($1))",
+        vec![format!("task-dependency:{}", owner.id()).into()],
+    ))
+    .await
+    .map_err(|e| AppError::Database(format!("获取依赖锁失败: {e}")))?;
+
+    let blocker_ids: Vec<String> = task_dependency::Entity::find()
+        .filter(task_dependency::Column::TaskId.eq(&task_model.id))
+        .all(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询任务依赖失败: {e}")))?
+        .into_iter()
+        .map(|d| d.depends_on_id)
+        .collect();
+
+    if blocker_ids.is_empty() {
+        return Ok(());
+    }
+
+    let open_blockers = task::Entity::find()
+        .filter(task::Column::Id.is_in(blocker_ids))
+        .filter(task::Column::Status.ne(task::TaskStatus::Done))
+        .lock_shared()
+        .all(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询前置任务失败: {e}")))?;
+
+    if open_blockers.is_empty() {
+        return Ok(());
+    }
+
+    let titles: Vec<String> = open_blockers.into_iter().map(|t| t.title).collect();
+    Err(AppError::ValidationWithDetails {
+        message: "存在未完成的前置任务，无法推进任务状态".to_string(),
+        details: serde_json::json!({ "openBlockers": titles }),
+    })
+}
+
+async fn load_dependencies(state: &AppState, task_id: &str) -> AppResult<TaskDependenciesResponse> {
+    let edges = task_dependency::Entity::find()
+        .filter(
+            sea_orm::Condition::any()
+                .add(task_dependency::Column::TaskId.eq(task_id))
+                .add(task_dependency::Column::DependsOnId.eq(task_id)),
+        )
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询任务依赖失败: {e}")))?;
+
+    let blocker_ids: Vec<String> =
+        edges.iter().filter(|d| d.task_id == task_id).map(|d| d.depends_on_id.clone()).collect();
+    let dependent_ids: Vec<String> =
+        edges.iter().filter(|d| d.depends_on_id == task_id).map(|d| d.task_id.clone()).collect();
+
+    let mut related_ids = blocker_ids.clone();
+    related_ids.extend(dependent_ids.iter().cloned());
+    let related: HashMap<String, task::Model> = if related_ids.is_empty() {
+        HashMap::new()
+    } else {
+        task::Entity::find()
+            .filter(task::Column::Id.is_in(related_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询关联任务失败: {e}")))?
+            .into_iter()
+            .map(|t| (t.id.clone(), t))
+            .collect()
+    };
+
+    let blockers: Vec<DependencyTaskItem> = blocker_ids
+        .iter()
+        .filter_map(|id| related.get(id).cloned())
+        .map(DependencyTaskItem::from)
+        .collect();
+    let dependents: Vec<DependencyTaskItem> = dependent_ids
+        .iter()
+        .filter_map(|id| related.get(id).cloned())
+        .map(DependencyTaskItem::from)
+        .collect();
+
+    let done = task::TaskStatus::Done.to_value();
+    Ok(TaskDependenciesResponse {
+        task_id: task_id.to_string(),
+        blocked: blockers.iter().any(|b| b.status != done),
+        blockers,
+        dependents,
+    })
+}
+
+async fn list_dependencies(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(task_id): Path<String>,
+) -> AppResult<Json<TaskDependenciesResponse>> {
+    let (task_model, _) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskView).await?;
+    Ok(Json(load_dependencies(&state, &task_model.id).await?))
+}
+
+async fn add_dependency(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(task_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<AddDependencyRequest>,
+) -> AppResult<Json<TaskDependenciesResponse>> {
+    let (task_model, scope) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskEdit).await?;
+    let depends_on_id = payload.depends_on_id.trim().to_string();
+
+    if depends_on_id == task_model.id {
+        return Err(AppError::Validation("任务不能依赖自身".to_string()));
+    }
+
+    let depends_on = task::Entity::find_by_id(&depends_on_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound(format!("任务 {} 不存在", depends_on_id)))?;
+
+    let owner = TaskOwner::of(&task_model)?;
+    if TaskOwner::of(&depends_on)? != owner {
+        return Err(AppError::Validation("仅支持同一案件/项目内的任务依赖".to_string()));
+    }
+
+    let creator_id = current_user.id().to_string();
+    state
+        .db
+        .transaction(|txn| {
+            let task_id = task_model.id.clone();
+            let tenant_id = scope.tenant_id().to_string();
+            Box::pin(async move {
+                // 同案件/项目依赖写入串行化，避免并发写入绕过环检测
+                txn.execute(Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    "SELECT pg_advisory_xact_lock(hashtext($1))",
+                    vec![format!("task-dependency:{}", owner.id()).into()],
+                ))
+                .await
+                .map_err(|e| AppError::Database(format!("获取依赖锁失败: {e}")))?;
+
+                let case_task_ids: Vec<String> = task::Entity::find()
+                    .filter(owner.condition())
+                    .all(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("查询案件任务失败: {e}")))?
+                    .into_iter()
+                    .map(|t| t.id)
+                    .collect();
+
+                let existing: Vec<(String, String)> = task_dependency::Entity::find()
+                    .filter(task_dependency::Column::TaskId.is_in(case_task_ids))
+                    .all(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("查询任务依赖失败: {e}")))?
+                    .into_iter()
+                    .map(|d| (d.task_id, d.depends_on_id))
+                    .collect();
+
+                if existing.iter().any(|(t, d)| *t == task_id && *d == depends_on_id) {
+                    return Ok(());
+                }
+                if would_create_cycle(&existing, &task_id, &depends_on_id) {
+                    return Err(AppError::Validation("该依赖会形成循环依赖".to_string()));
+                }
+
+                task_dependency::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    tenant_id: sea_orm::ActiveValue::Set(tenant_id.clone()),
+                    task_id: sea_orm::ActiveValue::Set(task_id.clone()),
+                    depends_on_id: sea_orm::ActiveValue::Set(depends_on_id.clone()),
+                    created_by_id: sea_orm::ActiveValue::Set(Some(creator_id)),
+                    created_at: sea_orm::ActiveValue::Set(Utc::now()),
+                }
+                .insert(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("创建任务依赖失败: {e}")))?;
+
+                touch_tenant_signal(
+                    txn,
+                    &tenant_id,
+                    TenantSignalKind::TasksChanged,
+                    Some(tasks_changed_payload("updated", &task_model)),
+                )
+                .await?;
+
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    Ok(Json(load_dependencies(&state, &task_id).await?))
+}
+
+async fn remove_dependency(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path((task_id, depends_on_id)): Path<(String, String)>,
+) -> AppResult<StatusCode> {
+    let (task_model, scope) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskEdit).await?;
+    Uuid::parse_str(&depends_on_id).map_err(|_| AppError::Validation("dependsOnId 无效".to_string()))?;
+
+    state
+        .db
+        .transaction(|txn| {
+            let tenant_id = scope.tenant_id().to_string();
+            Box::pin(async move {
+                let res = task_dependency::Entity::delete_many()
+                    .filter(task_dependency::Column::TaskId.eq(&task_model.id))
+                    .filter(task_dependency::Column::DependsOnId.eq(&depends_on_id))
+                    .exec(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("删除任务依赖失败: {e}")))?;
+
+                if res.rows_affected == 0 {
+                    return Err(AppError::NotFound("任务依赖不存在".to_string()));
+                }
+
+                touch_tenant_signal(
+                    txn,
+                    &tenant_id,
+                    TenantSignalKind::TasksChanged,
+                    Some(tasks_changed_payload("updated", &task_model)),
+                )
+                .await?;
+
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 案件关键路径
+///
+/// GET /api/v1/cases/:id/tasks/critical-path
+pub async fn case_critical_path(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(case_id): Path<String>,
+) -> AppResult<Json<CriticalPathResponse>> {
+    Uuid::parse_str(&case_id).map_err(|_| AppError::Validation("案件ID 无效".to_string()))?;
+
+    let role = current_user.model.role.clone();
+    crate::security::permissions::require_permission(role.clone(), Permission::TaskView)?;
+    require_case_access(&state, &case_id, current_user.id(), role, Permission::CaseView).await?;
+
+    // 仅未完成任务参与剩余工期计算
+    let open_tasks = task::Entity::find()
+        .filter(task::Column::CaseId.eq(&case_id))
+        .filter(task::Column::Status.ne(task::TaskStatus::Done))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?;
+
+    let ids: Vec<String> = open_tasks.iter().map(|t| t.id.clone()).collect();
+    let edges: Vec<(String, String)> = if ids.is_empty() {
+        vec![]
+    } else {
+        task_dependency::Entity::find()
+            .filter(task_dependency::Column::TaskId.is_in(ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询任务依赖失败: {e}")))?
+            .into_iter()
+            .map(|d| (d.task_id, d.depends_on_id))
+            .collect()
+    };
+
+    let nodes: Vec<(String, f64)> =
+        open_tasks.iter().map(|t| (t.id.clone(), t.estimated_hours.unwrap_or(0.0))).collect();
+    let chain_ids = longest_chain(&nodes, &edges);
+
+    let by_id: HashMap<String, task::Model> = open_tasks.into_iter().map(|t| (t.id.clone(), t)).collect();
+    let now = Utc::now();
+    let mut cumulative = 0.0_f64;
+    let mut chain: Vec<CriticalPathItem> = Vec::with_capacity(chain_ids.len());
+    for id in chain_ids {
+        let Some(t) = by_id.get(&id) else { continue };
+        let hours = t.estimated_hours.unwrap_or(0.0).max(0.0);
+        cumulative += hours;
+        let minutes = (cumulative / WORK_HOURS_PER_DAY * 24.0 * 60.0).round() as i64;
+        let projected_finish = now + Duration::minutes(minutes);
+        chain.push(CriticalPathItem {
+            task_id: t.id.clone(),
+            title: t.title.clone(),
+            status: t.status.to_value(),
+            estimated_hours: hours,
+            cumulative_hours: cumulative,
+            due_date: t.due_date,
+            projected_finish,
+            at_risk: t.due_date.is_some_and(|due| projected_finish > due),
+        });
+    }
+
+    Ok(Json(CriticalPathResponse {
+        case_id,
+        total_hours: cumulative,
+        projected_finish: chain.last().map(|c| c.projected_finish),
+        at_risk: chain.iter().any(|c| c.at_risk),
+        chain,
+    }))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/:id/dependencies", get(list_dependencies).post(add_dependency))
+        .route("/:id/dependencies/:depends_on_id", delete(remove_dependency))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(task: &str, depends_on: &str) -> (String, String) {
+        (task.to_string(), depends_on.to_string())
+    }
+
+    #[test]
+    fn detects_direct_and_transitive_cycles() {
+        let edges = vec![edge("b", "a"), edge("c", "b")];
+        assert!(would_create_cycle(&edges, "a", "a"));
+        assert!(would_create_cycle(&edges, "a", "b"));
+        assert!(would_create_cycle(&edges, "a", "c"));
+        assert!(!would_create_cycle(&edges, "c", "a"));
+        assert!(!would_create_cycle(&edges, "d", "c"));
+    }
+
+    #[test]
+    fn longest_chain_follows_heaviest_path() {
+        // a(2) -> b(1) -> d(4)
+        // a(2) -> c(5) -> d(4)
+        let nodes = vec![
+            ("a".to_string(), 2.0),
+            ("b".to_string(), 1.0),
+            ("c".to_string(), 5.0),
+            ("d".to_string(), 4.0),
+            ("e".to_string(), 3.0),
+        ];
+        let edges = vec![edge("b", "a"), edge("c", "a"), edge("d", "b"), edge("d", "c")];
+        assert_eq!(longest_chain(&nodes, &edges), vec!["a", "c", "d"]);
+    }
+
+    #[test]
+    fn longest_chain_handles_empty_and_isolated_nodes() {
+        assert!(longest_chain(&[], &[]).is_empty());
+        let nodes = vec![("x".to_string(), 1.0), ("y".to_string(), 3.0)];
+        assert_eq!(longest_chain(&nodes, &[]), vec!["y"]);
+    }
+}
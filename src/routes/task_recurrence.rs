@@ -0,0 +1,340 @@
+//! 周期任务路由模块（RRULE 系列）
+//!
+//! - 设置/编辑：`PUT /tasks/:id/recurrence`（任务需有 dueDate，作为系列 DTSTART）
+//! - 查看：`GET /tasks/:id/recurrence` 返回系列信息与后续若干次预览
+//! - 结束：`DELETE /tasks/:id/recurrence`（保留已生成实例，不再生成新实例）
+//! - 生成：某实例推进到 DONE 时，在同一事务内按规则生成下一实例（recurrenceIndex 唯一去重）
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::db::AppState;
+use crate::entity::tenant_signal::TenantSignalKind;
+use crate::entity::{task, task_recurrence};
+use crate::error::{AppError, AppResult};
+use crate::realtime::tenant_signal::touch_tenant_signal;
+use crate::routes::tasks::{find_task_with_access, next_task_order, tasks_changed_payload, TaskOwner};
+use crate::scheduling::rrule::RecurrenceRule;
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::Permission;
+use crate::security::validation::ValidatedJson;
+
+const PREVIEW_LIMIT: usize = 5;
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRecurrenceRequest {
+    /// RRULE 文本，如 `FREQ=MONTHLY;BYMONTHDAY=15`
+    #[validate(length(min = 1, max = 256, message = "rule 长度不合法"))]
+    pub rule: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceResponse {
+    pub task_id: String,
+    pub recurrence_id: Option<String>,
+    pub recurrence_index: Option<i32>,
+    pub rule: Option<String>,
+    pub anchor_date: Option<DateTime<Utc>>,
+    pub occurrence_count: i32,
+    pub active: bool,
+    pub ended_at: Option<DateTime<Utc>>,
+    /// 本实例之后的预计发生时间（系列已结束时为空）
+    pub upcoming: Vec<DateTime<Utc>>,
+}
+
+fn parse_rule(raw: &str) -> AppResult<RecurrenceRule> {
+    raw.parse::<RecurrenceRule>()
+}
+
+fn build_response(task_model: &task::Model, series: Option<task_recurrence::Model>) -> RecurrenceResponse {
+    let Some(series) = series else {
+        return RecurrenceResponse {
+            task_id: task_model.id.clone(),
+            recurrence_id: None,
+            recurrence_index: None,
+            rule: None,
+            anchor_date: None,
+            occurrence_count: 0,
+            active: false,
+            ended_at: None,
+            upcoming: vec![],
+        };
+    };
+
+    let active = series.ended_at.is_none();
+    let upcoming = match (active, parse_rule(&series.rule)) {
+        (true, Ok(rule)) => {
+            let after = task_model.due_date.unwrap_or(series.anchor_date);
+            let next_index = task_model.recurrence_index.unwrap_or(1).max(1) as u32 + 1;
+            rule.preview(series.anchor_date, after, next_index, PREVIEW_LIMIT)
+        }
+        _ => vec![],
+    };
+
+    RecurrenceResponse {
+        task_id: task_model.id.clone(),
+        recurrence_id: Some(series.id),
+        recurrence_index: task_model.recurrence_index,
+        rule: Some(series.rule),
+        anchor_date: Some(series.anchor_date),
+        occurrence_count: series.occurrence_count,
+        active,
+        ended_at: series.ended_at,
+        upcoming,
+    }
+}
+
+async fn find_series<C: ConnectionTrait>(db: &C, task_model: &task::Model) -> AppResult<Option<task_recurrence::Model>> {
+    let Some(recurrence_id) = task_model.recurrence_id.as_deref() else {
+        return Ok(None);
+    };
+    task_recurrence::Entity::find_by_id(recurrence_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询周期任务系列失败: {e}")))
+}
+
+/// 实例完成后生成下一实例（需在事务内调用；系列已结束/超出 COUNT 或 UNTIL/已生成时返回 None）
+///
+/// 复制标题、描述、优先级、负责人、阶段、泳道、任务类型、预估工时与检查清单模板（勾选状态重置）。
+pub(crate) async fn spawn_next_occurrence<C: ConnectionTrait>(
+    txn: &C,
+    completed: &task::Model,
+) -> AppResult<Option<task::Model>> {
+    let Some(recurrence_id) = completed.recurrence_id.as_deref() else {
+        return Ok(None);
+    };
+
+    // 行锁串行化同一系列的生成，避免并发完成重复生成
+    let Some(series) = task_recurrence::Entity::find_by_id(recurrence_id)
+        .lock_exclusive()
+        .one(txn)
+        .await
+        .map_err(|e| AppError::Database(format!("查询周期任务系列失败: {e}")))?
+    else {
+        return Ok(None);
+    };
+    if series.ended_at.is_some() {
+        return Ok(None);
+    }
+
+    let next_index = completed.recurrence_index.unwrap_or(1).max(1) + 1;
+    let already_spawned = task::Entity::find()
+        .filter(task::Column::RecurrenceId.eq(recurrence_id))
+        .filter(task::Column::RecurrenceIndex.eq(next_index))
+        .one(txn)
+        .await
+        .map_err(|e| AppError::Database(format!("查询周期任务实例失败: {e}")))?
+        .is_some();
+    if already_spawned {
+        return Ok(None);
+    }
+
+    let rule = parse_rule(&series.rule)?;
+    let after = completed.due_date.unwrap_or(series.anchor_date);
+    let next_due = if rule.allows_index(next_index as u32) { rule.next_after(series.anchor_date, after) } else { None };
+
+    let now = Utc::now();
+    let mut series_active: task_recurrence::ActiveModel = series.clone().into();
+    series_active.updated_at = sea_orm::ActiveValue::Set(now);
+
+    let Some(next_due) = next_due else {
+        // 规则已耗尽：自动结束系列
+        series_active.ended_at = sea_orm::ActiveValue::Set(Some(now));
+        series_active
+            .update(txn)
+            .await
+            .map_err(|e| AppError::Database(format!("结束周期任务系列失败: {e}")))?;
+        return Ok(None);
+    };
+
+    let checklist: Vec<task::ChecklistItem> = task::parse_checklist(completed.checklist.as_ref())
+        .into_iter()
+        .map(|item| task::ChecklistItem {
+            id: Uuid::new_v4().to_string(),
+            done: false,
+            done_by: None,
+            done_at: None,
+            ..item
+        })
+        .collect();
+    let checklist = if checklist.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_value(&checklist).map_err(|e| AppError::Internal(format!("序列化检查清单失败: {e}")))?)
+    };
+
+    let status = task::TaskStatus::Todo;
+    let owner = TaskOwner::of(completed)?;
+    let order = next_task_order(txn, &owner, &status, completed.swimlane.as_deref()).await?;
+
+    let inserted = task::ActiveModel {
+        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+        tenant_id: sea_orm::ActiveValue::Set(series.tenant_id.clone()),
+        case_id: sea_orm::ActiveValue::Set(completed.case_id.clone()),
+        project_id: sea_orm::ActiveValue::Set(completed.project_id.clone()),
+        title: sea_orm::ActiveValue::Set(completed.title.clone()),
+        description: sea_orm::ActiveValue::Set(completed.description.clone()),
+        status: sea_orm::ActiveValue::Set(status),
+        priority: sea_orm::ActiveValue::Set(completed.priority.clone()),
+        swimlane: sea_orm::ActiveValue::Set(completed.swimlane.clone()),
+        order: sea_orm::ActiveValue::Set(order),
+        checklist: sea_orm::ActiveValue::Set(checklist),
+        due_date: sea_orm::ActiveValue::Set(Some(next_due)),
+        stage: sea_orm::ActiveValue::Set(completed.stage.clone()),
+        task_type: sea_orm::ActiveValue::Set(completed.task_type.clone()),
+        estimated_hours: sea_orm::ActiveValue::Set(completed.estimated_hours),
+        assignee_id: sea_orm::ActiveValue::Set(completed.assignee_id.clone()),
+        recurrence_id: sea_orm::ActiveValue::Set(Some(series.id.clone())),
+        recurrence_index: sea_orm::ActiveValue::Set(Some(next_index)),
+        created_at: sea_orm::ActiveValue::Set(now),
+        updated_at: sea_orm::ActiveValue::Set(now),
+        ..Default::default()
+    }
+    .insert(txn)
+    .await
+    .map_err(|e| AppError::Database(format!("生成周期任务实例失败: {e}")))?;
+
+    series_active.occurrence_count = sea_orm::ActiveValue::Set(series.occurrence_count.max(next_index));
+    series_active
+        .update(txn)
+        .await
+        .map_err(|e| AppError::Database(format!("更新周期任务系列失败: {e}")))?;
+
+    Ok(Some(inserted))
+}
+
+async fn get_recurrence(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(task_id): Path<String>,
+) -> AppResult<Json<RecurrenceResponse>> {
+    let (task_model, _) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskView).await?;
+    let series = find_series(&state.db, &task_model).await?;
+    Ok(Json(build_response(&task_model, series)))
+}
+
+async fn set_recurrence(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(task_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<SetRecurrenceRequest>,
+) -> AppResult<Json<RecurrenceResponse>> {
+    let (task_model, scope) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskEdit).await?;
+    let rule = parse_rule(&payload.rule)?.to_string();
+    let creator_id = current_user.id().to_string();
+
+    let (task_model, series) = state
+        .db
+        .transaction(|txn| {
+            let tenant_id = scope.tenant_id().to_string();
+            Box::pin(async move {
+                let now = Utc::now();
+
+                if let Some(series) = find_series(txn, &task_model).await? {
+                    // 编辑系列：仅修改规则，锚点与已生成实例保持不变
+                    if series.ended_at.is_some() {
+                        return Err(AppError::Validation("周期任务系列已结束，无法编辑".to_string()));
+                    }
+                    let mut active: task_recurrence::ActiveModel = series.into();
+                    active.rule = sea_orm::ActiveValue::Set(rule);
+                    active.updated_at = sea_orm::ActiveValue::Set(now);
+                    let series = active
+                        .update(txn)
+                        .await
+                        .map_err(|e| AppError::Database(format!("更新周期任务系列失败: {e}")))?;
+                    return Ok((task_model, series));
+                }
+
+                let anchor_date = task_model
+                    .due_date
+                    .ok_or_else(|| AppError::Validation("设置周期规则前需先设置截止日期 dueDate".to_string()))?;
+
+                let series = task_recurrence::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    tenant_id: sea_orm::ActiveValue::Set(tenant_id.clone()),
+                    rule: sea_orm::ActiveValue::Set(rule),
+                    anchor_date: sea_orm::ActiveValue::Set(anchor_date),
+                    occurrence_count: sea_orm::ActiveValue::Set(1),
+                    ended_at: sea_orm::ActiveValue::Set(None),
+                    created_by_id: sea_orm::ActiveValue::Set(Some(creator_id)),
+                    created_at: sea_orm::ActiveValue::Set(now),
+                    updated_at: sea_orm::ActiveValue::Set(now),
+                }
+                .insert(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("创建周期任务系列失败: {e}")))?;
+
+                let mut active: task::ActiveModel = task_model.into();
+                active.recurrence_id = sea_orm::ActiveValue::Set(Some(series.id.clone()));
+                active.recurrence_index = sea_orm::ActiveValue::Set(Some(1));
+                active.updated_at = sea_orm::ActiveValue::Set(now);
+                let task_model = active
+                    .update(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("更新任务失败: {e}")))?;
+
+                touch_tenant_signal(
+                    txn,
+                    &tenant_id,
+                    TenantSignalKind::TasksChanged,
+                    Some(tasks_changed_payload("updated", &task_model)),
+                )
+                .await?;
+
+                Ok((task_model, series))
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    Ok(Json(build_response(&task_model, Some(series))))
+}
+
+async fn end_recurrence(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(task_id): Path<String>,
+) -> AppResult<StatusCode> {
+    let (task_model, _) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskEdit).await?;
+
+    let series = find_series(&state.db, &task_model)
+        .await?
+        .ok_or_else(|| AppError::NotFound("该任务不属于任何周期系列".to_string()))?;
+    if series.ended_at.is_some() {
+        return Ok(StatusCode::NO_CONTENT);
+    }
+
+    let now = Utc::now();
+    let mut active: task_recurrence::ActiveModel = series.into();
+    active.ended_at = sea_orm::ActiveValue::Set(Some(now));
+    active.updated_at = sea_orm::ActiveValue::Set(now);
+    active
+        .update(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("结束周期任务系列失败: {e}")))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/:id/recurrence", get(get_recurrence).put(set_recurrence).delete(end_recurrence))
+}
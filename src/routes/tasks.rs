@@ -3,8 +3,11 @@
 //! 对齐 Web 主线 `lawclick-next/src/actions/tasks-crud.ts` 的核心行为：
 //! - 鉴权：JWT Claims
 //! - 权限：`task:create` / `task:edit` + `case:view`
-//! - 可见性：按案件可见性过滤（originator/handler/members）
+//! - 归属：任务属于案件（caseId）或非案件项目（projectId），二者其一
+//! - 可见性：案件任务按案件可见性过滤（originator/handler/members）；项目任务按项目成员及 `ProjectMember.role`
 //! - 持久化：真实写入 PostgreSQL（与 Prisma 同库）
+//! - 实时：任务增删改与 `TenantSignal(TASKS_CHANGED)` 递增在同一事务内提交
+//! - 周期任务：实例推进到 DONE 时同事务生成下一实例（见 `task_recurrence`）
 
 use axum::{
     extract::{Path, Query, State},
@@ -14,16 +17,24 @@ use axum::{
     Router,
 };
 use chrono::{DateTime, Utc};
-use sea_orm::{ActiveEnum, ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, TransactionTrait,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::db::AppState;
-use crate::entity::task;
+use crate::entity::user::Role;
+use crate::entity::{case, project, task};
+use crate::entity::tenant_signal::TenantSignalKind;
 use crate::error::{AppError, AppResult};
+use crate::realtime::tenant_signal::touch_tenant_signal;
 use crate::security::case_access::require_case_access;
+use crate::security::project_access::require_project_access;
 use crate::security::current_user::CurrentUser;
 use crate::security::permissions::{require_permission, Permission};
 use crate::security::validation::{require_non_empty, ValidatedJson};
@@ -34,7 +45,8 @@ const TASK_POSITION_GAP: i32 = 1024;
 #[serde(rename_all = "camelCase")]
 pub struct TaskResponse {
     pub id: String,
-    pub case_id: String,
+    pub case_id: Option<String>,
+    pub project_id: Option<String>,
     pub title: String,
     pub description: Option<String>,
     pub status: String,
@@ -47,15 +59,38 @@ pub struct TaskResponse {
     pub estimated_hours: Option<f64>,
     pub order: i32,
     pub assignee_id: Option<String>,
+    pub checklist: Vec<task::ChecklistItem>,
+    pub checklist_progress: ChecklistProgress,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistProgress {
+    pub total: usize,
+    pub done: usize,
+    /// 完成百分比（0-100，整数）
+    pub percent: u8,
+}
+
+impl ChecklistProgress {
+    pub fn from_items(items: &[task::ChecklistItem]) -> Self {
+        let total = items.len();
+        let done = items.iter().filter(|i| i.done).count();
+        let percent = (done * 100).checked_div(total).unwrap_or(0) as u8;
+        Self { total, done, percent }
+    }
+}
+
 impl From<task::Model> for TaskResponse {
     fn from(model: task::Model) -> Self {
+        let checklist = task::parse_checklist(model.checklist.as_ref());
+        let checklist_progress = ChecklistProgress::from_items(&checklist);
         Self {
             id: model.id,
             case_id: model.case_id,
+            project_id: model.project_id,
             title: model.title,
             description: model.description,
             status: model.status.to_value(),
@@ -68,6 +103,8 @@ impl From<task::Model> for TaskResponse {
             estimated_hours: model.estimated_hours,
             order: model.order,
             assignee_id: model.assignee_id,
+            checklist,
+            checklist_progress,
             created_at: model.created_at,
             updated_at: model.updated_at,
         }
@@ -77,14 +114,19 @@ impl From<task::Model> for TaskResponse {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskListQuery {
-    pub case_id: String,
+    pub case_id: Option<String>,
+    pub project_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTaskRequest {
+    /// caseId / projectId 二选一
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub case_id: Option<String>,
+
     #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
-    pub case_id: String,
+    pub project_id: Option<String>,
 
     #[validate(length(min = 1, max = 200, message = "任务标题不能为空"))]
     pub title: String,
@@ -146,7 +188,129 @@ pub struct UpdateTaskRequest {
     pub order: Option<i32>,
 }
 
-fn parse_task_status(raw: Option<&str>) -> AppResult<task::TaskStatus> {
+/// 任务归属：案件或非案件项目（二者其一）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum TaskOwner {
+    Case(String),
+    Project(String),
+}
+
+impl TaskOwner {
+    pub(crate) fn of(model: &task::Model) -> AppResult<Self> {
+        match (model.case_id.as_deref(), model.project_id.as_deref()) {
+            (Some(case_id), _) => Ok(Self::Case(case_id.to_string())),
+            (None, Some(project_id)) => Ok(Self::Project(project_id.to_string())),
+            (None, None) => Err(AppError::Internal(format!("任务 {} 未关联案件或项目", model.id))),
+        }
+    }
+
+    /// 仅在 caseId / projectId 恰好提供一个时返回归属
+    pub(crate) fn from_ids(case_id: Option<&str>, project_id: Option<&str>) -> AppResult<Self> {
+        let case_id = case_id.map(|s| s.trim()).filter(|s| !s.is_empty());
+        let project_id = project_id.map(|s| s.trim()).filter(|s| !s.is_empty());
+        let owner = match (case_id, project_id) {
+            (Some(id), None) => Self::Case(id.to_string()),
+            (None, Some(id)) => Self::Project(id.to_string()),
+            _ => return Err(AppError::Validation("caseId 与 projectId 需且仅需提供一个".to_string())),
+        };
+        Uuid::parse_str(owner.id()).map_err(|_| AppError::Validation("caseId/projectId 无效".to_string()))?;
+        Ok(owner)
+    }
+
+    pub(crate) fn id(&self) -> &str {
+        match self {
+            Self::Case(id) | Self::Project(id) => id,
+        }
+    }
+
+    pub(crate) fn case_id(&self) -> Option<String> {
+        match self {
+            Self::Case(id) => Some(id.clone()),
+            Self::Project(_) => None,
+        }
+    }
+
+    pub(crate) fn project_id(&self) -> Option<String> {
+        match self {
+            Self::Case(_) => None,
+            Self::Project(id) => Some(id.clone()),
+        }
+    }
+
+    /// 同一看板（案件/项目）内任务的过滤条件
+    pub(crate) fn condition(&self) -> Condition {
+        match self {
+            Self::Case(id) => Condition::all().add(task::Column::CaseId.eq(id.as_str())),
+            Self::Project(id) => Condition::all().add(task::Column::ProjectId.eq(id.as_str())),
+        }
+    }
+}
+
+/// 已通过可见性校验的任务归属
+#[derive(Debug, Clone)]
+pub(crate) enum TaskScope {
+    Case(case::Model),
+    Project(project::Model),
+}
+
+impl TaskScope {
+    pub(crate) fn tenant_id(&self) -> &str {
+        match self {
+            Self::Case(c) => &c.tenant_id,
+            Self::Project(p) => &p.tenant_id,
+        }
+    }
+}
+
+/// 校验用户对任务归属的访问：功能权限 + 案件可见性（case:view）或项目成员角色
+pub(crate) async fn require_task_owner_access(
+    state: &AppState,
+    owner: &TaskOwner,
+    user_id: &str,
+    tenant_id: &str,
+    role: Role,
+    permission: Permission,
+) -> AppResult<TaskScope> {
+    match owner {
+        TaskOwner::Case(case_id) => {
+            require_permission(role.clone(), permission)?;
+            let case_model = require_case_access(state, case_id, user_id, role, Permission::CaseView).await?;
+            Ok(TaskScope::Case(case_model))
+        }
+        TaskOwner::Project(project_id) => {
+            let project_model = require_project_access(state, project_id, user_id, tenant_id, role, permission).await?;
+            Ok(TaskScope::Project(project_model))
+        }
+    }
+}
+
+/// 计算当前列/泳道末尾的 order（最大 order + 间隔）
+pub(crate) async fn next_task_order<C: ConnectionTrait>(
+    db: &C,
+    owner: &TaskOwner,
+    status: &task::TaskStatus,
+    swimlane: Option<&str>,
+) -> AppResult<i32> {
+    let mut max_query = task::Entity::find()
+        .filter(owner.condition())
+        .filter(task::Column::Status.eq(status.clone()));
+    max_query = match swimlane {
+        Some(v) => max_query.filter(task::Column::Swimlane.eq(v)),
+        None => max_query.filter(task::Column::Swimlane.is_null()),
+    };
+
+    let max_order = max_query
+        .order_by_desc(task::Column::Order)
+        .one(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询任务排序失败: {e}")))?
+        .map(|t| t.order)
+        .unwrap_or(0);
+
+    Ok(max_order.saturating_add(TASK_POSITION_GAP))
+}
+
+pub(crate) fn parse_task_status(raw: Option<&str>) -> AppResult<task::TaskStatus> {
     match raw.map(|s| s.trim()).filter(|s| !s.is_empty()) {
         None => Ok(task::TaskStatus::Todo),
         Some(v) => match v.to_uppercase().as_str() {
@@ -159,7 +323,7 @@ fn parse_task_status(raw: Option<&str>) -> AppResult<task::TaskStatus> {
     }
 }
 
-fn parse_task_priority(raw: Option<&str>) -> AppResult<task::TaskPriority> {
+pub(crate) fn parse_task_priority(raw: Option<&str>) -> AppResult<task::TaskPriority> {
     match raw.map(|s| s.trim()).filter(|s| !s.is_empty()) {
         None => Ok(task::TaskPriority::P2Medium),
         Some(v) => match v.to_uppercase().as_str() {
@@ -172,19 +336,54 @@ fn parse_task_priority(raw: Option<&str>) -> AppResult<task::TaskPriority> {
     }
 }
 
+pub(crate) fn tasks_changed_payload(action: &str, model: &task::Model) -> serde_json::Value {
+    json!({
+        "action": action,
+        "taskId": model.id,
+        "caseId": model.case_id,
+        "projectId": model.project_id,
+    })
+}
+
+/// 查询任务并校验：功能权限 + 所属案件/项目可见性（返回任务与归属）
+pub(crate) async fn find_task_with_access(
+    state: &AppState,
+    current_user: &CurrentUser,
+    task_id: &str,
+    permission: Permission,
+) -> AppResult<(task::Model, TaskScope)> {
+    Uuid::parse_str(task_id).map_err(|_| AppError::Validation("任务ID 无效".to_string()))?;
+
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), permission)?;
+
+    let task_model = task::Entity::find_by_id(task_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound(format!("任务 {} 不存在", task_id)))?;
+
+    let owner = TaskOwner::of(&task_model)?;
+    let tenant_id = &current_user.model.active_tenant_id;
+    let scope = require_task_owner_access(state, &owner, current_user.id(), tenant_id, role, permission).await?;
+
+    Ok((task_model, scope))
+}
+
 async fn list_case_tasks(
     State(state): State<Arc<AppState>>,
     current_user: CurrentUser,
     Query(query): Query<TaskListQuery>,
 ) -> AppResult<Json<Vec<TaskResponse>>> {
-    Uuid::parse_str(&query.case_id).map_err(|_| AppError::Validation("case_id 无效".to_string()))?;
+    let owner = TaskOwner::from_ids(query.case_id.as_deref(), query.project_id.as_deref())?;
 
+    // 读任务必须具备案件可见性 / 项目成员身份
     let role = current_user.model.role.clone();
-    // 读任务必须具备案件可见性
-    require_case_access(&state, &query.case_id, current_user.id(), role, Permission::CaseView).await?;
+    let tenant_id = &current_user.model.active_tenant_id;
+    require_task_owner_access(&state, &owner, current_user.id(), tenant_id, role, Permission::TaskView).await?;
 
     let tasks = task::Entity::find()
-        .filter(task::Column::CaseId.eq(&query.case_id))
+        .filter(owner.condition())
         .order_by_asc(task::Column::Order)
         .all(&state.db)
         .await
@@ -198,19 +397,7 @@ async fn get_task(
     current_user: CurrentUser,
     Path(task_id): Path<String>,
 ) -> AppResult<Json<TaskResponse>> {
-    Uuid::parse_str(&task_id).map_err(|_| AppError::Validation("任务ID 无效".to_string()))?;
-
-    let role = current_user.model.role.clone();
-    require_permission(role.clone(), Permission::CaseView)?;
-
-    let task_model = task::Entity::find_by_id(&task_id)
-        .one(&state.db)
-        .await
-        .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
-        .ok_or_else(|| AppError::NotFound(format!("任务 {} 不存在", task_id)))?;
-
-    require_case_access(&state, &task_model.case_id, current_user.id(), role, Permission::CaseView).await?;
-
+    let (task_model, _) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskView).await?;
     Ok(Json(TaskResponse::from(task_model)))
 }
 
@@ -219,10 +406,12 @@ async fn create_task(
     current_user: CurrentUser,
     ValidatedJson(payload): ValidatedJson<CreateTaskRequest>,
 ) -> AppResult<Json<TaskResponse>> {
+    let owner = TaskOwner::from_ids(payload.case_id.as_deref(), payload.project_id.as_deref())?;
     let role = current_user.model.role.clone();
-    require_permission(role.clone(), Permission::TaskCreate)?;
-    // 与主线一致：创建任务仍需具备案件可见性（case:view）
-    require_case_access(&state, &payload.case_id, current_user.id(), role, Permission::CaseView).await?;
+    // 与主线一致：创建任务仍需具备案件可见性（case:view）；项目任务需为非观察员成员
+    let tenant_id = &current_user.model.active_tenant_id;
+    let scope =
+        require_task_owner_access(&state, &owner, current_user.id(), tenant_id, role, Permission::TaskCreate).await?;
 
     let title = require_non_empty(&payload.title, "title", 200)?;
     let status = parse_task_status(payload.status.as_deref())?;
@@ -232,47 +421,54 @@ async fn create_task(
     let stage = payload.stage.as_deref().map(|s| s.trim()).filter(|s| !s.is_empty()).map(|s| s.to_string());
     let task_type = payload.task_type.as_deref().map(|s| s.trim()).filter(|s| !s.is_empty()).map(|s| s.to_string());
 
-    // 计算当前列/泳道最大 order
-    let mut max_query = task::Entity::find()
-        .filter(task::Column::CaseId.eq(&payload.case_id))
-        .filter(task::Column::Status.eq(status.clone()));
-    max_query = match swimlane.as_deref() {
-        Some(v) => max_query.filter(task::Column::Swimlane.eq(v)),
-        None => max_query.filter(task::Column::Swimlane.is_null()),
-    };
-
-    let max_order = max_query
-        .order_by_desc(task::Column::Order)
-        .one(&state.db)
-        .await
-        .map_err(|e| AppError::Database(format!("查询任务排序失败: {e}")))?
-        .map(|t| t.order)
-        .unwrap_or(0);
-
-    let order = max_order.saturating_add(TASK_POSITION_GAP);
-
-    let active = task::ActiveModel {
-        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
-        case_id: sea_orm::ActiveValue::Set(payload.case_id.clone()),
-        title: sea_orm::ActiveValue::Set(title),
-        description: sea_orm::ActiveValue::Set(payload.description),
-        status: sea_orm::ActiveValue::Set(status),
-        priority: sea_orm::ActiveValue::Set(priority),
-        swimlane: sea_orm::ActiveValue::Set(swimlane),
-        order: sea_orm::ActiveValue::Set(order),
-        due_date: sea_orm::ActiveValue::Set(payload.due_date),
-        stage: sea_orm::ActiveValue::Set(stage),
-        task_type: sea_orm::ActiveValue::Set(task_type),
-        document_id: sea_orm::ActiveValue::Set(payload.document_id),
-        estimated_hours: sea_orm::ActiveValue::Set(payload.estimated_hours),
-        assignee_id: sea_orm::ActiveValue::Set(payload.assignee_id),
-        ..Default::default()
-    };
-
-    let inserted = active
-        .insert(&state.db)
+    let inserted = state
+        .db
+        .transaction(|txn| {
+            let tenant_id = scope.tenant_id().to_string();
+            Box::pin(async move {
+                let order = next_task_order(txn, &owner, &status, swimlane.as_deref()).await?;
+
+                let active = task::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    tenant_id: sea_orm::ActiveValue::Set(tenant_id.clone()),
+                    case_id: sea_orm::ActiveValue::Set(owner.case_id()),
+                    project_id: sea_orm::ActiveValue::Set(owner.project_id()),
+                    title: sea_orm::ActiveValue::Set(title),
+                    description: sea_orm::ActiveValue::Set(payload.description),
+                    status: sea_orm::ActiveValue::Set(status),
+                    priority: sea_orm::ActiveValue::Set(priority),
+                    swimlane: sea_orm::ActiveValue::Set(swimlane),
+                    order: sea_orm::ActiveValue::Set(order),
+                    due_date: sea_orm::ActiveValue::Set(payload.due_date),
+                    stage: sea_orm::ActiveValue::Set(stage),
+                    task_type: sea_orm::ActiveValue::Set(task_type),
+                    document_id: sea_orm::ActiveValue::Set(payload.document_id),
+                    estimated_hours: sea_orm::ActiveValue::Set(payload.estimated_hours),
+                    assignee_id: sea_orm::ActiveValue::Set(payload.assignee_id),
+                    ..Default::default()
+                };
+
+                let inserted = active
+                    .insert(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("创建任务失败: {e}")))?;
+
+                touch_tenant_signal(
+                    txn,
+                    &tenant_id,
+                    TenantSignalKind::TasksChanged,
+                    Some(tasks_changed_payload("created", &inserted)),
+                )
+                .await?;
+
+                Ok(inserted)
+            })
+        })
         .await
-        .map_err(|e| AppError::Database(format!("创建任务失败: {e}")))?;
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
 
     Ok(Json(TaskResponse::from(inserted)))
 }
@@ -283,20 +479,9 @@ async fn update_task(
     Path(task_id): Path<String>,
     ValidatedJson(payload): ValidatedJson<UpdateTaskRequest>,
 ) -> AppResult<Json<TaskResponse>> {
-    Uuid::parse_str(&task_id).map_err(|_| AppError::Validation("任务ID 无效".to_string()))?;
-
-    let role = current_user.model.role.clone();
-    require_permission(role.clone(), Permission::TaskEdit)?;
-
-    let existing = task::Entity::find_by_id(&task_id)
-        .one(&state.db)
-        .await
-        .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
-        .ok_or_else(|| AppError::NotFound(format!("任务 {} 不存在", task_id)))?;
-
-    require_case_access(&state, &existing.case_id, current_user.id(), role, Permission::CaseView).await?;
+    let (existing, scope) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskEdit).await?;
 
-    let mut active: task::ActiveModel = existing.into();
+    let mut active: task::ActiveModel = existing.clone().into();
 
     if let Some(title) = payload.title.as_deref() {
         active.title = sea_orm::ActiveValue::Set(require_non_empty(title, "title", 200)?);
@@ -306,9 +491,7 @@ async fn update_task(
         active.description = sea_orm::ActiveValue::Set(Some(require_non_empty(desc, "description", 5000)?));
     }
 
-    if let Some(status) = payload.status.as_deref() {
-        active.status = sea_orm::ActiveValue::Set(parse_task_status(Some(status))?);
-    }
+    let status = payload.status.as_deref().map(|s| parse_task_status(Some(s))).transpose()?;
 
     if let Some(priority) = payload.priority.as_deref() {
         active.priority = sea_orm::ActiveValue::Set(parse_task_priority(Some(priority))?);
@@ -348,10 +531,54 @@ async fn update_task(
 
     active.updated_at = sea_orm::ActiveValue::Set(Utc::now());
 
-    let updated = active
-        .update(&state.db)
+    let updated = state
+        .db
+        .transaction(|txn| {
+            let tenant_id = scope.tenant_id().to_string();
+            Box::pin(async move {
+                // 状态门禁基于锁定后的最新状态，与前置任务检查同一事务
+                let locked = task::Entity::find_by_id(&existing.id)
+                    .lock_exclusive()
+                    .one(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
+                    .ok_or_else(|| AppError::NotFound("任务不存在".to_string()))?;
+                let mut completed_now = false;
+                if let Some(status) = status {
+                    let advancing = matches!(status, task::TaskStatus::InProgress | task::TaskStatus::Done);
+                    if advancing && status != locked.status {
+                        super::task_dependencies::ensure_no_open_blockers(txn, &locked).await?;
+                    }
+                    completed_now = status == task::TaskStatus::Done && locked.status != task::TaskStatus::Done;
+                    active.status = sea_orm::ActiveValue::Set(status);
+                }
+
+                let updated = active
+                    .update(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("更新任务失败: {e}")))?;
+
+                // 周期任务：本次完成后生成下一实例
+                if completed_now {
+                    super::task_recurrence::spawn_next_occurrence(txn, &updated).await?;
+                }
+
+                touch_tenant_signal(
+                    txn,
+                    &tenant_id,
+                    TenantSignalKind::TasksChanged,
+                    Some(tasks_changed_payload("updated", &updated)),
+                )
+                .await?;
+
+                Ok(updated)
+            })
+        })
         .await
-        .map_err(|e| AppError::Database(format!("更新任务失败: {e}")))?;
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
 
     Ok(Json(TaskResponse::from(updated)))
 }
@@ -361,23 +588,34 @@ async fn delete_task(
     current_user: CurrentUser,
     Path(task_id): Path<String>,
 ) -> AppResult<StatusCode> {
-    Uuid::parse_str(&task_id).map_err(|_| AppError::Validation("任务ID 无效".to_string()))?;
-
-    let role = current_user.model.role.clone();
-    require_permission(role.clone(), Permission::TaskDelete)?;
-
-    let existing = task::Entity::find_by_id(&task_id)
-        .one(&state.db)
-        .await
-        .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
-        .ok_or_else(|| AppError::NotFound(format!("任务 {} 不存在", task_id)))?;
-
-    require_case_access(&state, &existing.case_id, current_user.id(), role, Permission::CaseView).await?;
-
-    task::Entity::delete_by_id(&task_id)
-        .exec(&state.db)
+    let (existing, scope) = find_task_with_access(&state, &current_user, &task_id, Permission::TaskDelete).await?;
+
+    state
+        .db
+        .transaction(|txn| {
+            let tenant_id = scope.tenant_id().to_string();
+            Box::pin(async move {
+                task::Entity::delete_by_id(&task_id)
+                    .exec(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("删除任务失败: {e}")))?;
+
+                touch_tenant_signal(
+                    txn,
+                    &tenant_id,
+                    TenantSignalKind::TasksChanged,
+                    Some(tasks_changed_payload("deleted", &existing)),
+                )
+                .await?;
+
+                Ok(())
+            })
+        })
         .await
-        .map_err(|e| AppError::Database(format!("删除任务失败: {e}")))?;
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -386,4 +624,9 @@ pub fn router() -> Router<Arc<AppState>> {
     Router::new()
         .route("/", get(list_case_tasks).post(create_task))
         .route("/:id", get(get_task).patch(update_task).delete(delete_task))
+        .merge(super::task_checklist::router())
+        .merge(super::task_dependencies::router())
+        .merge(super::task_recurrence::router())
+        .merge(super::task_comments::router())
+        .merge(super::task_bulk::router())
 }
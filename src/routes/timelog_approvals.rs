@@ -0,0 +1,684 @@
+//! 工时审核路由（合并到 `/timelogs`）
+//!
+//! - 审核人：可见工时所属案件且具备 `timelog:approve`，或为该案件承办人
+//! - 仅 COMPLETED 工时可审核；非 PARTNER / ADMIN 不可审核本人工时
+//! - 动作：通过（可同时调整）/ 退回（需填写原因，状态保持 COMPLETED）/ 调整（时长、描述、是否计费）
+//! - 退回后律师修改工时（updatedAt 晚于退回时间）即重新进入待审队列
+//! - 每次审核写入 `TimeLogReview`（before/after 快照 + 审核人），并通知工时所属律师
+//! - 批量：`POST /timelogs/approvals/bulk` 同一事务提交，逐条返回结果
+//! - 外出代理：审核人在 `OutOfOffice` 时段内设置了代理人时，代理人可按审核人的范围代为审核（不含本人工时），
+//!   留痕记为 reviewer 代 onBehalfOf；时段结束后自动交回（见 `security::delegation`）
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, Condition, DatabaseTransaction, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::db::AppState;
+use crate::entity::time_log_review::{self, TimeLogReviewAction};
+use crate::entity::user::Role;
+use crate::entity::{notification, time_log, user};
+use crate::error::{AppError, AppResult};
+use crate::security::case_access::{require_case_access, visible_case_ids};
+use crate::security::current_user::CurrentUser;
+use crate::security::delegation::active_delegators;
+use crate::security::permissions::{has_permission, Permission};
+use crate::security::validation::{require_non_empty, ValidatedJson};
+
+use super::billing::resolve_increment_rule;
+use super::case_budget::apply_case_billing_mode;
+use super::rate_cards::resolve_billing_rate;
+use super::timelogs::{compute_billing, ensure_no_overlap, TimeLogResponse, MAX_MANUAL_DURATION_SECS};
+
+const BULK_MAX_TIME_LOGS: usize = 200;
+const MAX_PENDING: u64 = 200;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingQuery {
+    pub case_id: Option<String>,
+    pub user_id: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// 审核时的调整项（均为可选）
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewAdjustment {
+    /// 调整后的时长（秒）
+    pub duration: Option<i64>,
+    #[validate(length(min = 1, max = 5000, message = "description 长度不合法"))]
+    pub description: Option<String>,
+    pub is_billable: Option<bool>,
+    #[validate(length(max = 2000, message = "note 长度不合法"))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectRequest {
+    #[validate(length(min = 1, max = 2000, message = "reason 长度不合法"))]
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BulkReviewAction {
+    Approve,
+    Reject,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkReviewRequest {
+    #[validate(length(min = 1, max = 200, message = "timeLogIds 数量需在 1-200 之间"))]
+    pub time_log_ids: Vec<String>,
+    pub action: BulkReviewAction,
+    #[validate(length(min = 1, max = 2000, message = "reason 长度不合法"))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkReviewItemResult {
+    pub time_log_id: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkReviewResponse {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkReviewItemResult>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeLogReviewResponse {
+    pub id: String,
+    pub time_log_id: String,
+    pub reviewer_id: String,
+    /// 代理审核时为被代理的审核人
+    pub on_behalf_of_id: Option<String>,
+    pub action: String,
+    pub reason: Option<String>,
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<time_log_review::Model> for TimeLogReviewResponse {
+    fn from(m: time_log_review::Model) -> Self {
+        Self {
+            id: m.id,
+            time_log_id: m.time_log_id,
+            reviewer_id: m.reviewer_id,
+            on_behalf_of_id: m.on_behalf_of_id,
+            action: m.action.to_value(),
+            reason: m.reason,
+            before: m.before,
+            after: m.after,
+            created_at: m.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ReviewDecision {
+    Approve(ReviewAdjustment),
+    Reject(String),
+    Adjust(ReviewAdjustment),
+}
+
+impl ReviewDecision {
+    fn action(&self) -> TimeLogReviewAction {
+        match self {
+            Self::Approve(_) => TimeLogReviewAction::Approve,
+            Self::Reject(_) => TimeLogReviewAction::Reject,
+            Self::Adjust(_) => TimeLogReviewAction::Adjust,
+        }
+    }
+}
+
+fn is_admin(role: &Role) -> bool {
+    matches!(role, Role::Partner | Role::Admin)
+}
+
+fn snapshot(m: &time_log::Model) -> serde_json::Value {
+    json!({
+        "status": m.status.to_value(),
+        "startTime": m.start_time,
+        "endTime": m.end_time,
+        "duration": m.duration,
+        "billedDuration": m.billed_duration,
+        "description": m.description,
+        "isBillable": m.is_billable,
+        "billingRate": m.billing_rate.map(|v| v.to_string()),
+        "billingAmount": m.billing_amount.map(|v| v.to_string()),
+        "writeOffAmount": m.write_off_amount.map(|v| v.to_string()),
+    })
+}
+
+/// 最近一次退回后律师是否尚未修改（仍处于“已退回”状态）
+async fn rejected_since_edit<C: sea_orm::ConnectionTrait>(db: &C, log: &time_log::Model) -> AppResult<bool> {
+    let latest = time_log_review::Entity::find()
+        .filter(time_log_review::Column::TimeLogId.eq(&log.id))
+        .order_by_desc(time_log_review::Column::CreatedAt)
+        .one(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询审核记录失败: {e}")))?;
+    Ok(is_rejected_since_edit(latest.as_ref(), log))
+}
+
+fn is_rejected_since_edit(latest: Option<&time_log_review::Model>, log: &time_log::Model) -> bool {
+    latest.is_some_and(|r| r.action == TimeLogReviewAction::Reject && r.created_at >= log.updated_at)
+}
+
+/// 批量查询每条工时最近一次审核记录（`DISTINCT ON ("timeLogId")`）
+async fn latest_reviews(
+    state: &AppState,
+    time_log_ids: Vec<String>,
+) -> AppResult<HashMap<String, time_log_review::Model>> {
+    if time_log_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    Ok(time_log_review::Entity::find()
+        .distinct_on([time_log_review::Column::TimeLogId])
+        .filter(time_log_review::Column::TimeLogId.is_in(time_log_ids))
+        .order_by_asc(time_log_review::Column::TimeLogId)
+        .order_by_desc(time_log_review::Column::CreatedAt)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询审核记录失败: {e}")))?
+        .into_iter()
+        .map(|r| (r.time_log_id.clone(), r))
+        .collect())
+}
+
+/// 校验 `user_id` 可审核该工时所属案件
+async fn check_reviewer(state: &AppState, user_id: &str, role: Role, log: &time_log::Model) -> AppResult<()> {
+    if log.user_id == user_id && !is_admin(&role) {
+        return Err(AppError::Forbidden("不能审核本人的工时".to_string()));
+    }
+    let Some(case_id) = log.case_id.as_deref() else {
+        return if is_admin(&role) { Ok(()) } else { Err(AppError::Forbidden("无权审核该工时".to_string())) };
+    };
+
+    let case_model = require_case_access(state, case_id, user_id, role.clone(), Permission::CaseView).await?;
+    if has_permission(role, Permission::TimeLogApprove) || case_model.handler_id.as_deref() == Some(user_id) {
+        return Ok(());
+    }
+    Err(AppError::Forbidden("缺少权限：timelog:approve（或非案件承办人）".to_string()))
+}
+
+/// 校验当前用户可审核该工时：本人具备审核资格，或代外出的审核人审核（返回被代理的审核人）
+async fn require_reviewer(
+    state: &AppState,
+    current_user: &CurrentUser,
+    log: &time_log::Model,
+) -> AppResult<Option<String>> {
+    let denied = match check_reviewer(state, current_user.id(), current_user.model.role.clone(), log).await {
+        Ok(()) => return Ok(None),
+        Err(err @ AppError::Forbidden(_)) => err,
+        Err(err) => return Err(err),
+    };
+    if log.user_id == current_user.id() {
+        return Err(denied);
+    }
+
+    let delegators =
+        active_delegators(state, &current_user.model.active_tenant_id, current_user.id(), Utc::now()).await?;
+    for (principal_id, principal_role) in delegators {
+        if principal_id == log.user_id {
+            continue;
+        }
+        match check_reviewer(state, &principal_id, principal_role, log).await {
+            Ok(()) => return Ok(Some(principal_id)),
+            Err(AppError::Forbidden(_)) => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Err(denied)
+}
+
+/// 可审核工时的案件范围；PARTNER / ADMIN 返回 None（不限）
+async fn reviewable_case_ids(state: &AppState, user_id: &str, role: &Role) -> AppResult<Option<Vec<String>>> {
+    if is_admin(role) {
+        return Ok(None);
+    }
+    if has_permission(role.clone(), Permission::TimeLogApprove) {
+        return visible_case_ids(state, user_id, role).await;
+    }
+    // 无 timelog:approve 时仅承办案件
+    let case_ids = crate::entity::case::Entity::find()
+        .filter(crate::entity::case::Column::HandlerId.eq(user_id))
+        .select_only()
+        .column(crate::entity::case::Column::Id)
+        .into_values::<String, crate::entity::case::Column>()
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询承办案件失败: {e}")))?;
+    Ok(Some(case_ids))
+}
+
+/// 在事务内执行单条审核：加锁复核状态 → 应用调整 → 写留痕 → 通知律师
+async fn apply_review(
+    txn: &DatabaseTransaction,
+    reviewer_id: &str,
+    on_behalf_of: Option<&str>,
+    time_log_id: &str,
+    owners: &HashMap<String, user::Model>,
+    warn_percents: &[u32],
+    decision: &ReviewDecision,
+) -> AppResult<time_log::Model> {
+    let existing = time_log::Entity::find_by_id(time_log_id)
+        .lock_exclusive()
+        .one(txn)
+        .await
+        .map_err(|e| AppError::Database(format!("查询工时记录失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("工时记录不存在".to_string()))?;
+    if existing.status != time_log::TimeLogStatus::Completed {
+        return Err(AppError::Validation("仅已完成（待审核）的工时可审核".to_string()));
+    }
+    if rejected_since_edit(txn, &existing).await? && !matches!(decision, ReviewDecision::Adjust(_)) {
+        return Err(AppError::Validation("该工时已退回，待律师修改后再审核".to_string()));
+    }
+
+    let before = snapshot(&existing);
+    let now = Utc::now();
+    let owner = owners.get(&existing.user_id).ok_or_else(|| AppError::NotFound("工时所属用户不存在".to_string()))?;
+
+    let updated = match decision {
+        ReviewDecision::Reject(_) => existing.clone(),
+        ReviewDecision::Approve(adj) | ReviewDecision::Adjust(adj) => {
+            let mut duration = existing.duration;
+            let mut end_time = existing.end_time;
+            if let Some(secs) = adj.duration {
+                if secs <= 0 || secs > MAX_MANUAL_DURATION_SECS {
+                    return Err(AppError::Validation("duration 需在 1 秒到 24 小时之间".to_string()));
+                }
+                let next_end = existing.start_time + Duration::seconds(secs);
+                if Some(next_end) != existing.end_time {
+                    ensure_no_overlap(txn, &existing.user_id, existing.start_time, next_end, Some(&existing.id))
+                        .await?;
+                }
+                duration = secs as i32;
+                end_time = Some(next_end);
+            }
+            let is_billable = adj.is_billable.unwrap_or(existing.is_billable);
+            let rule = resolve_increment_rule(txn, &existing.tenant_id, existing.case_id.as_deref()).await?;
+            let rate =
+                resolve_billing_rate(txn, owner, existing.case_id.as_deref(), existing.task_id.as_deref()).await?;
+            let billing = compute_billing(is_billable, existing.billing_rate, rate.rate, duration, &rule);
+            let billing = apply_case_billing_mode(
+                txn,
+                warn_percents,
+                reviewer_id,
+                existing.case_id.as_deref(),
+                Some(&existing.id),
+                billing,
+            )
+            .await?;
+
+            let mut active: time_log::ActiveModel = existing.clone().into();
+            if let Some(description) = adj.description.as_deref() {
+                active.description = sea_orm::ActiveValue::Set(require_non_empty(description, "description", 5000)?);
+            }
+            active.duration = sea_orm::ActiveValue::Set(duration);
+            active.end_time = sea_orm::ActiveValue::Set(end_time);
+            active.billed_duration = sea_orm::ActiveValue::Set(billing.billed_duration);
+            active.is_billable = sea_orm::ActiveValue::Set(is_billable);
+            active.billing_rate = sea_orm::ActiveValue::Set(billing.rate);
+            active.billing_amount = sea_orm::ActiveValue::Set(billing.amount);
+            active.write_off_amount = sea_orm::ActiveValue::Set(billing.write_off_amount);
+            if matches!(decision, ReviewDecision::Approve(_)) {
+                active.status = sea_orm::ActiveValue::Set(time_log::TimeLogStatus::Approved);
+            }
+            active.updated_at = sea_orm::ActiveValue::Set(now);
+            active.update(txn).await.map_err(|e| AppError::Database(format!("更新工时记录失败: {e}")))?
+        }
+    };
+
+    let reason = match decision {
+        ReviewDecision::Reject(reason) => Some(reason.clone()),
+        ReviewDecision::Approve(adj) | ReviewDecision::Adjust(adj) => adj.note.clone(),
+    };
+    time_log_review::ActiveModel {
+        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+        tenant_id: sea_orm::ActiveValue::Set(existing.tenant_id.clone()),
+        time_log_id: sea_orm::ActiveValue::Set(existing.id.clone()),
+        reviewer_id: sea_orm::ActiveValue::Set(reviewer_id.to_string()),
+        on_behalf_of_id: sea_orm::ActiveValue::Set(on_behalf_of.map(str::to_string)),
+        action: sea_orm::ActiveValue::Set(decision.action()),
+        reason: sea_orm::ActiveValue::Set(reason.clone()),
+        before: sea_orm::ActiveValue::Set(before),
+        after: sea_orm::ActiveValue::Set(snapshot(&updated)),
+        created_at: sea_orm::ActiveValue::Set(now),
+    }
+    .insert(txn)
+    .await
+    .map_err(|e| AppError::Database(format!("写入审核记录失败: {e}")))?;
+
+    let title = match decision {
+        ReviewDecision::Approve(_) => "工时已审批通过",
+        ReviewDecision::Reject(_) => "工时被退回，请修改后重新提交",
+        ReviewDecision::Adjust(_) => "工时已被审核人调整",
+    };
+    let summary: String = updated.description.chars().take(100).collect();
+    let content = match reason.as_deref() {
+        Some(reason) => format!("{summary}（{reason}）"),
+        None => summary,
+    };
+    notification::ActiveModel {
+        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+        user_id: sea_orm::ActiveValue::Set(updated.user_id.clone()),
+        actor_id: sea_orm::ActiveValue::Set(Some(reviewer_id.to_string())),
+        notification_type: sea_orm::ActiveValue::Set(notification::NotificationType::TimeLogReviewed),
+        title: sea_orm::ActiveValue::Set(title.to_string()),
+        content: sea_orm::ActiveValue::Set(Some(content)),
+        action_url: sea_orm::ActiveValue::Set(Some("/timelog".to_string())),
+        metadata: sea_orm::ActiveValue::Set(Some(json!({
+            "timeLogId": updated.id,
+            "caseId": updated.case_id,
+            "action": decision.action().to_value(),
+            "onBehalfOfId": on_behalf_of,
+        }))),
+        read_at: sea_orm::ActiveValue::Set(None),
+        created_at: sea_orm::ActiveValue::Set(now),
+    }
+    .insert(txn)
+    .await
+    .map_err(|e| AppError::Database(format!("写入通知失败: {e}")))?;
+
+    Ok(updated)
+}
+
+async fn owners_of(state: &AppState, user_ids: Vec<String>) -> AppResult<HashMap<String, user::Model>> {
+    Ok(user::Entity::find()
+        .filter(user::Column::Id.is_in(user_ids))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+        .into_iter()
+        .map(|u| (u.id.clone(), u))
+        .collect())
+}
+
+async fn review_single(
+    state: &AppState,
+    current_user: &CurrentUser,
+    time_log_id: &str,
+    decision: ReviewDecision,
+) -> AppResult<TimeLogResponse> {
+    Uuid::parse_str(time_log_id).map_err(|_| AppError::Validation("timeLogId 无效".to_string()))?;
+    let log = time_log::Entity::find_by_id(time_log_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询工时记录失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("工时记录不存在".to_string()))?;
+    let on_behalf_of = require_reviewer(state, current_user, &log).await?;
+
+    let owners = owners_of(state, vec![log.user_id.clone()]).await?;
+    let reviewer_id = current_user.id().to_string();
+    let time_log_id = log.id.clone();
+    let warn_percents = state.config.case_budget_warn_percents.clone();
+    let updated = state
+        .db
+        .transaction::<_, time_log::Model, AppError>(|txn| {
+            Box::pin(async move {
+                let on_behalf_of = on_behalf_of.as_deref();
+                apply_review(txn, &reviewer_id, on_behalf_of, &time_log_id, &owners, &warn_percents, &decision).await
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+    Ok(TimeLogResponse::from(updated))
+}
+
+/// GET /api/v1/timelogs/approvals（待审核队列）
+async fn list_pending(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<PendingQuery>,
+) -> AppResult<Json<Vec<TimeLogResponse>>> {
+    let role = current_user.model.role.clone();
+
+    let mut select = time_log::Entity::find()
+        .filter(time_log::Column::Status.eq(time_log::TimeLogStatus::Completed))
+        .order_by_desc(time_log::Column::StartTime);
+
+    if !is_admin(&role) {
+        let mut scope = Condition::any();
+        if let Some(case_ids) = reviewable_case_ids(&state, current_user.id(), &role).await? {
+            if !case_ids.is_empty() {
+                scope = scope.add(time_log::Column::CaseId.is_in(case_ids));
+            }
+        }
+        // 外出委托人可审核的工时（不含委托人本人的工时）
+        let tenant_id = &current_user.model.active_tenant_id;
+        let delegators = active_delegators(&state, tenant_id, current_user.id(), Utc::now()).await?;
+        for (principal_id, principal_role) in delegators {
+            let mut delegated = Condition::all().add(time_log::Column::UserId.ne(principal_id.as_str()));
+            match reviewable_case_ids(&state, &principal_id, &principal_role).await? {
+                Some(case_ids) if case_ids.is_empty() => continue,
+                Some(case_ids) => delegated = delegated.add(time_log::Column::CaseId.is_in(case_ids)),
+                None => delegated = delegated.add(time_log::Column::TenantId.eq(tenant_id)),
+            }
+            scope = scope.add(delegated);
+        }
+        if scope.is_empty() {
+            return Ok(Json(vec![]));
+        }
+        select = select.filter(time_log::Column::UserId.ne(current_user.id())).filter(scope);
+    }
+
+    if let Some(case_id) = query.case_id.as_deref() {
+        select = select.filter(time_log::Column::CaseId.eq(case_id));
+    }
+    if let Some(user_id) = query.user_id.as_deref() {
+        select = select.filter(time_log::Column::UserId.eq(user_id));
+    }
+    if let Some(from) = query.from {
+        select = select.filter(time_log::Column::StartTime.gte(from));
+    }
+    if let Some(to) = query.to {
+        select = select.filter(time_log::Column::StartTime.lt(to));
+    }
+
+    let logs = select
+        .limit(MAX_PENDING)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询待审核工时失败: {e}")))?;
+
+    let latest = latest_reviews(&state, logs.iter().map(|l| l.id.clone()).collect()).await?;
+    Ok(Json(
+        logs.into_iter()
+            .filter(|log| !is_rejected_since_edit(latest.get(&log.id), log))
+            .map(TimeLogResponse::from)
+            .collect(),
+    ))
+}
+
+/// POST /api/v1/timelogs/:id/approve
+async fn approve_time_log(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(time_log_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<ReviewAdjustment>,
+) -> AppResult<Json<TimeLogResponse>> {
+    Ok(Json(review_single(&state, &current_user, &time_log_id, ReviewDecision::Approve(payload)).await?))
+}
+
+/// POST /api/v1/timelogs/:id/reject
+async fn reject_time_log(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(time_log_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<RejectRequest>,
+) -> AppResult<Json<TimeLogResponse>> {
+    let reason = require_non_empty(&payload.reason, "reason", 2000)?;
+    Ok(Json(review_single(&state, &current_user, &time_log_id, ReviewDecision::Reject(reason)).await?))
+}
+
+/// POST /api/v1/timelogs/:id/adjust
+async fn adjust_time_log(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(time_log_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<ReviewAdjustment>,
+) -> AppResult<Json<TimeLogResponse>> {
+    if payload.duration.is_none() && payload.description.is_none() && payload.is_billable.is_none() {
+        return Err(AppError::Validation("至少调整 duration / description / isBillable 之一".to_string()));
+    }
+    Ok(Json(review_single(&state, &current_user, &time_log_id, ReviewDecision::Adjust(payload)).await?))
+}
+
+/// GET /api/v1/timelogs/:id/reviews（审核留痕：工时本人或审核人可见）
+async fn list_reviews(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(time_log_id): Path<String>,
+) -> AppResult<Json<Vec<TimeLogReviewResponse>>> {
+    Uuid::parse_str(&time_log_id).map_err(|_| AppError::Validation("timeLogId 无效".to_string()))?;
+    let log = time_log::Entity::find_by_id(&time_log_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询工时记录失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("工时记录不存在".to_string()))?;
+    if log.user_id != current_user.id() {
+        require_reviewer(&state, &current_user, &log).await?;
+    }
+
+    let reviews = time_log_review::Entity::find()
+        .filter(time_log_review::Column::TimeLogId.eq(&log.id))
+        .order_by_asc(time_log_review::Column::CreatedAt)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询审核记录失败: {e}")))?;
+    Ok(Json(reviews.into_iter().map(TimeLogReviewResponse::from).collect()))
+}
+
+/// POST /api/v1/timelogs/approvals/bulk
+async fn bulk_review(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(payload): ValidatedJson<BulkReviewRequest>,
+) -> AppResult<Json<BulkReviewResponse>> {
+    let decision = match payload.action {
+        BulkReviewAction::Approve => ReviewDecision::Approve(ReviewAdjustment::default()),
+        BulkReviewAction::Reject => {
+            let reason =
+                payload.reason.as_deref().ok_or_else(|| AppError::Validation("退回必须填写原因".to_string()))?;
+            ReviewDecision::Reject(require_non_empty(reason, "reason", 2000)?)
+        }
+    };
+
+    let mut ids: Vec<String> = Vec::with_capacity(payload.time_log_ids.len());
+    for raw in &payload.time_log_ids {
+        let id = raw.trim().to_string();
+        Uuid::parse_str(&id).map_err(|_| AppError::Validation(format!("工时ID 无效: {id}")))?;
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    if ids.len() > BULK_MAX_TIME_LOGS {
+        return Err(AppError::Validation(format!("单次最多审核 {BULK_MAX_TIME_LOGS} 条工时")));
+    }
+
+    let mut logs: HashMap<String, time_log::Model> = time_log::Entity::find()
+        .filter(time_log::Column::Id.is_in(ids.clone()))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询工时记录失败: {e}")))?
+        .into_iter()
+        .map(|l| (l.id.clone(), l))
+        .collect();
+
+    let mut results: BTreeMap<usize, BulkReviewItemResult> = BTreeMap::new();
+    let mut runnable: Vec<(usize, String, Option<String>)> = Vec::new();
+    for (idx, id) in ids.iter().enumerate() {
+        let outcome = match logs.remove(id) {
+            None => Err(AppError::NotFound("工时记录不存在".to_string())),
+            Some(log) => require_reviewer(&state, &current_user, &log).await.map(|on_behalf_of| (log, on_behalf_of)),
+        };
+        match outcome {
+            Ok((log, on_behalf_of)) => runnable.push((idx, log.user_id.clone(), on_behalf_of)),
+            Err(err) => {
+                results.insert(
+                    idx,
+                    BulkReviewItemResult { time_log_id: id.clone(), ok: false, error: Some(err.to_string()) },
+                );
+            }
+        }
+    }
+
+    let owners = owners_of(&state, runnable.iter().map(|(_, user_id, _)| user_id.clone()).collect()).await?;
+    let reviewer_id = current_user.id().to_string();
+    let warn_percents = state.config.case_budget_warn_percents.clone();
+    let runnable_ids: Vec<(usize, String, Option<String>)> =
+        runnable.into_iter().map(|(idx, _, on_behalf_of)| (idx, ids[idx].clone(), on_behalf_of)).collect();
+    let outcomes = state
+        .db
+        .transaction::<_, Vec<(usize, String, Option<AppError>)>, AppError>(|txn| {
+            Box::pin(async move {
+                let mut outcomes = Vec::with_capacity(runnable_ids.len());
+                for (idx, id, on_behalf_of) in runnable_ids {
+                    let on_behalf_of = on_behalf_of.as_deref();
+                    match apply_review(txn, &reviewer_id, on_behalf_of, &id, &owners, &warn_percents, &decision).await {
+                        Ok(_) => outcomes.push((idx, id, None)),
+                        // 数据库错误整体回滚；业务校验失败仅记为该条失败
+                        Err(err @ (AppError::Database(_) | AppError::Internal(_))) => return Err(err),
+                        Err(err) => outcomes.push((idx, id, Some(err))),
+                    }
+                }
+                Ok(outcomes)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    for (idx, id, err) in outcomes {
+        results.insert(
+            idx,
+            BulkReviewItemResult { time_log_id: id, ok: err.is_none(), error: err.map(|e| e.to_string()) },
+        );
+    }
+
+    let results: Vec<BulkReviewItemResult> = results.into_values().collect();
+    let succeeded = results.iter().filter(|r| r.ok).count();
+    Ok(Json(BulkReviewResponse { succeeded, failed: results.len() - succeeded, results }))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/approvals", get(list_pending))
+        .route("/approvals/bulk", post(bulk_review))
+        .route("/:id/approve", post(approve_time_log))
+        .route("/:id/reject", post(reject_time_log))
+        .route("/:id/adjust", post(adjust_time_log))
+        .route("/:id/reviews", get(list_reviews))
+}
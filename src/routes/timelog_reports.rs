@@ -0,0 +1,590 @@
+//! 工时查询与报表路由（合并到 `/timelogs`）
+//!
+//! - `GET /timelogs`：按用户/案件/任务/时间范围/状态/是否计费筛选，分页返回
+//! - `GET /timelogs/timesheet`：某用户一周工时，按天 × 案件汇总
+//! - `GET /timelogs/utilization`：个人/团队利用率（计费工时 vs 目标）与实现率，支持 `format=csv`
+//!
+//! 可见性：
+//! - 本人工时始终可见；PARTNER / ADMIN 可查看任意用户
+//! - 上级可查看 `supervisorId` 链路下的全部下属（含间接下属）
+//! - 指定案件/任务时按案件可见性放行，返回该案件下所有人的工时（与 Web 主线 `getCaseTimeLogs` 一致）
+
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use sea_orm::prelude::Decimal;
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::AppState;
+use crate::entity::user::Role;
+use crate::entity::{case, task, time_log, user};
+use crate::error::{AppError, AppResult};
+use crate::security::case_access::require_case_access;
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+
+use super::cases::PaginatedResponse;
+use super::timelogs::TimeLogResponse;
+
+/// 上级链路最大深度（防御 supervisorId 成环）
+const MAX_SUPERVISOR_DEPTH: usize = 10;
+const MAX_REPORT_DAYS: i64 = 366;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeLogListQuery {
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+    pub user_id: Option<String>,
+    pub case_id: Option<String>,
+    pub task_id: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// 逗号分隔，如 `COMPLETED,APPROVED`
+    pub status: Option<String>,
+    pub billable: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimesheetQuery {
+    pub user_id: Option<String>,
+    /// 周内任意一天（默认本周）；按周一对齐
+    pub week_start: Option<NaiveDate>,
+    pub tz_offset_minutes: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimesheetRow {
+    pub case_id: Option<String>,
+    pub case_code: Option<String>,
+    pub case_title: Option<String>,
+    /// 周一至周日每天的秒数
+    pub seconds: [i64; 7],
+    pub total_seconds: i64,
+    pub billable_seconds: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimesheetResponse {
+    pub user_id: String,
+    pub week_start: NaiveDate,
+    pub days: Vec<NaiveDate>,
+    pub rows: Vec<TimesheetRow>,
+    pub day_totals: [i64; 7],
+    pub total_seconds: i64,
+    pub billable_seconds: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtilizationQuery {
+    pub from: NaiveDate,
+    /// 含当天
+    pub to: NaiveDate,
+    pub user_id: Option<String>,
+    /// self（默认）/ team（本人 + 全部下属）/ all（仅 PARTNER / ADMIN）
+    pub scope: Option<String>,
+    /// 每个工作日的目标计费工时（默认 6）
+    pub target_hours_per_day: Option<f64>,
+    pub tz_offset_minutes: Option<i32>,
+    /// json（默认）/ csv
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtilizationRow {
+    pub user_id: String,
+    pub name: Option<String>,
+    pub email: String,
+    pub total_hours: f64,
+    pub billable_hours: f64,
+    pub target_hours: f64,
+    /// 计费工时 / 目标工时
+    pub utilization: Option<f64>,
+    /// 计费工时 × 当前小时费率
+    pub standard_value: String,
+    /// 计费工时的 billingAmount 合计
+    pub billing_amount: String,
+    /// 其中已开票（BILLED）部分
+    pub billed_amount: String,
+    /// billingAmount / standardValue
+    pub realization: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtilizationResponse {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub workdays: i64,
+    pub users: Vec<UtilizationRow>,
+    pub team: Option<UtilizationRow>,
+}
+
+#[derive(Debug, Default)]
+struct UtilizationTotals {
+    total_seconds: i64,
+    billable_seconds: i64,
+    target_hours: f64,
+    standard_value: Decimal,
+    billing_amount: Decimal,
+    billed_amount: Decimal,
+}
+
+impl UtilizationTotals {
+    fn add(&mut self, other: &UtilizationTotals) {
+        self.total_seconds += other.total_seconds;
+        self.billable_seconds += other.billable_seconds;
+        self.target_hours += other.target_hours;
+        self.standard_value += other.standard_value;
+        self.billing_amount += other.billing_amount;
+        self.billed_amount += other.billed_amount;
+    }
+
+    fn into_row(self, user_id: String, name: Option<String>, email: String) -> UtilizationRow {
+        let billable_hours = self.billable_seconds as f64 / 3600.0;
+        let realization = if self.standard_value.is_zero() {
+            None
+        } else {
+            (self.billing_amount / self.standard_value).round_dp(4).to_string().parse::<f64>().ok()
+        };
+        UtilizationRow {
+            user_id,
+            name,
+            email,
+            total_hours: round2(self.total_seconds as f64 / 3600.0),
+            billable_hours: round2(billable_hours),
+            target_hours: round2(self.target_hours),
+            utilization: (self.target_hours > 0.0).then(|| round4(billable_hours / self.target_hours)),
+            standard_value: self.standard_value.round_dp(2).to_string(),
+            billing_amount: self.billing_amount.round_dp(2).to_string(),
+            billed_amount: self.billed_amount.round_dp(2).to_string(),
+            realization,
+        }
+    }
+}
+
+fn round2(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
+
+fn round4(v: f64) -> f64 {
+    (v * 10000.0).round() / 10000.0
+}
+
+pub(crate) fn parse_offset(tz_offset_minutes: Option<i32>) -> AppResult<FixedOffset> {
+    FixedOffset::east_opt(tz_offset_minutes.unwrap_or(0).saturating_mul(60))
+        .ok_or_else(|| AppError::Validation("tzOffsetMinutes 超出范围".to_string()))
+}
+
+fn local_midnight(date: NaiveDate, offset: FixedOffset) -> DateTime<Utc> {
+    offset
+        .from_local_datetime(&date.and_time(NaiveTime::MIN))
+        .single()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| date.and_time(NaiveTime::MIN).and_utc())
+}
+
+/// [from, to] 内的工作日（周一至周五）天数
+fn count_workdays(from: NaiveDate, to: NaiveDate) -> i64 {
+    from.iter_days()
+        .take_while(|d| *d <= to)
+        .filter(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+        .count() as i64
+}
+
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn parse_time_log_status(value: &str) -> AppResult<time_log::TimeLogStatus> {
+    match value.trim().to_uppercase().as_str() {
+        "RUNNING" => Ok(time_log::TimeLogStatus::Running),
+        "PAUSED" => Ok(time_log::TimeLogStatus::Paused),
+        "COMPLETED" => Ok(time_log::TimeLogStatus::Completed),
+        "APPROVED" => Ok(time_log::TimeLogStatus::Approved),
+        "BILLED" => Ok(time_log::TimeLogStatus::Billed),
+        other => Err(AppError::Validation(format!("无效的工时状态: {other}"))),
+    }
+}
+
+/// 沿 supervisorId 向下收集全部下属（不含本人）
+pub(crate) async fn subordinate_ids(state: &AppState, supervisor_id: &str) -> AppResult<Vec<String>> {
+    let mut seen: HashSet<String> = HashSet::from([supervisor_id.to_string()]);
+    let mut result: Vec<String> = Vec::new();
+    let mut frontier = vec![supervisor_id.to_string()];
+
+    for _ in 0..MAX_SUPERVISOR_DEPTH {
+        if frontier.is_empty() {
+            break;
+        }
+        let reports = user::Entity::find()
+            .filter(user::Column::SupervisorId.is_in(frontier))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询下属失败: {e}")))?;
+        frontier = Vec::new();
+        for report in reports {
+            if seen.insert(report.id.clone()) {
+                result.push(report.id.clone());
+                frontier.push(report.id);
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// 查看他人工时：本人 / PARTNER / ADMIN / 上级链路
+pub(crate) async fn require_user_report_access(
+    state: &AppState,
+    current_user: &CurrentUser,
+    target_user_id: &str,
+) -> AppResult<user::Model> {
+    let target = user::Entity::find_by_id(target_user_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("用户不存在".to_string()))?;
+
+    if target.id == current_user.id() || matches!(current_user.model.role, Role::Partner | Role::Admin) {
+        return Ok(target);
+    }
+    if subordinate_ids(state, current_user.id()).await?.contains(&target.id) {
+        return Ok(target);
+    }
+    Err(AppError::Forbidden("无权查看该用户的工时".to_string()))
+}
+
+/// GET /api/v1/timelogs
+pub async fn list_time_logs(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<TimeLogListQuery>,
+) -> AppResult<Json<PaginatedResponse<TimeLogResponse>>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::CaseView)?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
+
+    let mut select = time_log::Entity::find().order_by_desc(time_log::Column::StartTime);
+
+    if let Some(task_id) = query.task_id.as_deref() {
+        Uuid::parse_str(task_id).map_err(|_| AppError::Validation("taskId 无效".to_string()))?;
+        let task_model = task::Entity::find_by_id(task_id)
+            .one(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
+            .ok_or_else(|| AppError::NotFound("任务不存在".to_string()))?;
+        let case_id = task_model.case_id.ok_or_else(|| AppError::Validation("项目任务暂无工时记录".to_string()))?;
+        require_case_access(&state, &case_id, current_user.id(), role.clone(), Permission::CaseView).await?;
+        select = select.filter(time_log::Column::TaskId.eq(task_id));
+    }
+
+    if let Some(case_id) = query.case_id.as_deref() {
+        Uuid::parse_str(case_id).map_err(|_| AppError::Validation("caseId 无效".to_string()))?;
+        require_case_access(&state, case_id, current_user.id(), role.clone(), Permission::CaseView).await?;
+        select = select.filter(time_log::Column::CaseId.eq(case_id));
+    }
+
+    let scoped_to_case = query.case_id.is_some() || query.task_id.is_some();
+    match query.user_id.as_deref() {
+        Some(user_id) => {
+            if !scoped_to_case {
+                require_user_report_access(&state, &current_user, user_id).await?;
+            }
+            select = select.filter(time_log::Column::UserId.eq(user_id));
+        }
+        None if !scoped_to_case => {
+            select = select.filter(time_log::Column::UserId.eq(current_user.id()));
+        }
+        None => {}
+    }
+
+    if let Some(from) = query.from {
+        select = select.filter(time_log::Column::StartTime.gte(from));
+    }
+    if let Some(to) = query.to {
+        select = select.filter(time_log::Column::StartTime.lt(to));
+    }
+    if let Some(raw) = query.status.as_deref() {
+        let statuses = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(parse_time_log_status)
+            .collect::<AppResult<Vec<_>>>()?;
+        if !statuses.is_empty() {
+            select = select.filter(time_log::Column::Status.is_in(statuses));
+        }
+    }
+    if let Some(billable) = query.billable {
+        select = select.filter(time_log::Column::IsBillable.eq(billable));
+    }
+
+    let paginator = select.paginate(&state.db, page_size);
+    let total = paginator.num_items().await.map_err(|e| AppError::Database(format!("计数失败: {e}")))?;
+    let total_pages = paginator.num_pages().await.map_err(|e| AppError::Database(format!("分页失败: {e}")))?;
+    let logs = paginator
+        .fetch_page(page - 1)
+        .await
+        .map_err(|e| AppError::Database(format!("查询工时记录失败: {e}")))?;
+
+    Ok(Json(PaginatedResponse {
+        data: logs.into_iter().map(TimeLogResponse::from).collect(),
+        total,
+        page,
+        page_size,
+        total_pages,
+    }))
+}
+
+/// GET /api/v1/timelogs/timesheet
+async fn get_timesheet(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<TimesheetQuery>,
+) -> AppResult<Json<TimesheetResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::CaseView)?;
+
+    let user_id = query.user_id.clone().unwrap_or_else(|| current_user.id().to_string());
+    require_user_report_access(&state, &current_user, &user_id).await?;
+
+    let offset = parse_offset(query.tz_offset_minutes)?;
+    let anchor = query.week_start.unwrap_or_else(|| Utc::now().with_timezone(&offset).date_naive());
+    let week_start = anchor - Duration::days(i64::from(anchor.weekday().num_days_from_monday()));
+    let days: Vec<NaiveDate> = week_start.iter_days().take(7).collect();
+    let range_start = local_midnight(week_start, offset);
+    let range_end = local_midnight(week_start + Duration::days(7), offset);
+
+    let logs = time_log::Entity::find()
+        .filter(time_log::Column::UserId.eq(&user_id))
+        .filter(time_log::Column::StartTime.gte(range_start))
+        .filter(time_log::Column::StartTime.lt(range_end))
+        .order_by_asc(time_log::Column::StartTime)
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询工时记录失败: {e}")))?;
+
+    let mut rows: BTreeMap<Option<String>, TimesheetRow> = BTreeMap::new();
+    let mut day_totals = [0i64; 7];
+    for log in &logs {
+        let day = log.start_time.with_timezone(&offset).date_naive();
+        let idx = (day - week_start).num_days().clamp(0, 6) as usize;
+        let seconds = i64::from(log.duration);
+        let row = rows.entry(log.case_id.clone()).or_insert_with(|| TimesheetRow {
+            case_id: log.case_id.clone(),
+            case_code: None,
+            case_title: None,
+            seconds: [0; 7],
+            total_seconds: 0,
+            billable_seconds: 0,
+        });
+        row.seconds[idx] += seconds;
+        row.total_seconds += seconds;
+        if log.is_billable {
+            row.billable_seconds += seconds;
+        }
+        day_totals[idx] += seconds;
+    }
+
+    let case_ids: Vec<String> = rows.keys().flatten().cloned().collect();
+    if !case_ids.is_empty() {
+        let cases: HashMap<String, case::Model> = case::Entity::find()
+            .filter(case::Column::Id.is_in(case_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询案件失败: {e}")))?
+            .into_iter()
+            .map(|c| (c.id.clone(), c))
+            .collect();
+        for row in rows.values_mut() {
+            if let Some(c) = row.case_id.as_ref().and_then(|id| cases.get(id)) {
+                row.case_code = Some(c.case_code.clone());
+                row.case_title = Some(c.title.clone());
+            }
+        }
+    }
+
+    let rows: Vec<TimesheetRow> = rows.into_values().collect();
+    let total_seconds = rows.iter().map(|r| r.total_seconds).sum();
+    let billable_seconds = rows.iter().map(|r| r.billable_seconds).sum();
+
+    Ok(Json(TimesheetResponse { user_id, week_start, days, rows, day_totals, total_seconds, billable_seconds }))
+}
+
+/// GET /api/v1/timelogs/utilization
+async fn get_utilization(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<UtilizationQuery>,
+) -> AppResult<Response> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::CaseView)?;
+
+    if query.to < query.from {
+        return Err(AppError::Validation("to 不能早于 from".to_string()));
+    }
+    if (query.to - query.from).num_days() >= MAX_REPORT_DAYS {
+        return Err(AppError::Validation(format!("统计区间不能超过 {MAX_REPORT_DAYS} 天")));
+    }
+    let target_per_day = query.target_hours_per_day.unwrap_or(6.0);
+    if !(0.0..=24.0).contains(&target_per_day) {
+        return Err(AppError::Validation("targetHoursPerDay 需在 0-24 之间".to_string()));
+    }
+    let offset = parse_offset(query.tz_offset_minutes)?;
+
+    let scope = query.scope.as_deref().unwrap_or("self").to_lowercase();
+    let users: Vec<user::Model> = match scope.as_str() {
+        "self" => {
+            let user_id = query.user_id.as_deref().unwrap_or(current_user.id());
+            vec![require_user_report_access(&state, &current_user, user_id).await?]
+        }
+        "team" => {
+            let mut ids = subordinate_ids(&state, current_user.id()).await?;
+            ids.insert(0, current_user.id().to_string());
+            user::Entity::find()
+                .filter(user::Column::Id.is_in(ids))
+                .order_by_asc(user::Column::Name)
+                .all(&state.db)
+                .await
+                .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+        }
+        "all" => {
+            if !matches!(role, Role::Partner | Role::Admin) {
+                return Err(AppError::Forbidden("仅合伙人/管理员可查看全所利用率".to_string()));
+            }
+            user::Entity::find()
+                .filter(user::Column::TenantId.eq(&current_user.model.active_tenant_id))
+                .filter(user::Column::IsActive.eq(true))
+                .order_by_asc(user::Column::Name)
+                .all(&state.db)
+                .await
+                .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+        }
+        other => return Err(AppError::Validation(format!("无效的 scope: {other}"))),
+    };
+
+    let workdays = count_workdays(query.from, query.to);
+    let range_start = local_midnight(query.from, offset);
+    let range_end = local_midnight(query.to + Duration::days(1), offset);
+    let user_ids: Vec<String> = users.iter().map(|u| u.id.clone()).collect();
+
+    let logs = time_log::Entity::find()
+        .filter(time_log::Column::UserId.is_in(user_ids))
+        .filter(time_log::Column::StartTime.gte(range_start))
+        .filter(time_log::Column::StartTime.lt(range_end))
+        .filter(time_log::Column::Status.is_in(vec![
+            time_log::TimeLogStatus::Completed,
+            time_log::TimeLogStatus::Approved,
+            time_log::TimeLogStatus::Billed,
+        ]))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询工时记录失败: {e}")))?;
+
+    let mut per_user: HashMap<String, UtilizationTotals> = HashMap::new();
+    for log in &logs {
+        let totals = per_user.entry(log.user_id.clone()).or_default();
+        totals.total_seconds += i64::from(log.duration);
+        if log.is_billable {
+            totals.billable_seconds += i64::from(log.duration);
+            let amount = log.billing_amount.unwrap_or_default();
+            totals.billing_amount += amount;
+            if log.status == time_log::TimeLogStatus::Billed {
+                totals.billed_amount += amount;
+            }
+        }
+    }
+
+    let mut team = UtilizationTotals::default();
+    let mut rows = Vec::with_capacity(users.len());
+    for u in users {
+        let mut totals = per_user.remove(&u.id).unwrap_or_default();
+        totals.target_hours = workdays as f64 * target_per_day;
+        totals.standard_value = u.hourly_rate * Decimal::from(totals.billable_seconds) / Decimal::from(3600);
+        team.add(&totals);
+        rows.push(totals.into_row(u.id, u.name, u.email));
+    }
+    let team = (scope != "self").then(|| team.into_row("TEAM".to_string(), Some("合计".to_string()), String::new()));
+
+    let report = UtilizationResponse { from: query.from, to: query.to, workdays, users: rows, team };
+
+    if query.format.as_deref().map(str::to_lowercase).as_deref() == Some("csv") {
+        return Ok(utilization_csv(&report));
+    }
+    Ok(Json(report).into_response())
+}
+
+fn utilization_csv(report: &UtilizationResponse) -> Response {
+    let mut out = String::from("\u{feff}");
+    out.push_str(
+        "userId,name,email,totalHours,billableHours,targetHours,utilization,standardValue,billingAmount,billedAmount,realization\n",
+    );
+    for row in report.users.iter().chain(report.team.iter()) {
+        let fields = [
+            csv_field(&row.user_id),
+            csv_field(row.name.as_deref().unwrap_or_default()),
+            csv_field(&row.email),
+            row.total_hours.to_string(),
+            row.billable_hours.to_string(),
+            row.target_hours.to_string(),
+            row.utilization.map(|v| v.to_string()).unwrap_or_default(),
+            row.standard_value.clone(),
+            row.billing_amount.clone(),
+            row.billed_amount.clone(),
+            row.realization.map(|v| v.to_string()).unwrap_or_default(),
+        ];
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv; charset=utf-8"));
+    let disposition = format!("attachment; filename=\"utilization_{}_{}.csv\"", report.from, report.to);
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&disposition).unwrap_or(HeaderValue::from_static("attachment")),
+    );
+    (StatusCode::OK, headers, out).into_response()
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/timesheet", get(get_timesheet))
+        .route("/utilization", get(get_utilization))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_workdays_and_escapes_csv() {
+        let from = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap(); // 周一
+        let to = NaiveDate::from_ymd_opt(2026, 10, 25).unwrap(); // 两周后的周日
+        assert_eq!(count_workdays(from, to), 10);
+        assert_eq!(count_workdays(to, to), 0);
+
+        assert_eq!(csv_field("张三"), "张三");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
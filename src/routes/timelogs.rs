@@ -3,30 +3,45 @@
 //! 对齐 Web 主线 `lawclick-next/src/actions/timelogs-crud.ts` 的核心行为：
 //! - 开始计时：必须关联案件/任务；同一用户仅允许 1 个 RUNNING/PAUSED
 //! - 暂停/恢复/停止：仅允许本人操作
-//! - 计费：停止时快照费率并计算金额（秒→小时）
+//! - 补录：`POST /timelogs` 以 start/end 或 start + duration 直接写入 COMPLETED 记录
+//! - 修改/删除：仅本人的 COMPLETED 记录；APPROVED/BILLED 不可变
+//! - 时间段：同一用户的工时不可重叠（按用户加 advisory lock 后校验）
+//! - 计费：停止/补录/修改时按费率卡解析并快照费率，按计费取整规则（案件 > 租户默认）得到计费时长并计算金额；
+//!   再按案件收费模式（FIXED / CAPPED）拆分计费与核销金额
+//! - 闲置：后台任务自动暂停闲置计时（见 `jobs::idle_timers`），`POST /timelogs/:id/idle` 选择保留或丢弃闲置时段；
+//!   未处理即停止计时视为丢弃
 //! - 可见性：关联案件需满足案件可见性（originator/handler/members）
 
 use axum::{
     extract::{Path, State},
     response::Json,
-    routing::{get, post},
+    routing::{get, patch, post},
     Router,
 };
-use chrono::{DateTime, Utc};
-use sea_orm::{ActiveEnum, ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::prelude::Decimal;
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter,
+    QuerySelect, Statement, TransactionTrait,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::billing::increments::IncrementRule;
 use crate::db::AppState;
-use crate::entity::{task, time_log, user};
+use crate::entity::{case, task, time_log, user};
 use crate::error::{AppError, AppResult};
 use crate::security::case_access::require_case_access;
 use crate::security::current_user::CurrentUser;
 use crate::security::permissions::{require_permission, Permission};
 use crate::security::validation::{require_non_empty, ValidatedJson};
 
+use super::billing::resolve_increment_rule;
+use super::case_budget::apply_case_billing_mode;
+use super::rate_cards::resolve_billing_rate;
+
 #[derive(Debug, Deserialize, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct StartTimerRequest {
@@ -51,9 +66,14 @@ pub struct StartTimerResponse {
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StopTimerResponse {
+    /// 实际时长（秒）
     pub duration: i32,
+    /// 按计费规则取整后的计费时长（秒）；不计费时为空
+    pub billed_duration: Option<i32>,
     pub billing_rate: Option<String>,
     pub billing_amount: Option<String>,
+    /// 核销金额（封顶超额 / 固定收费案件）
+    pub write_off_amount: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -66,35 +86,256 @@ pub struct ActiveTimerResponse {
     pub duration: i32,
     pub case_id: Option<String>,
     pub task_id: Option<String>,
+    /// 闲置自动暂停时间点（待用户保留/丢弃）
+    pub idle_paused_at: Option<DateTime<Utc>>,
+    /// 待处理的闲置时长（秒）
+    pub idle_seconds: Option<i32>,
 }
 
-async fn start_timer(
-    State(state): State<Arc<AppState>>,
-    current_user: CurrentUser,
-    ValidatedJson(payload): ValidatedJson<StartTimerRequest>,
-) -> AppResult<Json<StartTimerResponse>> {
-    let role = current_user.model.role.clone();
-    require_permission(role.clone(), Permission::CaseView)?;
+/// 闲置时段处理：`KEEP` 计入时长 | `DISCARD` 丢弃
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveIdleRequest {
+    pub action: String,
+}
+
+/// 手工补录：endTime 与 duration（秒）二选一
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateManualTimeLogRequest {
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub case_id: Option<String>,
+
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub task_id: Option<String>,
+
+    #[validate(length(min = 1, max = 5000, message = "description 长度不合法"))]
+    pub description: String,
+
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub duration: Option<i64>,
+
+    pub is_billable: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTimeLogRequest {
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub case_id: Option<String>,
+
+    #[validate(custom(function = "crate::security::validation::validate_uuid_str"))]
+    pub task_id: Option<String>,
+
+    #[validate(length(min = 1, max = 5000, message = "description 长度不合法"))]
+    pub description: Option<String>,
+
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub duration: Option<i64>,
+
+    pub is_billable: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeLogResponse {
+    pub id: String,
+    pub description: String,
+    pub status: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub duration: i32,
+    pub billed_duration: Option<i32>,
+    pub is_billable: bool,
+    pub billing_rate: Option<String>,
+    pub billing_amount: Option<String>,
+    pub write_off_amount: Option<String>,
+    pub user_id: String,
+    pub case_id: Option<String>,
+    pub task_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<time_log::Model> for TimeLogResponse {
+    fn from(m: time_log::Model) -> Self {
+        Self {
+            id: m.id,
+            description: m.description,
+            status: m.status.to_value(),
+            start_time: m.start_time,
+            end_time: m.end_time,
+            duration: m.duration,
+            billed_duration: m.billed_duration,
+            is_billable: m.is_billable,
+            billing_rate: m.billing_rate.map(|v| v.to_string()),
+            billing_amount: m.billing_amount.map(|v| v.to_string()),
+            write_off_amount: m.write_off_amount.map(|v| v.to_string()),
+            user_id: m.user_id,
+            case_id: m.case_id,
+            task_id: m.task_id,
+            created_at: m.created_at,
+            updated_at: m.updated_at,
+        }
+    }
+}
+
+/// 单条补录工时上限（24 小时）
+pub(crate) const MAX_MANUAL_DURATION_SECS: i64 = 24 * 3600;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct BillingSnapshot {
+    pub rate: Option<Decimal>,
+    pub amount: Option<Decimal>,
+    pub billed_duration: Option<i32>,
+    pub write_off_amount: Option<Decimal>,
+}
 
-    let mut case_id = payload.case_id.clone();
+/// 计费快照：可计费时沿用已快照费率（无则取费率卡解析出的费率），
+/// 计费时长按取整规则换算，金额 = 费率 × 计费秒数 / 3600
+pub(crate) fn compute_billing(
+    is_billable: bool,
+    snapshot_rate: Option<Decimal>,
+    resolved_rate: Decimal,
+    duration: i32,
+    rule: &IncrementRule,
+) -> BillingSnapshot {
+    if !is_billable {
+        return BillingSnapshot::default();
+    }
+    let rate = snapshot_rate.unwrap_or(resolved_rate);
+    let billed = rule.billed_seconds(duration);
+    let amount = rate * Decimal::from(billed) / Decimal::from(3600);
+    BillingSnapshot { rate: Some(rate), amount: Some(amount), billed_duration: Some(billed), write_off_amount: None }
+}
 
-    if case_id.is_none() && payload.task_id.is_some() {
-        let task_id = payload.task_id.as_deref().unwrap_or_default();
-        Uuid::parse_str(task_id).map_err(|_| AppError::Validation("task_id 无效".to_string()))?;
+/// 解析工时归属案件：指定任务时以任务所属案件为准（同时给出 caseId 时必须一致），并校验案件可见性
+async fn resolve_log_case(
+    state: &AppState,
+    current_user: &CurrentUser,
+    case_id: Option<&str>,
+    task_id: Option<&str>,
+) -> AppResult<case::Model> {
+    let mut case_id = case_id.map(str::to_string);
 
+    if let Some(task_id) = task_id {
         let task_model = task::Entity::find_by_id(task_id)
             .one(&state.db)
             .await
             .map_err(|e| AppError::Database(format!("查询任务失败: {e}")))?
             .ok_or_else(|| AppError::NotFound("任务不存在".to_string()))?;
+        let task_case_id = task_model
+            .case_id
+            .ok_or_else(|| AppError::Validation("项目任务暂不支持登记工时".to_string()))?;
+        if case_id.as_deref().is_some_and(|id| id != task_case_id) {
+            return Err(AppError::Validation("任务不属于该案件".to_string()));
+        }
+        case_id = Some(task_case_id);
+    }
+
+    let case_id = case_id.ok_or_else(|| AppError::Validation("工时记录必须关联案件/任务".to_string()))?;
+    require_case_access(state, &case_id, current_user.id(), current_user.model.role.clone(), Permission::CaseView).await
+}
+
+/// 由 start + (end | duration) 得到时间段与秒数
+fn resolve_period(
+    start_time: DateTime<Utc>,
+    end_time: Option<DateTime<Utc>>,
+    duration: Option<i64>,
+) -> AppResult<(DateTime<Utc>, i32)> {
+    let end_time = match (end_time, duration) {
+        (Some(end), None) => end,
+        (None, Some(secs)) if secs > 0 => start_time + Duration::seconds(secs),
+        (None, Some(_)) => return Err(AppError::Validation("duration 必须大于 0".to_string())),
+        _ => return Err(AppError::Validation("endTime 与 duration 需且仅需提供一个".to_string())),
+    };
+    if end_time <= start_time {
+        return Err(AppError::Validation("结束时间必须晚于开始时间".to_string()));
+    }
+    if end_time > Utc::now() {
+        return Err(AppError::Validation("不能登记未来时间段的工时".to_string()));
+    }
+    let secs = (end_time - start_time).num_seconds();
+    if secs > MAX_MANUAL_DURATION_SECS {
+        return Err(AppError::Validation("单条工时不能超过 24 小时".to_string()));
+    }
+    Ok((end_time, secs as i32))
+}
 
-        case_id = Some(task_model.case_id);
+/// 同一用户工时时间段不可重叠；进行中或暂停的计时视为从首次开始（createdAt）持续到当前
+pub(crate) async fn ensure_no_overlap<C: ConnectionTrait>(
+    db: &C,
+    user_id: &str,
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    exclude_id: Option<&str>,
+) -> AppResult<()> {
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT pg_advisory_xact_lock(hashtext($1))",
+        [format!("timelog:{user_id}").into()],
+    ))
+    .await
+    .map_err(|e| AppError::Database(format!("获取工时锁失败: {e}")))?;
+
+    let mut select = time_log::Entity::find()
+        .filter(time_log::Column::UserId.eq(user_id))
+        .filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(time_log::Column::StartTime.lt(end_time))
+                        .add(time_log::Column::EndTime.gt(start_time)),
+                )
+                .add(
+                    Condition::all()
+                        .add(time_log::Column::EndTime.is_null())
+                        .add(time_log::Column::Status.is_in([
+                            time_log::TimeLogStatus::Running,
+                            time_log::TimeLogStatus::Paused,
+                        ]))
+                        .add(time_log::Column::CreatedAt.lt(end_time)),
+                ),
+        );
+    if let Some(id) = exclude_id {
+        select = select.filter(time_log::Column::Id.ne(id));
     }
 
-    let case_id = case_id.ok_or_else(|| AppError::Validation("计时必须关联案件/任务".to_string()))?;
-    Uuid::parse_str(&case_id).map_err(|_| AppError::Validation("case_id 无效".to_string()))?;
+    let conflicts = select
+        .all(db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询重叠工时失败: {e}")))?;
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+
+    Err(AppError::ValidationWithDetails {
+        message: "与已有工时记录时间段重叠".to_string(),
+        details: serde_json::json!({
+            "conflicts": conflicts
+                .iter()
+                .map(|c| serde_json::json!({
+                    "id": c.id,
+                    "startTime": c.start_time,
+                    "endTime": c.end_time,
+                    "status": c.status.to_value(),
+                }))
+                .collect::<Vec<_>>(),
+        }),
+    })
+}
+
+async fn start_timer(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(payload): ValidatedJson<StartTimerRequest>,
+) -> AppResult<Json<StartTimerResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::CaseView)?;
 
-    require_case_access(&state, &case_id, current_user.id(), role, Permission::CaseView).await?;
+    let case_model =
+        resolve_log_case(&state, &current_user, payload.case_id.as_deref(), payload.task_id.as_deref()).await?;
 
     // 同一用户只允许 1 个活动计时
     let active = time_log::Entity::find()
@@ -110,16 +351,19 @@ async fn start_timer(
 
     let description = require_non_empty(&payload.description, "description", 5000)?;
 
+    let now = Utc::now();
     let active_model = time_log::ActiveModel {
         id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+        tenant_id: sea_orm::ActiveValue::Set(case_model.tenant_id),
         user_id: sea_orm::ActiveValue::Set(current_user.id().to_string()),
-        case_id: sea_orm::ActiveValue::Set(Some(case_id)),
+        case_id: sea_orm::ActiveValue::Set(Some(case_model.id)),
         task_id: sea_orm::ActiveValue::Set(payload.task_id.clone()),
         description: sea_orm::ActiveValue::Set(description),
         is_billable: sea_orm::ActiveValue::Set(payload.is_billable.unwrap_or(true)),
         status: sea_orm::ActiveValue::Set(time_log::TimeLogStatus::Running),
-        start_time: sea_orm::ActiveValue::Set(Utc::now()),
+        start_time: sea_orm::ActiveValue::Set(now),
         duration: sea_orm::ActiveValue::Set(0),
+        created_at: sea_orm::ActiveValue::Set(now),
         ..Default::default()
     };
 
@@ -152,50 +396,89 @@ async fn stop_timer(
         return Err(AppError::Validation("计时已停止".to_string()));
     }
 
-    let now = Utc::now();
-    let elapsed = if time_log_model.status == time_log::TimeLogStatus::Running {
-        let diff = now.signed_duration_since(time_log_model.start_time).num_seconds();
-        diff.max(0).min(i64::from(i32::MAX)) as i32
-    } else {
-        0
-    };
-
-    let duration = time_log_model.duration.saturating_add(elapsed);
-
     let user_model = user::Entity::find_by_id(current_user.id())
         .one(&state.db)
         .await
         .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
         .ok_or_else(|| AppError::NotFound("用户不存在".to_string()))?;
 
-    let billing_rate = if time_log_model.is_billable {
-        Some(time_log_model.billing_rate.clone().unwrap_or_else(|| user_model.hourly_rate.clone()))
-    } else {
-        None
-    };
-
-    let billing_amount = billing_rate.as_ref().map(|rate| {
-        let seconds = sea_orm::prelude::Decimal::from(duration);
-        rate * seconds / sea_orm::prelude::Decimal::from(3600)
-    });
-
-    let mut active: time_log::ActiveModel = time_log_model.into();
-    active.end_time = sea_orm::ActiveValue::Set(Some(now));
-    active.duration = sea_orm::ActiveValue::Set(duration);
-    active.status = sea_orm::ActiveValue::Set(time_log::TimeLogStatus::Completed);
-    active.billing_rate = sea_orm::ActiveValue::Set(billing_rate.clone());
-    active.billing_amount = sea_orm::ActiveValue::Set(billing_amount.clone());
-    active.updated_at = sea_orm::ActiveValue::Set(now);
-
-    active
-        .update(&state.db)
+    let case_id = time_log_model.case_id.as_deref();
+    let rule = resolve_increment_rule(&state.db, &time_log_model.tenant_id, case_id).await?;
+    let rate =
+        resolve_billing_rate(&state.db, &user_model, case_id, time_log_model.task_id.as_deref()).await?.rate;
+
+    let warn_percents = state.config.case_budget_warn_percents.clone();
+    let actor_id = current_user.id().to_string();
+    let (duration, billing) = state
+        .db
+        .transaction::<_, (i32, BillingSnapshot), AppError>(|txn| {
+            Box::pin(async move {
+                // 事务内加锁重读：并发的停止 / 闲置暂停以最先提交者为准
+                let locked = time_log::Entity::find_by_id(&time_log_model.id)
+                    .lock_exclusive()
+                    .one(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("查询计时记录失败: {e}")))?
+                    .ok_or_else(|| AppError::NotFound("记录不存在".to_string()))?;
+                if !matches!(locked.status, time_log::TimeLogStatus::Running | time_log::TimeLogStatus::Paused) {
+                    return Err(AppError::Validation("计时已停止".to_string()));
+                }
+
+                let now = Utc::now();
+                let elapsed = if locked.status == time_log::TimeLogStatus::Running {
+                    let diff = now.signed_duration_since(locked.start_time).num_seconds();
+                    diff.max(0).min(i64::from(i32::MAX)) as i32
+                } else {
+                    0
+                };
+                let duration = locked.duration.saturating_add(elapsed);
+
+                // 恢复计时会把 startTime 重置为本段开始，按首次开始时间校验并落库，覆盖此前各段
+                let first_start = locked.created_at.min(locked.start_time);
+                ensure_no_overlap(txn, &locked.user_id, first_start, now, Some(&locked.id)).await?;
+
+                let billing = compute_billing(locked.is_billable, locked.billing_rate, rate, duration, &rule);
+                let case_id = locked.case_id.clone();
+                let log_id = locked.id.clone();
+                let billing = apply_case_billing_mode(
+                    txn,
+                    &warn_percents,
+                    &actor_id,
+                    case_id.as_deref(),
+                    Some(&log_id),
+                    billing,
+                )
+                .await?;
+
+                let mut active: time_log::ActiveModel = locked.into();
+                active.start_time = sea_orm::ActiveValue::Set(first_start);
+                active.end_time = sea_orm::ActiveValue::Set(Some(now));
+                active.duration = sea_orm::ActiveValue::Set(duration);
+                active.status = sea_orm::ActiveValue::Set(time_log::TimeLogStatus::Completed);
+                active.billed_duration = sea_orm::ActiveValue::Set(billing.billed_duration);
+                active.billing_rate = sea_orm::ActiveValue::Set(billing.rate);
+                active.billing_amount = sea_orm::ActiveValue::Set(billing.amount);
+                active.write_off_amount = sea_orm::ActiveValue::Set(billing.write_off_amount);
+                active.idle_paused_at = sea_orm::ActiveValue::Set(None);
+                active.idle_seconds = sea_orm::ActiveValue::Set(None);
+                active.updated_at = sea_orm::ActiveValue::Set(now);
+
+                active.update(txn).await.map_err(|e| AppError::Database(format!("停止计时失败: {e}")))?;
+                Ok((duration, billing))
+            })
+        })
         .await
-        .map_err(|e| AppError::Database(format!("停止计时失败: {e}")))?;
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
 
     Ok(Json(StopTimerResponse {
         duration,
-        billing_rate: billing_rate.map(|v| v.to_string()),
-        billing_amount: billing_amount.map(|v| v.to_string()),
+        billed_duration: billing.billed_duration,
+        billing_rate: billing.rate.map(|v| v.to_string()),
+        billing_amount: billing.amount.map(|v| v.to_string()),
+        write_off_amount: billing.write_off_amount.map(|v| v.to_string()),
     }))
 }
 
@@ -277,18 +560,241 @@ async fn resume_timer(
     Ok(Json(()))
 }
 
-async fn get_active_timer(
+/// POST /api/v1/timelogs（手工补录）
+async fn create_manual_time_log(
     State(state): State<Arc<AppState>>,
     current_user: CurrentUser,
-) -> AppResult<Json<Option<ActiveTimerResponse>>> {
-    let timer = time_log::Entity::find()
+    ValidatedJson(payload): ValidatedJson<CreateManualTimeLogRequest>,
+) -> AppResult<Json<TimeLogResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::CaseView)?;
+
+    let case_model =
+        resolve_log_case(&state, &current_user, payload.case_id.as_deref(), payload.task_id.as_deref()).await?;
+    let description = require_non_empty(&payload.description, "description", 5000)?;
+    let start_time = payload.start_time;
+    let (end_time, duration) = resolve_period(start_time, payload.end_time, payload.duration)?;
+
+    let is_billable = payload.is_billable.unwrap_or(true);
+    let rule = resolve_increment_rule(&state.db, &case_model.tenant_id, Some(&case_model.id)).await?;
+    let rate =
+        resolve_billing_rate(&state.db, &current_user.model, Some(&case_model.id), payload.task_id.as_deref()).await?;
+    let billing = compute_billing(is_billable, None, rate.rate, duration, &rule);
+
+    let user_id = current_user.id().to_string();
+    let task_id = payload.task_id.clone();
+    let warn_percents = state.config.case_budget_warn_percents.clone();
+    let inserted = state
+        .db
+        .transaction::<_, time_log::Model, AppError>(|txn| {
+            Box::pin(async move {
+                ensure_no_overlap(txn, &user_id, start_time, end_time, None).await?;
+                let billing =
+                    apply_case_billing_mode(txn, &warn_percents, &user_id, Some(&case_model.id), None, billing).await?;
+
+                let now = Utc::now();
+                time_log::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    tenant_id: sea_orm::ActiveValue::Set(case_model.tenant_id),
+                    user_id: sea_orm::ActiveValue::Set(user_id),
+                    case_id: sea_orm::ActiveValue::Set(Some(case_model.id)),
+                    task_id: sea_orm::ActiveValue::Set(task_id),
+                    description: sea_orm::ActiveValue::Set(description),
+                    start_time: sea_orm::ActiveValue::Set(start_time),
+                    end_time: sea_orm::ActiveValue::Set(Some(end_time)),
+                    duration: sea_orm::ActiveValue::Set(duration),
+                    billed_duration: sea_orm::ActiveValue::Set(billing.billed_duration),
+                    status: sea_orm::ActiveValue::Set(time_log::TimeLogStatus::Completed),
+                    is_billable: sea_orm::ActiveValue::Set(is_billable),
+                    billing_rate: sea_orm::ActiveValue::Set(billing.rate),
+                    billing_amount: sea_orm::ActiveValue::Set(billing.amount),
+                    write_off_amount: sea_orm::ActiveValue::Set(billing.write_off_amount),
+                    idle_paused_at: sea_orm::ActiveValue::Set(None),
+                    idle_seconds: sea_orm::ActiveValue::Set(None),
+                    created_at: sea_orm::ActiveValue::Set(now),
+                    updated_at: sea_orm::ActiveValue::Set(now),
+                }
+                .insert(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("添加工时失败: {e}")))
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    Ok(Json(TimeLogResponse::from(inserted)))
+}
+
+/// 本人且状态为 COMPLETED 的工时记录（APPROVED/BILLED 不可变，进行中的需先停止）
+async fn find_own_completed_log(
+    state: &AppState,
+    current_user: &CurrentUser,
+    time_log_id: &str,
+) -> AppResult<time_log::Model> {
+    Uuid::parse_str(time_log_id).map_err(|_| AppError::Validation("timeLogId 无效".to_string()))?;
+
+    let time_log_model = time_log::Entity::find_by_id(time_log_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询工时记录失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("工时记录不存在".to_string()))?;
+
+    if time_log_model.user_id != current_user.id() {
+        return Err(AppError::Forbidden("无权操作".to_string()));
+    }
+
+    match time_log_model.status {
+        time_log::TimeLogStatus::Completed => Ok(time_log_model),
+        time_log::TimeLogStatus::Approved | time_log::TimeLogStatus::Billed => {
+            Err(AppError::Validation("已审批/已计费的工时不可修改或删除".to_string()))
+        }
+        time_log::TimeLogStatus::Running | time_log::TimeLogStatus::Paused => {
+            Err(AppError::Validation("进行中的计时请先停止".to_string()))
+        }
+    }
+}
+
+/// PATCH /api/v1/timelogs/:id
+async fn update_time_log(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(time_log_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<UpdateTimeLogRequest>,
+) -> AppResult<Json<TimeLogResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::CaseView)?;
+    let existing = find_own_completed_log(&state, &current_user, &time_log_id).await?;
+
+    let (case_id, task_id) = match (payload.case_id.as_deref(), payload.task_id.as_deref()) {
+        (case_id, Some(task_id)) => {
+            let resolved = resolve_log_case(&state, &current_user, case_id, Some(task_id)).await?;
+            (Some(resolved.id), Some(task_id.to_string()))
+        }
+        // 改到其它案件时解除与原任务的关联（原任务不属于新案件）
+        (Some(case_id), None) if existing.case_id.as_deref() != Some(case_id) => {
+            let resolved = resolve_log_case(&state, &current_user, Some(case_id), None).await?;
+            (Some(resolved.id), None)
+        }
+        _ => (existing.case_id.clone(), existing.task_id.clone()),
+    };
+
+    let description = match payload.description.as_deref() {
+        Some(value) => require_non_empty(value, "description", 5000)?,
+        None => existing.description.clone(),
+    };
+
+    let period_changed = payload.start_time.is_some() || payload.end_time.is_some() || payload.duration.is_some();
+    let start_time = payload.start_time.unwrap_or(existing.start_time);
+    let (end_time, duration) = if period_changed {
+        // 仅改开始时间时保持原时长
+        let (end, secs) = match (payload.end_time, payload.duration) {
+            (None, None) => (None, Some(i64::from(existing.duration))),
+            other => other,
+        };
+        resolve_period(start_time, end, secs)?
+    } else {
+        (existing.end_time.unwrap_or(existing.start_time), existing.duration)
+    };
+
+    let is_billable = payload.is_billable.unwrap_or(existing.is_billable);
+    // 改到其它案件/任务时重新按费率卡取费率，否则沿用已快照的费率
+    let snapshot_rate =
+        if case_id == existing.case_id && task_id == existing.task_id { existing.billing_rate } else { None };
+    let rule = resolve_increment_rule(&state.db, &existing.tenant_id, case_id.as_deref()).await?;
+    let rate = resolve_billing_rate(&state.db, &current_user.model, case_id.as_deref(), task_id.as_deref()).await?;
+    let billing = compute_billing(is_billable, snapshot_rate, rate.rate, duration, &rule);
+
+    let user_id = current_user.id().to_string();
+    let warn_percents = state.config.case_budget_warn_percents.clone();
+    let updated = state
+        .db
+        .transaction::<_, time_log::Model, AppError>(|txn| {
+            Box::pin(async move {
+                // 事务内复核状态，避免与审批并发
+                let locked = time_log::Entity::find_by_id(&existing.id)
+                    .lock_exclusive()
+                    .one(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("查询工时记录失败: {e}")))?;
+                if !locked.is_some_and(|m| m.status == time_log::TimeLogStatus::Completed) {
+                    return Err(AppError::Validation("工时记录状态已变更，请刷新后重试".to_string()));
+                }
+                if period_changed {
+                    ensure_no_overlap(txn, &user_id, start_time, end_time, Some(&existing.id)).await?;
+                }
+                let billing = apply_case_billing_mode(
+                    txn,
+                    &warn_percents,
+                    &user_id,
+                    case_id.as_deref(),
+                    Some(&existing.id),
+                    billing,
+                )
+                .await?;
+
+                let mut active: time_log::ActiveModel = existing.into();
+                active.case_id = sea_orm::ActiveValue::Set(case_id);
+                active.task_id = sea_orm::ActiveValue::Set(task_id);
+                active.description = sea_orm::ActiveValue::Set(description);
+                active.start_time = sea_orm::ActiveValue::Set(start_time);
+                active.end_time = sea_orm::ActiveValue::Set(Some(end_time));
+                active.duration = sea_orm::ActiveValue::Set(duration);
+                active.billed_duration = sea_orm::ActiveValue::Set(billing.billed_duration);
+                active.is_billable = sea_orm::ActiveValue::Set(is_billable);
+                active.billing_rate = sea_orm::ActiveValue::Set(billing.rate);
+                active.billing_amount = sea_orm::ActiveValue::Set(billing.amount);
+                active.write_off_amount = sea_orm::ActiveValue::Set(billing.write_off_amount);
+                active.updated_at = sea_orm::ActiveValue::Set(Utc::now());
+
+                active
+                    .update(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("更新工时记录失败: {e}")))
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+
+    Ok(Json(TimeLogResponse::from(updated)))
+}
+
+/// DELETE /api/v1/timelogs/:id
+async fn delete_time_log(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(time_log_id): Path<String>,
+) -> AppResult<Json<()>> {
+    require_permission(current_user.model.role.clone(), Permission::CaseView)?;
+    let existing = find_own_completed_log(&state, &current_user, &time_log_id).await?;
+
+    let result = time_log::Entity::delete_many()
+        .filter(time_log::Column::Id.eq(&existing.id))
         .filter(time_log::Column::UserId.eq(current_user.id()))
+        .filter(time_log::Column::Status.eq(time_log::TimeLogStatus::Completed))
+        .exec(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("删除工时记录失败: {e}")))?;
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound("工时记录不存在".to_string()));
+    }
+
+    Ok(Json(()))
+}
+
+/// 当前用户进行中（RUNNING/PAUSED）的计时；同一用户至多 1 条
+pub(crate) async fn find_active_timer(state: &AppState, user_id: &str) -> AppResult<Option<ActiveTimerResponse>> {
+    let timer = time_log::Entity::find()
+        .filter(time_log::Column::UserId.eq(user_id))
         .filter(time_log::Column::Status.is_in(vec![time_log::TimeLogStatus::Running, time_log::TimeLogStatus::Paused]))
         .one(&state.db)
         .await
         .map_err(|e| AppError::Database(format!("查询计时失败: {e}")))?;
 
-    Ok(Json(timer.map(|t| ActiveTimerResponse {
+    Ok(timer.map(|t| ActiveTimerResponse {
         id: t.id,
         description: t.description,
         status: t.status.to_value(),
@@ -296,14 +802,73 @@ async fn get_active_timer(
         duration: t.duration,
         case_id: t.case_id,
         task_id: t.task_id,
-    })))
+        idle_paused_at: t.idle_paused_at,
+        idle_seconds: t.idle_seconds,
+    }))
+}
+
+/// POST /api/v1/timelogs/:id/idle（处理自动暂停留下的闲置时段）
+async fn resolve_idle(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(time_log_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<ResolveIdleRequest>,
+) -> AppResult<Json<TimeLogResponse>> {
+    Uuid::parse_str(&time_log_id).map_err(|_| AppError::Validation("timeLogId 无效".to_string()))?;
+    let keep = match payload.action.trim() {
+        "KEEP" => true,
+        "DISCARD" => false,
+        other => return Err(AppError::Validation(format!("action 无效: {other}"))),
+    };
+
+    let time_log_model = time_log::Entity::find_by_id(&time_log_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询计时记录失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("记录不存在".to_string()))?;
+
+    if time_log_model.user_id != current_user.id() {
+        return Err(AppError::Forbidden("无权操作".to_string()));
+    }
+    if !matches!(time_log_model.status, time_log::TimeLogStatus::Running | time_log::TimeLogStatus::Paused) {
+        return Err(AppError::Validation("计时已停止".to_string()));
+    }
+    let Some(idle_seconds) = time_log_model.idle_seconds else {
+        return Err(AppError::Validation("没有待处理的闲置时段".to_string()));
+    };
+
+    let duration =
+        if keep { time_log_model.duration.saturating_add(idle_seconds) } else { time_log_model.duration };
+    let mut active: time_log::ActiveModel = time_log_model.into();
+    active.duration = sea_orm::ActiveValue::Set(duration);
+    active.idle_paused_at = sea_orm::ActiveValue::Set(None);
+    active.idle_seconds = sea_orm::ActiveValue::Set(None);
+    active.updated_at = sea_orm::ActiveValue::Set(Utc::now());
+
+    let updated = active
+        .update(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("处理闲置时段失败: {e}")))?;
+    Ok(Json(TimeLogResponse::from(updated)))
+}
+
+async fn get_active_timer(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+) -> AppResult<Json<Option<ActiveTimerResponse>>> {
+    Ok(Json(find_active_timer(&state, current_user.id()).await?))
 }
 
 pub fn router() -> Router<Arc<AppState>> {
     Router::new()
+        .route("/", get(super::timelog_reports::list_time_logs).post(create_manual_time_log))
+        .route("/:id", patch(update_time_log).delete(delete_time_log))
         .route("/start", post(start_timer))
         .route("/:id/stop", post(stop_timer))
         .route("/:id/pause", post(pause_timer))
         .route("/:id/resume", post(resume_timer))
+        .route("/:id/idle", post(resolve_idle))
         .route("/active", get(get_active_timer))
+        .merge(super::timelog_reports::router())
+        .merge(super::timelog_approvals::router())
 }
@@ -0,0 +1,872 @@
+//! 信托（预收款）账路由
+//!
+//! - 按客户 + 案件分户；未指定案件为客户级分户，仅 PARTNER / ADMIN 可操作；其余按案件可见性控制
+//! - 复式记账：每笔流水生成借贷平衡的两条分录（规则见 `billing::trust`）；流水与分录只增不改（数据库触发器保证），
+//!   更正一律通过冲正流水
+//! - `GET /trust/balances?clientId=&caseId=`：分户余额（`billing:view`）
+//! - `GET /trust/transactions?clientId=&caseId=&page=&pageSize=`：流水及分录（`billing:view`）
+//! - `POST /trust/deposits`：客户存入（`billing:edit`）
+//! - `POST /trust/transfers`：从发票所属客户/案件分户划转至经营账户，同时登记为该发票的收款（`billing:approve`）；
+//!   金额不超过分户余额与发票未收金额
+//! - `POST /trust/refunds`：退还客户（`billing:approve`）
+//! - `POST /trust/transactions/:id/reverse`：冲正（`billing:approve`）；划转冲正时将对应收款标记作废（保留记录，不计入已收）；每笔流水只能冲正一次
+//! - `GET /trust/reconciliation?asOf=&statementBalance=`：对账报告（`billing:approve`，仅 PARTNER / ADMIN）
+//! - 写入前按分户加 advisory lock，分户余额任何时候不得为负
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::{Decimal, Expr};
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Statement, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::billing::invoice::round_money;
+use crate::billing::trust::{balance_after, is_balanced, ledger_balance, postings, reconcile, Direction, Ledger};
+use crate::db::AppState;
+use crate::entity::payment::{self, PaymentMethod};
+use crate::entity::trust_entry::{self, TrustAccount};
+use crate::entity::trust_transaction::{self, TrustTransactionType};
+use crate::entity::user::Role;
+use crate::entity::{case, invoice};
+use crate::error::{AppError, AppResult};
+use crate::security::case_access::{require_case_access, visible_case_ids};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+use crate::security::validation::{require_non_empty, ValidatedJson};
+
+use super::cases::PaginatedResponse;
+use super::invoices::require_invoice_access;
+use super::payments::{lock_receivable_invoice, paid_total, refresh_status};
+use super::receivables::contact_names;
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustMovementRequest {
+    pub client_id: String,
+    /// 缺省为客户级分户
+    pub case_id: Option<String>,
+    /// 金额（Decimal 字符串，最多 2 位小数）
+    pub amount: String,
+    pub occurred_at: DateTime<Utc>,
+    #[validate(length(max = 200, message = "reference 长度不合法"))]
+    pub reference: Option<String>,
+    #[validate(length(max = 2000, message = "note 长度不合法"))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustTransferRequest {
+    pub invoice_id: String,
+    pub amount: String,
+    pub occurred_at: DateTime<Utc>,
+    #[validate(length(max = 200, message = "reference 长度不合法"))]
+    pub reference: Option<String>,
+    #[validate(length(max = 2000, message = "note 长度不合法"))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ReverseTrustRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustBalanceQuery {
+    pub client_id: Option<String>,
+    pub case_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustTransactionListQuery {
+    pub client_id: Option<String>,
+    pub case_id: Option<String>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustReconciliationQuery {
+    /// 截止时间（含），缺省为当前
+    pub as_of: Option<DateTime<Utc>>,
+    /// 银行对账单余额（Decimal 字符串）
+    pub statement_balance: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustEntryResponse {
+    pub id: String,
+    pub account: String,
+    pub debit: String,
+    pub credit: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustTransactionResponse {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub client_id: String,
+    pub case_id: Option<String>,
+    pub amount: String,
+    pub occurred_at: DateTime<Utc>,
+    pub invoice_id: Option<String>,
+    pub payment_id: Option<String>,
+    pub reverses_id: Option<String>,
+    pub reference: Option<String>,
+    pub note: Option<String>,
+    pub created_by_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub entries: Vec<TrustEntryResponse>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustBalanceResponse {
+    pub client_id: String,
+    pub client_name: Option<String>,
+    pub case_id: Option<String>,
+    pub case_code: Option<String>,
+    pub balance: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustReconciliationResponse {
+    pub as_of: DateTime<Utc>,
+    /// 信托银行存款账面余额
+    pub book_balance: String,
+    /// 客户分户余额合计
+    pub client_total: String,
+    pub book_difference: String,
+    pub statement_balance: Option<String>,
+    pub statement_difference: Option<String>,
+    pub balanced: bool,
+    /// 余额为负的分户（正常情况下为空）
+    pub negative_balances: Vec<TrustBalanceResponse>,
+    /// 分录借贷不平或与流水金额不符的流水（正常情况下为空）
+    pub unbalanced_transaction_ids: Vec<String>,
+    pub balances: Vec<TrustBalanceResponse>,
+}
+
+/// 待写入的信托流水
+struct NewTrustTransaction {
+    tenant_id: String,
+    kind: TrustTransactionType,
+    direction: Direction,
+    client_id: String,
+    case_id: Option<String>,
+    amount: Decimal,
+    occurred_at: DateTime<Utc>,
+    invoice_id: Option<String>,
+    payment_id: Option<String>,
+    reverses_id: Option<String>,
+    reference: Option<String>,
+    note: Option<String>,
+    created_by_id: String,
+}
+
+/// 分户余额（按客户 + 案件汇总）
+struct LedgerRow {
+    client_id: String,
+    case_id: Option<String>,
+    balance: Decimal,
+}
+
+fn is_admin(role: &Role) -> bool {
+    matches!(role, Role::Partner | Role::Admin)
+}
+
+fn parse_amount(raw: &str) -> AppResult<Decimal> {
+    match Decimal::from_str(raw.trim()) {
+        Ok(v) if v > Decimal::ZERO && round_money(v) == v => Ok(v),
+        _ => Err(AppError::Validation(format!("amount 无效: {raw}（需为正数，最多 2 位小数）"))),
+    }
+}
+
+fn trim_optional(value: Option<String>) -> Option<String> {
+    value.map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+fn account_of(ledger: Ledger) -> TrustAccount {
+    match ledger {
+        Ledger::TrustBank => TrustAccount::TrustBank,
+        Ledger::ClientLedger => TrustAccount::ClientLedger,
+    }
+}
+
+/// 流水对客户分户的资金方向；冲正流水不可再冲正，返回 None
+fn direction_of(kind: TrustTransactionType) -> Option<Direction> {
+    match kind {
+        TrustTransactionType::Deposit => Some(Direction::Inflow),
+        TrustTransactionType::Transfer | TrustTransactionType::Refund => Some(Direction::Outflow),
+        TrustTransactionType::Reversal => None,
+    }
+}
+
+/// 校验客户属于当前租户、案件属于该客户且可见；客户级分户仅 PARTNER / ADMIN
+async fn ensure_ledger_access(
+    state: &AppState,
+    current_user: &CurrentUser,
+    client_id: &str,
+    case_id: Option<&str>,
+) -> AppResult<()> {
+    let tenant_id = &current_user.model.active_tenant_id;
+    let role = current_user.model.role.clone();
+    state
+        .db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT 1 FROM "Contact" WHERE id = $1 AND "tenantId" = $2 AND "deletedAt" IS NULL LIMIT 1"#,
+            [client_id.into(), tenant_id.as_str().into()],
+        ))
+        .await
+        .map_err(|e| AppError::Database(format!("查询客户失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound("客户不存在".to_string()))?;
+
+    match case_id {
+        Some(case_id) => {
+            Uuid::parse_str(case_id).map_err(|_| AppError::Validation("caseId 无效".to_string()))?;
+            let case_model =
+                require_case_access(state, case_id, current_user.id(), role, Permission::CaseView).await?;
+            if case_model.tenant_id != *tenant_id {
+                return Err(AppError::NotFound(format!("案件 {case_id} 不存在")));
+            }
+            if case_model.client_id != client_id {
+                return Err(AppError::Validation("案件不属于该客户".to_string()));
+            }
+        }
+        None if !is_admin(&role) => {
+            return Err(AppError::Forbidden("客户级信托分户仅合伙人或管理员可操作".to_string()));
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+/// 按分户加事务级 advisory lock
+async fn lock_ledger<C: ConnectionTrait>(
+    db: &C,
+    tenant_id: &str,
+    client_id: &str,
+    case_id: Option<&str>,
+) -> AppResult<()> {
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT pg_advisory_xact_lock(hashtext($1))",
+        [format!("trust:{tenant_id}:{client_id}:{}", case_id.unwrap_or("-")).into()],
+    ))
+    .await
+    .map_err(|e| AppError::Database(format!("获取信托分户锁失败: {e}")))?;
+    Ok(())
+}
+
+/// 客户分户当前余额
+async fn client_balance<C: ConnectionTrait>(
+    db: &C,
+    tenant_id: &str,
+    client_id: &str,
+    case_id: Option<&str>,
+) -> AppResult<Decimal> {
+    let mut select = trust_entry::Entity::find()
+        .filter(trust_entry::Column::TenantId.eq(tenant_id))
+        .filter(trust_entry::Column::Account.eq(TrustAccount::ClientLedger))
+        .filter(trust_entry::Column::ClientId.eq(client_id));
+    select = match case_id {
+        Some(case_id) => select.filter(trust_entry::Column::CaseId.eq(case_id)),
+        None => select.filter(trust_entry::Column::CaseId.is_null()),
+    };
+    let (debit, credit) = select
+        .select_only()
+        .column_as(trust_entry::Column::Debit.sum(), "debit")
+        .column_as(trust_entry::Column::Credit.sum(), "credit")
+        .into_tuple::<(Option<Decimal>, Option<Decimal>)>()
+        .one(db)
+        .await
+        .map_err(|e| AppError::Database(format!("汇总信托余额失败: {e}")))?
+        .unwrap_or_default();
+    Ok(ledger_balance(Ledger::ClientLedger, debit.unwrap_or_default(), credit.unwrap_or_default()))
+}
+
+/// 加锁校验余额后写入流水及其分录
+async fn post_transaction<C: ConnectionTrait>(db: &C, new: NewTrustTransaction) -> AppResult<trust_transaction::Model> {
+    lock_ledger(db, &new.tenant_id, &new.client_id, new.case_id.as_deref()).await?;
+    let balance = client_balance(db, &new.tenant_id, &new.client_id, new.case_id.as_deref()).await?;
+    if balance_after(balance, new.direction, new.amount).is_none() {
+        return Err(AppError::Validation(format!("信托余额不足（可用 {balance}）")));
+    }
+
+    let lines = postings(new.direction, new.amount);
+    if !is_balanced(&lines) {
+        return Err(AppError::Internal("信托分录借贷不平".to_string()));
+    }
+
+    let now = Utc::now();
+    let saved = trust_transaction::ActiveModel {
+        id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+        tenant_id: sea_orm::ActiveValue::Set(new.tenant_id.clone()),
+        kind: sea_orm::ActiveValue::Set(new.kind),
+        client_id: sea_orm::ActiveValue::Set(new.client_id.clone()),
+        case_id: sea_orm::ActiveValue::Set(new.case_id.clone()),
+        amount: sea_orm::ActiveValue::Set(new.amount),
+        occurred_at: sea_orm::ActiveValue::Set(new.occurred_at),
+        invoice_id: sea_orm::ActiveValue::Set(new.invoice_id),
+        payment_id: sea_orm::ActiveValue::Set(new.payment_id),
+        reverses_id: sea_orm::ActiveValue::Set(new.reverses_id),
+        reference: sea_orm::ActiveValue::Set(new.reference),
+        note: sea_orm::ActiveValue::Set(new.note),
+        created_by_id: sea_orm::ActiveValue::Set(Some(new.created_by_id)),
+        created_at: sea_orm::ActiveValue::Set(now),
+    }
+    .insert(db)
+    .await
+    .map_err(|e| AppError::Database(format!("写入信托流水失败: {e}")))?;
+
+    for posting in lines {
+        trust_entry::ActiveModel {
+            id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+            tenant_id: sea_orm::ActiveValue::Set(new.tenant_id.clone()),
+            transaction_id: sea_orm::ActiveValue::Set(saved.id.clone()),
+            account: sea_orm::ActiveValue::Set(account_of(posting.ledger)),
+            client_id: sea_orm::ActiveValue::Set(new.client_id.clone()),
+            case_id: sea_orm::ActiveValue::Set(new.case_id.clone()),
+            debit: sea_orm::ActiveValue::Set(posting.debit),
+            credit: sea_orm::ActiveValue::Set(posting.credit),
+            created_at: sea_orm::ActiveValue::Set(now),
+        }
+        .insert(db)
+        .await
+        .map_err(|e| AppError::Database(format!("写入信托分录失败: {e}")))?;
+    }
+    Ok(saved)
+}
+
+async fn transaction_responses<C: ConnectionTrait>(
+    db: &C,
+    models: Vec<trust_transaction::Model>,
+) -> AppResult<Vec<TrustTransactionResponse>> {
+    let ids: Vec<String> = models.iter().map(|m| m.id.clone()).collect();
+    let mut entries: HashMap<String, Vec<TrustEntryResponse>> = HashMap::new();
+    if !ids.is_empty() {
+        let rows = trust_entry::Entity::find()
+            .filter(trust_entry::Column::TransactionId.is_in(ids))
+            .order_by_asc(trust_entry::Column::Account)
+            .all(db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询信托分录失败: {e}")))?;
+        for e in rows {
+            entries.entry(e.transaction_id.clone()).or_default().push(TrustEntryResponse {
+                id: e.id,
+                account: e.account.to_value(),
+                debit: e.debit.to_string(),
+                credit: e.credit.to_string(),
+            });
+        }
+    }
+    Ok(models
+        .into_iter()
+        .map(|m| TrustTransactionResponse {
+            entries: entries.remove(&m.id).unwrap_or_default(),
+            id: m.id,
+            kind: m.kind.to_value(),
+            client_id: m.client_id,
+            case_id: m.case_id,
+            amount: m.amount.to_string(),
+            occurred_at: m.occurred_at,
+            invoice_id: m.invoice_id,
+            payment_id: m.payment_id,
+            reverses_id: m.reverses_id,
+            reference: m.reference,
+            note: m.note,
+            created_by_id: m.created_by_id,
+            created_at: m.created_at,
+        })
+        .collect())
+}
+
+async fn single_response<C: ConnectionTrait>(
+    db: &C,
+    model: trust_transaction::Model,
+) -> AppResult<TrustTransactionResponse> {
+    transaction_responses(db, vec![model])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::Internal("信托流水响应为空".to_string()))
+}
+
+/// 指定科目按客户 + 案件汇总的余额（按流水发生时间截止）
+async fn ledger_rows(
+    state: &AppState,
+    tenant_id: &str,
+    ledger: Ledger,
+    client_id: Option<&str>,
+    as_of: Option<DateTime<Utc>>,
+) -> AppResult<Vec<LedgerRow>> {
+    let rows = state
+        .db
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT e."clientId" AS client_id, e."caseId" AS case_id,
+                      SUM(e.debit) AS debit, SUM(e.credit) AS credit
+               FROM "TrustEntry" e JOIN "TrustTransaction" t ON t.id = e."transactionId"
+               WHERE e."tenantId" = $1 AND e.account::text = $2
+                 AND ($3::text IS NULL OR e."clientId" = $3)
+                 AND ($4::timestamp IS NULL OR t."occurredAt" <= $4)
+               GROUP BY e."clientId", e."caseId"
+               ORDER BY e."clientId", e."caseId" NULLS FIRST"#,
+            [
+                tenant_id.into(),
+                account_of(ledger).to_value().into(),
+                client_id.map(str::to_string).into(),
+                as_of.map(|d| d.naive_utc()).into(),
+            ],
+        ))
+        .await
+        .map_err(|e| AppError::Database(format!("汇总信托余额失败: {e}")))?;
+    rows.into_iter()
+        .map(|row| {
+            let read = |e: sea_orm::DbErr| AppError::Database(format!("读取信托余额失败: {e}"));
+            let debit: Decimal = row.try_get("", "debit").map_err(read)?;
+            let credit: Decimal = row.try_get("", "credit").map_err(read)?;
+            Ok(LedgerRow {
+                client_id: row.try_get("", "client_id").map_err(read)?,
+                case_id: row.try_get("", "case_id").map_err(read)?,
+                balance: ledger_balance(ledger, debit, credit),
+            })
+        })
+        .collect()
+}
+
+async fn balance_responses(state: &AppState, rows: &[LedgerRow]) -> AppResult<Vec<TrustBalanceResponse>> {
+    let client_ids: Vec<String> =
+        rows.iter().map(|r| r.client_id.clone()).collect::<HashSet<_>>().into_iter().collect();
+    let names = contact_names(state, client_ids).await?;
+    let case_ids: Vec<String> = rows.iter().filter_map(|r| r.case_id.clone()).collect();
+    let codes: HashMap<String, String> = if case_ids.is_empty() {
+        HashMap::new()
+    } else {
+        case::Entity::find()
+            .filter(case::Column::Id.is_in(case_ids))
+            .all(&state.db)
+            .await
+            .map_err(|e| AppError::Database(format!("查询案件失败: {e}")))?
+            .into_iter()
+            .map(|c| (c.id, c.case_code))
+            .collect()
+    };
+    Ok(rows
+        .iter()
+        .map(|r| TrustBalanceResponse {
+            client_id: r.client_id.clone(),
+            client_name: names.get(&r.client_id).cloned(),
+            case_id: r.case_id.clone(),
+            case_code: r.case_id.as_ref().and_then(|id| codes.get(id)).cloned(),
+            balance: r.balance.to_string(),
+        })
+        .collect())
+}
+
+/// GET /api/v1/trust/balances
+async fn list_balances(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<TrustBalanceQuery>,
+) -> AppResult<Json<Vec<TrustBalanceResponse>>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::BillingView)?;
+    let tenant_id = &current_user.model.active_tenant_id;
+
+    let mut rows = ledger_rows(&state, tenant_id, Ledger::ClientLedger, query.client_id.as_deref(), None).await?;
+    if let Some(case_id) = query.case_id.as_deref() {
+        Uuid::parse_str(case_id).map_err(|_| AppError::Validation("caseId 无效".to_string()))?;
+        require_case_access(&state, case_id, current_user.id(), role.clone(), Permission::CaseView).await?;
+        rows.retain(|r| r.case_id.as_deref() == Some(case_id));
+    }
+    if let Some(ids) = visible_case_ids(&state, current_user.id(), &role).await? {
+        rows.retain(|r| r.case_id.as_ref().is_some_and(|id| ids.contains(id)));
+    }
+    Ok(Json(balance_responses(&state, &rows).await?))
+}
+
+/// GET /api/v1/trust/transactions
+async fn list_transactions(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<TrustTransactionListQuery>,
+) -> AppResult<Json<PaginatedResponse<TrustTransactionResponse>>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::BillingView)?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
+
+    let mut select = trust_transaction::Entity::find()
+        .filter(trust_transaction::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .order_by_desc(trust_transaction::Column::OccurredAt)
+        .order_by_desc(trust_transaction::Column::CreatedAt);
+    if let Some(client_id) = query.client_id.as_deref() {
+        select = select.filter(trust_transaction::Column::ClientId.eq(client_id));
+    }
+    match query.case_id.as_deref() {
+        Some(case_id) => {
+            Uuid::parse_str(case_id).map_err(|_| AppError::Validation("caseId 无效".to_string()))?;
+            require_case_access(&state, case_id, current_user.id(), role, Permission::CaseView).await?;
+            select = select.filter(trust_transaction::Column::CaseId.eq(case_id));
+        }
+        None => {
+            if let Some(ids) = visible_case_ids(&state, current_user.id(), &role).await? {
+                select = select.filter(trust_transaction::Column::CaseId.is_in(ids));
+            }
+        }
+    }
+
+    let paginator = select.paginate(&state.db, page_size);
+    let total = paginator.num_items().await.map_err(|e| AppError::Database(format!("计数失败: {e}")))?;
+    let total_pages = paginator.num_pages().await.map_err(|e| AppError::Database(format!("分页失败: {e}")))?;
+    let models = paginator
+        .fetch_page(page - 1)
+        .await
+        .map_err(|e| AppError::Database(format!("查询信托流水失败: {e}")))?;
+
+    Ok(Json(PaginatedResponse {
+        data: transaction_responses(&state.db, models).await?,
+        total,
+        page,
+        page_size,
+        total_pages,
+    }))
+}
+
+async fn create_movement(
+    state: &AppState,
+    current_user: &CurrentUser,
+    kind: TrustTransactionType,
+    direction: Direction,
+    payload: TrustMovementRequest,
+) -> AppResult<TrustTransactionResponse> {
+    let amount = parse_amount(&payload.amount)?;
+    let client_id = require_non_empty(&payload.client_id, "clientId", 100)?;
+    let case_id = trim_optional(payload.case_id);
+    ensure_ledger_access(state, current_user, &client_id, case_id.as_deref()).await?;
+
+    let new = NewTrustTransaction {
+        tenant_id: current_user.model.active_tenant_id.clone(),
+        kind,
+        direction,
+        client_id,
+        case_id,
+        amount,
+        occurred_at: payload.occurred_at,
+        invoice_id: None,
+        payment_id: None,
+        reverses_id: None,
+        reference: trim_optional(payload.reference),
+        note: trim_optional(payload.note),
+        created_by_id: current_user.id().to_string(),
+    };
+    state
+        .db
+        .transaction::<_, TrustTransactionResponse, AppError>(|txn| {
+            Box::pin(async move {
+                let saved = post_transaction(txn, new).await?;
+                single_response(txn, saved).await
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })
+}
+
+/// POST /api/v1/trust/deposits
+async fn create_deposit(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(payload): ValidatedJson<TrustMovementRequest>,
+) -> AppResult<Json<TrustTransactionResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    let created =
+        create_movement(&state, &current_user, TrustTransactionType::Deposit, Direction::Inflow, payload).await?;
+    Ok(Json(created))
+}
+
+/// POST /api/v1/trust/refunds
+async fn create_refund(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(payload): ValidatedJson<TrustMovementRequest>,
+) -> AppResult<Json<TrustTransactionResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingApprove)?;
+    let created =
+        create_movement(&state, &current_user, TrustTransactionType::Refund, Direction::Outflow, payload).await?;
+    Ok(Json(created))
+}
+
+/// POST /api/v1/trust/transfers
+async fn create_transfer(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(payload): ValidatedJson<TrustTransferRequest>,
+) -> AppResult<Json<TrustTransactionResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingApprove)?;
+    let amount = parse_amount(&payload.amount)?;
+    let invoice_model = require_invoice_access(&state, &current_user, &payload.invoice_id).await?;
+    let client_id = invoice_model
+        .client_id
+        .clone()
+        .ok_or_else(|| AppError::Validation("发票未关联客户，无法从信托账划转".to_string()))?;
+    ensure_ledger_access(&state, &current_user, &client_id, invoice_model.case_id.as_deref()).await?;
+
+    let recorder_id = current_user.id().to_string();
+    let reference = trim_optional(payload.reference);
+    let note = trim_optional(payload.note);
+    let created = state
+        .db
+        .transaction::<_, TrustTransactionResponse, AppError>(|txn| {
+            Box::pin(async move {
+                let locked = lock_receivable_invoice(txn, &invoice_model.id).await?;
+                let paid = paid_total(txn, &locked.id).await?;
+                let outstanding = locked.total_amount - paid;
+                if amount > outstanding {
+                    return Err(AppError::Validation(format!(
+                        "划转金额超过发票未收金额 {}",
+                        outstanding.max(Decimal::ZERO)
+                    )));
+                }
+
+                let now = Utc::now();
+                let payment_model = payment::ActiveModel {
+                    id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                    tenant_id: sea_orm::ActiveValue::Set(locked.tenant_id.clone()),
+                    invoice_id: sea_orm::ActiveValue::Set(locked.id.clone()),
+                    amount: sea_orm::ActiveValue::Set(amount),
+                    method: sea_orm::ActiveValue::Set(PaymentMethod::Trust),
+                    received_at: sea_orm::ActiveValue::Set(payload.occurred_at),
+                    reference: sea_orm::ActiveValue::Set(reference.clone()),
+                    note: sea_orm::ActiveValue::Set(note.clone()),
+                    recorder_id: sea_orm::ActiveValue::Set(Some(recorder_id.clone())),
+                    voided_at: sea_orm::ActiveValue::Set(None),
+                    created_at: sea_orm::ActiveValue::Set(now),
+                }
+                .insert(txn)
+                .await
+                .map_err(|e| AppError::Database(format!("登记收款失败: {e}")))?;
+
+                let saved = post_transaction(
+                    txn,
+                    NewTrustTransaction {
+                        tenant_id: locked.tenant_id.clone(),
+                        kind: TrustTransactionType::Transfer,
+                        direction: Direction::Outflow,
+                        client_id,
+                        case_id: locked.case_id.clone(),
+                        amount,
+                        occurred_at: payload.occurred_at,
+                        invoice_id: Some(locked.id.clone()),
+                        payment_id: Some(payment_model.id),
+                        reverses_id: None,
+                        reference,
+                        note,
+                        created_by_id: recorder_id,
+                    },
+                )
+                .await?;
+                refresh_status(txn, locked, paid + amount, now).await?;
+                single_response(txn, saved).await
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+    Ok(Json(created))
+}
+
+/// POST /api/v1/trust/transactions/:id/reverse
+async fn reverse_transaction(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(transaction_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<ReverseTrustRequest>,
+) -> AppResult<Json<TrustTransactionResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingApprove)?;
+    Uuid::parse_str(&transaction_id).map_err(|_| AppError::Validation("流水ID 无效".to_string()))?;
+    let reason = require_non_empty(&payload.reason, "reason", 2000)?;
+    let original = trust_transaction::Entity::find_by_id(&transaction_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询信托流水失败: {e}")))?
+        .filter(|t| t.tenant_id == current_user.model.active_tenant_id)
+        .ok_or_else(|| AppError::NotFound("信托流水不存在".to_string()))?;
+    ensure_ledger_access(&state, &current_user, &original.client_id, original.case_id.as_deref()).await?;
+    let direction = direction_of(original.kind)
+        .ok_or_else(|| AppError::Validation("冲正流水不可再冲正".to_string()))?
+        .reversed();
+
+    let created_by_id = current_user.id().to_string();
+    let created = state
+        .db
+        .transaction::<_, TrustTransactionResponse, AppError>(|txn| {
+            Box::pin(async move {
+                // 划转冲正需先锁发票，与划转保持同样的加锁顺序（发票 → 分户）
+                let locked_invoice = match (original.payment_id.as_deref(), original.invoice_id.as_deref()) {
+                    (Some(_), Some(invoice_id)) => Some(
+                        invoice::Entity::find_by_id(invoice_id)
+                            .lock_exclusive()
+                            .one(txn)
+                            .await
+                            .map_err(|e| AppError::Database(format!("查询发票失败: {e}")))?
+                            .ok_or_else(|| AppError::NotFound("发票不存在".to_string()))?,
+                    ),
+                    _ => None,
+                };
+                lock_ledger(txn, &original.tenant_id, &original.client_id, original.case_id.as_deref()).await?;
+                let reversed = trust_transaction::Entity::find()
+                    .filter(trust_transaction::Column::ReversesId.eq(&original.id))
+                    .one(txn)
+                    .await
+                    .map_err(|e| AppError::Database(format!("查询信托流水失败: {e}")))?;
+                if reversed.is_some() {
+                    return Err(AppError::Validation("该流水已冲正".to_string()));
+                }
+
+                let saved = post_transaction(
+                    txn,
+                    NewTrustTransaction {
+                        tenant_id: original.tenant_id.clone(),
+                        kind: TrustTransactionType::Reversal,
+                        direction,
+                        client_id: original.client_id.clone(),
+                        case_id: original.case_id.clone(),
+                        amount: original.amount,
+                        occurred_at: Utc::now(),
+                        invoice_id: original.invoice_id.clone(),
+                        payment_id: None,
+                        reverses_id: Some(original.id.clone()),
+                        reference: original.reference.clone(),
+                        note: Some(reason),
+                        created_by_id,
+                    },
+                )
+                .await?;
+
+                // 划转流水仍引用该收款：保留记录并标记作废，不再计入已收金额
+                if let (Some(locked), Some(payment_id)) = (locked_invoice, original.payment_id.as_deref()) {
+                    let now = Utc::now();
+                    payment::Entity::update_many()
+                        .col_expr(payment::Column::VoidedAt, Expr::value(now))
+                        .filter(payment::Column::Id.eq(payment_id))
+                        .exec(txn)
+                        .await
+                        .map_err(|e| AppError::Database(format!("作废收款失败: {e}")))?;
+                    let paid = paid_total(txn, &locked.id).await?;
+                    refresh_status(txn, locked, paid, now).await?;
+                }
+                single_response(txn, saved).await
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            sea_orm::TransactionError::Connection(db) => AppError::Database(format!("事务连接失败: {db}")),
+            sea_orm::TransactionError::Transaction(app) => app,
+        })?;
+    Ok(Json(created))
+}
+
+/// GET /api/v1/trust/reconciliation
+async fn get_reconciliation(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<TrustReconciliationQuery>,
+) -> AppResult<Json<TrustReconciliationResponse>> {
+    let role = current_user.model.role.clone();
+    require_permission(role.clone(), Permission::BillingApprove)?;
+    if !is_admin(&role) {
+        return Err(AppError::Forbidden("信托对账仅合伙人或管理员可查看".to_string()));
+    }
+    let statement_balance = match query.statement_balance.as_deref() {
+        Some(raw) => Some(
+            Decimal::from_str(raw.trim())
+                .map_err(|_| AppError::Validation(format!("statementBalance 无效: {raw}")))?,
+        ),
+        None => None,
+    };
+    let as_of = query.as_of.unwrap_or_else(Utc::now);
+    let tenant_id = &current_user.model.active_tenant_id;
+
+    let bank_rows = ledger_rows(&state, tenant_id, Ledger::TrustBank, None, Some(as_of)).await?;
+    let client_rows = ledger_rows(&state, tenant_id, Ledger::ClientLedger, None, Some(as_of)).await?;
+    let book_balance: Decimal = bank_rows.iter().map(|r| r.balance).sum();
+    let client_total: Decimal = client_rows.iter().map(|r| r.balance).sum();
+    let result = reconcile(book_balance, client_total, statement_balance);
+
+    let unbalanced = state
+        .db
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT t.id AS id
+               FROM "TrustTransaction" t LEFT JOIN "TrustEntry" e ON e."transactionId" = t.id
+               WHERE t."tenantId" = $1 AND t."occurredAt" <= $2
+               GROUP BY t.id, t.amount
+               HAVING COALESCE(SUM(e.debit), 0) <> t.amount OR COALESCE(SUM(e.credit), 0) <> t.amount
+               ORDER BY t.id"#,
+            [tenant_id.as_str().into(), as_of.naive_utc().into()],
+        ))
+        .await
+        .map_err(|e| AppError::Database(format!("核对信托分录失败: {e}")))?
+        .into_iter()
+        .map(|row| row.try_get::<String>("", "id").map_err(|e| AppError::Database(format!("读取信托流水失败: {e}"))))
+        .collect::<AppResult<Vec<String>>>()?;
+
+    let balances = balance_responses(&state, &client_rows).await?;
+    let negative_balances = client_rows
+        .iter()
+        .zip(&balances)
+        .filter(|(row, _)| row.balance < Decimal::ZERO)
+        .map(|(_, b)| b.clone())
+        .collect::<Vec<_>>();
+
+    Ok(Json(TrustReconciliationResponse {
+        as_of,
+        book_balance: result.book_balance.to_string(),
+        client_total: result.client_total.to_string(),
+        book_difference: result.book_difference.to_string(),
+        statement_balance: statement_balance.map(|v| v.to_string()),
+        statement_difference: result.statement_difference.map(|v| v.to_string()),
+        balanced: result.is_balanced() && unbalanced.is_empty() && negative_balances.is_empty(),
+        negative_balances,
+        unbalanced_transaction_ids: unbalanced,
+        balances,
+    }))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/balances", get(list_balances))
+        .route("/transactions", get(list_transactions))
+        .route("/transactions/:id/reverse", post(reverse_transaction))
+        .route("/deposits", post(create_deposit))
+        .route("/transfers", post(create_transfer))
+        .route("/refunds", post(create_refund))
+        .route("/reconciliation", get(get_reconciliation))
+}
@@ -0,0 +1,176 @@
+//! UTBMS 代码映射路由（合并到 `/billing`）
+//!
+//! - `TASK`：按任务类型（`Task.taskType`）完全匹配；`ACTIVITY`：按工时描述包含关键词匹配（最长关键词优先）；
+//!   `EXPENSE`：按费用类别完全匹配；匹配值 `*` 为该类型的缺省代码
+//! - 仅用于电子账单导出（见 `invoice_exports`），随时修改、导出时生效
+//! - 查看需 `billing:view`；维护需 `billing:edit`
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{delete, get},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{ActiveEnum, ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::db::AppState;
+use crate::entity::utbms_code_mapping::{self, UtbmsCodeKind};
+use crate::error::{AppError, AppResult};
+use crate::security::current_user::CurrentUser;
+use crate::security::permissions::{require_permission, Permission};
+use crate::security::validation::{require_non_empty, ValidatedJson};
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertUtbmsCodeRequest {
+    /// `TASK` | `ACTIVITY` | `EXPENSE`
+    pub kind: String,
+    /// 匹配值；`*` 表示缺省代码
+    pub match_value: String,
+    /// UTBMS 代码，如 `L110` / `A101` / `E101`
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtbmsCodeListQuery {
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtbmsCodeResponse {
+    pub id: String,
+    pub kind: String,
+    pub match_value: String,
+    pub code: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<utbms_code_mapping::Model> for UtbmsCodeResponse {
+    fn from(m: utbms_code_mapping::Model) -> Self {
+        Self {
+            id: m.id,
+            kind: m.kind.to_value(),
+            match_value: m.match_value,
+            code: m.code,
+            updated_at: m.updated_at,
+        }
+    }
+}
+
+fn parse_kind(raw: &str) -> AppResult<UtbmsCodeKind> {
+    match raw.trim() {
+        "TASK" => Ok(UtbmsCodeKind::Task),
+        "ACTIVITY" => Ok(UtbmsCodeKind::Activity),
+        "EXPENSE" => Ok(UtbmsCodeKind::Expense),
+        other => Err(AppError::Validation(format!("kind 无效: {other}"))),
+    }
+}
+
+fn parse_code(raw: &str) -> AppResult<String> {
+    let code = raw.trim().to_uppercase();
+    if code.is_empty() || code.len() > 20 || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(AppError::Validation(format!("code 无效: {raw}（需为字母或数字，如 L110）")));
+    }
+    Ok(code)
+}
+
+/// GET /api/v1/billing/utbms-codes
+async fn list_codes(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Query(query): Query<UtbmsCodeListQuery>,
+) -> AppResult<Json<Vec<UtbmsCodeResponse>>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingView)?;
+    let mut select = utbms_code_mapping::Entity::find()
+        .filter(utbms_code_mapping::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .order_by_asc(utbms_code_mapping::Column::Kind)
+        .order_by_asc(utbms_code_mapping::Column::MatchValue);
+    if let Some(kind) = query.kind.as_deref() {
+        select = select.filter(utbms_code_mapping::Column::Kind.eq(parse_kind(kind)?));
+    }
+    let mappings = select
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询 UTBMS 代码映射失败: {e}")))?;
+    Ok(Json(mappings.into_iter().map(UtbmsCodeResponse::from).collect()))
+}
+
+/// PUT /api/v1/billing/utbms-codes（按类型 + 匹配值新增或更新）
+async fn upsert_code(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    ValidatedJson(payload): ValidatedJson<UpsertUtbmsCodeRequest>,
+) -> AppResult<Json<UtbmsCodeResponse>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    let kind = parse_kind(&payload.kind)?;
+    let match_value = require_non_empty(&payload.match_value, "matchValue", 100)?;
+    let code = parse_code(&payload.code)?;
+    let tenant_id = current_user.model.active_tenant_id.clone();
+
+    let existing = utbms_code_mapping::Entity::find()
+        .filter(utbms_code_mapping::Column::TenantId.eq(&tenant_id))
+        .filter(utbms_code_mapping::Column::Kind.eq(kind))
+        .filter(utbms_code_mapping::Column::MatchValue.eq(&match_value))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询 UTBMS 代码映射失败: {e}")))?;
+
+    let now = Utc::now();
+    let saved = match existing {
+        Some(model) => {
+            let mut active: utbms_code_mapping::ActiveModel = model.into();
+            active.code = sea_orm::ActiveValue::Set(code);
+            active.updated_at = sea_orm::ActiveValue::Set(now);
+            active.update(&state.db).await
+        }
+        None => {
+            utbms_code_mapping::ActiveModel {
+                id: sea_orm::ActiveValue::Set(Uuid::new_v4().to_string()),
+                tenant_id: sea_orm::ActiveValue::Set(tenant_id),
+                kind: sea_orm::ActiveValue::Set(kind),
+                match_value: sea_orm::ActiveValue::Set(match_value),
+                code: sea_orm::ActiveValue::Set(code),
+                created_at: sea_orm::ActiveValue::Set(now),
+                updated_at: sea_orm::ActiveValue::Set(now),
+            }
+            .insert(&state.db)
+            .await
+        }
+    };
+    let saved = saved.map_err(|e| AppError::Database(format!("保存 UTBMS 代码映射失败: {e}")))?;
+    Ok(Json(UtbmsCodeResponse::from(saved)))
+}
+
+/// DELETE /api/v1/billing/utbms-codes/:id
+async fn delete_code(
+    State(state): State<Arc<AppState>>,
+    current_user: CurrentUser,
+    Path(mapping_id): Path<String>,
+) -> AppResult<Json<()>> {
+    require_permission(current_user.model.role.clone(), Permission::BillingEdit)?;
+    Uuid::parse_str(&mapping_id).map_err(|_| AppError::Validation("映射ID 无效".to_string()))?;
+
+    let result = utbms_code_mapping::Entity::delete_many()
+        .filter(utbms_code_mapping::Column::Id.eq(&mapping_id))
+        .filter(utbms_code_mapping::Column::TenantId.eq(&current_user.model.active_tenant_id))
+        .exec(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("删除 UTBMS 代码映射失败: {e}")))?;
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound("UTBMS 代码映射不存在".to_string()));
+    }
+    Ok(Json(()))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/utbms-codes", get(list_codes).put(upsert_code))
+        .route("/utbms-codes/:id", delete(delete_code))
+}
@@ -0,0 +1,5 @@
+//! 排期/周期规则模块
+//!
+//! 与数据库无关的纯计算逻辑（便于单元测试）。
+
+pub mod rrule;
@@ -0,0 +1,337 @@
+//! RRULE（RFC 5545）子集解析与下一次发生时间计算
+//!
+//! 支持：`FREQ=DAILY|WEEKLY|MONTHLY|YEARLY`、`INTERVAL`、`COUNT`、`UNTIL`、
+//! `BYDAY`（仅 WEEKLY，不支持序数前缀）、`BYMONTHDAY`（仅 MONTHLY，单值，支持负数表示倒数第 N 天）。
+//! 时刻沿用 DTSTART（系列锚点）的时分秒；月末不足的日期按当月最后一天处理，且不会因此漂移。
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Utc, Weekday};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{AppError, AppResult};
+
+const MAX_INTERVAL: u32 = 366;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    /// 系列实例总数上限（含首个实例）
+    pub count: Option<u32>,
+    /// 最后一次发生时间上限（含）
+    pub until: Option<DateTime<Utc>>,
+    pub by_day: Vec<Weekday>,
+    pub by_month_day: Option<i32>,
+}
+
+fn invalid(message: impl Into<String>) -> AppError {
+    AppError::Validation(format!("RRULE 无效: {}", message.into()))
+}
+
+fn parse_weekday(raw: &str) -> AppResult<Weekday> {
+    match raw {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        other => Err(invalid(format!("不支持的 BYDAY 取值 {other}"))),
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn parse_until(raw: &str) -> AppResult<DateTime<Utc>> {
+    if let Ok(dt) = NaiveDateTime::parse_from_str(raw, "%Y%m%dT%H%M%SZ") {
+        return Ok(dt.and_utc());
+    }
+    // 纯日期形式按当天结束处理（含当天）
+    NaiveDate::parse_from_str(raw, "%Y%m%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(23, 59, 59))
+        .map(|dt| dt.and_utc())
+        .ok_or_else(|| invalid(format!("UNTIL 格式应为 YYYYMMDD 或 YYYYMMDDTHHMMSSZ: {raw}")))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .map(|d| d.day())
+        .unwrap_or(28)
+}
+
+/// 指定年月的第 `day` 天（负数为倒数），超出月份天数时取当月最后一天
+fn resolve_month_day(year: i32, month: u32, day: i32) -> Option<NaiveDate> {
+    let last = days_in_month(year, month) as i32;
+    let resolved = if day > 0 { day.min(last) } else { (last + 1 + day).max(1) };
+    NaiveDate::from_ymd_opt(year, month, resolved as u32)
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = AppError;
+
+    fn from_str(raw: &str) -> AppResult<Self> {
+        let text = raw.trim();
+        let text = text.strip_prefix("RRULE:").unwrap_or(text);
+        if text.is_empty() {
+            return Err(invalid("规则不能为空"));
+        }
+
+        let mut freq = None;
+        let mut interval = 1_u32;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = Vec::new();
+        let mut by_month_day = None;
+
+        for part in text.split(';').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            let (key, value) = part.split_once('=').ok_or_else(|| invalid(format!("缺少 '=': {part}")))?;
+            let key = key.trim().to_uppercase();
+            let value = value.trim().to_uppercase();
+            match key.as_str() {
+                "FREQ" => {
+                    freq = Some(match value.as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        other => return Err(invalid(format!("不支持的 FREQ {other}"))),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|v| (1..=MAX_INTERVAL).contains(v))
+                        .ok_or_else(|| invalid(format!("INTERVAL 取值范围 1-{MAX_INTERVAL}")))?;
+                }
+                "COUNT" => {
+                    count = Some(value.parse::<u32>().ok().filter(|v| *v >= 1).ok_or_else(|| invalid("COUNT 需为正整数"))?);
+                }
+                "UNTIL" => until = Some(parse_until(&value)?),
+                "BYDAY" => {
+                    for code in value.split(',').map(|c| c.trim()).filter(|c| !c.is_empty()) {
+                        let day = parse_weekday(code)?;
+                        if !by_day.contains(&day) {
+                            by_day.push(day);
+                        }
+                    }
+                    by_day.sort_by_key(|d| d.num_days_from_monday());
+                }
+                "BYMONTHDAY" => {
+                    by_month_day = Some(
+                        value
+                            .parse::<i32>()
+                            .ok()
+                            .filter(|v| *v != 0 && (-31..=31).contains(v))
+                            .ok_or_else(|| invalid("BYMONTHDAY 仅支持单个 -31..31（非 0）取值"))?,
+                    );
+                }
+                other => return Err(invalid(format!("不支持的字段 {other}"))),
+            }
+        }
+
+        let freq = freq.ok_or_else(|| invalid("缺少 FREQ"))?;
+        if count.is_some() && until.is_some() {
+            return Err(invalid("COUNT 与 UNTIL 不能同时出现"));
+        }
+        if !by_day.is_empty() && freq != Frequency::Weekly {
+            return Err(invalid("BYDAY 仅支持 FREQ=WEEKLY"));
+        }
+        if by_month_day.is_some() && freq != Frequency::Monthly {
+            return Err(invalid("BYMONTHDAY 仅支持 FREQ=MONTHLY"));
+        }
+
+        Ok(Self { freq, interval, count, until, by_day, by_month_day })
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    /// 规范化输出（不含 `RRULE:` 前缀），用于落库
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FREQ={}", self.freq.as_str())?;
+        if self.interval > 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self.by_day.iter().map(|d| weekday_code(*d)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if let Some(day) = self.by_month_day {
+            write!(f, ";BYMONTHDAY={day}")?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={count}")?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+        Ok(())
+    }
+}
+
+impl RecurrenceRule {
+    /// 第 `index` 个实例（从 1 开始）是否在 COUNT 范围内
+    pub fn allows_index(&self, index: u32) -> bool {
+        self.count.map_or(true, |count| index <= count)
+    }
+
+    /// 计算 `after` 之后的下一次发生时间；超出 UNTIL 时返回 None
+    ///
+    /// `anchor` 为系列起点（DTSTART），决定时刻、周/月相位与默认月内日期。
+    pub fn next_after(&self, anchor: DateTime<Utc>, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let time = anchor.time();
+        let at = |date: NaiveDate| date.and_time(time).and_utc();
+        let interval = self.interval as i64;
+
+        let next = match self.freq {
+            Frequency::Daily => {
+                let elapsed = (after.date_naive() - anchor.date_naive()).num_days().max(0);
+                let mut n = elapsed / interval;
+                loop {
+                    let candidate = at(anchor.date_naive() + Duration::days(n * interval));
+                    if candidate > after {
+                        break candidate;
+                    }
+                    n += 1;
+                }
+            }
+            Frequency::Weekly => {
+                let anchor_week = week_start(anchor.date_naive());
+                let days: Vec<Weekday> =
+                    if self.by_day.is_empty() { vec![anchor.weekday()] } else { self.by_day.clone() };
+                let start = after.date_naive().max(anchor.date_naive());
+                // 最多扫描 interval 周 + 1 周即可覆盖下一个满足相位的周
+                (0..=(7 * (interval + 1)))
+                    .map(|offset| start + Duration::days(offset))
+                    .filter(|d| (week_start(*d) - anchor_week).num_days() / 7 % interval == 0)
+                    .filter(|d| days.contains(&d.weekday()))
+                    .map(at)
+                    .find(|candidate| *candidate > after && *candidate >= anchor)?
+            }
+            Frequency::Monthly | Frequency::Yearly => {
+                let step = if self.freq == Frequency::Yearly { 12 * interval } else { interval };
+                let day = self.by_month_day.unwrap_or(anchor.day() as i32);
+                let anchor_months = anchor.year() as i64 * 12 + anchor.month0() as i64;
+                let after_months = after.year() as i64 * 12 + after.month0() as i64;
+                let mut n = ((after_months - anchor_months).max(0) / step).max(0);
+                loop {
+                    let months = anchor_months + n * step;
+                    let date = resolve_month_day((months / 12) as i32, (months % 12) as u32 + 1, day)?;
+                    let candidate = at(date);
+                    if candidate > after {
+                        break candidate;
+                    }
+                    n += 1;
+                }
+            }
+        };
+
+        match self.until {
+            Some(until) if next > until => None,
+            _ => Some(next),
+        }
+    }
+
+    /// 预览自 `after` 起的后续至多 `limit` 次发生时间（`next_index` 为下一实例序号，用于 COUNT 截断）
+    pub fn preview(&self, anchor: DateTime<Utc>, after: DateTime<Utc>, next_index: u32, limit: usize) -> Vec<DateTime<Utc>> {
+        let mut out = Vec::with_capacity(limit);
+        let mut cursor = after;
+        let mut index = next_index;
+        while out.len() < limit && self.allows_index(index) {
+            let Some(next) = self.next_after(anchor, cursor) else { break };
+            out.push(next);
+            cursor = next;
+            index += 1;
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 9, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn parses_and_normalizes() {
+        let rule: RecurrenceRule = "RRULE:freq=weekly;byday=fr,mo;interval=2;count=5".parse().unwrap();
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;COUNT=5");
+        assert!("FREQ=HOURLY".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=DAILY;COUNT=2;UNTIL=20260101".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=DAILY;BYDAY=MO".parse::<RecurrenceRule>().is_err());
+        assert!("INTERVAL=2".parse::<RecurrenceRule>().is_err());
+    }
+
+    #[test]
+    fn monthly_clamps_to_month_end_without_drift() {
+        let rule: RecurrenceRule = "FREQ=MONTHLY".parse().unwrap();
+        let anchor = utc(2026, 1, 31);
+        let feb = rule.next_after(anchor, anchor).unwrap();
+        assert_eq!(feb, utc(2026, 2, 28));
+        assert_eq!(rule.next_after(anchor, feb).unwrap(), utc(2026, 3, 31));
+
+        let last_day: RecurrenceRule = "FREQ=MONTHLY;BYMONTHDAY=-1".parse().unwrap();
+        assert_eq!(last_day.next_after(utc(2026, 3, 31), utc(2026, 3, 31)).unwrap(), utc(2026, 4, 30));
+    }
+
+    #[test]
+    fn weekly_byday_respects_interval_phase() {
+        // 2026-10-05 为周一
+        let rule: RecurrenceRule = "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH".parse().unwrap();
+        let anchor = utc(2026, 10, 5);
+        let dates = rule.preview(anchor, anchor, 2, 3);
+        assert_eq!(dates, vec![utc(2026, 10, 8), utc(2026, 10, 19), utc(2026, 10, 22)]);
+    }
+
+    #[test]
+    fn count_and_until_stop_the_series() {
+        let rule: RecurrenceRule = "FREQ=DAILY;COUNT=3".parse().unwrap();
+        let anchor = utc(2026, 1, 1);
+        assert_eq!(rule.preview(anchor, anchor, 2, 10).len(), 2);
+
+        let until: RecurrenceRule = "FREQ=YEARLY;UNTIL=20270101".parse().unwrap();
+        assert_eq!(until.next_after(anchor, anchor), Some(utc(2027, 1, 1)));
+        assert_eq!(until.next_after(anchor, utc(2027, 1, 1)), None);
+    }
+}
@@ -7,7 +7,7 @@
 //!   - handlerId == userId
 //!   - CaseMember 中存在 (caseId, userId)
 
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QuerySelect};
 
 use crate::db::AppState;
 use crate::entity::{case, case_member, user::Role};
@@ -53,3 +53,35 @@ pub async fn require_case_access(
     Err(AppError::Forbidden("无案件访问权限".to_string()))
 }
 
+/// 当前用户可见的案件 ID 列表；PARTNER / ADMIN 返回 None（表示不限）
+pub async fn visible_case_ids(state: &AppState, user_id: &str, role: &Role) -> AppResult<Option<Vec<String>>> {
+    if matches!(role, Role::Partner | Role::Admin) {
+        return Ok(None);
+    }
+
+    let member_case_ids: Vec<String> = case_member::Entity::find()
+        .filter(case_member::Column::UserId.eq(user_id))
+        .select_only()
+        .column(case_member::Column::CaseId)
+        .into_values::<String, case_member::Column>()
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询案件成员失败: {e}")))?;
+
+    let mut visibility = Condition::any()
+        .add(case::Column::OriginatorId.eq(user_id))
+        .add(case::Column::HandlerId.eq(user_id));
+    if !member_case_ids.is_empty() {
+        visibility = visibility.add(case::Column::Id.is_in(member_case_ids));
+    }
+
+    let ids = case::Entity::find()
+        .filter(visibility)
+        .select_only()
+        .column(case::Column::Id)
+        .into_values::<String, case::Column>()
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询可见案件失败: {e}")))?;
+    Ok(Some(ids))
+}
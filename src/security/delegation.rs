@@ -0,0 +1,71 @@
+//! 外出期间的审批代理（OutOfOffice.delegateId）
+//!
+//! 规则：
+//! - 外出时段为左闭右开区间 [startTime, endTime)，时段内委托人的待审批事项可由代理人代为处理
+//! - 代理关系在每次查询 / 审批时按当前时间判定，不改写待办的审批人；时段结束后事项自动交回委托人
+//! - 代理不传递：代理人本人外出时，不会再转交给代理人的代理人
+//! - 委托人已停用时代理随之失效
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+use crate::db::AppState;
+use crate::entity::{out_of_office, user, user::Role};
+use crate::error::{AppError, AppResult};
+
+/// 当前委托 `delegate_id` 代为审批的用户（id 与角色）
+pub async fn active_delegators(
+    state: &AppState,
+    tenant_id: &str,
+    delegate_id: &str,
+    now: DateTime<Utc>,
+) -> AppResult<Vec<(String, Role)>> {
+    let mut principal_ids: Vec<String> = out_of_office::Entity::find()
+        .filter(out_of_office::Column::TenantId.eq(tenant_id))
+        .filter(out_of_office::Column::DelegateId.eq(delegate_id))
+        .filter(out_of_office::Column::StartTime.lte(now))
+        .filter(out_of_office::Column::EndTime.gt(now))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询外出代理失败: {e}")))?
+        .into_iter()
+        .map(|o| o.user_id)
+        .filter(|id| id != delegate_id)
+        .collect();
+    if principal_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    principal_ids.sort();
+    principal_ids.dedup();
+
+    Ok(user::Entity::find()
+        .filter(user::Column::Id.is_in(principal_ids))
+        .filter(user::Column::IsActive.eq(true))
+        .all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询用户失败: {e}")))?
+        .into_iter()
+        .map(|u| (u.id, u.role))
+        .collect())
+}
+
+/// `user_id` 当前生效的代理人（未外出或未设置代理人时为 None）
+pub async fn active_delegate(
+    state: &AppState,
+    tenant_id: &str,
+    user_id: &str,
+    now: DateTime<Utc>,
+) -> AppResult<Option<String>> {
+    Ok(out_of_office::Entity::find()
+        .filter(out_of_office::Column::TenantId.eq(tenant_id))
+        .filter(out_of_office::Column::UserId.eq(user_id))
+        .filter(out_of_office::Column::DelegateId.is_not_null())
+        .filter(out_of_office::Column::StartTime.lte(now))
+        .filter(out_of_office::Column::EndTime.gt(now))
+        .order_by_desc(out_of_office::Column::StartTime)
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询外出代理失败: {e}")))?
+        .and_then(|o| o.delegate_id)
+        .filter(|id| id != user_id))
+}
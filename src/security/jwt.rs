@@ -258,13 +258,13 @@ fn decrypt_authjs_jwe_a256cbc_hs512(token: &str, secret: &str, salt: &str) -> Ap
 fn parse_usize_claim(value: Option<&serde_json::Value>, field: &'static str) -> AppResult<usize> {
     let v = value.ok_or_else(|| AppError::Unauthorized(format!("Token 缺少字段：{field}")))?;
     if let Some(u) = v.as_u64() {
-        return Ok(usize::try_from(u).map_err(|_| AppError::Unauthorized(format!("Token 字段溢出：{field}")))?);
+        return usize::try_from(u).map_err(|_| AppError::Unauthorized(format!("Token 字段溢出：{field}")));
     }
     if let Some(i) = v.as_i64() {
         if i < 0 {
             return Err(AppError::Unauthorized(format!("Token 字段为负：{field}")));
         }
-        return Ok(usize::try_from(i as u64).map_err(|_| AppError::Unauthorized(format!("Token 字段溢出：{field}")))?);
+        return usize::try_from(i as u64).map_err(|_| AppError::Unauthorized(format!("Token 字段溢出：{field}")));
     }
     Err(AppError::Unauthorized(format!("Token 字段类型不合法：{field}")))
 }
@@ -380,6 +380,18 @@ mod tests {
             openai_api_key: None,
             openai_base_url: "https://api.openai.com/v1".to_string(),
             openai_model: "gpt-4o-mini".to_string(),
+            task_reminder_offsets_minutes: vec![1440, 60],
+            task_reminder_interval_secs: 60,
+            case_budget_warn_percents: vec![80, 100],
+            timer_idle_max_minutes: 240,
+            timer_idle_away_grace_minutes: 30,
+            timer_idle_sweep_interval_secs: 300,
+            invoice_tax_rate: sea_orm::prelude::Decimal::new(6, 2),
+            invoice_due_days: 30,
+            invoice_overdue_interval_secs: 3600,
+            ledes_law_firm_id: None,
+            contract_expiry_reminder_days: vec![30, 7, 1],
+            contract_expiry_interval_secs: 86400,
         };
 
         let claims = decode_claims_any(token, &config).expect("should decode authjs token");
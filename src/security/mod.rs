@@ -11,3 +11,5 @@ pub mod permissions;
 pub mod validation;
 pub mod case_access;
 pub mod current_user;
+pub mod project_access;
+pub mod delegation;
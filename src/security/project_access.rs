@@ -0,0 +1,79 @@
+//! 项目可见性与访问控制（与 Web 主线 `requireProjectAccess` 口径对齐）
+//!
+//! 规则：
+//! - PARTNER / ADMIN：可访问当前租户内所有未删除项目
+//! - 其它角色：仅可访问 ownerId == userId 或 ProjectMember 中存在 (projectId, userId) 的项目
+//! - ProjectMember.role = VIEWER 仅可执行 `task:view`，其余操作（创建/编辑/删除任务等）拒绝
+//! - 项目本身的编辑/删除/成员管理：仅项目负责人（ownerId 或 OWNER 成员）与 PARTNER / ADMIN
+
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+use crate::db::AppState;
+use crate::entity::{project, project_member, user::Role};
+use crate::error::{AppError, AppResult};
+use crate::security::permissions::{require_permission, Permission};
+
+/// 当前用户在项目中的有效角色（PARTNER / ADMIN 仅在项目属于当前租户时视为 OWNER）
+pub async fn project_role_of(
+    state: &AppState,
+    project_model: &project::Model,
+    user_id: &str,
+    tenant_id: &str,
+    role: &Role,
+) -> AppResult<Option<project_member::ProjectRole>> {
+    let admin_in_tenant = matches!(role, Role::Partner | Role::Admin) && project_model.tenant_id == tenant_id;
+    if admin_in_tenant || project_model.owner_id == user_id {
+        return Ok(Some(project_member::ProjectRole::Owner));
+    }
+
+    let membership = project_member::Entity::find()
+        .filter(project_member::Column::ProjectId.eq(&project_model.id))
+        .filter(project_member::Column::UserId.eq(user_id))
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询项目成员失败: {e}")))?;
+
+    Ok(membership.map(|m| m.role))
+}
+
+pub async fn require_project_access(
+    state: &AppState,
+    project_id: &str,
+    user_id: &str,
+    tenant_id: &str,
+    role: Role,
+    permission: Permission,
+) -> AppResult<project::Model> {
+    require_permission(role.clone(), permission)?;
+
+    let project_model = project::Entity::find_by_id(project_id)
+        .filter(project::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("查询项目失败: {e}")))?
+        .ok_or_else(|| AppError::NotFound(format!("项目 {} 不存在", project_id)))?;
+
+    match project_role_of(state, &project_model, user_id, tenant_id, &role).await? {
+        None => Err(AppError::Forbidden("无项目访问权限".to_string())),
+        Some(project_member::ProjectRole::Viewer) if permission != Permission::TaskView => {
+            Err(AppError::Forbidden("项目观察员仅可查看".to_string()))
+        }
+        Some(_) => Ok(project_model),
+    }
+}
+
+/// 项目管理（编辑/删除/成员管理）：仅项目负责人与 PARTNER / ADMIN
+pub async fn require_project_manage(
+    state: &AppState,
+    project_id: &str,
+    user_id: &str,
+    tenant_id: &str,
+    role: Role,
+) -> AppResult<project::Model> {
+    let project_model =
+        require_project_access(state, project_id, user_id, tenant_id, role.clone(), Permission::TaskEdit).await?;
+    match project_role_of(state, &project_model, user_id, tenant_id, &role).await? {
+        Some(project_member::ProjectRole::Owner) => Ok(project_model),
+        _ => Err(AppError::Forbidden("只有项目负责人或管理员可以管理项目".to_string())),
+    }
+}
@@ -0,0 +1,81 @@
+//! 审批路由：规则分档与顺序步骤推进
+//!
+//! - 同一审批类型可按金额下限配置多档规则，取下限不超过申请金额的最高一档；无金额的申请按 0 计
+//! - 步骤按 stepOrder 顺序审批；只有申请人本人可审批的步骤（指定审批人即申请人，或该角色仅申请人一人）在提交时自动跳过
+//! - 当前步骤通过后推进到下一个未跳过的步骤，没有后续步骤即审批完成
+
+use sea_orm::prelude::Decimal;
+
+/// 选择适用的规则档位，返回其在 `min_amounts` 中的下标；没有任何一档适用时返回 None
+pub fn select_tier(min_amounts: &[Decimal], amount: Option<Decimal>) -> Option<usize> {
+    let amount = amount.unwrap_or(Decimal::ZERO);
+    min_amounts
+        .iter()
+        .enumerate()
+        .filter(|(_, min)| **min <= amount)
+        .max_by_key(|(_, min)| **min)
+        .map(|(index, _)| index)
+}
+
+/// 步骤快照（只关心顺序与是否跳过）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedStep {
+    pub order: i32,
+    pub skipped: bool,
+}
+
+/// 候选审批人全部是申请人本人时跳过该步骤；没有候选人时由调用方报错
+pub fn step_skipped(requester_id: &str, candidates: &[&str]) -> bool {
+    !candidates.is_empty() && candidates.iter().all(|c| *c == requester_id)
+}
+
+/// `current` 之后的第一个未跳过步骤；`current` 为 None 时返回首个待审批步骤，None 表示审批完成
+pub fn next_step(steps: &[PlannedStep], current: Option<i32>) -> Option<i32> {
+    steps
+        .iter()
+        .filter(|s| !s.skipped && current.map_or(true, |c| s.order > c))
+        .map(|s| s.order)
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(v: i64) -> Decimal {
+        Decimal::from(v)
+    }
+
+    #[test]
+    fn selects_highest_tier_not_above_amount() {
+        let tiers = [d(0), d(50_000), d(10_000)];
+        assert_eq!(select_tier(&tiers, None), Some(0));
+        assert_eq!(select_tier(&tiers, Some(d(9_999))), Some(0));
+        assert_eq!(select_tier(&tiers, Some(d(10_000))), Some(2));
+        assert_eq!(select_tier(&tiers, Some(d(80_000))), Some(1));
+        assert_eq!(select_tier(&[d(1_000)], Some(d(500))), None);
+        assert_eq!(select_tier(&[], Some(d(500))), None);
+    }
+
+    #[test]
+    fn advances_past_skipped_steps() {
+        let steps = [
+            PlannedStep { order: 1, skipped: true },
+            PlannedStep { order: 2, skipped: false },
+            PlannedStep { order: 3, skipped: true },
+            PlannedStep { order: 4, skipped: false },
+        ];
+        assert_eq!(next_step(&steps, None), Some(2));
+        assert_eq!(next_step(&steps, Some(2)), Some(4));
+        assert_eq!(next_step(&steps, Some(4)), None);
+        assert_eq!(next_step(&[PlannedStep { order: 1, skipped: true }], None), None);
+    }
+
+    #[test]
+    fn skips_steps_only_the_requester_can_approve() {
+        assert!(step_skipped("alice", &["alice"]));
+        assert!(!step_skipped("alice", &["bob"]));
+        assert!(!step_skipped("alice", &["alice", "bob"]));
+        assert!(!step_skipped("alice", &[]));
+    }
+}